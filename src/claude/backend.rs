@@ -0,0 +1,534 @@
+//! Coding-agent backends.
+//!
+//! Every agent session autom8 starts (stories, review, correction, commits,
+//! PR descriptions, PR reviews and spec generation) goes through an
+//! [`AgentBackend`]: it spawns the agent process for a prompt, turns each
+//! line the process writes into [`AgentEvent`]s, kills it when a limit is hit
+//! and decides whether the output says all stories are complete.
+//!
+//! [`ClaudeCliBackend`] runs the `claude` CLI and is the default.
+//! [`CommandBackend`] runs any command that speaks one of the
+//! [`LineProtocol`]s, so other agent CLIs or in-house wrappers can be used
+//! from the `[agent]` config table.
+//!
+//! Backends are built from a run's config with [`from_config`], along with
+//! the sandbox their processes run in, and handed to the run's
+//! [`ClaudeRunner`](super::ClaudeRunner)s.
+
+use std::fmt;
+use std::io::{self, Write};
+use std::path::Path;
+use std::process::{Child, Command, Stdio};
+use std::sync::Arc;
+
+use serde::Deserialize;
+
+use crate::config::{AgentKind, ClaudeConfig, Config, LineProtocol};
+use crate::error::{Autom8Error, Result};
+
+use super::sandbox::{self, Sandbox};
+use super::stream::{
+    extract_result_error_from_stream_line, extract_text_from_stream_line,
+    extract_tool_activity_from_stream_line, extract_usage_from_result_line,
+};
+use super::types::{ClaudePhase, ClaudeUsage};
+
+/// What the story prompt asks the agent to write once every story passes.
+pub const COMPLETION_SIGNAL: &str = "<promise>COMPLETE</promise>";
+
+/// Placeholder in [`CommandBackend`] arguments that is replaced with the
+/// prompt. Without it the prompt is written to stdin.
+pub const PROMPT_PLACEHOLDER: &str = "{prompt}";
+
+/// Something an agent reported in a line of its output.
+#[derive(Debug, Clone, PartialEq)]
+pub enum AgentEvent {
+    /// Text the agent wrote, to show and to scan for signals
+    Text(String),
+    /// A tool call, e.g. `Bash: cargo test`
+    ToolUse(String),
+    /// Token usage for the session so far
+    Usage(ClaudeUsage),
+    /// An error the agent reported about the session, e.g. an API error
+    Error(String),
+}
+
+/// A coding-agent CLI that autom8 can run sessions with.
+pub trait AgentBackend: fmt::Debug + Send + Sync {
+    /// Short name used in messages, e.g. `claude`.
+    fn name(&self) -> &str;
+
+    /// Start a session for `phase` with `prompt`, in `working_dir` (or the
+    /// current directory), with stdout and stderr piped. `spec_path` is a
+    /// spec file outside the working directory the session may update.
+    fn spawn(
+        &self,
+        phase: ClaudePhase,
+        prompt: &str,
+        working_dir: Option<&Path>,
+        spec_path: Option<&Path>,
+    ) -> Result<Child>;
+
+    /// The events in one line of the session's stdout.
+    fn parse_line(&self, line: &str) -> Vec<AgentEvent>;
+
+    /// Stop a running session.
+    fn kill(&self, child: &mut Child) -> io::Result<()> {
+        child.kill()
+    }
+
+    /// Whether a story session's output says all stories are complete.
+    fn is_complete(&self, output: &str) -> bool {
+        output.contains(COMPLETION_SIGNAL)
+    }
+
+    /// The model sessions for `phase` are asked to use, if the backend
+    /// chooses one.
+    fn model(&self, _phase: ClaudePhase) -> Option<String> {
+        None
+    }
+}
+
+/// The `claude` CLI in stream-json mode, run as the `[claude]` config table
+/// says.
+#[derive(Debug, Clone, Default)]
+pub struct ClaudeCliBackend {
+    config: ClaudeConfig,
+    sandbox: Option<Sandbox>,
+}
+
+impl ClaudeCliBackend {
+    pub fn new(config: ClaudeConfig) -> Self {
+        Self {
+            config,
+            sandbox: None,
+        }
+    }
+
+    /// Run sessions inside `sandbox`, or unconfined if it is `None`.
+    pub fn with_sandbox(mut self, sandbox: Option<Sandbox>) -> Self {
+        self.sandbox = sandbox;
+        self
+    }
+}
+
+impl AgentBackend for ClaudeCliBackend {
+    fn name(&self) -> &str {
+        "claude"
+    }
+
+    fn spawn(
+        &self,
+        phase: ClaudePhase,
+        prompt: &str,
+        working_dir: Option<&Path>,
+        spec_path: Option<&Path>,
+    ) -> Result<Child> {
+        let mut command = Command::new(self.config.binary());
+        match self.config.permissions_for(phase) {
+            None => {
+                command.arg("--dangerously-skip-permissions");
+            }
+            Some(profile) => {
+                for tool in profile.allowed_tools() {
+                    command.arg("--allowedTools").arg(tool);
+                }
+            }
+        }
+        command.args(["--print", "--output-format", "stream-json", "--verbose"]);
+        if let Some(model) = self.config.model_for(phase) {
+            command.args(["--model", model]);
+        }
+        command.args(self.config.args_for(phase));
+        spawn_command(
+            self.config.binary(),
+            command,
+            Some(prompt),
+            working_dir,
+            spec_path,
+            self.sandbox.as_ref(),
+        )
+    }
+
+    fn model(&self, phase: ClaudePhase) -> Option<String> {
+        self.config.model_for(phase).map(str::to_string)
+    }
+
+    fn parse_line(&self, line: &str) -> Vec<AgentEvent> {
+        parse_stream_json_line(line)
+    }
+}
+
+/// Any agent command that writes one of the [`LineProtocol`]s to stdout.
+///
+/// The prompt goes to stdin, or replaces [`PROMPT_PLACEHOLDER`] in the
+/// arguments. The session's phase is passed in `AUTOM8_PHASE`.
+#[derive(Debug, Clone)]
+pub struct CommandBackend {
+    command: String,
+    args: Vec<String>,
+    protocol: LineProtocol,
+    sandbox: Option<Sandbox>,
+}
+
+impl CommandBackend {
+    pub fn new(command: impl Into<String>, args: Vec<String>, protocol: LineProtocol) -> Self {
+        Self {
+            command: command.into(),
+            args,
+            protocol,
+            sandbox: None,
+        }
+    }
+
+    /// Run sessions inside `sandbox`, or unconfined if it is `None`.
+    pub fn with_sandbox(mut self, sandbox: Option<Sandbox>) -> Self {
+        self.sandbox = sandbox;
+        self
+    }
+}
+
+impl AgentBackend for CommandBackend {
+    fn name(&self) -> &str {
+        &self.command
+    }
+
+    fn spawn(
+        &self,
+        phase: ClaudePhase,
+        prompt: &str,
+        working_dir: Option<&Path>,
+        spec_path: Option<&Path>,
+    ) -> Result<Child> {
+        let prompt_in_args = self.args.iter().any(|a| a.contains(PROMPT_PLACEHOLDER));
+        let mut command = Command::new(&self.command);
+        command
+            .args(
+                self.args
+                    .iter()
+                    .map(|a| a.replace(PROMPT_PLACEHOLDER, prompt)),
+            )
+            .env("AUTOM8_PHASE", phase.name());
+        let stdin = (!prompt_in_args).then_some(prompt);
+        spawn_command(
+            self.name(),
+            command,
+            stdin,
+            working_dir,
+            spec_path,
+            self.sandbox.as_ref(),
+        )
+    }
+
+    fn parse_line(&self, line: &str) -> Vec<AgentEvent> {
+        match self.protocol {
+            LineProtocol::StreamJson => parse_stream_json_line(line),
+            LineProtocol::Jsonl => parse_jsonl_line(line),
+            LineProtocol::Text => vec![AgentEvent::Text(format!("{}\n", line))],
+        }
+    }
+}
+
+/// The backend for the `[agent]` config table, with the Claude CLI run as
+/// `[claude]` says, in the sandbox the `sandbox` setting asks for.
+pub fn from_config(config: &Config) -> Result<Arc<dyn AgentBackend>> {
+    let agent = &config.agent;
+    match agent.backend {
+        AgentKind::Claude => Ok(Arc::new(
+            ClaudeCliBackend::new(config.claude.clone())
+                .with_sandbox(Sandbox::from_config(config)?),
+        )),
+        AgentKind::Command => {
+            let command = agent
+                .command
+                .as_deref()
+                .filter(|c| !c.trim().is_empty())
+                .ok_or_else(|| {
+                    Autom8Error::Config(
+                        "[agent] backend = \"command\" needs a command to run, e.g. command = \"my-agent\""
+                            .to_string(),
+                    )
+                })?;
+            Ok(Arc::new(
+                CommandBackend::new(command, agent.args.clone(), agent.protocol)
+                    .with_sandbox(Sandbox::from_config(config)?),
+            ))
+        }
+    }
+}
+
+/// Spawn `command` with piped output, in `sandbox` if there is one,
+/// writing `stdin` to it if given.
+fn spawn_command(
+    name: &str,
+    command: Command,
+    stdin: Option<&str>,
+    working_dir: Option<&Path>,
+    spec_path: Option<&Path>,
+    sandbox: Option<&Sandbox>,
+) -> Result<Child> {
+    let spec_dir = spec_path.and_then(Path::parent);
+    let mut command = sandbox::confine(sandbox, command, working_dir, spec_dir)?;
+    command
+        .stdin(if stdin.is_some() {
+            Stdio::piped()
+        } else {
+            Stdio::null()
+        })
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped());
+    if let Some(dir) = working_dir {
+        command.current_dir(dir);
+    }
+    let mut child = command
+        .spawn()
+        .map_err(|e| Autom8Error::ClaudeError(format!("Failed to spawn {}: {}", name, e)))?;
+
+    // Write the prompt, then drop stdin to close it
+    if let (Some(prompt), Some(mut pipe)) = (stdin, child.stdin.take()) {
+        pipe.write_all(prompt.as_bytes())
+            .map_err(|e| Autom8Error::ClaudeError(format!("Failed to write to stdin: {}", e)))?;
+    }
+    Ok(child)
+}
+
+/// Events in a line of Claude CLI stream-json output.
+fn parse_stream_json_line(line: &str) -> Vec<AgentEvent> {
+    let mut events = Vec::new();
+    if let Some(text) = extract_text_from_stream_line(line) {
+        events.push(AgentEvent::Text(text));
+    }
+    if let Some(tool) = extract_tool_activity_from_stream_line(line) {
+        events.push(AgentEvent::ToolUse(tool));
+    }
+    if let Some(usage) = extract_usage_from_result_line(line) {
+        events.push(AgentEvent::Usage(usage));
+    }
+    if let Some(error) = extract_result_error_from_stream_line(line) {
+        events.push(AgentEvent::Error(error));
+    }
+    events
+}
+
+/// A line of the `jsonl` protocol.
+#[derive(Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+enum JsonlEvent {
+    Text {
+        text: String,
+    },
+    Tool {
+        name: String,
+    },
+    Usage {
+        #[serde(default)]
+        input_tokens: u64,
+        #[serde(default)]
+        output_tokens: u64,
+        #[serde(default)]
+        cache_read_tokens: u64,
+        #[serde(default)]
+        cache_creation_tokens: u64,
+        #[serde(default)]
+        model: Option<String>,
+    },
+    Error {
+        message: String,
+    },
+}
+
+/// Events in a line of the `jsonl` protocol. Lines that aren't JSON are
+/// taken as text; JSON of another type is ignored.
+fn parse_jsonl_line(line: &str) -> Vec<AgentEvent> {
+    let Ok(value) = serde_json::from_str::<serde_json::Value>(line) else {
+        return vec![AgentEvent::Text(format!("{}\n", line))];
+    };
+    let event = match serde_json::from_value(value) {
+        Ok(JsonlEvent::Text { text }) => AgentEvent::Text(text),
+        Ok(JsonlEvent::Tool { name }) => AgentEvent::ToolUse(name),
+        Ok(JsonlEvent::Usage {
+            input_tokens,
+            output_tokens,
+            cache_read_tokens,
+            cache_creation_tokens,
+            model,
+        }) => AgentEvent::Usage(ClaudeUsage {
+            input_tokens,
+            output_tokens,
+            cache_read_tokens,
+            cache_creation_tokens,
+            thinking_tokens: 0,
+            model,
+            cost_usd: None,
+        }),
+        Ok(JsonlEvent::Error { message }) => AgentEvent::Error(message),
+        Err(_) => return Vec::new(),
+    };
+    vec![event]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::{AgentConfig, ClaudePhaseConfig, PermissionProfile};
+
+    #[test]
+    fn test_jsonl_protocol() {
+        let backend = CommandBackend::new("agent", Vec::new(), LineProtocol::Jsonl);
+        assert_eq!(
+            backend.parse_line(r#"{"type":"text","text":"Hello"}"#),
+            vec![AgentEvent::Text("Hello".into())]
+        );
+        assert_eq!(
+            backend.parse_line(r#"{"type":"tool","name":"Bash: cargo test"}"#),
+            vec![AgentEvent::ToolUse("Bash: cargo test".into())]
+        );
+        assert_eq!(
+            backend
+                .parse_line(r#"{"type":"usage","input_tokens":10,"output_tokens":4,"model":"m"}"#),
+            vec![AgentEvent::Usage(ClaudeUsage {
+                input_tokens: 10,
+                output_tokens: 4,
+                model: Some("m".into()),
+                ..Default::default()
+            })]
+        );
+        assert_eq!(
+            backend.parse_line(r#"{"type":"error","message":"rate limited"}"#),
+            vec![AgentEvent::Error("rate limited".into())]
+        );
+        assert!(backend.parse_line(r#"{"type":"heartbeat"}"#).is_empty());
+        assert_eq!(
+            backend.parse_line("plain output"),
+            vec![AgentEvent::Text("plain output\n".into())]
+        );
+    }
+
+    #[test]
+    fn test_stream_json_protocol_matches_claude() {
+        let line = r#"{"type":"assistant","message":{"content":[{"type":"text","text":"Done"},{"type":"tool_use","name":"Bash","input":{"command":"cargo test"}}]}}"#;
+        let events = ClaudeCliBackend::default().parse_line(line);
+        assert_eq!(
+            events,
+            vec![
+                AgentEvent::Text("Done".into()),
+                AgentEvent::ToolUse("Bash: cargo test".into())
+            ]
+        );
+        let backend = CommandBackend::new("wrapper", Vec::new(), LineProtocol::StreamJson);
+        assert_eq!(backend.parse_line(line), events);
+    }
+
+    #[test]
+    fn test_command_backend_takes_prompt_from_args_or_stdin() {
+        let output = |args: &[&str]| {
+            let backend = CommandBackend::new(
+                "sh",
+                args.iter().map(|a| a.to_string()).collect(),
+                LineProtocol::Text,
+            );
+            let child = backend
+                .spawn(ClaudePhase::Review, "the prompt", None, None)
+                .unwrap();
+            String::from_utf8(child.wait_with_output().unwrap().stdout).unwrap()
+        };
+        assert_eq!(
+            output(&["-c", "echo \"$AUTOM8_PHASE: $0\"", "{prompt}"]),
+            "review: the prompt\n"
+        );
+        assert_eq!(output(&["-c", "cat"]), "the prompt");
+    }
+
+    #[test]
+    fn test_from_config() {
+        let command = |command: Option<&str>| Config {
+            agent: AgentConfig {
+                backend: AgentKind::Command,
+                command: command.map(str::to_string),
+                ..Default::default()
+            },
+            ..Default::default()
+        };
+        assert!(from_config(&command(None)).is_err());
+        assert_eq!(
+            from_config(&command(Some("my-agent"))).unwrap().name(),
+            "my-agent"
+        );
+        assert_eq!(from_config(&Config::default()).unwrap().name(), "claude");
+    }
+
+    #[test]
+    fn test_claude_backend_passes_phase_model_and_args() {
+        use std::os::unix::fs::PermissionsExt;
+
+        let dir = tempfile::TempDir::new().unwrap();
+        let binary = dir.path().join("claude");
+        std::fs::write(&binary, "#!/bin/sh\ncat >/dev/null\necho \"$@\"\n").unwrap();
+        std::fs::set_permissions(&binary, std::fs::Permissions::from_mode(0o755)).unwrap();
+
+        let backend = ClaudeCliBackend::new(ClaudeConfig {
+            binary: Some(binary.to_string_lossy().into_owned()),
+            model: Some("opus".into()),
+            args: vec!["--effort".into(), "high".into()],
+            commit: ClaudePhaseConfig {
+                model: Some("haiku".into()),
+                args: Some(Vec::new()),
+                ..Default::default()
+            },
+            ..Default::default()
+        });
+        let args = |phase| {
+            let child = backend.spawn(phase, "prompt", None, None).unwrap();
+            let stdout = child.wait_with_output().unwrap().stdout;
+            String::from_utf8(stdout).unwrap()
+        };
+
+        assert!(args(ClaudePhase::Story).ends_with("--verbose --model opus --effort high\n"));
+        assert!(args(ClaudePhase::Commit).ends_with("--verbose --model haiku\n"));
+        assert_eq!(backend.model(ClaudePhase::Commit).as_deref(), Some("haiku"));
+    }
+
+    #[test]
+    fn test_claude_backend_applies_permission_profiles() {
+        use std::os::unix::fs::PermissionsExt;
+
+        let dir = tempfile::TempDir::new().unwrap();
+        let binary = dir.path().join("claude");
+        std::fs::write(
+            &binary,
+            "#!/bin/sh\ncat >/dev/null\nfor arg in \"$@\"; do echo \"$arg\"; done\n",
+        )
+        .unwrap();
+        std::fs::set_permissions(&binary, std::fs::Permissions::from_mode(0o755)).unwrap();
+
+        let backend = ClaudeCliBackend::new(ClaudeConfig {
+            binary: Some(binary.to_string_lossy().into_owned()),
+            correct: ClaudePhaseConfig {
+                permissions: Some("fix".into()),
+                ..Default::default()
+            },
+            profiles: [(
+                "fix".to_string(),
+                PermissionProfile {
+                    tools: vec!["Edit".into()],
+                    bash: vec!["cargo test:*".into()],
+                },
+            )]
+            .into(),
+            ..Default::default()
+        });
+        let args = |phase| {
+            let child = backend.spawn(phase, "prompt", None, None).unwrap();
+            let stdout = child.wait_with_output().unwrap().stdout;
+            String::from_utf8(stdout).unwrap()
+        };
+
+        assert!(args(ClaudePhase::Story).starts_with("--dangerously-skip-permissions\n"));
+        assert!(args(ClaudePhase::Correct)
+            .starts_with("--allowedTools\nEdit\n--allowedTools\nBash(cargo test:*)\n--print\n"));
+        let review = args(ClaudePhase::Review);
+        assert!(!review.contains("--dangerously-skip-permissions"));
+        assert!(review.contains("--allowedTools\nRead\n"));
+        assert!(review.contains("--allowedTools\nBash(git diff:*)\n"));
+    }
+}
@@ -0,0 +1,268 @@
+//! Classification of Claude process failures.
+//!
+//! A failed `claude` run is either worth retrying (an overloaded API, a 5xx,
+//! a dropped connection), blocked until a usage limit resets, or permanent.
+//! The classification is read from stderr and the final stream-json `result`
+//! event, which is where the CLI reports API errors.
+
+use chrono::{DateTime, Duration, Local, NaiveTime, TimeZone, Utc};
+use regex::Regex;
+use serde::{Deserialize, Serialize};
+
+/// How long to wait before trying again when a usage limit resets at a
+/// clock time in a time zone that can't be resolved. Another usage-limit
+/// failure just waits again, so this only bounds the wait.
+const UNKNOWN_ZONE_RETRY_MINUTES: i64 = 30;
+
+/// Why a Claude process failed, as far as retrying it goes.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum ClaudeFailureKind {
+    /// Overload, server error or network problem; retrying may succeed.
+    Transient,
+    /// The account's usage limit was reached and resets at `resets_at`.
+    UsageLimit { resets_at: DateTime<Utc> },
+    /// Anything else; retrying won't help.
+    #[default]
+    Permanent,
+}
+
+impl ClaudeFailureKind {
+    /// Whether the failed run should be tried again.
+    pub fn is_retryable(&self) -> bool {
+        !matches!(self, ClaudeFailureKind::Permanent)
+    }
+}
+
+/// Classify a failure from the text Claude reported (stderr and/or the
+/// final `result` event).
+pub fn classify_failure(text: &str) -> ClaudeFailureKind {
+    classify_failure_at(text, Local::now(), local_zone_name().as_deref())
+}
+
+/// [`classify_failure`] relative to `now`, which reset times like "3pm" are
+/// resolved against. `local_zone` is the IANA name of the local time zone,
+/// if known, so a reset time given in that zone can be resolved.
+pub fn classify_failure_at(
+    text: &str,
+    now: DateTime<Local>,
+    local_zone: Option<&str>,
+) -> ClaudeFailureKind {
+    // The CLI's wording, e.g. "Claude AI usage limit reached" or "5-hour
+    // limit reached"; API rate limits ("Rate limit reached") are transient
+    let usage_limit_re =
+        Regex::new(r"(?i)usage limit|\b(?:\d+-hour|weekly|session|opus|sonnet) limit reached")
+            .unwrap();
+    if usage_limit_re.is_match(text) {
+        // A usage limit without a reset time can't be waited out
+        return match parse_usage_limit_reset(text, now, local_zone) {
+            Some(resets_at) => ClaudeFailureKind::UsageLimit { resets_at },
+            None => ClaudeFailureKind::Permanent,
+        };
+    }
+
+    let transient_re = Regex::new(
+        r"(?i)overloaded|api error:?\s*\(?(?:429|5\d\d)\b|internal server error|bad gateway|service unavailable|gateway timeout|too many requests|rate.?limit|request timed out|connection error|connection (?:reset|refused|closed)|socket hang up|fetch failed|network error|econnreset|econnrefused|etimedout|enotfound|eai_again",
+    )
+    .unwrap();
+    if transient_re.is_match(text) {
+        ClaudeFailureKind::Transient
+    } else {
+        ClaudeFailureKind::Permanent
+    }
+}
+
+/// Find when a usage limit resets.
+///
+/// Understands the CLI's `usage limit reached|<unix seconds>` form, and
+/// "resets 3pm" / "reset at 11:30am (Europe/London)", taking the next
+/// occurrence of that time. A time in UTC or in the local zone (or with no
+/// zone) is resolved exactly; in any other zone, the wait is capped at
+/// [`UNKNOWN_ZONE_RETRY_MINUTES`].
+fn parse_usage_limit_reset(
+    text: &str,
+    now: DateTime<Local>,
+    local_zone: Option<&str>,
+) -> Option<DateTime<Utc>> {
+    let epoch_re = Regex::new(r"\|\s*(\d{9,11})\b").unwrap();
+    if let Some(caps) = epoch_re.captures(text) {
+        let secs: i64 = caps[1].parse().ok()?;
+        return Utc.timestamp_opt(secs, 0).single();
+    }
+
+    let clock_re = Regex::new(
+        r"(?i)resets?(?:\s+at)?\s+(\d{1,2})(?::(\d{2}))?\s*(am|pm)\b(?:\s*\(([^)]+)\))?",
+    )
+    .unwrap();
+    let caps = clock_re.captures(text)?;
+    let hour: u32 = caps[1].parse().ok()?;
+    let minute: u32 = caps.get(2).map_or(Some(0), |m| m.as_str().parse().ok())?;
+    if !(1..=12).contains(&hour) {
+        return None;
+    }
+    let hour = match (hour, caps[3].eq_ignore_ascii_case("pm")) {
+        (12, false) => 0,
+        (12, true) => 12,
+        (hour, true) => hour + 12,
+        (hour, false) => hour,
+    };
+    let time = NaiveTime::from_hms_opt(hour, minute, 0)?;
+
+    match caps.get(4).map(|zone| zone.as_str().trim()) {
+        None => next_occurrence(&now, time),
+        Some(zone) if local_zone.is_some_and(|local| local.eq_ignore_ascii_case(zone)) => {
+            next_occurrence(&now, time)
+        }
+        Some(zone) if is_utc_zone(zone) => next_occurrence(&now.with_timezone(&Utc), time),
+        Some(_) => Some(now.with_timezone(&Utc) + Duration::minutes(UNKNOWN_ZONE_RETRY_MINUTES)),
+    }
+}
+
+/// The next time the clock shows `time` in `now`'s time zone.
+fn next_occurrence<Tz: TimeZone>(now: &DateTime<Tz>, time: NaiveTime) -> Option<DateTime<Utc>> {
+    let today = now.date_naive().and_time(time);
+    let mut reset = now.timezone().from_local_datetime(&today).earliest()?;
+    if reset <= *now {
+        reset += Duration::days(1);
+    }
+    Some(reset.with_timezone(&Utc))
+}
+
+fn is_utc_zone(zone: &str) -> bool {
+    ["UTC", "GMT", "Etc/UTC", "Etc/GMT", "Z"]
+        .iter()
+        .any(|utc| utc.eq_ignore_ascii_case(zone))
+}
+
+/// The IANA name of the local time zone, from `TZ` or the `/etc/localtime` link.
+fn local_zone_name() -> Option<String> {
+    if let Ok(tz) = std::env::var("TZ") {
+        let tz = tz.trim_start_matches(':');
+        if !tz.is_empty() {
+            return Some(tz.to_string());
+        }
+    }
+    let target = std::fs::read_link("/etc/localtime").ok()?;
+    let target = target.to_string_lossy();
+    let (_, zone) = target.split_once("zoneinfo/")?;
+    Some(zone.to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn local(y: i32, mo: u32, d: u32, h: u32, mi: u32) -> DateTime<Local> {
+        Local.with_ymd_and_hms(y, mo, d, h, mi, 0).unwrap()
+    }
+
+    #[test]
+    fn test_classify_transient_failures() {
+        for text in [
+            r#"API Error: 529 {"type":"error","error":{"type":"overloaded_error","message":"Overloaded"}}"#,
+            "API Error: 429 Rate limit reached for requests",
+            "Rate limit reached",
+            "API Error: 500 Internal server error",
+            "API Error (Request timed out.)",
+            "API Error: Connection error.",
+            "Error: read ECONNRESET",
+            "502 Bad Gateway",
+        ] {
+            assert_eq!(
+                classify_failure(text),
+                ClaudeFailureKind::Transient,
+                "{}",
+                text
+            );
+        }
+    }
+
+    #[test]
+    fn test_classify_permanent_failures() {
+        for text in [
+            "Invalid API key · Please run /login",
+            "error: unknown option '--bogus'",
+            "Failed to process 500 files",
+            "",
+        ] {
+            assert_eq!(
+                classify_failure(text),
+                ClaudeFailureKind::Permanent,
+                "{}",
+                text
+            );
+        }
+    }
+
+    #[test]
+    fn test_classify_usage_limit_with_epoch_reset() {
+        let kind = classify_failure("Claude AI usage limit reached|1760994000");
+        assert_eq!(
+            kind,
+            ClaudeFailureKind::UsageLimit {
+                resets_at: Utc.timestamp_opt(1760994000, 0).unwrap()
+            }
+        );
+        assert!(kind.is_retryable());
+    }
+
+    #[test]
+    fn test_classify_usage_limit_with_clock_reset() {
+        let now = local(2026, 3, 10, 13, 45);
+        let kind = classify_failure_at("5-hour limit reached ∙ resets 3pm", now, None);
+        let expected = local(2026, 3, 10, 15, 0).with_timezone(&Utc);
+        assert_eq!(
+            kind,
+            ClaudeFailureKind::UsageLimit {
+                resets_at: expected
+            }
+        );
+
+        // A time already past today means tomorrow
+        let kind = classify_failure_at(
+            "Claude usage limit reached. Your limit will reset at 11:30am (Europe/London).",
+            local(2026, 3, 10, 13, 45),
+            Some("Europe/London"),
+        );
+        let expected = local(2026, 3, 11, 11, 30).with_timezone(&Utc);
+        assert_eq!(
+            kind,
+            ClaudeFailureKind::UsageLimit {
+                resets_at: expected
+            }
+        );
+    }
+
+    #[test]
+    fn test_classify_usage_limit_reset_in_other_zones() {
+        let now = local(2026, 3, 10, 13, 45);
+        let text = "Claude usage limit reached. Your limit will reset at 11:30am (Europe/London).";
+
+        // A zone that isn't local can't be resolved, so the wait is bounded
+        let kind = classify_failure_at(text, now, Some("America/New_York"));
+        let expected = now.with_timezone(&Utc) + Duration::minutes(UNKNOWN_ZONE_RETRY_MINUTES);
+        assert_eq!(
+            kind,
+            ClaudeFailureKind::UsageLimit {
+                resets_at: expected
+            }
+        );
+
+        // UTC needs no time zone data
+        let kind = classify_failure_at("5-hour limit reached ∙ resets 11pm (UTC)", now, None);
+        let ClaudeFailureKind::UsageLimit { resets_at } = kind else {
+            panic!("expected a usage limit, got {:?}", kind);
+        };
+        assert_eq!(resets_at.format("%H:%M").to_string(), "23:00");
+        assert!(resets_at > now.with_timezone(&Utc));
+        assert!(resets_at <= now.with_timezone(&Utc) + Duration::days(1));
+    }
+
+    #[test]
+    fn test_classify_usage_limit_without_reset_is_permanent() {
+        assert_eq!(
+            classify_failure("Claude usage limit reached."),
+            ClaudeFailureKind::Permanent
+        );
+    }
+}
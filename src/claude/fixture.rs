@@ -0,0 +1,321 @@
+//! Recording and replaying Claude sessions.
+//!
+//! When recording, every Claude session is saved to a fixture file: its
+//! prompt, the raw output lines it wrote, how it exited and the file edits it
+//! made, as a patch. When replaying, fixtures stand in for the agent process:
+//! their lines go through the backend's usual parsing and their patch is
+//! applied to the working tree. Runs can then be reproduced and
+//! demoed without spending tokens, and tested end to end offline.
+//!
+//! Recording is turned on with `AUTOM8_RECORD_DIR` or `record` in the
+//! `[fixtures]` config table, replay with `AUTOM8_REPLAY_DIR` or `replay`.
+//!
+//! Replay hands out fixtures in recorded order, matched by phase and story,
+//! so parallel stories replay too. It recreates file edits and the spec
+//! changes story sessions make, but not commits the agent made itself.
+
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::{Arc, Mutex};
+
+use serde::{Deserialize, Serialize};
+
+use crate::config::FixturesConfig;
+use crate::error::{Autom8Error, Result};
+
+use super::types::{ClaudePhase, SessionExit};
+
+/// Environment variable naming a directory to record sessions to.
+pub const RECORD_DIR_ENV: &str = "AUTOM8_RECORD_DIR";
+
+/// Environment variable naming a directory of sessions to replay.
+pub const REPLAY_DIR_ENV: &str = "AUTOM8_REPLAY_DIR";
+
+/// One recorded Claude session.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SessionFixture {
+    pub phase: ClaudePhase,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub story_id: Option<String>,
+    pub prompt: String,
+    /// Raw output lines, in the order they were written
+    pub lines: Vec<String>,
+    pub exit: SessionExit,
+    /// Edits the session made to the working tree, as a binary git patch
+    #[serde(default)]
+    pub patch: String,
+    /// The spec file after the session, if the session changed it
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub spec: Option<String>,
+}
+
+/// Whether Claude sessions run for real, are recorded or are replayed.
+#[derive(Debug, Clone, Default)]
+pub enum FixtureMode {
+    #[default]
+    Live,
+    Record(Arc<Recorder>),
+    Replay(Arc<Replayer>),
+}
+
+impl FixtureMode {
+    /// The mode selected by the environment or, failing that, `config`.
+    ///
+    /// Relative directories are resolved against the current directory.
+    pub fn from_config(config: &FixturesConfig) -> Result<Self> {
+        let env = |name| std::env::var(name).ok().filter(|v| !v.trim().is_empty());
+        let (record, replay) = match (env(RECORD_DIR_ENV), env(REPLAY_DIR_ENV)) {
+            (None, None) => (config.record.clone(), config.replay.clone()),
+            from_env => from_env,
+        };
+
+        match (record, replay) {
+            (Some(_), Some(_)) => Err(Autom8Error::Fixture(
+                "sessions can't be recorded and replayed at the same time".to_string(),
+            )),
+            (Some(dir), None) => Ok(FixtureMode::Record(Arc::new(Recorder::new(
+                std::env::current_dir()?.join(dir),
+            )?))),
+            (None, Some(dir)) => Ok(FixtureMode::Replay(Arc::new(Replayer::load(
+                std::env::current_dir()?.join(dir),
+            )?))),
+            (None, None) => Ok(FixtureMode::Live),
+        }
+    }
+
+    /// A line saying where sessions are recorded to or replayed from, if
+    /// they aren't live.
+    pub fn describe(&self) -> Option<String> {
+        match self {
+            FixtureMode::Live => None,
+            FixtureMode::Record(recorder) => Some(format!(
+                "Recording Claude sessions to {}",
+                recorder.dir.display()
+            )),
+            FixtureMode::Replay(replayer) => Some(format!(
+                "Replaying {} recorded Claude sessions from {} instead of running Claude",
+                replayer.remaining(),
+                replayer.dir.display()
+            )),
+        }
+    }
+}
+
+/// Saves sessions to numbered fixture files in a directory.
+#[derive(Debug)]
+pub struct Recorder {
+    dir: PathBuf,
+    next: AtomicUsize,
+}
+
+impl Recorder {
+    /// Record to `dir`, after any fixtures already there (e.g. from before
+    /// the run was resumed).
+    pub fn new(dir: impl Into<PathBuf>) -> Result<Self> {
+        let dir = dir.into();
+        fs::create_dir_all(&dir)?;
+        let existing = fixture_files(&dir)?.len();
+        Ok(Self {
+            dir,
+            next: AtomicUsize::new(existing + 1),
+        })
+    }
+
+    /// Write `fixture` to the next file, e.g. `0003-story-US-002.json`.
+    pub fn save(&self, fixture: &SessionFixture) -> Result<PathBuf> {
+        let number = self.next.fetch_add(1, Ordering::SeqCst);
+        let story = fixture
+            .story_id
+            .as_deref()
+            .map(|id| format!("-{}", id))
+            .unwrap_or_default();
+        let path = self
+            .dir
+            .join(format!("{:04}-{}{}.json", number, fixture.phase, story));
+        let json = serde_json::to_string_pretty(fixture)?;
+        fs::write(&path, json)?;
+        Ok(path)
+    }
+}
+
+/// Hands out recorded sessions in place of running Claude.
+#[derive(Debug)]
+pub struct Replayer {
+    dir: PathBuf,
+    /// Fixtures in recorded order; `None` once replayed
+    fixtures: Mutex<Vec<Option<SessionFixture>>>,
+}
+
+impl Replayer {
+    /// Load every fixture in `dir`.
+    pub fn load(dir: impl Into<PathBuf>) -> Result<Self> {
+        let dir = dir.into();
+        if !dir.is_dir() {
+            return Err(Autom8Error::Fixture(format!(
+                "{} is not a directory of recorded sessions",
+                dir.display()
+            )));
+        }
+
+        let fixtures = fixture_files(&dir)?
+            .into_iter()
+            .map(|path| {
+                let content = fs::read_to_string(&path)?;
+                serde_json::from_str(&content).map(Some).map_err(|e| {
+                    Autom8Error::Fixture(format!(
+                        "{} is not a valid fixture: {}",
+                        path.display(),
+                        e
+                    ))
+                })
+            })
+            .collect::<Result<Vec<_>>>()?;
+        if fixtures.is_empty() {
+            return Err(Autom8Error::Fixture(format!(
+                "no recorded sessions in {}",
+                dir.display()
+            )));
+        }
+
+        Ok(Self {
+            dir,
+            fixtures: Mutex::new(fixtures),
+        })
+    }
+
+    /// Take the first recorded session for `phase` and `story_id` that
+    /// hasn't been replayed yet.
+    pub fn next(&self, phase: ClaudePhase, story_id: Option<&str>) -> Result<SessionFixture> {
+        let mut fixtures = self.fixtures.lock().unwrap_or_else(|e| e.into_inner());
+        fixtures
+            .iter_mut()
+            .find(|f| {
+                f.as_ref()
+                    .is_some_and(|f| f.phase == phase && f.story_id.as_deref() == story_id)
+            })
+            .and_then(Option::take)
+            .ok_or_else(|| {
+                let story = story_id
+                    .map(|id| format!(" for {}", id))
+                    .unwrap_or_default();
+                Autom8Error::Fixture(format!(
+                    "no recorded {} session{} is left in {}; the run has gone differently from the recording",
+                    phase,
+                    story,
+                    self.dir.display()
+                ))
+            })
+    }
+
+    /// How many sessions haven't been replayed yet.
+    pub fn remaining(&self) -> usize {
+        let fixtures = self.fixtures.lock().unwrap_or_else(|e| e.into_inner());
+        fixtures.iter().filter(|f| f.is_some()).count()
+    }
+}
+
+/// The fixture files in `dir`, sorted by name (so in recorded order).
+fn fixture_files(dir: &Path) -> Result<Vec<PathBuf>> {
+    let mut files: Vec<PathBuf> = fs::read_dir(dir)?
+        .filter_map(|entry| entry.ok().map(|e| e.path()))
+        .filter(|path| path.is_file() && path.extension().is_some_and(|ext| ext == "json"))
+        .collect();
+    files.sort();
+    Ok(files)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn fixture(phase: ClaudePhase, story_id: Option<&str>, text: &str) -> SessionFixture {
+        SessionFixture {
+            phase,
+            story_id: story_id.map(str::to_string),
+            prompt: "prompt".to_string(),
+            lines: vec![text.to_string()],
+            exit: SessionExit {
+                success: true,
+                code: Some(0),
+                status: "exit status: 0".to_string(),
+                stderr: String::new(),
+            },
+            patch: String::new(),
+            spec: None,
+        }
+    }
+
+    #[test]
+    fn test_recorded_sessions_replay_in_order_by_phase_and_story() {
+        let dir = tempfile::TempDir::new().unwrap();
+        let recorder = Recorder::new(dir.path()).unwrap();
+        recorder
+            .save(&fixture(ClaudePhase::Story, Some("US-001"), "one"))
+            .unwrap();
+        recorder
+            .save(&fixture(ClaudePhase::Story, Some("US-002"), "two"))
+            .unwrap();
+        let path = recorder
+            .save(&fixture(ClaudePhase::Story, Some("US-001"), "one again"))
+            .unwrap();
+        assert!(path.ends_with("0003-story-US-001.json"));
+        recorder
+            .save(&fixture(ClaudePhase::Review, None, "review"))
+            .unwrap();
+
+        // A recorder picks up numbering after existing fixtures
+        let resumed = Recorder::new(dir.path()).unwrap();
+        let path = resumed
+            .save(&fixture(ClaudePhase::Commit, None, "commit"))
+            .unwrap();
+        assert!(path.ends_with("0005-commit.json"));
+
+        let replayer = Replayer::load(dir.path()).unwrap();
+        assert_eq!(replayer.remaining(), 5);
+        let next = |phase, story| replayer.next(phase, story).unwrap().lines[0].clone();
+        assert_eq!(next(ClaudePhase::Review, None), "review");
+        assert_eq!(next(ClaudePhase::Story, Some("US-001")), "one");
+        assert_eq!(next(ClaudePhase::Story, Some("US-001")), "one again");
+        assert_eq!(next(ClaudePhase::Story, Some("US-002")), "two");
+        assert_eq!(replayer.remaining(), 1);
+
+        let err = replayer
+            .next(ClaudePhase::Story, Some("US-001"))
+            .unwrap_err();
+        assert!(err
+            .to_string()
+            .contains("no recorded story session for US-001 is left"));
+    }
+
+    #[test]
+    fn test_mode_from_config() {
+        let dir = tempfile::TempDir::new().unwrap();
+        let path = dir.path().to_string_lossy().into_owned();
+
+        let both = FixturesConfig {
+            record: Some(path.clone()),
+            replay: Some(path.clone()),
+        };
+        assert!(FixtureMode::from_config(&both).is_err());
+
+        let replay = FixturesConfig {
+            record: None,
+            replay: Some(path.clone()),
+        };
+        // Nothing recorded yet
+        assert!(FixtureMode::from_config(&replay).is_err());
+
+        let record = FixturesConfig {
+            record: Some(path),
+            replay: None,
+        };
+        let mode = FixtureMode::from_config(&record).unwrap();
+        assert!(matches!(mode, FixtureMode::Record(_)));
+        assert!(mode.describe().unwrap().starts_with("Recording"));
+        assert!(FixtureMode::from_config(&FixturesConfig::default())
+            .unwrap()
+            .describe()
+            .is_none());
+    }
+}
@@ -4,10 +4,14 @@
 //! with context from previous autom8 runs. Unlike other Claude modules that
 //! capture output, this module hands off control to an interactive session.
 
+use std::path::Path;
 use std::process::{Command, Stdio};
 
+use crate::config::{get_effective_config, Config};
 use crate::error::{Autom8Error, Result};
 
+use super::sandbox::{self, Sandbox};
+
 /// Result of an interactive improve session.
 #[derive(Debug)]
 pub struct ImproveSessionResult {
@@ -17,6 +21,24 @@ pub struct ImproveSessionResult {
     pub exit_code: Option<i32>,
 }
 
+/// The `claude` CLI from `config`, started with `prompt` for an interactive
+/// session in the current directory and confined to the sandbox `config`
+/// asks for, with `spec_dir` left writable so the session can save specs.
+pub fn interactive_claude(
+    config: &Config,
+    prompt: &str,
+    spec_dir: Option<&Path>,
+) -> Result<Command> {
+    let mut claude = Command::new(config.claude.binary());
+    claude.arg(prompt);
+    sandbox::confine(
+        Sandbox::from_config(config)?.as_ref(),
+        claude,
+        None,
+        spec_dir,
+    )
+}
+
 /// Spawn an interactive Claude session with the given prompt.
 ///
 /// This function hands off control to Claude for an interactive session.
@@ -25,13 +47,13 @@ pub struct ImproveSessionResult {
 ///
 /// # Arguments
 /// * `prompt` - The context prompt to inject into the Claude session
+/// * `spec_dir` - Spec directory the session may write to when sandboxed
 ///
 /// # Returns
 /// * `Ok(ImproveSessionResult)` - Session completed (check `success` for exit status)
 /// * `Err` - Failed to spawn Claude (e.g., not installed)
-pub fn run_improve_session(prompt: &str) -> Result<ImproveSessionResult> {
-    let status = Command::new("claude")
-        .arg(prompt)
+pub fn run_improve_session(prompt: &str, spec_dir: Option<&Path>) -> Result<ImproveSessionResult> {
+    let status = interactive_claude(&get_effective_config()?, prompt, spec_dir)?
         .stdin(Stdio::inherit())
         .stdout(Stdio::inherit())
         .stderr(Stdio::inherit())
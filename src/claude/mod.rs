@@ -6,6 +6,11 @@
 //! # Modules
 //!
 //! - [`types`] - Core result types and error handling
+//! - [`backend`] - The agent CLIs sessions run with
+//! - [`fixture`] - Recording and replaying Claude sessions
+//! - [`sandbox`] - Bubblewrap sandbox around agent processes
+//! - [`failure`] - Transient/permanent classification of Claude failures
+//! - [`pricing`] - Estimated cost of token usage per model
 //! - [`stream`] - JSON stream parsing for Claude CLI output
 //! - [`runner`] - Main story implementation runner
 //! - [`spec`] - Spec generation from markdown
@@ -14,29 +19,49 @@
 //! - [`pr_review`] - PR review analysis
 //! - [`improve`] - Interactive improve sessions
 //! - [`utils`] - Utility functions
+//! - [`watchdog`] - Wall-clock and inactivity limits for Claude subprocesses
 
+pub mod backend;
 mod commit;
+mod failure;
+pub mod fixture;
 mod improve;
 mod pr_review;
+mod pricing;
 mod review;
 mod runner;
+pub mod sandbox;
 mod spec;
 mod stream;
 mod types;
 mod utils;
+mod watchdog;
 
 // Re-export all public types and functions
-pub use commit::{run_for_commit, CommitOutcome, CommitResult};
-pub use improve::{run_improve_session, ImproveSessionResult};
+pub use backend::{AgentBackend, AgentEvent, ClaudeCliBackend, CommandBackend};
+pub use commit::{build_commit_prompt, run_for_commit, CommitOutcome, CommitResult};
+pub use failure::{classify_failure, ClaudeFailureKind};
+pub use improve::{interactive_claude, run_improve_session, ImproveSessionResult};
 pub use pr_review::{run_pr_review, PRReviewResult, PRReviewSummary};
+pub use pricing::{estimate_cost_usd, is_known_model, pricing_for_model, ModelPricing};
 pub use review::{
-    run_corrector, run_reviewer, CorrectorOutcome, CorrectorResult, ReviewOutcome, ReviewResult,
+    build_corrector_prompt, build_reviewer_prompt, run_corrector, run_reviewer, CorrectorOutcome,
+    CorrectorResult, ReviewOutcome, ReviewResult,
 };
-pub use runner::{run_claude, ClaudeRunner};
+pub(crate) use runner::Session;
+pub use runner::{build_story_prompt, run_claude, AgentSessions, ClaudeRunner};
 pub use spec::{run_for_spec_generation, SpecGenerationResult};
-pub use stream::{extract_text_from_stream_line, extract_usage_from_result_line};
-pub use types::{ClaudeErrorInfo, ClaudeOutcome, ClaudeResult, ClaudeStoryResult, ClaudeUsage};
+pub use stream::{
+    extract_result_error_from_stream_line, extract_text_from_stream_line,
+    extract_tool_activity_from_stream_line, extract_usage_from_result_line,
+};
+pub use types::{
+    ClaudeErrorInfo, ClaudeOutcome, ClaudePhase, ClaudeResult, ClaudeStoryResult, ClaudeUsage,
+    SessionExit,
+};
 pub use utils::{
-    build_knowledge_context, build_previous_context, extract_decisions, extract_files_context,
-    extract_patterns, extract_work_summary, fix_json_syntax, Decision, FileContextEntry, Pattern,
+    build_knowledge_context, build_previous_context, build_story_knowledge_context,
+    estimate_tokens, extract_decisions, extract_files_context, extract_patterns,
+    extract_work_summary, fix_json_syntax, Decision, FileContextEntry, KnowledgeContext, Pattern,
 };
+pub use watchdog::{ActivitySnapshot, ClaudeActivity};
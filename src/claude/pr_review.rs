@@ -2,15 +2,13 @@
 //!
 //! Analyzes PR comments and fixes real issues while ignoring red herrings.
 
-use std::io::{BufRead, BufReader, Write};
-use std::process::{Command, Stdio};
-
-use crate::error::{Autom8Error, Result};
+use crate::error::Result;
 use crate::gh::{BranchContext, PRContext};
 use crate::prompts::PR_REVIEW_PROMPT;
 
-use super::stream::extract_text_from_stream_line;
-use super::types::ClaudeErrorInfo;
+use super::backend::AgentEvent;
+use super::runner::{ClaudeRunner, Session};
+use super::types::{ClaudeErrorInfo, ClaudePhase};
 
 /// Summary of the PR review analysis
 #[derive(Debug, Clone, PartialEq, Default)]
@@ -77,6 +75,7 @@ pub enum PRReviewResult {
 
 /// Run the PR review agent to analyze PR comments and fix real issues.
 pub fn run_pr_review<F>(
+    runner: &ClaudeRunner,
     pr_context: &PRContext,
     branch_context: &BranchContext,
     mut on_output: F,
@@ -86,62 +85,16 @@ where
 {
     let prompt = build_pr_review_prompt(pr_context, branch_context);
 
-    let mut child = Command::new("claude")
-        .args([
-            "--dangerously-skip-permissions",
-            "--print",
-            "--output-format",
-            "stream-json",
-            "--verbose",
-        ])
-        .stdin(Stdio::piped())
-        .stdout(Stdio::piped())
-        .stderr(Stdio::piped())
-        .spawn()
-        .map_err(|e| Autom8Error::ClaudeError(format!("Failed to spawn claude: {}", e)))?;
-
-    if let Some(mut stdin) = child.stdin.take() {
-        stdin
-            .write_all(prompt.as_bytes())
-            .map_err(|e| Autom8Error::ClaudeError(format!("Failed to write to stdin: {}", e)))?;
-    }
-
-    let stderr = child.stderr.take();
-
-    let stdout = child
-        .stdout
-        .take()
-        .ok_or_else(|| Autom8Error::ClaudeError("Failed to capture stdout".into()))?;
-
-    let reader = BufReader::new(stdout);
     let mut accumulated_text = String::new();
-
-    for line in reader.lines() {
-        let line = line.map_err(|e| Autom8Error::ClaudeError(format!("Read error: {}", e)))?;
-
-        if let Some(text) = extract_text_from_stream_line(&line) {
-            on_output(&text);
-            accumulated_text.push_str(&text);
+    let exit = runner.run_session(Session::new(ClaudePhase::PrReview, &prompt), |event| {
+        if let AgentEvent::Text(text) = event {
+            on_output(text);
+            accumulated_text.push_str(text);
         }
-    }
+    })?;
 
-    let status = child
-        .wait()
-        .map_err(|e| Autom8Error::ClaudeError(format!("Wait error: {}", e)))?;
-
-    if !status.success() {
-        let stderr_content = stderr
-            .map(|s| std::io::read_to_string(s).unwrap_or_default())
-            .unwrap_or_default();
-        let error_info = ClaudeErrorInfo::from_process_failure(
-            status,
-            if stderr_content.is_empty() {
-                None
-            } else {
-                Some(stderr_content)
-            },
-        );
-        return Ok(PRReviewResult::Error(error_info));
+    if let Some(exit) = exit.filter(|e| !e.success) {
+        return Ok(PRReviewResult::Error(exit.error_info()));
     }
 
     let summary = PRReviewSummary::parse_from_output(&accumulated_text);
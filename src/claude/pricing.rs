@@ -0,0 +1,157 @@
+//! Estimated cost of Claude usage.
+//!
+//! The CLI reports token counts but not what they cost, so spend is estimated
+//! from list prices per model. Cache reads and writes are billed at their own
+//! rates, which matters for long agent sessions where most input is cached.
+
+use super::ClaudeUsage;
+
+/// Prices in USD per million tokens.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct ModelPricing {
+    pub input: f64,
+    pub output: f64,
+    /// Reading a cached prompt prefix.
+    pub cache_read: f64,
+    /// Writing a prompt prefix to the cache (5-minute TTL).
+    pub cache_write: f64,
+}
+
+const OPUS_4: ModelPricing = ModelPricing {
+    input: 15.0,
+    output: 75.0,
+    cache_read: 1.5,
+    cache_write: 18.75,
+};
+
+const OPUS_4_5: ModelPricing = ModelPricing {
+    input: 5.0,
+    output: 25.0,
+    cache_read: 0.5,
+    cache_write: 6.25,
+};
+
+const SONNET: ModelPricing = ModelPricing {
+    input: 3.0,
+    output: 15.0,
+    cache_read: 0.3,
+    cache_write: 3.75,
+};
+
+const HAIKU_4_5: ModelPricing = ModelPricing {
+    input: 1.0,
+    output: 5.0,
+    cache_read: 0.1,
+    cache_write: 1.25,
+};
+
+const HAIKU_3_5: ModelPricing = ModelPricing {
+    input: 0.8,
+    output: 4.0,
+    cache_read: 0.08,
+    cache_write: 1.0,
+};
+
+/// Pricing by model ID prefix. The first match wins, so more specific
+/// prefixes come first.
+const PRICING: &[(&str, ModelPricing)] = &[
+    ("claude-opus-4-5", OPUS_4_5),
+    ("claude-opus-4", OPUS_4),
+    ("claude-3-opus", OPUS_4),
+    ("claude-sonnet-4", SONNET),
+    ("claude-3-7-sonnet", SONNET),
+    ("claude-3-5-sonnet", SONNET),
+    ("claude-haiku-4-5", HAIKU_4_5),
+    ("claude-3-5-haiku", HAIKU_3_5),
+    // CLI aliases, which point at the latest model of each family
+    ("opus", OPUS_4_5),
+    ("sonnet", SONNET),
+    ("haiku", HAIKU_4_5),
+];
+
+/// Pricing for models this table doesn't know: the most expensive known
+/// rates, so a cost budget is never reached later than it should be.
+const UNKNOWN_MODEL: ModelPricing = OPUS_4;
+
+fn known_pricing(model: &str) -> Option<ModelPricing> {
+    PRICING
+        .iter()
+        .find(|(prefix, _)| model.starts_with(prefix))
+        .map(|(_, pricing)| *pricing)
+}
+
+/// Whether `model` has known pricing, rather than the fallback for unknown
+/// models.
+pub fn is_known_model(model: &str) -> bool {
+    known_pricing(model).is_some()
+}
+
+/// Pricing for a model ID such as `claude-sonnet-4-20250514`.
+///
+/// Unknown models, and usage that doesn't name its model, are priced at the
+/// most expensive known rates.
+pub fn pricing_for_model(model: Option<&str>) -> ModelPricing {
+    model.and_then(known_pricing).unwrap_or(UNKNOWN_MODEL)
+}
+
+/// Estimated cost of `usage` in USD.
+///
+/// Usage that was already priced model by model keeps that cost; otherwise
+/// all of its tokens are priced at `usage.model`'s rates.
+pub fn estimate_cost_usd(usage: &ClaudeUsage) -> f64 {
+    if let Some(cost) = usage.cost_usd {
+        return cost;
+    }
+    let pricing = pricing_for_model(usage.model.as_deref());
+    let per_token = |tokens: u64, per_million: f64| tokens as f64 * per_million / 1_000_000.0;
+    per_token(usage.input_tokens, pricing.input)
+        + per_token(usage.output_tokens, pricing.output)
+        + per_token(usage.cache_read_tokens, pricing.cache_read)
+        + per_token(usage.cache_creation_tokens, pricing.cache_write)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn usage(model: Option<&str>) -> ClaudeUsage {
+        ClaudeUsage {
+            input_tokens: 1_000_000,
+            output_tokens: 100_000,
+            cache_read_tokens: 2_000_000,
+            cache_creation_tokens: 400_000,
+            thinking_tokens: 0,
+            model: model.map(str::to_string),
+            cost_usd: None,
+        }
+    }
+
+    #[test]
+    fn test_pricing_matches_most_specific_prefix() {
+        assert_eq!(
+            pricing_for_model(Some("claude-opus-4-5-20251101")),
+            OPUS_4_5
+        );
+        assert_eq!(pricing_for_model(Some("claude-opus-4-1-20250805")), OPUS_4);
+        assert_eq!(
+            pricing_for_model(Some("claude-haiku-4-5-20251001")),
+            HAIKU_4_5
+        );
+        assert_eq!(pricing_for_model(Some("haiku")), HAIKU_4_5);
+        assert_eq!(pricing_for_model(Some("claude-opus-5")), OPUS_4);
+        assert!(!is_known_model("claude-opus-5"));
+        assert!(is_known_model("claude-sonnet-4-5-20250929"));
+        assert_eq!(pricing_for_model(None), OPUS_4);
+    }
+
+    #[test]
+    fn test_estimate_cost_includes_cache_rates() {
+        // 1M input ($3) + 100k output ($1.50) + 2M cache reads ($0.60)
+        // + 400k cache writes ($1.50)
+        let cost = estimate_cost_usd(&usage(Some("claude-sonnet-4-20250514")));
+        assert!((cost - 6.6).abs() < 1e-9, "{}", cost);
+
+        let opus = estimate_cost_usd(&usage(Some("claude-opus-4-20250514")));
+        assert!((opus - 33.0).abs() < 1e-9, "{}", opus);
+    }
+}
@@ -2,16 +2,16 @@
 //!
 //! Handles reviewing completed work and correcting issues.
 
-use std::io::{BufRead, BufReader, Write};
-use std::path::Path;
-use std::process::{Command, Stdio};
+use std::path::{Path, PathBuf};
 
-use crate::error::{Autom8Error, Result};
+use crate::error::Result;
+use crate::git;
 use crate::prompts::{CORRECTOR_PROMPT, REVIEWER_PROMPT};
 use crate::spec::Spec;
 
-use super::stream::{extract_text_from_stream_line, extract_usage_from_result_line};
-use super::types::{ClaudeErrorInfo, ClaudeUsage};
+use super::backend::AgentEvent;
+use super::runner::{ClaudeRunner, Session};
+use super::types::{ClaudeErrorInfo, ClaudePhase, ClaudeUsage};
 
 const REVIEW_FILE: &str = "autom8_review.md";
 
@@ -21,6 +21,8 @@ pub struct ReviewResult {
     pub outcome: ReviewOutcome,
     /// Token usage data from the Claude API response
     pub usage: Option<ClaudeUsage>,
+    /// Files the reviewer changed or created, which it isn't meant to do
+    pub changed_files: Vec<PathBuf>,
 }
 
 #[derive(Debug, Clone, PartialEq)]
@@ -45,7 +47,15 @@ pub enum CorrectorOutcome {
 }
 
 /// Run the reviewer agent to check completed work for quality issues.
+///
+/// The reviewer runs under `runner`'s time and stall limits, so it returns
+/// `Autom8Error::ClaudeTimeout` or `Autom8Error::ClaudeStalled` if it hits them.
+///
+/// The working tree, untracked files included, is snapshotted before and
+/// after the session, and any file whose content the reviewer changed is
+/// reported in [`ReviewResult::changed_files`].
 pub fn run_reviewer<F>(
+    runner: &ClaudeRunner,
     spec: &Spec,
     iteration: u32,
     max_iterations: u32,
@@ -56,68 +66,34 @@ where
 {
     let prompt = build_reviewer_prompt(spec, iteration, max_iterations);
 
-    let mut child = Command::new("claude")
-        .args([
-            "--dangerously-skip-permissions",
-            "--print",
-            "--output-format",
-            "stream-json",
-            "--verbose",
-        ])
-        .stdin(Stdio::piped())
-        .stdout(Stdio::piped())
-        .stderr(Stdio::piped())
-        .spawn()
-        .map_err(|e| Autom8Error::ClaudeError(format!("Failed to spawn claude: {}", e)))?;
-
-    if let Some(mut stdin) = child.stdin.take() {
-        stdin
-            .write_all(prompt.as_bytes())
-            .map_err(|e| Autom8Error::ClaudeError(format!("Failed to write to stdin: {}", e)))?;
-    }
-
-    let stderr = child.stderr.take();
-
-    let stdout = child
-        .stdout
-        .take()
-        .ok_or_else(|| Autom8Error::ClaudeError("Failed to capture stdout".into()))?;
-
-    let reader = BufReader::new(stdout);
     let mut usage: Option<ClaudeUsage> = None;
+    let dir = std::env::current_dir()?;
+    let before = if git::is_git_repo() {
+        Some(git::snapshot_tree_in(&dir)?)
+    } else {
+        None
+    };
 
-    for line in reader.lines() {
-        let line = line.map_err(|e| Autom8Error::ClaudeError(format!("Read error: {}", e)))?;
-
-        if let Some(text) = extract_text_from_stream_line(&line) {
-            on_output(&text);
-        }
-
-        // Try to extract usage from result events
-        if let Some(line_usage) = extract_usage_from_result_line(&line) {
-            usage = Some(line_usage);
-        }
-    }
+    let exit = runner.run_session(
+        Session::new(ClaudePhase::Review, &prompt),
+        |event| match event {
+            AgentEvent::Text(text) => on_output(text),
+            AgentEvent::Usage(line_usage) => usage = Some(line_usage.clone()),
+            _ => {}
+        },
+    )?;
+
+    let changed_files = match before {
+        Some(before) => changed_files(&dir, &before)?,
+        None => Vec::new(),
+    };
 
-    let status = child
-        .wait()
-        .map_err(|e| Autom8Error::ClaudeError(format!("Wait error: {}", e)))?;
-
-    if !status.success() {
-        let stderr_content = stderr
-            .map(|s| std::io::read_to_string(s).unwrap_or_default())
-            .unwrap_or_default();
-        let error_info = ClaudeErrorInfo::from_process_failure(
-            status,
-            if stderr_content.is_empty() {
-                None
-            } else {
-                Some(stderr_content)
-            },
-        );
+    if let Some(exit) = exit.filter(|e| !e.success) {
+        let error_info = exit.error_info();
         return Ok(ReviewResult {
             outcome: ReviewOutcome::Error(error_info),
             usage,
+            changed_files,
         });
     }
 
@@ -136,76 +112,51 @@ where
         ReviewOutcome::Pass
     };
 
-    Ok(ReviewResult { outcome, usage })
+    Ok(ReviewResult {
+        outcome,
+        usage,
+        changed_files,
+    })
+}
+
+/// Files in the working tree at `dir` whose content differs from the
+/// snapshot `before`, other than the review file.
+fn changed_files(dir: &Path, before: &str) -> Result<Vec<PathBuf>> {
+    let after = git::snapshot_tree_in(dir)?;
+    let mut changed = git::changed_paths_in(dir, before, &after)?;
+    changed.retain(|path| path != Path::new(REVIEW_FILE));
+    Ok(changed)
 }
 
 /// Run the corrector agent to fix issues identified by the reviewer.
-pub fn run_corrector<F>(spec: &Spec, iteration: u32, mut on_output: F) -> Result<CorrectorResult>
+///
+/// The corrector runs under `runner`'s time and stall limits, like
+/// [`run_reviewer`].
+pub fn run_corrector<F>(
+    runner: &ClaudeRunner,
+    spec: &Spec,
+    iteration: u32,
+    mut on_output: F,
+) -> Result<CorrectorResult>
 where
     F: FnMut(&str),
 {
     let max_iterations = 3;
     let prompt = build_corrector_prompt(spec, iteration, max_iterations);
 
-    let mut child = Command::new("claude")
-        .args([
-            "--dangerously-skip-permissions",
-            "--print",
-            "--output-format",
-            "stream-json",
-            "--verbose",
-        ])
-        .stdin(Stdio::piped())
-        .stdout(Stdio::piped())
-        .stderr(Stdio::piped())
-        .spawn()
-        .map_err(|e| Autom8Error::ClaudeError(format!("Failed to spawn claude: {}", e)))?;
-
-    if let Some(mut stdin) = child.stdin.take() {
-        stdin
-            .write_all(prompt.as_bytes())
-            .map_err(|e| Autom8Error::ClaudeError(format!("Failed to write to stdin: {}", e)))?;
-    }
-
-    let stderr = child.stderr.take();
-
-    let stdout = child
-        .stdout
-        .take()
-        .ok_or_else(|| Autom8Error::ClaudeError("Failed to capture stdout".into()))?;
-
-    let reader = BufReader::new(stdout);
     let mut usage: Option<ClaudeUsage> = None;
 
-    for line in reader.lines() {
-        let line = line.map_err(|e| Autom8Error::ClaudeError(format!("Read error: {}", e)))?;
-
-        if let Some(text) = extract_text_from_stream_line(&line) {
-            on_output(&text);
-        }
-
-        // Try to extract usage from result events
-        if let Some(line_usage) = extract_usage_from_result_line(&line) {
-            usage = Some(line_usage);
-        }
-    }
-
-    let status = child
-        .wait()
-        .map_err(|e| Autom8Error::ClaudeError(format!("Wait error: {}", e)))?;
-
-    if !status.success() {
-        let stderr_content = stderr
-            .map(|s| std::io::read_to_string(s).unwrap_or_default())
-            .unwrap_or_default();
-        let error_info = ClaudeErrorInfo::from_process_failure(
-            status,
-            if stderr_content.is_empty() {
-                None
-            } else {
-                Some(stderr_content)
-            },
-        );
+    let exit = runner.run_session(
+        Session::new(ClaudePhase::Correct, &prompt),
+        |event| match event {
+            AgentEvent::Text(text) => on_output(text),
+            AgentEvent::Usage(line_usage) => usage = Some(line_usage.clone()),
+            _ => {}
+        },
+    )?;
+
+    if let Some(exit) = exit.filter(|e| !e.success) {
+        let error_info = exit.error_info();
         return Ok(CorrectorResult {
             outcome: CorrectorOutcome::Error(error_info),
             usage,
@@ -218,7 +169,8 @@ where
     })
 }
 
-fn build_reviewer_prompt(spec: &Spec, iteration: u32, max_iterations: u32) -> String {
+/// The reviewer's prompt for review `iteration` of `max_iterations`.
+pub fn build_reviewer_prompt(spec: &Spec, iteration: u32, max_iterations: u32) -> String {
     let stories_context = spec
         .user_stories
         .iter()
@@ -245,7 +197,8 @@ fn build_reviewer_prompt(spec: &Spec, iteration: u32, max_iterations: u32) -> St
         .replace("{max_iterations}", &max_iterations.to_string())
 }
 
-fn build_corrector_prompt(spec: &Spec, iteration: u32, max_iterations: u32) -> String {
+/// The corrector's prompt for review `iteration` of `max_iterations`.
+pub fn build_corrector_prompt(spec: &Spec, iteration: u32, max_iterations: u32) -> String {
     let stories_context = spec
         .user_stories
         .iter()
@@ -275,7 +228,7 @@ fn build_corrector_prompt(spec: &Spec, iteration: u32, max_iterations: u32) -> S
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::spec::UserStory;
+    use crate::spec::{StoryStatus, UserStory};
 
     #[test]
     fn test_review_outcome_variants() {
@@ -301,6 +254,7 @@ mod tests {
         let result = ReviewResult {
             outcome: ReviewOutcome::Pass,
             usage: Some(usage.clone()),
+            changed_files: Vec::new(),
         };
         assert!(matches!(result.outcome, ReviewOutcome::Pass));
         assert!(result.usage.is_some());
@@ -312,11 +266,57 @@ mod tests {
         let result = ReviewResult {
             outcome: ReviewOutcome::IssuesFound,
             usage: None,
+            changed_files: Vec::new(),
         };
         assert!(matches!(result.outcome, ReviewOutcome::IssuesFound));
         assert!(result.usage.is_none());
     }
 
+    #[test]
+    fn test_changed_files_compares_working_tree_contents() {
+        let repo = tempfile::TempDir::new().unwrap();
+        let git = |args: &[&str]| {
+            let status = std::process::Command::new("git")
+                .args(args)
+                .current_dir(repo.path())
+                .output()
+                .unwrap()
+                .status;
+            assert!(status.success());
+        };
+        git(&["init", "-q"]);
+        let write = |path: &str, content: &str| std::fs::write(repo.path().join(path), content);
+        write("lib.rs", "let a = 1;\n").unwrap();
+        write("old.rs", "old\n").unwrap();
+        write("untracked.rs", "draft\n").unwrap();
+        git(&["add", "lib.rs", "old.rs"]);
+        git(&[
+            "-c",
+            "user.name=t",
+            "-c",
+            "user.email=t@t",
+            "commit",
+            "-qm",
+            "init",
+        ]);
+        write("lib.rs", "let a = 2;\n").unwrap();
+
+        let before = git::snapshot_tree_in(repo.path()).unwrap();
+        assert!(changed_files(repo.path(), &before).unwrap().is_empty());
+
+        // Same line counts as before, an untracked edit and a new file
+        write("lib.rs", "let a = 3;\n").unwrap();
+        write("untracked.rs", "edited\n").unwrap();
+        write("new.rs", "new\n").unwrap();
+        std::fs::remove_file(repo.path().join("old.rs")).unwrap();
+        write(REVIEW_FILE, "issues\n").unwrap();
+
+        assert_eq!(
+            changed_files(repo.path(), &before).unwrap(),
+            ["lib.rs", "new.rs", "old.rs", "untracked.rs"].map(PathBuf::from)
+        );
+    }
+
     #[test]
     fn test_corrector_outcome_variants() {
         let complete = CorrectorOutcome::Complete;
@@ -358,6 +358,7 @@ mod tests {
     #[test]
     fn test_build_reviewer_prompt() {
         let spec = Spec {
+            schema_version: crate::spec::SPEC_SCHEMA_VERSION,
             project: "TestProject".into(),
             branch_name: "test-branch".into(),
             description: "A test feature description".into(),
@@ -367,9 +368,12 @@ mod tests {
                 description: "First story description".into(),
                 acceptance_criteria: vec!["Criterion A".into()],
                 priority: 1,
-                passes: true,
+                status: StoryStatus::done(),
                 notes: String::new(),
+                depends_on: Vec::new(),
+                verify: None,
             }],
+            verify: Vec::new(),
         };
 
         let prompt = build_reviewer_prompt(&spec, 1, 3);
@@ -2,21 +2,25 @@
 //!
 //! Handles running Claude to implement individual user stories.
 
-use std::io::{BufRead, BufReader, Write};
-use std::path::Path;
-use std::process::{Child, Command, Stdio};
+use std::fs;
+use std::io::{BufRead, BufReader};
+use std::path::{Path, PathBuf};
+use std::process::{Child, ExitStatus};
 use std::sync::{Arc, Mutex};
+use std::time::Duration;
 
+use crate::config::Config;
 use crate::error::{Autom8Error, Result};
+use crate::git;
 use crate::knowledge::ProjectKnowledge;
 use crate::spec::{Spec, UserStory};
 use crate::state::IterationRecord;
 
-use super::stream::{extract_text_from_stream_line, extract_usage_from_result_line};
-use super::types::{ClaudeErrorInfo, ClaudeOutcome, ClaudeStoryResult, ClaudeUsage};
-use super::utils::{build_knowledge_context, build_previous_context, extract_work_summary};
-
-const COMPLETION_SIGNAL: &str = "<promise>COMPLETE</promise>";
+use super::backend::{self, AgentBackend, AgentEvent, ClaudeCliBackend};
+use super::fixture::{FixtureMode, SessionFixture};
+use super::types::{ClaudeOutcome, ClaudePhase, ClaudeStoryResult, ClaudeUsage, SessionExit};
+use super::utils::{build_previous_context, build_story_knowledge_context, extract_work_summary};
+use super::watchdog::{ClaudeActivity, Watchdog};
 
 /// Manages a running Claude subprocess, allowing it to be killed for cleanup.
 ///
@@ -26,16 +30,167 @@ const COMPLETION_SIGNAL: &str = "<promise>COMPLETE</promise>";
 #[derive(Clone)]
 pub struct ClaudeRunner {
     child: Arc<Mutex<Option<Child>>>,
+    /// Directory to run Claude in. `None` uses the current directory.
+    working_dir: Option<PathBuf>,
+    /// Wall-clock limit for one subprocess. `None` means no limit.
+    timeout: Option<Duration>,
+    /// How long the subprocess may go without output before it is killed.
+    stall_limit: Option<Duration>,
+    /// When the subprocess last wrote a line, and its last tool call.
+    activity: ClaudeActivity,
+    /// Whether sessions run Claude, are recorded or are replayed.
+    fixtures: FixtureMode,
+    /// The agent CLI sessions are run with.
+    backend: Arc<dyn AgentBackend>,
+    /// Estimated tokens of project knowledge in a story prompt. `None`
+    /// means no limit.
+    knowledge_budget: Option<u64>,
+}
+
+/// How a run's agent sessions are started: the backend, with the sandbox
+/// its processes run in, and whether sessions are recorded or replayed.
+///
+/// Built once from the run's config snapshot, so every session of the run
+/// follows the config the run started with, and recorded or replayed
+/// sessions keep their order across the run's runners.
+#[derive(Debug, Clone)]
+pub struct AgentSessions {
+    backend: Arc<dyn AgentBackend>,
+    fixtures: FixtureMode,
+}
+
+impl AgentSessions {
+    /// The backend, sandbox and fixture mode `config` asks for.
+    ///
+    /// Fails if the `[agent]` table is incomplete, bubblewrap can't be run
+    /// or the fixture settings conflict.
+    pub fn from_config(config: &Config) -> Result<Self> {
+        Ok(Self {
+            backend: backend::from_config(config)?,
+            fixtures: FixtureMode::from_config(&config.fixtures)?,
+        })
+    }
+
+    /// A runner for one of the run's sessions.
+    pub fn runner(&self) -> ClaudeRunner {
+        ClaudeRunner::new()
+            .with_backend(self.backend.clone())
+            .with_fixtures(self.fixtures.clone())
+    }
+
+    /// The model sessions for `phase` are asked to use, if the backend
+    /// chooses one.
+    pub fn model(&self, phase: ClaudePhase) -> Option<String> {
+        self.backend.model(phase)
+    }
+
+    /// A line saying where sessions are recorded to or replayed from, if
+    /// they aren't live.
+    pub fn describe(&self) -> Option<String> {
+        self.fixtures.describe()
+    }
+}
+
+impl Default for AgentSessions {
+    /// Live sessions of the Claude CLI, unconfined.
+    fn default() -> Self {
+        Self {
+            backend: Arc::new(ClaudeCliBackend::default()),
+            fixtures: FixtureMode::Live,
+        }
+    }
+}
+
+/// What a Claude session is for and what it is told.
+pub(crate) struct Session<'a> {
+    pub phase: ClaudePhase,
+    pub prompt: &'a str,
+    /// The story the session works on, if any
+    pub story_id: Option<&'a str>,
+    /// A spec file outside the working tree that the session may edit, so
+    /// it is recorded and replayed along with the working tree
+    pub spec_path: Option<&'a Path>,
+}
+
+impl<'a> Session<'a> {
+    pub fn new(phase: ClaudePhase, prompt: &'a str) -> Self {
+        Self {
+            phase,
+            prompt,
+            story_id: None,
+            spec_path: None,
+        }
+    }
 }
 
 impl ClaudeRunner {
     /// Creates a new `ClaudeRunner` with no active subprocess.
+    ///
+    /// Sessions run the Claude CLI live and unconfined; runners for a run
+    /// come from its [`AgentSessions`] instead.
     pub fn new() -> Self {
         Self {
             child: Arc::new(Mutex::new(None)),
+            working_dir: None,
+            timeout: None,
+            stall_limit: None,
+            activity: ClaudeActivity::new(),
+            fixtures: FixtureMode::Live,
+            backend: Arc::new(ClaudeCliBackend::default()),
+            knowledge_budget: None,
         }
     }
 
+    /// Run this runner's sessions with `backend` instead of the Claude CLI.
+    pub fn with_backend(mut self, backend: Arc<dyn AgentBackend>) -> Self {
+        self.backend = backend;
+        self
+    }
+
+    /// Record or replay this runner's sessions as `mode` says, instead of
+    /// running them live.
+    pub fn with_fixtures(mut self, mode: FixtureMode) -> Self {
+        self.fixtures = mode;
+        self
+    }
+
+    /// Run Claude in `dir` instead of the current directory.
+    ///
+    /// Used for parallel stories, which each run in their own worktree.
+    pub fn with_working_dir(mut self, dir: impl Into<PathBuf>) -> Self {
+        self.working_dir = Some(dir.into());
+        self
+    }
+
+    /// Kill the subprocess if it runs longer than `timeout`.
+    ///
+    /// A killed run returns `Autom8Error::ClaudeTimeout`. `None` means no limit.
+    pub fn with_timeout(mut self, timeout: Option<Duration>) -> Self {
+        self.timeout = timeout;
+        self
+    }
+
+    /// Kill the subprocess if it writes no output for `limit`.
+    ///
+    /// A killed run returns `Autom8Error::ClaudeStalled`. `None` means no limit.
+    pub fn with_stall_limit(mut self, limit: Option<Duration>) -> Self {
+        self.stall_limit = limit;
+        self
+    }
+
+    /// Fit the project knowledge in story prompts to `budget` estimated
+    /// tokens. `None` means no limit.
+    pub fn with_knowledge_budget(mut self, budget: Option<u64>) -> Self {
+        self.knowledge_budget = budget;
+        self
+    }
+
+    /// What the current (or last) subprocess was doing, updated as its
+    /// output is read.
+    pub fn activity(&self) -> &ClaudeActivity {
+        &self.activity
+    }
+
     /// Kills the subprocess if it is running.
     ///
     /// This method:
@@ -51,7 +206,7 @@ impl ClaudeRunner {
 
         if let Some(mut child) = child_guard.take() {
             // Kill the process
-            if let Err(e) = child.kill() {
+            if let Err(e) = self.backend.kill(&mut child) {
                 // Process may have already exited - not an error
                 if e.kind() != std::io::ErrorKind::InvalidInput {
                     return Err(Autom8Error::ClaudeError(format!(
@@ -93,6 +248,68 @@ impl ClaudeRunner {
         })?;
         Ok(child_guard.take())
     }
+
+    /// Stream a spawned subprocess's stdout line by line, then wait for it.
+    ///
+    /// The child is stored so `kill()` can reach it while output is read.
+    /// If the runner's timeout passes first, the child is killed and
+    /// `Autom8Error::ClaudeTimeout` is returned; if it goes quiet for longer
+    /// than the stall limit, `Autom8Error::ClaudeStalled`. Returns `Ok(None)` if the
+    /// child was killed some other way (e.g. by a signal handler).
+    pub(crate) fn stream_output<F>(
+        &self,
+        mut child: Child,
+        mut on_line: F,
+    ) -> Result<Option<ExitStatus>>
+    where
+        F: FnMut(&str),
+    {
+        let stdout = child
+            .stdout
+            .take()
+            .ok_or_else(|| Autom8Error::ClaudeError("Failed to capture stdout".into()))?;
+
+        // Store the child so kill() can access it
+        self.set_child(child)?;
+
+        self.activity.reset();
+        let runner = self.clone();
+        let watchdog = Watchdog::start(
+            self.timeout,
+            self.stall_limit,
+            self.activity.clone(),
+            move || {
+                let _ = runner.kill();
+            },
+        );
+
+        let mut read_result = Ok(());
+        for line in BufReader::new(stdout).lines() {
+            match line {
+                Ok(line) => {
+                    self.activity.record_line();
+                    on_line(&line);
+                }
+                Err(e) => {
+                    read_result = Err(Autom8Error::ClaudeError(format!("Read error: {}", e)));
+                    break;
+                }
+            }
+        }
+
+        // A timeout or stall explains any read error, so report it first
+        watchdog.finish()?;
+        read_result?;
+
+        // Take the child back to wait for completion
+        match self.take_child()? {
+            Some(mut child) => child
+                .wait()
+                .map(Some)
+                .map_err(|e| Autom8Error::ClaudeError(format!("Wait error: {}", e))),
+            None => Ok(None),
+        }
+    }
 }
 
 impl Default for ClaudeRunner {
@@ -101,6 +318,129 @@ impl Default for ClaudeRunner {
     }
 }
 
+impl ClaudeRunner {
+    /// Run one agent session, passing the events in each line of its output
+    /// to `on_event`.
+    ///
+    /// Returns how the process exited, or `None` if it was killed. Runs under
+    /// the runner's time and stall limits like [`Self::stream_output`]. When
+    /// sessions are recorded, the session's raw output is saved as a fixture
+    /// once it exits; when they are replayed, the next matching fixture stands
+    /// in for the process.
+    pub(crate) fn run_session<F>(
+        &self,
+        session: Session<'_>,
+        mut on_event: F,
+    ) -> Result<Option<SessionExit>>
+    where
+        F: FnMut(&AgentEvent),
+    {
+        let model = self.backend.model(session.phase);
+        let mut on_line = |line: &str| {
+            for mut event in self.backend.parse_line(line) {
+                match &mut event {
+                    AgentEvent::ToolUse(tool) => self.activity.record_tool(tool),
+                    // Usage is priced by model, so name the one that was asked for
+                    // when the agent doesn't say
+                    AgentEvent::Usage(usage) if usage.model.is_none() => {
+                        usage.model = model.clone();
+                    }
+                    _ => {}
+                }
+                on_event(&event);
+            }
+        };
+
+        let dir = self
+            .working_dir
+            .clone()
+            .unwrap_or_else(|| PathBuf::from("."));
+        match &self.fixtures {
+            FixtureMode::Live => self.spawn_session(&session, on_line),
+            FixtureMode::Replay(replayer) => {
+                let fixture = replayer.next(session.phase, session.story_id)?;
+                self.activity.reset();
+                for line in &fixture.lines {
+                    self.activity.record_line();
+                    on_line(line);
+                }
+                git::apply_patch_in(&dir, &fixture.patch)?;
+                if let (Some(path), Some(spec)) = (session.spec_path, &fixture.spec) {
+                    fs::write(path, spec)?;
+                }
+                Ok(Some(fixture.exit))
+            }
+            FixtureMode::Record(recorder) => {
+                // Outside a git repository there are no file edits to record
+                let before = git::snapshot_tree_in(&dir).ok();
+                let spec_before = session.spec_path.and_then(|p| fs::read_to_string(p).ok());
+
+                let mut lines = Vec::new();
+                let exit = self.spawn_session(&session, |line| {
+                    lines.push(line.to_string());
+                    on_line(line);
+                })?;
+                // A session that was killed part way through isn't recorded
+                let Some(exit) = exit else {
+                    return Ok(None);
+                };
+
+                let patch = match before {
+                    Some(before) => {
+                        let after = git::snapshot_tree_in(&dir)?;
+                        git::diff_trees_in(&dir, &before, &after)?
+                    }
+                    None => String::new(),
+                };
+                let spec = session
+                    .spec_path
+                    .and_then(|p| fs::read_to_string(p).ok())
+                    .filter(|after| spec_before.as_ref() != Some(after));
+                recorder.save(&SessionFixture {
+                    phase: session.phase,
+                    story_id: session.story_id.map(str::to_string),
+                    prompt: session.prompt.to_string(),
+                    lines,
+                    exit: exit.clone(),
+                    patch,
+                    spec,
+                })?;
+                Ok(Some(exit))
+            }
+        }
+    }
+
+    /// Spawn the agent for `session` and stream its output lines.
+    fn spawn_session<F>(&self, session: &Session<'_>, on_line: F) -> Result<Option<SessionExit>>
+    where
+        F: FnMut(&str),
+    {
+        let mut child = self.backend.spawn(
+            session.phase,
+            session.prompt,
+            self.working_dir.as_deref(),
+            session.spec_path,
+        )?;
+
+        // Take stderr handle before storing child
+        let stderr = child.stderr.take();
+
+        let status = self.stream_output(child, on_line)?;
+        Ok(status.map(|status| {
+            // Only read stderr after a failure: on success, processes the
+            // agent left running could keep it open
+            let stderr = if status.success() {
+                String::new()
+            } else {
+                stderr
+                    .map(|s| std::io::read_to_string(s).unwrap_or_default())
+                    .unwrap_or_default()
+            };
+            SessionExit::from_status(status, stderr)
+        }))
+    }
+}
+
 impl ClaudeRunner {
     /// Runs Claude to implement a user story.
     ///
@@ -128,106 +468,49 @@ impl ClaudeRunner {
     where
         F: FnMut(&str),
     {
-        let previous_context = build_previous_context(previous_iterations);
-        let knowledge_context = build_knowledge_context(knowledge);
-        let prompt = build_prompt(
+        let prompt = build_story_prompt(
             spec,
             story,
             spec_path,
-            knowledge_context.as_deref(),
-            previous_context.as_deref(),
+            previous_iterations,
+            knowledge,
+            self.knowledge_budget,
         );
+        let session = Session {
+            story_id: Some(&story.id),
+            spec_path: Some(spec_path),
+            ..Session::new(ClaudePhase::Story, &prompt)
+        };
 
-        let mut child = Command::new("claude")
-            .args([
-                "--dangerously-skip-permissions",
-                "--print",
-                "--output-format",
-                "stream-json",
-                "--verbose",
-            ])
-            .stdin(Stdio::piped())
-            .stdout(Stdio::piped())
-            .stderr(Stdio::piped())
-            .spawn()
-            .map_err(|e| Autom8Error::ClaudeError(format!("Failed to spawn claude: {}", e)))?;
-
-        // Write prompt to stdin - take and drop stdin handle to close it
-        if let Some(mut stdin) = child.stdin.take() {
-            stdin.write_all(prompt.as_bytes()).map_err(|e| {
-                Autom8Error::ClaudeError(format!("Failed to write to stdin: {}", e))
-            })?;
-            // stdin is dropped here, closing the handle
-        }
-
-        // Take stderr handle before storing child
-        let stderr = child.stderr.take();
-
-        // Take stdout handle before storing child
-        let stdout = child
-            .stdout
-            .take()
-            .ok_or_else(|| Autom8Error::ClaudeError("Failed to capture stdout".into()))?;
-
-        // Store the child so kill() can access it
-        self.set_child(child)?;
-
-        // Stream stdout and check for completion
-        let reader = BufReader::new(stdout);
-        let mut found_complete = false;
         let mut accumulated_text = String::new();
         let mut usage: Option<ClaudeUsage> = None;
+        let mut result_error: Option<String> = None;
 
-        for line in reader.lines() {
-            let line = line.map_err(|e| Autom8Error::ClaudeError(format!("Read error: {}", e)))?;
-
-            // Parse stream-json output and extract text content
-            if let Some(text) = extract_text_from_stream_line(&line) {
-                on_output(&text);
-                accumulated_text.push_str(&text);
-
-                if text.contains(COMPLETION_SIGNAL) || accumulated_text.contains(COMPLETION_SIGNAL)
-                {
-                    found_complete = true;
-                }
-            }
-
-            // Try to extract usage from result events
-            if let Some(line_usage) = extract_usage_from_result_line(&line) {
-                usage = Some(line_usage);
+        let exit = self.run_session(session, |event| match event {
+            AgentEvent::Text(text) => {
+                on_output(text);
+                accumulated_text.push_str(text);
             }
-        }
-
-        // Take the child back to wait for completion
-        let child = self.take_child()?;
-
-        if let Some(mut child) = child {
-            // Wait for process to complete
-            let status = child
-                .wait()
-                .map_err(|e| Autom8Error::ClaudeError(format!("Wait error: {}", e)))?;
+            AgentEvent::Usage(line_usage) => usage = Some(line_usage.clone()),
+            AgentEvent::Error(error) => result_error = Some(error.clone()),
+            AgentEvent::ToolUse(_) => {}
+        })?;
 
-            if !status.success() {
-                // Read stderr for error details
-                let stderr_content = stderr
-                    .map(|s| std::io::read_to_string(s).unwrap_or_default())
-                    .unwrap_or_default();
-                let error_info = ClaudeErrorInfo::from_process_failure(
-                    status,
-                    if stderr_content.is_empty() {
-                        None
-                    } else {
-                        Some(stderr_content)
-                    },
-                );
-                return Err(Autom8Error::ClaudeError(error_info.message.clone()));
-            }
+        if let Some(exit) = exit.filter(|e| !e.success) {
+            let error_info = exit.error_info().with_result_error(result_error);
+            // Reported as an outcome so the caller can retry transient failures
+            return Ok(ClaudeStoryResult {
+                outcome: ClaudeOutcome::Error(error_info),
+                work_summary: None,
+                full_output: accumulated_text,
+                usage,
+            });
         }
 
         // Extract work summary from accumulated output
         let work_summary = extract_work_summary(&accumulated_text);
 
-        let outcome = if found_complete {
+        let outcome = if self.backend.is_complete(&accumulated_text) {
             ClaudeOutcome::AllStoriesComplete
         } else {
             ClaudeOutcome::IterationComplete
@@ -269,6 +552,30 @@ where
     )
 }
 
+/// The prompt for one iteration of `story`, with the run's previous work and
+/// project knowledge injected. The knowledge is fitted to `knowledge_budget`
+/// estimated tokens (`None` means no limit).
+pub fn build_story_prompt(
+    spec: &Spec,
+    story: &UserStory,
+    spec_path: &Path,
+    previous_iterations: &[IterationRecord],
+    knowledge: &ProjectKnowledge,
+    knowledge_budget: Option<u64>,
+) -> String {
+    let previous_context = build_previous_context(previous_iterations);
+    let knowledge_context = build_story_knowledge_context(knowledge, Some(story), knowledge_budget);
+    build_prompt(
+        spec,
+        story,
+        spec_path,
+        knowledge_context
+            .as_ref()
+            .map(|context| context.text.as_str()),
+        previous_context.as_deref(),
+    )
+}
+
 fn build_prompt(
     spec: &Spec,
     story: &UserStory,
@@ -285,6 +592,25 @@ fn build_prompt(
 
     let spec_path_str = spec_path.display();
 
+    // Tell Claude which commands autom8 will use to check its work
+    let verify_commands = spec.verify_commands(story);
+    let verification_section = if verify_commands.is_empty() {
+        String::new()
+    } else {
+        format!(
+            r#"
+### Verification
+After you set `passes: true`, autom8 runs these commands. The story only counts as passing if every one exits 0:
+{}
+"#,
+            verify_commands
+                .iter()
+                .map(|c| format!("- `{}`", c))
+                .collect::<Vec<_>>()
+                .join("\n")
+        )
+    };
+
     // Build the project knowledge section if we have context
     let knowledge_section = match knowledge_context {
         Some(context) => format!(
@@ -325,7 +651,7 @@ Implement user story **{story_id}: {story_title}**
 
 ### Acceptance Criteria
 {acceptance_criteria}
-
+{verification}
 ## Instructions
 
 1. Implement the user story according to the acceptance criteria
@@ -389,6 +715,7 @@ These are optional - only include them when they add value for subsequent work.
         story_title = story.title,
         story_description = story.description,
         acceptance_criteria = acceptance_criteria,
+        verification = verification_section,
         spec_description = spec.description,
         spec_path = spec_path_str,
         knowledge = knowledge_section,
@@ -404,14 +731,19 @@ These are optional - only include them when they add value for subsequent work.
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::claude::fixture;
+    use crate::spec::StoryStatus;
+    use std::process::{Command, Stdio};
 
     #[test]
     fn test_build_prompt() {
         let spec = Spec {
+            schema_version: crate::spec::SPEC_SCHEMA_VERSION,
             project: "TestProject".into(),
             branch_name: "test-branch".into(),
             description: "A test project".into(),
             user_stories: vec![],
+            verify: Vec::new(),
         };
         let story = UserStory {
             id: "US-001".into(),
@@ -419,8 +751,10 @@ mod tests {
             description: "A test story".into(),
             acceptance_criteria: vec!["Criterion 1".into(), "Criterion 2".into()],
             priority: 1,
-            passes: false,
+            status: StoryStatus::default(),
             notes: String::new(),
+            depends_on: Vec::new(),
+            verify: None,
         };
         let spec_path = Path::new("/tmp/spec-test.json");
 
@@ -432,13 +766,55 @@ mod tests {
         assert!(!prompt.contains("Project Knowledge"));
     }
 
+    #[test]
+    fn test_build_prompt_lists_verify_commands() {
+        let mut spec = Spec {
+            schema_version: crate::spec::SPEC_SCHEMA_VERSION,
+            project: "TestProject".into(),
+            branch_name: "test-branch".into(),
+            description: "A test project".into(),
+            user_stories: vec![],
+            verify: vec!["cargo test".into()],
+        };
+        let mut story = UserStory {
+            id: "US-001".into(),
+            title: "Test Story".into(),
+            description: "A test story".into(),
+            acceptance_criteria: vec!["Criterion 1".into()],
+            priority: 1,
+            status: StoryStatus::default(),
+            notes: String::new(),
+            depends_on: Vec::new(),
+            verify: None,
+        };
+        let spec_path = Path::new("/tmp/spec-test.json");
+
+        let prompt = build_prompt(&spec, &story, spec_path, None, None);
+        assert!(prompt.contains("### Verification"));
+        assert!(prompt.contains("- `cargo test`"));
+
+        // A story-level list overrides the spec default
+        story.verify = Some(vec!["npm test".into()]);
+        let prompt = build_prompt(&spec, &story, spec_path, None, None);
+        assert!(prompt.contains("- `npm test`"));
+        assert!(!prompt.contains("cargo test"));
+
+        // An empty story-level list disables verification
+        story.verify = Some(Vec::new());
+        spec.verify.clear();
+        let prompt = build_prompt(&spec, &story, spec_path, None, None);
+        assert!(!prompt.contains("### Verification"));
+    }
+
     #[test]
     fn test_build_prompt_includes_structured_context_section() {
         let spec = Spec {
+            schema_version: crate::spec::SPEC_SCHEMA_VERSION,
             project: "TestProject".into(),
             branch_name: "test-branch".into(),
             description: "A test project".into(),
             user_stories: vec![],
+            verify: Vec::new(),
         };
         let story = UserStory {
             id: "US-001".into(),
@@ -446,8 +822,10 @@ mod tests {
             description: "A test story".into(),
             acceptance_criteria: vec!["Test criterion".into()],
             priority: 1,
-            passes: false,
+            status: StoryStatus::default(),
             notes: String::new(),
+            depends_on: Vec::new(),
+            verify: None,
         };
         let spec_path = Path::new("/tmp/spec-test.json");
 
@@ -458,10 +836,12 @@ mod tests {
     #[test]
     fn test_build_prompt_includes_files_context_instructions() {
         let spec = Spec {
+            schema_version: crate::spec::SPEC_SCHEMA_VERSION,
             project: "TestProject".into(),
             branch_name: "test-branch".into(),
             description: "A test project".into(),
             user_stories: vec![],
+            verify: Vec::new(),
         };
         let story = UserStory {
             id: "US-001".into(),
@@ -469,8 +849,10 @@ mod tests {
             description: "A test story".into(),
             acceptance_criteria: vec!["Test criterion".into()],
             priority: 1,
-            passes: false,
+            status: StoryStatus::default(),
             notes: String::new(),
+            depends_on: Vec::new(),
+            verify: None,
         };
         let spec_path = Path::new("/tmp/spec-test.json");
 
@@ -483,10 +865,12 @@ mod tests {
     #[test]
     fn test_build_prompt_includes_decisions_instructions() {
         let spec = Spec {
+            schema_version: crate::spec::SPEC_SCHEMA_VERSION,
             project: "TestProject".into(),
             branch_name: "test-branch".into(),
             description: "A test project".into(),
             user_stories: vec![],
+            verify: Vec::new(),
         };
         let story = UserStory {
             id: "US-001".into(),
@@ -494,8 +878,10 @@ mod tests {
             description: "A test story".into(),
             acceptance_criteria: vec!["Test criterion".into()],
             priority: 1,
-            passes: false,
+            status: StoryStatus::default(),
             notes: String::new(),
+            depends_on: Vec::new(),
+            verify: None,
         };
         let spec_path = Path::new("/tmp/spec-test.json");
 
@@ -508,10 +894,12 @@ mod tests {
     #[test]
     fn test_build_prompt_includes_patterns_instructions() {
         let spec = Spec {
+            schema_version: crate::spec::SPEC_SCHEMA_VERSION,
             project: "TestProject".into(),
             branch_name: "test-branch".into(),
             description: "A test project".into(),
             user_stories: vec![],
+            verify: Vec::new(),
         };
         let story = UserStory {
             id: "US-001".into(),
@@ -519,8 +907,10 @@ mod tests {
             description: "A test story".into(),
             acceptance_criteria: vec!["Test criterion".into()],
             priority: 1,
-            passes: false,
+            status: StoryStatus::default(),
             notes: String::new(),
+            depends_on: Vec::new(),
+            verify: None,
         };
         let spec_path = Path::new("/tmp/spec-test.json");
 
@@ -532,10 +922,12 @@ mod tests {
     #[test]
     fn test_build_prompt_structured_context_is_optional() {
         let spec = Spec {
+            schema_version: crate::spec::SPEC_SCHEMA_VERSION,
             project: "TestProject".into(),
             branch_name: "test-branch".into(),
             description: "A test project".into(),
             user_stories: vec![],
+            verify: Vec::new(),
         };
         let story = UserStory {
             id: "US-001".into(),
@@ -543,8 +935,10 @@ mod tests {
             description: "A test story".into(),
             acceptance_criteria: vec!["Test criterion".into()],
             priority: 1,
-            passes: false,
+            status: StoryStatus::default(),
             notes: String::new(),
+            depends_on: Vec::new(),
+            verify: None,
         };
         let spec_path = Path::new("/tmp/spec-test.json");
 
@@ -558,10 +952,12 @@ mod tests {
     #[test]
     fn test_build_prompt_with_empty_knowledge_no_section() {
         let spec = Spec {
+            schema_version: crate::spec::SPEC_SCHEMA_VERSION,
             project: "TestProject".into(),
             branch_name: "test-branch".into(),
             description: "A test project".into(),
             user_stories: vec![],
+            verify: Vec::new(),
         };
         let story = UserStory {
             id: "US-001".into(),
@@ -569,8 +965,10 @@ mod tests {
             description: "A test story".into(),
             acceptance_criteria: vec!["Test criterion".into()],
             priority: 1,
-            passes: false,
+            status: StoryStatus::default(),
             notes: String::new(),
+            depends_on: Vec::new(),
+            verify: None,
         };
         let spec_path = Path::new("/tmp/spec-test.json");
 
@@ -582,10 +980,12 @@ mod tests {
     #[test]
     fn test_build_prompt_with_knowledge_context() {
         let spec = Spec {
+            schema_version: crate::spec::SPEC_SCHEMA_VERSION,
             project: "TestProject".into(),
             branch_name: "test-branch".into(),
             description: "A test project".into(),
             user_stories: vec![],
+            verify: Vec::new(),
         };
         let story = UserStory {
             id: "US-002".into(),
@@ -593,8 +993,10 @@ mod tests {
             description: "A second test story".into(),
             acceptance_criteria: vec!["Test criterion".into()],
             priority: 2,
-            passes: false,
+            status: StoryStatus::default(),
             notes: String::new(),
+            depends_on: Vec::new(),
+            verify: None,
         };
         let spec_path = Path::new("/tmp/spec-test.json");
 
@@ -621,10 +1023,12 @@ mod tests {
     #[test]
     fn test_build_prompt_knowledge_appears_before_previous_work() {
         let spec = Spec {
+            schema_version: crate::spec::SPEC_SCHEMA_VERSION,
             project: "TestProject".into(),
             branch_name: "test-branch".into(),
             description: "A test project".into(),
             user_stories: vec![],
+            verify: Vec::new(),
         };
         let story = UserStory {
             id: "US-003".into(),
@@ -632,8 +1036,10 @@ mod tests {
             description: "A third story".into(),
             acceptance_criteria: vec!["Test criterion".into()],
             priority: 3,
-            passes: false,
+            status: StoryStatus::default(),
             notes: String::new(),
+            depends_on: Vec::new(),
+            verify: None,
         };
         let spec_path = Path::new("/tmp/spec-test.json");
 
@@ -664,10 +1070,12 @@ mod tests {
     #[test]
     fn test_build_prompt_with_previous_work_only() {
         let spec = Spec {
+            schema_version: crate::spec::SPEC_SCHEMA_VERSION,
             project: "TestProject".into(),
             branch_name: "test-branch".into(),
             description: "A test project".into(),
             user_stories: vec![],
+            verify: Vec::new(),
         };
         let story = UserStory {
             id: "US-002".into(),
@@ -675,8 +1083,10 @@ mod tests {
             description: "A second story".into(),
             acceptance_criteria: vec!["Test criterion".into()],
             priority: 2,
-            passes: false,
+            status: StoryStatus::default(),
             notes: String::new(),
+            depends_on: Vec::new(),
+            verify: None,
         };
         let spec_path = Path::new("/tmp/spec-test.json");
 
@@ -693,10 +1103,12 @@ mod tests {
     #[test]
     fn test_build_prompt_with_both_knowledge_and_previous_work() {
         let spec = Spec {
+            schema_version: crate::spec::SPEC_SCHEMA_VERSION,
             project: "TestProject".into(),
             branch_name: "test-branch".into(),
             description: "A test project".into(),
             user_stories: vec![],
+            verify: Vec::new(),
         };
         let story = UserStory {
             id: "US-003".into(),
@@ -704,8 +1116,10 @@ mod tests {
             description: "Build on previous work".into(),
             acceptance_criteria: vec!["Test criterion".into()],
             priority: 3,
-            passes: false,
+            status: StoryStatus::default(),
             notes: String::new(),
+            depends_on: Vec::new(),
+            verify: None,
         };
         let spec_path = Path::new("/tmp/spec-test.json");
 
@@ -735,10 +1149,12 @@ mod tests {
     #[test]
     fn test_build_prompt_knowledge_section_structure() {
         let spec = Spec {
+            schema_version: crate::spec::SPEC_SCHEMA_VERSION,
             project: "TestProject".into(),
             branch_name: "test-branch".into(),
             description: "A test project".into(),
             user_stories: vec![],
+            verify: Vec::new(),
         };
         let story = UserStory {
             id: "US-002".into(),
@@ -746,8 +1162,10 @@ mod tests {
             description: "Test description".into(),
             acceptance_criteria: vec!["Test".into()],
             priority: 1,
-            passes: false,
+            status: StoryStatus::default(),
             notes: String::new(),
+            depends_on: Vec::new(),
+            verify: None,
         };
         let spec_path = Path::new("/tmp/spec-test.json");
 
@@ -759,4 +1177,255 @@ mod tests {
         // followed by the content
         assert!(prompt.contains("## Project Knowledge\n\nTest knowledge content"));
     }
+
+    #[test]
+    fn test_stream_output_kills_child_after_timeout() {
+        // exec so the killed process is the one holding stdout open
+        let child = Command::new("sh")
+            .args(["-c", "echo started; exec sleep 30"])
+            .stdout(Stdio::piped())
+            .spawn()
+            .unwrap();
+        let runner = ClaudeRunner::new().with_timeout(Some(Duration::from_millis(200)));
+
+        let mut lines = Vec::new();
+        let result = runner.stream_output(child, |line| lines.push(line.to_string()));
+
+        assert!(matches!(result, Err(Autom8Error::ClaudeTimeout(_))));
+        assert_eq!(lines, vec!["started"]);
+        assert!(!runner.is_running());
+    }
+
+    #[test]
+    fn test_stream_output_returns_exit_status_within_timeout() {
+        let child = Command::new("sh")
+            .args(["-c", "echo done"])
+            .stdout(Stdio::piped())
+            .spawn()
+            .unwrap();
+        let runner = ClaudeRunner::new().with_timeout(Some(Duration::from_secs(30)));
+
+        let status = runner.stream_output(child, |_| {}).unwrap();
+        assert!(status.is_some_and(|s| s.success()));
+    }
+
+    #[test]
+    fn test_stream_output_kills_child_that_stops_writing() {
+        let child = Command::new("sh")
+            .args(["-c", "echo started; exec sleep 30"])
+            .stdout(Stdio::piped())
+            .spawn()
+            .unwrap();
+        let runner = ClaudeRunner::new()
+            .with_timeout(Some(Duration::from_secs(30)))
+            .with_stall_limit(Some(Duration::from_millis(200)));
+
+        let result = runner.stream_output(child, |_| {});
+
+        assert!(matches!(result, Err(Autom8Error::ClaudeStalled(_))));
+        assert!(!runner.is_running());
+    }
+
+    #[test]
+    fn test_replayed_story_session_streams_output_and_applies_edits() {
+        let repo = tempfile::TempDir::new().unwrap();
+        let git = |args: &[&str]| {
+            let status = Command::new("git")
+                .args(args)
+                .current_dir(repo.path())
+                .output()
+                .unwrap()
+                .status;
+            assert!(status.success());
+        };
+        git(&["init", "-q"]);
+        let file = repo.path().join("lib.rs");
+        fs::write(&file, "old\n").unwrap();
+
+        // The edit a recorded session made, as the recorder captures it
+        let before = git::snapshot_tree_in(repo.path()).unwrap();
+        fs::write(&file, "new\n").unwrap();
+        let after = git::snapshot_tree_in(repo.path()).unwrap();
+        let patch = git::diff_trees_in(repo.path(), &before, &after).unwrap();
+        fs::write(&file, "old\n").unwrap();
+
+        let fixtures = tempfile::TempDir::new().unwrap();
+        fixture::Recorder::new(fixtures.path())
+            .unwrap()
+            .save(&SessionFixture {
+                phase: ClaudePhase::Story,
+                story_id: Some("US-001".into()),
+                prompt: "prompt".into(),
+                lines: vec![
+                    r#"{"type":"assistant","message":{"content":[{"type":"text","text":"Done <promise>COMPLETE</promise>"}]}}"#.into(),
+                    r#"{"type":"result","subtype":"success","result":"done","usage":{"inputTokens":100,"outputTokens":50}}"#.into(),
+                ],
+                exit: SessionExit {
+                    success: true,
+                    code: Some(0),
+                    status: "exit status: 0".into(),
+                    stderr: String::new(),
+                },
+                patch,
+                spec: Some("{\"edited\": true}".into()),
+            })
+            .unwrap();
+        let replayer = fixture::Replayer::load(fixtures.path()).unwrap();
+
+        let story = UserStory {
+            id: "US-001".into(),
+            title: "Test Story".into(),
+            description: "A test story".into(),
+            acceptance_criteria: vec![],
+            priority: 1,
+            status: StoryStatus::default(),
+            notes: String::new(),
+            depends_on: Vec::new(),
+            verify: None,
+        };
+        let spec = Spec {
+            schema_version: crate::spec::SPEC_SCHEMA_VERSION,
+            project: "TestProject".into(),
+            branch_name: "test-branch".into(),
+            description: "A test project".into(),
+            user_stories: vec![story.clone()],
+            verify: Vec::new(),
+        };
+        let spec_path = fixtures.path().join("spec.json");
+
+        let runner = ClaudeRunner::new()
+            .with_working_dir(repo.path())
+            .with_fixtures(FixtureMode::Replay(Arc::new(replayer)));
+        let mut output = String::new();
+        let result = runner
+            .run(
+                &spec,
+                &story,
+                &spec_path,
+                &[],
+                &ProjectKnowledge::default(),
+                |text| output.push_str(text),
+            )
+            .unwrap();
+
+        assert_eq!(result.outcome, ClaudeOutcome::AllStoriesComplete);
+        assert!(output.contains("Done"));
+        assert_eq!(result.usage.unwrap().input_tokens, 100);
+        assert_eq!(fs::read_to_string(&file).unwrap(), "new\n");
+        assert_eq!(
+            fs::read_to_string(&spec_path).unwrap(),
+            "{\"edited\": true}"
+        );
+
+        // A second run of the story has nothing left to replay
+        let err = runner
+            .run(
+                &spec,
+                &story,
+                &spec_path,
+                &[],
+                &ProjectKnowledge::default(),
+                |_| {},
+            )
+            .unwrap_err();
+        assert!(matches!(err, Autom8Error::Fixture(_)));
+    }
+
+    #[test]
+    fn test_story_runs_with_command_backend() {
+        let script = r#"cat >/dev/null
+echo '{"type":"tool","name":"Bash: cargo test"}'
+echo '{"type":"text","text":"All done <promise>COMPLETE</promise>"}'
+echo '{"type":"usage","input_tokens":7,"output_tokens":3}'"#;
+        let backend = backend::CommandBackend::new(
+            "sh",
+            vec!["-c".into(), script.into()],
+            crate::config::LineProtocol::Jsonl,
+        );
+        let runner = ClaudeRunner::new()
+            .with_fixtures(FixtureMode::Live)
+            .with_backend(Arc::new(backend));
+
+        let story = UserStory {
+            id: "US-001".into(),
+            title: "Test Story".into(),
+            description: "A test story".into(),
+            acceptance_criteria: vec![],
+            priority: 1,
+            status: StoryStatus::default(),
+            notes: String::new(),
+            depends_on: Vec::new(),
+            verify: None,
+        };
+        let spec = Spec {
+            schema_version: crate::spec::SPEC_SCHEMA_VERSION,
+            project: "TestProject".into(),
+            branch_name: "test-branch".into(),
+            description: "A test project".into(),
+            user_stories: vec![story.clone()],
+            verify: Vec::new(),
+        };
+        let result = runner
+            .run(
+                &spec,
+                &story,
+                Path::new("/tmp/spec-test.json"),
+                &[],
+                &ProjectKnowledge::default(),
+                |_| {},
+            )
+            .unwrap();
+
+        assert_eq!(result.outcome, ClaudeOutcome::AllStoriesComplete);
+        assert_eq!(result.usage.unwrap().total_tokens(), 10);
+        assert_eq!(
+            runner.activity().snapshot().last_tool.as_deref(),
+            Some("Bash: cargo test")
+        );
+    }
+
+    #[test]
+    fn test_agent_sessions_follow_config() {
+        use crate::config::{AgentConfig, AgentKind, ClaudeConfig, LineProtocol};
+
+        let config = Config {
+            agent: AgentConfig {
+                backend: AgentKind::Command,
+                command: Some("sh".into()),
+                args: vec!["-c".into(), "cat >/dev/null; echo \"$AUTOM8_PHASE\"".into()],
+                protocol: LineProtocol::Text,
+            },
+            ..Default::default()
+        };
+        let agent = AgentSessions::from_config(&config).unwrap();
+        let mut output = String::new();
+        agent
+            .runner()
+            .run_session(Session::new(ClaudePhase::Review, "prompt"), |event| {
+                if let AgentEvent::Text(text) = event {
+                    output.push_str(text);
+                }
+            })
+            .unwrap();
+        assert_eq!(output, "review\n");
+
+        let claude = Config {
+            claude: ClaudeConfig {
+                model: Some("opus".into()),
+                ..Default::default()
+            },
+            ..Default::default()
+        };
+        let agent = AgentSessions::from_config(&claude).unwrap();
+        assert_eq!(agent.model(ClaudePhase::Story).as_deref(), Some("opus"));
+
+        let incomplete = Config {
+            agent: AgentConfig {
+                backend: AgentKind::Command,
+                ..Default::default()
+            },
+            ..Default::default()
+        };
+        assert!(AgentSessions::from_config(&incomplete).is_err());
+    }
 }
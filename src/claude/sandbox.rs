@@ -0,0 +1,430 @@
+//! Bubblewrap sandbox around agent processes.
+//!
+//! With `sandbox = "bwrap"`, every agent process autom8 starts runs inside a
+//! [bubblewrap](https://github.com/containers/bubblewrap) namespace. Only the
+//! session's working directory (the run's worktree), the repository's git
+//! directory, the directory of the spec the session updates, a private
+//! `/tmp`, the system directories and the paths in the `[bwrap]` config table
+//! are visible, and only the first four, Claude's own settings and
+//! `[bwrap] writable` can be written. The repository's hooks and git config
+//! stay read-only, since autom8 runs git outside the sandbox when it commits
+//! and merges.
+//!
+//! Agent CLIs are often installed under the home directory (`~/.local/bin`,
+//! nvm, `~/.npm-global`), which the sandbox otherwise hides, so the
+//! directories the agent binary is installed in are made visible too, and
+//! the agent is started once inside the sandbox before the run begins.
+
+use std::ffi::OsString;
+use std::path::{Path, PathBuf};
+use std::process::{Command, Stdio};
+
+use crate::config::{AgentKind, BwrapConfig, Config, SandboxKind};
+use crate::error::{Autom8Error, Result};
+
+/// The bubblewrap binary.
+pub const BWRAP: &str = "bwrap";
+
+/// System directories every sandbox can read, when they exist.
+const SYSTEM_PATHS: &[&str] = &[
+    "/usr",
+    "/bin",
+    "/sbin",
+    "/lib",
+    "/lib32",
+    "/lib64",
+    "/etc",
+    "/run/systemd/resolve",
+];
+
+/// Where the Claude CLI keeps its settings and credentials, writable in
+/// every sandbox so it can log in and store sessions.
+const CLAUDE_PATHS: &[&str] = &["~/.claude", "~/.claude.json"];
+
+/// What a session can reach besides its working directory.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct SessionPaths {
+    /// The repository's git directory, shared by its worktrees
+    pub git_dir: Option<PathBuf>,
+    /// The directory git runs hooks from, which is kept read-only
+    pub hooks_dir: Option<PathBuf>,
+    /// The directory of a spec file the session updates
+    pub spec_dir: Option<PathBuf>,
+}
+
+/// A bubblewrap namespace agent processes are confined to.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Sandbox {
+    read_only: Vec<PathBuf>,
+    writable: Vec<PathBuf>,
+    network: bool,
+}
+
+impl Sandbox {
+    pub fn new(config: &BwrapConfig) -> Self {
+        let paths = |paths: &[String]| paths.iter().map(|p| expand_home(p)).collect();
+        Self {
+            read_only: paths(&config.read_only),
+            writable: paths(&config.writable),
+            network: config.network,
+        }
+    }
+
+    /// The sandbox `config` asks for, if any, after checking that bubblewrap
+    /// can be run and the agent CLI starts inside it.
+    pub fn from_config(config: &Config) -> Result<Option<Self>> {
+        match config.sandbox {
+            SandboxKind::None => Ok(None),
+            SandboxKind::Bwrap => {
+                check_bwrap()?;
+                let mut sandbox = Self::new(&config.bwrap);
+                let agent = match config.agent.backend {
+                    AgentKind::Claude => None,
+                    AgentKind::Command => config.agent.command.as_deref(),
+                };
+                for binary in [Some(config.claude.binary()), agent].into_iter().flatten() {
+                    for dir in install_dirs(binary) {
+                        if !sandbox.read_only.contains(&dir) {
+                            sandbox.read_only.push(dir);
+                        }
+                    }
+                }
+                match agent {
+                    None => sandbox.probe(config.claude.binary(), &["--version"])?,
+                    Some(command) => sandbox.probe("sh", &["-c", "command -v \"$0\"", command])?,
+                }
+                Ok(Some(sandbox))
+            }
+        }
+    }
+
+    /// Run `program` with `args` inside the sandbox, in the current
+    /// directory, and fail with the reason if it doesn't succeed.
+    fn probe(&self, program: &str, args: &[&str]) -> Result<()> {
+        let mut command = Command::new(program);
+        command.args(args);
+        let output = self
+            .wrap(
+                &command,
+                &std::env::current_dir()?,
+                &SessionPaths::default(),
+            )
+            .stdin(Stdio::null())
+            .output()
+            .map_err(|e| Autom8Error::Sandbox(format!("could not run `{}`: {}", BWRAP, e)))?;
+        if output.status.success() {
+            return Ok(());
+        }
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        let reason = stderr
+            .lines()
+            .find(|line| !line.trim().is_empty())
+            .map_or_else(|| output.status.to_string(), str::to_string);
+        Err(Autom8Error::Sandbox(format!(
+            "the agent CLI doesn't start inside the sandbox (`{} {}`: {}). Add the \
+             directories it and its runtime are installed in to `read_only` in the \
+             [bwrap] config table",
+            program,
+            args.join(" "),
+            reason.trim()
+        )))
+    }
+
+    /// `command`, run through bubblewrap with `working_dir` writable and as
+    /// the current directory, and `paths` reachable.
+    pub fn wrap(&self, command: &Command, working_dir: &Path, paths: &SessionPaths) -> Command {
+        let mut wrapped = Command::new(BWRAP);
+        wrapped.args(self.args(working_dir, paths));
+        wrapped.arg("--").arg(command.get_program());
+        wrapped.args(command.get_args());
+        for (key, value) in command.get_envs() {
+            match value {
+                Some(value) => wrapped.env(key, value),
+                None => wrapped.env_remove(key),
+            };
+        }
+        wrapped.current_dir(working_dir);
+        wrapped
+    }
+
+    /// The bubblewrap options, up to the `--` before the command.
+    fn args(&self, working_dir: &Path, paths: &SessionPaths) -> Vec<OsString> {
+        let mut args: Vec<OsString> = ["--die-with-parent", "--unshare-all"]
+            .into_iter()
+            .map(OsString::from)
+            .collect();
+        if self.network {
+            args.push("--share-net".into());
+        }
+        for arg in ["--proc", "/proc", "--dev", "/dev", "--tmpfs", "/tmp"] {
+            args.push(arg.into());
+        }
+        let system = SYSTEM_PATHS.iter().map(PathBuf::from);
+        for path in system.chain(self.read_only.iter().cloned()) {
+            bind(&mut args, "--ro-bind-try", &path);
+        }
+        let claude = CLAUDE_PATHS.iter().map(|p| expand_home(p));
+        for path in claude.chain(self.writable.iter().cloned()) {
+            bind(&mut args, "--bind-try", &path);
+        }
+        if let Some(spec_dir) = &paths.spec_dir {
+            bind(&mut args, "--bind-try", spec_dir);
+        }
+        if let Some(git_dir) = &paths.git_dir {
+            bind(&mut args, "--bind", git_dir);
+        }
+        bind(&mut args, "--bind", working_dir);
+        // Mounted last so they stay read-only inside a writable git dir or
+        // working directory
+        if let Some(hooks_dir) = &paths.hooks_dir {
+            bind(&mut args, "--ro-bind-try", hooks_dir);
+        }
+        if let Some(git_dir) = &paths.git_dir {
+            bind(&mut args, "--ro-bind-try", &git_dir.join("config"));
+        }
+        args.push("--chdir".into());
+        args.push(working_dir.into());
+        for arg in ["--setenv", "TMPDIR", "/tmp"] {
+            args.push(arg.into());
+        }
+        args
+    }
+}
+
+/// Bind `path` at the same place inside the sandbox.
+fn bind(args: &mut Vec<OsString>, option: &str, path: &Path) {
+    args.push(option.into());
+    args.push(path.into());
+    args.push(path.into());
+}
+
+/// `path` with a leading `~` replaced by the home directory.
+fn expand_home(path: &str) -> PathBuf {
+    let home = dirs::home_dir().unwrap_or_default();
+    match path.strip_prefix('~') {
+        Some("") => home,
+        Some(rest) if rest.starts_with('/') => home.join(&rest[1..]),
+        _ => PathBuf::from(path),
+    }
+}
+
+/// The directories `binary` is installed in, outside the system
+/// directories: the one it is found in on `PATH` and, if that is a link,
+/// the one the link points into.
+fn install_dirs(binary: &str) -> Vec<PathBuf> {
+    let found = if binary.contains('/') {
+        Some(expand_home(binary)).filter(|path| path.is_file())
+    } else {
+        std::env::var_os("PATH").and_then(|paths| {
+            std::env::split_paths(&paths)
+                .map(|dir| dir.join(binary))
+                .find(|path| path.is_file())
+        })
+    };
+    let Some(found) = found.and_then(|path| std::path::absolute(path).ok()) else {
+        return Vec::new();
+    };
+
+    let mut dirs = Vec::new();
+    for path in [Some(found.clone()), found.canonicalize().ok()]
+        .into_iter()
+        .flatten()
+    {
+        if let Some(dir) = path.parent() {
+            let system = SYSTEM_PATHS.iter().any(|system| dir.starts_with(system));
+            if !system && !dirs.iter().any(|known: &PathBuf| known == dir) {
+                dirs.push(dir.to_path_buf());
+            }
+        }
+    }
+    dirs
+}
+
+/// Check that bubblewrap is installed and runs.
+fn check_bwrap() -> Result<()> {
+    let status = Command::new(BWRAP)
+        .arg("--version")
+        .stdout(Stdio::null())
+        .stderr(Stdio::null())
+        .status();
+    match status {
+        Ok(status) if status.success() => Ok(()),
+        Ok(status) => Err(Autom8Error::Sandbox(format!(
+            "`{} --version` failed ({})",
+            BWRAP, status
+        ))),
+        Err(e) => Err(Autom8Error::Sandbox(format!(
+            "could not run `{}`: {}",
+            BWRAP, e
+        ))),
+    }
+}
+
+/// The git directory shared by `dir`'s worktrees and the directory git runs
+/// its hooks from, which follows `core.hooksPath`.
+fn git_paths(dir: &Path) -> Option<(PathBuf, PathBuf)> {
+    let output = Command::new("git")
+        .arg("-C")
+        .arg(dir)
+        .args(["rev-parse", "--git-common-dir", "--git-path", "hooks"])
+        .output()
+        .ok()
+        .filter(|output| output.status.success())?;
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    let mut lines = stdout.lines().map(|line| dir.join(line.trim()));
+    let common_dir = lines.next()?.canonicalize().ok()?;
+    let hooks_dir = std::path::absolute(lines.next()?).ok()?;
+    // A missing hooks directory could otherwise be created from inside
+    let _ = std::fs::create_dir_all(&hooks_dir);
+    Some((common_dir, hooks_dir.canonicalize().unwrap_or(hooks_dir)))
+}
+
+/// `command` confined to `sandbox` with `working_dir` (or the current
+/// directory) as its worktree and `spec_dir` writable, or `command` itself
+/// if there is no sandbox.
+pub fn confine(
+    sandbox: Option<&Sandbox>,
+    command: Command,
+    working_dir: Option<&Path>,
+    spec_dir: Option<&Path>,
+) -> Result<Command> {
+    let Some(sandbox) = sandbox else {
+        return Ok(command);
+    };
+    let working_dir = match working_dir {
+        Some(dir) => dir.to_path_buf(),
+        None => std::env::current_dir()?,
+    };
+    let (git_dir, hooks_dir) = git_paths(&working_dir).unzip();
+    // Created up front so `--bind-try` has something to mount
+    let spec_dir = spec_dir.and_then(|dir| {
+        let _ = std::fs::create_dir_all(dir);
+        std::path::absolute(dir).ok()
+    });
+    let paths = SessionPaths {
+        git_dir,
+        hooks_dir,
+        spec_dir,
+    };
+    Ok(sandbox.wrap(&command, &working_dir, &paths))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn args_of(command: &Command) -> Vec<String> {
+        command
+            .get_args()
+            .map(|a| a.to_string_lossy().into_owned())
+            .collect()
+    }
+
+    #[test]
+    fn test_wrap_confines_command_to_worktree() {
+        let sandbox = Sandbox::new(&BwrapConfig {
+            read_only: vec!["/opt/toolchain".into()],
+            writable: vec!["/var/cache/build".into()],
+            network: false,
+        });
+        let mut command = Command::new("claude");
+        command
+            .args(["--print", "--verbose"])
+            .env("AUTOM8_PHASE", "story");
+
+        let paths = SessionPaths {
+            git_dir: Some("/work/repo/.git".into()),
+            hooks_dir: Some("/work/repo/.git/hooks".into()),
+            spec_dir: Some("/home/me/.config/autom8/repo/spec".into()),
+        };
+        let wrapped = sandbox.wrap(&command, Path::new("/work/tree"), &paths);
+        let args = args_of(&wrapped);
+        let has = |window: &[&str]| args.windows(window.len()).any(|w| w == window);
+
+        assert_eq!(wrapped.get_program(), BWRAP);
+        assert!(!args.contains(&"--share-net".to_string()));
+        assert!(has(&["--ro-bind-try", "/usr", "/usr"]));
+        assert!(has(&["--ro-bind-try", "/opt/toolchain", "/opt/toolchain"]));
+        assert!(has(&["--bind-try", "/var/cache/build", "/var/cache/build"]));
+        assert!(has(&["--bind", "/work/repo/.git", "/work/repo/.git"]));
+        assert!(has(&["--bind", "/work/tree", "/work/tree"]));
+        let spec_dir = "/home/me/.config/autom8/repo/spec";
+        assert!(has(&["--bind-try", spec_dir, spec_dir]));
+
+        // Hooks and git config are mounted read-only over the writable binds
+        let hooks = "/work/repo/.git/hooks";
+        let config = "/work/repo/.git/config";
+        let position = |window: &[&str]| args.windows(window.len()).position(|w| w == window);
+        assert!(has(&["--ro-bind-try", hooks, hooks]));
+        assert!(has(&["--ro-bind-try", config, config]));
+        assert!(position(&["--ro-bind-try", hooks]) > position(&["--bind", "/work/tree"]));
+        assert!(has(&["--chdir", "/work/tree"]));
+        assert!(args.ends_with(&["--", "claude", "--print", "--verbose"].map(String::from)));
+        assert!(wrapped
+            .get_envs()
+            .any(|(k, v)| k == "AUTOM8_PHASE" && v == Some("story".as_ref())));
+    }
+
+    #[test]
+    fn test_network_and_home_paths() {
+        let sandbox = Sandbox::new(&BwrapConfig {
+            read_only: vec!["~/.rustup".into()],
+            ..Default::default()
+        });
+        let args = args_of(&sandbox.wrap(
+            &Command::new("claude"),
+            Path::new("/w"),
+            &SessionPaths::default(),
+        ));
+        let home = dirs::home_dir().unwrap_or_default();
+
+        assert!(args.contains(&"--share-net".to_string()));
+        assert!(args.contains(&home.join(".rustup").to_string_lossy().into_owned()));
+        assert!(args.contains(&home.join(".claude").to_string_lossy().into_owned()));
+        assert_eq!(expand_home("/abs/path"), PathBuf::from("/abs/path"));
+        assert_eq!(expand_home("~"), home);
+    }
+
+    #[test]
+    fn test_confine_binds_the_spec_dir_writable() {
+        let dir = tempfile::TempDir::new().unwrap();
+        let spec_dir = dir.path().join("config/spec");
+        let sandbox = Sandbox::new(&BwrapConfig::default());
+
+        let confined = confine(
+            Some(&sandbox),
+            Command::new("claude"),
+            Some(dir.path()),
+            Some(&spec_dir),
+        )
+        .unwrap();
+        let args = args_of(&confined);
+        let spec_dir_arg = spec_dir.to_string_lossy().into_owned();
+
+        assert!(spec_dir.is_dir());
+        assert!(args
+            .windows(3)
+            .any(|w| w == ["--bind-try", spec_dir_arg.as_str(), spec_dir_arg.as_str()]));
+    }
+
+    #[test]
+    fn test_install_dirs_follow_links() {
+        use std::os::unix::fs::symlink;
+
+        let dir = tempfile::TempDir::new().unwrap();
+        let bin = dir.path().join("bin");
+        let package = dir.path().join("lib/agent");
+        std::fs::create_dir_all(&bin).unwrap();
+        std::fs::create_dir_all(&package).unwrap();
+        std::fs::write(package.join("cli.js"), "").unwrap();
+        symlink(package.join("cli.js"), bin.join("agent")).unwrap();
+
+        let dirs = install_dirs(&bin.join("agent").to_string_lossy());
+        assert_eq!(dirs.len(), 2);
+        assert_eq!(dirs[0], bin);
+        assert_eq!(dirs[1], package.canonicalize().unwrap());
+
+        assert!(install_dirs("sh").is_empty());
+        assert!(install_dirs("autom8-no-such-agent").is_empty());
+    }
+}
@@ -2,16 +2,15 @@
 //!
 //! Converts markdown spec files to JSON format using Claude.
 
-use std::io::{BufRead, BufReader, Write};
 use std::path::Path;
-use std::process::{Command, Stdio};
 
 use crate::error::{Autom8Error, Result};
 use crate::prompts::{SPEC_JSON_CORRECTION_PROMPT, SPEC_JSON_PROMPT};
 use crate::spec::Spec;
 
-use super::stream::{extract_text_from_stream_line, extract_usage_from_result_line};
-use super::types::{ClaudeErrorInfo, ClaudeUsage};
+use super::backend::AgentEvent;
+use super::runner::{ClaudeRunner, Session};
+use super::types::{ClaudePhase, ClaudeUsage};
 use super::utils::{extract_json, fix_json_syntax, truncate_json_preview};
 
 const MAX_JSON_RETRY_ATTEMPTS: u32 = 3;
@@ -33,6 +32,7 @@ struct ClaudeCallResult {
 /// Run Claude to convert a spec-<feature>.md markdown file into spec-<feature>.json
 /// Implements retry logic (up to 3 attempts) when JSON parsing fails.
 pub fn run_for_spec_generation<F>(
+    runner: &ClaudeRunner,
     spec_content: &str,
     output_path: &Path,
     mut on_output: F,
@@ -54,7 +54,7 @@ where
 
     // First attempt with the initial prompt
     let initial_prompt = SPEC_JSON_PROMPT.replace("{spec_content}", spec_content);
-    let call_result = run_claude_with_prompt(&initial_prompt, &mut on_output)?;
+    let call_result = run_claude_with_prompt(runner, &initial_prompt, output_path, &mut on_output)?;
     let mut full_output = call_result.output;
     accumulate_usage(call_result.usage);
 
@@ -111,7 +111,12 @@ where
                     .replace("{attempt}", &(attempt + 1).to_string())
                     .replace("{max_attempts}", &MAX_JSON_RETRY_ATTEMPTS.to_string());
 
-                let call_result = run_claude_with_prompt(&correction_prompt, &mut on_output)?;
+                let call_result = run_claude_with_prompt(
+                    runner,
+                    &correction_prompt,
+                    output_path,
+                    &mut on_output,
+                )?;
                 full_output = call_result.output;
                 accumulate_usage(call_result.usage);
 
@@ -158,72 +163,36 @@ where
 }
 
 /// Helper function to run Claude with a given prompt and return the raw output and usage.
-fn run_claude_with_prompt<F>(prompt: &str, mut on_output: F) -> Result<ClaudeCallResult>
+/// Claude may write the spec to `output_path` itself, so the session may edit it.
+fn run_claude_with_prompt<F>(
+    runner: &ClaudeRunner,
+    prompt: &str,
+    output_path: &Path,
+    mut on_output: F,
+) -> Result<ClaudeCallResult>
 where
     F: FnMut(&str),
 {
-    let mut child = Command::new("claude")
-        .args([
-            "--dangerously-skip-permissions",
-            "--print",
-            "--output-format",
-            "stream-json",
-            "--verbose",
-        ])
-        .stdin(Stdio::piped())
-        .stdout(Stdio::piped())
-        .stderr(Stdio::piped())
-        .spawn()
-        .map_err(|e| Autom8Error::ClaudeError(format!("Failed to spawn claude: {}", e)))?;
-
-    if let Some(mut stdin) = child.stdin.take() {
-        stdin
-            .write_all(prompt.as_bytes())
-            .map_err(|e| Autom8Error::ClaudeError(format!("Failed to write to stdin: {}", e)))?;
-    }
-
-    let stderr = child.stderr.take();
-
-    let stdout = child
-        .stdout
-        .take()
-        .ok_or_else(|| Autom8Error::ClaudeError("Failed to capture stdout".into()))?;
-
-    let reader = BufReader::new(stdout);
     let mut full_output = String::new();
     let mut usage: Option<ClaudeUsage> = None;
 
-    for line in reader.lines() {
-        let line = line.map_err(|e| Autom8Error::ClaudeError(format!("Read error: {}", e)))?;
-
-        if let Some(text) = extract_text_from_stream_line(&line) {
-            on_output(&text);
-            full_output.push_str(&text);
-        }
-
-        // Try to extract usage from result events
-        if let Some(line_usage) = extract_usage_from_result_line(&line) {
-            usage = Some(line_usage);
-        }
-    }
+    let exit = runner.run_session(
+        Session {
+            spec_path: Some(output_path),
+            ..Session::new(ClaudePhase::Spec, prompt)
+        },
+        |event| match event {
+            AgentEvent::Text(text) => {
+                on_output(text);
+                full_output.push_str(text);
+            }
+            AgentEvent::Usage(line_usage) => usage = Some(line_usage.clone()),
+            _ => {}
+        },
+    )?;
 
-    let status = child
-        .wait()
-        .map_err(|e| Autom8Error::ClaudeError(format!("Wait error: {}", e)))?;
-
-    if !status.success() {
-        let stderr_content = stderr
-            .map(|s| std::io::read_to_string(s).unwrap_or_default())
-            .unwrap_or_default();
-        let error_info = ClaudeErrorInfo::from_process_failure(
-            status,
-            if stderr_content.is_empty() {
-                None
-            } else {
-                Some(stderr_content)
-            },
-        );
-        return Err(Autom8Error::SpecGenerationFailed(error_info.message));
+    if let Some(exit) = exit.filter(|e| !e.success) {
+        return Err(Autom8Error::SpecGenerationFailed(exit.error_info().message));
     }
 
     Ok(ClaudeCallResult {
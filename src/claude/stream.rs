@@ -19,6 +19,10 @@ struct StreamLine {
     message: Option<AssistantMessage>,
     #[serde(default)]
     result: Option<String>,
+    #[serde(default)]
+    subtype: Option<String>,
+    #[serde(default)]
+    is_error: bool,
 }
 
 /// Inner event content for stream_event types
@@ -44,15 +48,35 @@ struct AssistantMessage {
     content: Vec<ContentBlock>,
 }
 
-/// Content block that may contain text
+/// Content block that may contain text or a tool call
 #[derive(Debug, Deserialize)]
 struct ContentBlock {
     #[serde(rename = "type")]
     block_type: String,
     #[serde(default)]
     text: Option<String>,
+    /// Tool name, for `tool_use` blocks
+    #[serde(default)]
+    name: Option<String>,
+    /// Tool input, for `tool_use` blocks
+    #[serde(default)]
+    input: Option<serde_json::Value>,
 }
 
+/// Tool input fields that best describe what a tool call is doing, in order
+/// of preference.
+const TOOL_INPUT_SUMMARY_FIELDS: &[&str] = &[
+    "command",
+    "file_path",
+    "path",
+    "pattern",
+    "url",
+    "description",
+];
+
+/// Longest tool input summary kept, in characters.
+const TOOL_INPUT_SUMMARY_MAX_CHARS: usize = 80;
+
 /// Result event from Claude CLI containing usage metadata.
 ///
 /// This struct deserializes the final `result` event from Claude CLI's
@@ -102,8 +126,8 @@ struct ModelUsageEntry {
 ///
 /// Parses the final `result` event from Claude CLI output and extracts
 /// token usage information. When multiple models are present in `modelUsage`,
-/// tokens are summed across all models but only the primary (first) model
-/// name is stored.
+/// tokens are summed across all models, each model's share is priced at its
+/// own rates, and the model with the most tokens is stored as `model`.
 ///
 /// # Arguments
 /// * `line` - A JSON line from Claude CLI's stream-json output
@@ -122,14 +146,30 @@ pub fn extract_usage_from_result_line(line: &str) -> Option<ClaudeUsage> {
     // Prefer modelUsage if available (more detailed, includes thinking tokens)
     if let Some(model_usage) = parsed.model_usage {
         if !model_usage.is_empty() {
-            let mut usage = ClaudeUsage::default();
-
-            // Get the primary model name (first key)
-            let primary_model = model_usage.keys().next().cloned();
-            usage.model = primary_model;
-
-            // Sum tokens across all models
-            for entry in model_usage.values() {
+            let mut usage = ClaudeUsage {
+                cost_usd: Some(0.0),
+                ..Default::default()
+            };
+            let mut primary_tokens = 0;
+
+            // Sum tokens across all models, pricing each at its own rates
+            for (model, entry) in &model_usage {
+                let model_usage = ClaudeUsage {
+                    input_tokens: entry.input_tokens,
+                    output_tokens: entry.output_tokens,
+                    cache_read_tokens: entry.cache_read_input_tokens,
+                    cache_creation_tokens: entry.cache_creation_input_tokens,
+                    thinking_tokens: entry.thinking_tokens,
+                    model: Some(model.clone()),
+                    cost_usd: None,
+                };
+                let tokens = model_usage.total_tokens();
+                if usage.model.is_none() || tokens > primary_tokens {
+                    usage.model = model_usage.model.clone();
+                    primary_tokens = tokens;
+                }
+                usage.cost_usd =
+                    Some(usage.cost_usd.unwrap_or_default() + model_usage.estimated_cost_usd());
                 usage.input_tokens += entry.input_tokens;
                 usage.output_tokens += entry.output_tokens;
                 usage.cache_read_tokens += entry.cache_read_input_tokens;
@@ -150,6 +190,7 @@ pub fn extract_usage_from_result_line(line: &str) -> Option<ClaudeUsage> {
             cache_creation_tokens: result_usage.cache_creation_input_tokens,
             thinking_tokens: 0,
             model: None,
+            cost_usd: None,
         });
     }
 
@@ -195,6 +236,64 @@ pub fn extract_text_from_stream_line(line: &str) -> Option<String> {
     }
 }
 
+/// Extract a short description of the last tool call in a stream JSON line.
+///
+/// Returns e.g. `Bash: cargo test` for an assistant message that calls a
+/// tool, or `None` for any other line.
+pub fn extract_tool_activity_from_stream_line(line: &str) -> Option<String> {
+    let parsed: StreamLine = serde_json::from_str(line).ok()?;
+    if parsed.event_type != "assistant" {
+        return None;
+    }
+
+    let block = parsed
+        .message?
+        .content
+        .into_iter()
+        .rev()
+        .find(|block| block.block_type == "tool_use")?;
+    let name = block.name?;
+
+    let summary = block.input.as_ref().and_then(|input| {
+        TOOL_INPUT_SUMMARY_FIELDS
+            .iter()
+            .find_map(|field| input.get(field)?.as_str())
+    });
+    match summary {
+        Some(summary) => {
+            let summary = summary.lines().next().unwrap_or_default();
+            let mut short: String = summary.chars().take(TOOL_INPUT_SUMMARY_MAX_CHARS).collect();
+            if short.len() < summary.len() {
+                short.push_str("...");
+            }
+            Some(format!("{}: {}", name, short))
+        }
+        None => Some(name),
+    }
+}
+
+/// Extract the error text from a failed final `result` event.
+///
+/// Returns the result text (or the error subtype when there is none) for a
+/// result event flagged `is_error` or with an `error_*` subtype, and `None`
+/// for any other line.
+pub fn extract_result_error_from_stream_line(line: &str) -> Option<String> {
+    let parsed: StreamLine = serde_json::from_str(line).ok()?;
+    if parsed.event_type != "result" {
+        return None;
+    }
+    let error_subtype = parsed
+        .subtype
+        .filter(|subtype| subtype.starts_with("error"));
+    if !parsed.is_error && error_subtype.is_none() {
+        return None;
+    }
+    parsed
+        .result
+        .filter(|text| !text.trim().is_empty())
+        .or(error_subtype)
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -234,6 +333,57 @@ mod tests {
         assert_eq!(text, None);
     }
 
+    // Result error extraction tests
+
+    #[test]
+    fn test_extract_result_error_from_failed_result() {
+        let line = r#"{"type":"result","subtype":"success","is_error":true,"result":"API Error: 529 {\"type\":\"overloaded_error\"}"}"#;
+        assert_eq!(
+            extract_result_error_from_stream_line(line),
+            Some(r#"API Error: 529 {"type":"overloaded_error"}"#.to_string())
+        );
+
+        let line = r#"{"type":"result","subtype":"error_during_execution","is_error":false}"#;
+        assert_eq!(
+            extract_result_error_from_stream_line(line),
+            Some("error_during_execution".to_string())
+        );
+    }
+
+    #[test]
+    fn test_extract_result_error_ignores_successful_result() {
+        let line = r#"{"type":"result","subtype":"success","is_error":false,"result":"Done"}"#;
+        assert_eq!(extract_result_error_from_stream_line(line), None);
+        assert_eq!(extract_result_error_from_stream_line("not json"), None);
+    }
+
+    // Tool activity extraction tests
+
+    #[test]
+    fn test_extract_tool_activity_from_tool_use() {
+        let line = r#"{"type":"assistant","message":{"content":[{"type":"text","text":"Running tests"},{"type":"tool_use","id":"t1","name":"Bash","input":{"command":"cargo test\necho done","description":"Run tests"}}]}}"#;
+        assert_eq!(
+            extract_tool_activity_from_stream_line(line),
+            Some("Bash: cargo test".to_string())
+        );
+
+        let line = r#"{"type":"assistant","message":{"content":[{"type":"tool_use","id":"t2","name":"TodoWrite","input":{"todos":[]}}]}}"#;
+        assert_eq!(
+            extract_tool_activity_from_stream_line(line),
+            Some("TodoWrite".to_string())
+        );
+    }
+
+    #[test]
+    fn test_extract_tool_activity_ignores_text_and_other_events() {
+        let text_only =
+            r#"{"type":"assistant","message":{"content":[{"type":"text","text":"Hi"}]}}"#;
+        assert_eq!(extract_tool_activity_from_stream_line(text_only), None);
+        let result = r#"{"type":"result","subtype":"success","result":"done"}"#;
+        assert_eq!(extract_tool_activity_from_stream_line(result), None);
+        assert_eq!(extract_tool_activity_from_stream_line("not json"), None);
+    }
+
     // Usage extraction tests
 
     #[test]
@@ -263,8 +413,25 @@ mod tests {
         assert_eq!(usage.cache_read_tokens, 75);
         assert_eq!(usage.cache_creation_tokens, 30);
         assert_eq!(usage.thinking_tokens, 5);
-        // Model should be one of the keys (we just take the first)
-        assert!(usage.model.is_some());
+        // The model with the most tokens is reported
+        assert_eq!(usage.model.as_deref(), Some("claude-haiku-3-20240307"));
+    }
+
+    #[test]
+    fn test_multiple_models_are_priced_at_their_own_rates() {
+        // 1M Sonnet output ($15) and 1M Haiku 4.5 output ($5)
+        let line = r#"{"type":"result","modelUsage":{"claude-sonnet-4-20250514":{"outputTokens":1000000},"claude-haiku-4-5-20251001":{"outputTokens":1000000}}}"#;
+        let usage = extract_usage_from_result_line(line).unwrap();
+        assert!((usage.estimated_cost_usd() - 20.0).abs() < 1e-9);
+
+        // Adding it to a run's total keeps both prices
+        let mut total = ClaudeUsage {
+            output_tokens: 1_000_000,
+            model: Some("claude-opus-4-20250514".to_string()),
+            ..Default::default()
+        };
+        total.add(&usage);
+        assert!((total.estimated_cost_usd() - 95.0).abs() < 1e-9);
     }
 
     #[test]
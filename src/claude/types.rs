@@ -5,6 +5,8 @@
 
 use serde::{Deserialize, Serialize};
 
+use super::failure::{classify_failure, ClaudeFailureKind};
+
 /// Represents token usage data from Claude CLI responses.
 ///
 /// This struct captures detailed token consumption metrics from Claude API calls,
@@ -31,6 +33,10 @@ pub struct ClaudeUsage {
     /// The Claude model used (e.g., "claude-sonnet-4-20250514")
     #[serde(default)]
     pub model: Option<String>,
+    /// Estimated cost in USD, priced model by model, when the tokens came
+    /// from more than one model. Otherwise the cost is estimated from `model`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub cost_usd: Option<f64>,
 }
 
 impl ClaudeUsage {
@@ -39,11 +45,20 @@ impl ClaudeUsage {
         self.input_tokens + self.output_tokens
     }
 
+    /// Estimated cost in USD, priced for `model`.
+    pub fn estimated_cost_usd(&self) -> f64 {
+        super::estimate_cost_usd(self)
+    }
+
     /// Accumulates token counts from another ClaudeUsage instance.
     ///
     /// This is useful for aggregating usage across multiple Claude calls
     /// within a single phase or story.
     pub fn add(&mut self, other: &ClaudeUsage) {
+        // Price each side at its own model before the counts are merged
+        if self.cost_usd.is_some() || other.cost_usd.is_some() || self.model != other.model {
+            self.cost_usd = Some(self.estimated_cost_usd() + other.estimated_cost_usd());
+        }
         self.input_tokens += other.input_tokens;
         self.output_tokens += other.output_tokens;
         self.cache_read_tokens += other.cache_read_tokens;
@@ -65,6 +80,8 @@ pub struct ClaudeErrorInfo {
     pub exit_code: Option<i32>,
     /// Stderr output from the subprocess, if available
     pub stderr: Option<String>,
+    /// Whether the failure is worth retrying
+    pub kind: ClaudeFailureKind,
 }
 
 impl ClaudeErrorInfo {
@@ -74,12 +91,18 @@ impl ClaudeErrorInfo {
             message: message.into(),
             exit_code: None,
             stderr: None,
+            kind: ClaudeFailureKind::Permanent,
         }
     }
 
     /// Create error info from a process exit status and stderr
     pub fn from_process_failure(status: std::process::ExitStatus, stderr: Option<String>) -> Self {
-        let exit_code = status.code();
+        Self::from_exit(status.code(), &status.to_string(), stderr)
+    }
+
+    /// Create error info from an exit code (`None` if the process was killed
+    /// by a signal), a description of the exit status and stderr.
+    fn from_exit(exit_code: Option<i32>, status: &str, stderr: Option<String>) -> Self {
         let stderr_trimmed = stderr.as_ref().map(|s| s.trim().to_string());
 
         let message = match (&stderr_trimmed, exit_code) {
@@ -97,12 +120,34 @@ impl ClaudeErrorInfo {
             }
         };
 
+        let stderr = stderr_trimmed.filter(|s| !s.is_empty());
         Self {
             message,
             exit_code,
-            stderr: stderr_trimmed.filter(|s| !s.is_empty()),
+            kind: classify_failure(stderr.as_deref().unwrap_or_default()),
+            stderr,
         }
     }
+
+    /// Add the error Claude reported in its final `result` event.
+    ///
+    /// The CLI reports API errors (overload, usage limits) there rather than
+    /// on stderr, so the failure is classified again with it included.
+    pub fn with_result_error(mut self, result_error: Option<String>) -> Self {
+        let Some(result_error) = result_error else {
+            return self;
+        };
+        if self.stderr.is_none() {
+            self.message = format!("{}: {}", self.message, result_error);
+        }
+        let text = format!(
+            "{}\n{}",
+            self.stderr.as_deref().unwrap_or_default(),
+            result_error
+        );
+        self.kind = classify_failure(&text);
+        self
+    }
 }
 
 impl std::fmt::Display for ClaudeErrorInfo {
@@ -117,6 +162,85 @@ impl From<ClaudeErrorInfo> for String {
     }
 }
 
+/// What a Claude session is for.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ClaudePhase {
+    /// Converting a Markdown spec to JSON
+    Spec,
+    /// Implementing a story
+    Story,
+    Review,
+    Correct,
+    Commit,
+    /// Filling in the repository's PR template
+    PrTemplate,
+    /// Addressing PR review comments
+    PrReview,
+}
+
+impl ClaudePhase {
+    /// Every phase, in the order a run goes through them.
+    pub const ALL: [ClaudePhase; 7] = [
+        ClaudePhase::Spec,
+        ClaudePhase::Story,
+        ClaudePhase::Review,
+        ClaudePhase::Correct,
+        ClaudePhase::Commit,
+        ClaudePhase::PrTemplate,
+        ClaudePhase::PrReview,
+    ];
+
+    /// The phase's name in fixture files, e.g. `pr_template`.
+    pub fn name(self) -> &'static str {
+        match self {
+            ClaudePhase::Spec => "spec",
+            ClaudePhase::Story => "story",
+            ClaudePhase::Review => "review",
+            ClaudePhase::Correct => "correct",
+            ClaudePhase::Commit => "commit",
+            ClaudePhase::PrTemplate => "pr_template",
+            ClaudePhase::PrReview => "pr_review",
+        }
+    }
+}
+
+impl std::fmt::Display for ClaudePhase {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(self.name())
+    }
+}
+
+/// How a Claude session's process exited.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SessionExit {
+    pub success: bool,
+    /// Exit code, or `None` if the process was killed by a signal
+    pub code: Option<i32>,
+    /// The exit status as the OS describes it, e.g. `exit status: 1`
+    pub status: String,
+    /// Everything the process wrote to stderr
+    #[serde(default)]
+    pub stderr: String,
+}
+
+impl SessionExit {
+    pub fn from_status(status: std::process::ExitStatus, stderr: String) -> Self {
+        Self {
+            success: status.success(),
+            code: status.code(),
+            status: status.to_string(),
+            stderr,
+        }
+    }
+
+    /// Error info for a session that didn't exit successfully.
+    pub fn error_info(&self) -> ClaudeErrorInfo {
+        let stderr = Some(self.stderr.clone()).filter(|s| !s.is_empty());
+        ClaudeErrorInfo::from_exit(self.code, &self.status, stderr)
+    }
+}
+
 /// Result from running Claude on a story task
 #[derive(Debug, Clone, PartialEq)]
 pub struct ClaudeStoryResult {
@@ -186,6 +310,7 @@ mod tests {
             cache_creation_tokens: 10,
             thinking_tokens: 5,
             model: None,
+            cost_usd: None,
         };
         let usage2 = ClaudeUsage {
             input_tokens: 200,
@@ -194,6 +319,7 @@ mod tests {
             cache_creation_tokens: 20,
             thinking_tokens: 10,
             model: Some("claude-sonnet-4-20250514".to_string()),
+            cost_usd: None,
         };
 
         usage1.add(&usage2);
@@ -245,6 +371,7 @@ mod tests {
             cache_creation_tokens: 10,
             thinking_tokens: 5,
             model: Some("test-model".to_string()),
+            cost_usd: None,
         };
         let cloned = usage.clone();
         assert_eq!(usage.input_tokens, cloned.input_tokens);
@@ -264,6 +391,7 @@ mod tests {
             cache_creation_tokens: 10,
             thinking_tokens: 5,
             model: Some("test-model".to_string()),
+            cost_usd: None,
         };
 
         let json = serde_json::to_string(&usage).unwrap();
@@ -337,6 +465,7 @@ mod tests {
             message: "cloned error".to_string(),
             exit_code: Some(42),
             stderr: Some("stderr content".to_string()),
+            kind: ClaudeFailureKind::Transient,
         };
         let cloned = info.clone();
         assert_eq!(info, cloned);
@@ -350,4 +479,17 @@ mod tests {
         assert_eq!(info1, info2);
         assert_ne!(info1, info3);
     }
+
+    #[test]
+    fn test_claude_error_info_classifies_result_error() {
+        let info = ClaudeErrorInfo::new("Claude exited with status: 1");
+        assert_eq!(info.kind, ClaudeFailureKind::Permanent);
+
+        let info = info.with_result_error(Some("API Error: 529 Overloaded".to_string()));
+        assert_eq!(info.kind, ClaudeFailureKind::Transient);
+        assert_eq!(
+            info.message,
+            "Claude exited with status: 1: API Error: 529 Overloaded"
+        );
+    }
 }
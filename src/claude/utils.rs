@@ -2,10 +2,12 @@
 //!
 //! Provides helper functions for JSON fixing, context building, and output parsing.
 
-use crate::knowledge::ProjectKnowledge;
+use crate::knowledge::{ProjectKnowledge, StoryChanges};
+use crate::spec::UserStory;
 use crate::state::IterationRecord;
 use serde::{Deserialize, Serialize};
-use std::path::PathBuf;
+use std::collections::{HashMap, HashSet};
+use std::path::{Path, PathBuf};
 
 const WORK_SUMMARY_START: &str = "<work-summary>";
 const WORK_SUMMARY_END: &str = "</work-summary>";
@@ -256,8 +258,10 @@ fn parse_symbol_list(input: &str) -> Vec<String> {
 }
 
 /// Build a context string from previous iteration work summaries.
-/// Returns None if there are no previous iterations with summaries.
-/// Format: "US-001: [summary]\nUS-002: [summary]"
+/// Returns None if there are no previous iterations with summaries or
+/// verification failures.
+/// Format: "US-001: [summary]\nUS-002: [summary]", followed by the output of
+/// any verification commands that failed on a story's latest iteration.
 pub fn build_previous_context(iterations: &[IterationRecord]) -> Option<String> {
     let summaries: Vec<String> = iterations
         .iter()
@@ -268,10 +272,93 @@ pub fn build_previous_context(iterations: &[IterationRecord]) -> Option<String>
         })
         .collect();
 
-    if summaries.is_empty() {
+    // Only a story's latest attempt matters; earlier failures may be fixed
+    let mut latest: Vec<&IterationRecord> = Vec::new();
+    for iter in iterations.iter().rev() {
+        if !latest.iter().any(|l| l.story_id == iter.story_id) {
+            latest.push(iter);
+        }
+    }
+    let failures: Vec<String> = latest
+        .iter()
+        .rev()
+        .filter_map(|iter| {
+            iter.verify_failure.as_ref().map(|failure| {
+                format!(
+                    "Verification failed after the last attempt at {}, so it is not complete yet. \
+                     Fix this before marking it as passing:\n{}",
+                    iter.story_id, failure
+                )
+            })
+        })
+        .collect();
+
+    let mut sections = Vec::new();
+    if !summaries.is_empty() {
+        sections.push(summaries.join("\n"));
+    }
+    sections.extend(failures);
+
+    if sections.is_empty() {
         None
     } else {
-        Some(summaries.join("\n"))
+        Some(sections.join("\n\n"))
+    }
+}
+
+/// Rough size of `text` in tokens, at about four characters per token.
+pub fn estimate_tokens(text: &str) -> u64 {
+    (text.chars().count() as u64).div_ceil(4)
+}
+
+/// Stories whose changes stay listed one by one when the knowledge context is
+/// over budget. Older stories are summarized in a single line.
+const RECENT_STORIES_IN_FULL: usize = 3;
+
+/// Relevance added to an entry whose file the story mentions.
+const MENTIONED_SCORE: u32 = 100;
+/// Relevance added to an entry from the story itself or a story it depends on.
+const RELATED_SCORE: u32 = 40;
+/// Relevance added to an entry from the most recent story, scaled down for
+/// older ones.
+const RECENCY_SCORE: u32 = 30;
+
+/// Project knowledge rendered for a prompt, and what was cut to fit the
+/// token budget.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct KnowledgeContext {
+    pub text: String,
+    /// Entries left out to fit the budget
+    pub dropped_entries: usize,
+    /// Estimated tokens of the entries left out
+    pub dropped_tokens: u64,
+    /// Earlier stories whose changes were summarized in one line
+    pub summarized_stories: usize,
+}
+
+impl KnowledgeContext {
+    /// Whether anything was summarized or left out to fit the budget.
+    pub fn is_trimmed(&self) -> bool {
+        self.summarized_stories > 0 || self.dropped_entries > 0
+    }
+
+    /// What was cut to fit the budget, e.g. "summarized 9 earlier stories,
+    /// left out 12 entries (~640 tokens)".
+    pub fn trim_summary(&self) -> String {
+        let mut parts = Vec::new();
+        if self.summarized_stories > 0 {
+            parts.push(format!(
+                "summarized {} earlier stories",
+                self.summarized_stories
+            ));
+        }
+        if self.dropped_entries > 0 {
+            parts.push(format!(
+                "left out {} entries (~{} tokens)",
+                self.dropped_entries, self.dropped_tokens
+            ));
+        }
+        parts.join(", ")
     }
 }
 
@@ -283,105 +370,435 @@ pub fn build_previous_context(iterations: &[IterationRecord]) -> Option<String>
 /// - Architectural Decisions (topic, choice, rationale)
 /// - Patterns to Follow (list of patterns)
 /// - Recent Work (summary of completed story changes)
+/// - Inherited from Earlier Runs (files, decisions and patterns from the
+///   project's knowledge store that this run hasn't learned itself)
 ///
-/// Formatting is kept concise to stay under ~1500 tokens.
+/// Nothing is left out; see [`build_story_knowledge_context`] for a context
+/// fitted to a token budget.
 pub fn build_knowledge_context(knowledge: &ProjectKnowledge) -> Option<String> {
-    // Check if knowledge is empty
-    if knowledge.files.is_empty()
-        && knowledge.decisions.is_empty()
-        && knowledge.patterns.is_empty()
-        && knowledge.story_changes.is_empty()
-    {
+    build_story_knowledge_context(knowledge, None, None).map(|context| context.text)
+}
+
+/// Build the knowledge context for a prompt about `story`, fitted to
+/// `budget` estimated tokens (`None` means no limit).
+///
+/// Over budget, the changes of all but the last few stories are summarized in
+/// one line. If that isn't enough, the entries least relevant to `story` are
+/// left out: relevance favours files the story mentions, entries from the
+/// story and the stories it depends on, and recent entries. The context ends
+/// with a note saying how much was left out.
+pub fn build_story_knowledge_context(
+    knowledge: &ProjectKnowledge,
+    story: Option<&UserStory>,
+    budget: Option<u64>,
+) -> Option<KnowledgeContext> {
+    let relevance = Relevance::new(knowledge, story);
+    let sections = knowledge_sections(knowledge, &relevance, false);
+    let text = render_sections(&sections, |_| true);
+    if text.is_empty() {
         return None;
     }
+    let untrimmed = KnowledgeContext {
+        text,
+        dropped_entries: 0,
+        dropped_tokens: 0,
+        summarized_stories: 0,
+    };
+    let Some(budget) = budget.filter(|&budget| estimate_tokens(&untrimmed.text) > budget) else {
+        return Some(untrimmed);
+    };
 
-    let mut sections: Vec<String> = Vec::new();
+    let summarized_stories = knowledge
+        .story_changes
+        .len()
+        .saturating_sub(RECENT_STORIES_IN_FULL);
+    let sections = knowledge_sections(knowledge, &relevance, true);
+    let text = render_sections(&sections, |_| true);
+    if estimate_tokens(&text) <= budget {
+        return Some(KnowledgeContext {
+            text,
+            summarized_stories,
+            ..untrimmed
+        });
+    }
 
-    // Files Modified section (as a table)
-    if !knowledge.files.is_empty() {
-        let mut files_section = String::from("## Files Modified in This Run\n\n");
-        files_section.push_str("| Path | Purpose | Key Symbols | Stories |\n");
-        files_section.push_str("|------|---------|-------------|--------|\n");
+    let kept = select_entries(&sections, budget.saturating_sub(omission_note_tokens()));
+    let dropped: Vec<&ContextEntry> = entries(&sections)
+        .filter(|(id, _)| !kept.contains(id))
+        .map(|(_, entry)| entry)
+        .collect();
+    let dropped_tokens = dropped
+        .iter()
+        .map(|entry| estimate_tokens(&entry.line))
+        .sum();
+    let mut text = render_sections(&sections, |id| kept.contains(&id));
+    text.push_str(&omission_note(dropped.len(), dropped_tokens));
+    Some(KnowledgeContext {
+        text,
+        dropped_entries: dropped.len(),
+        dropped_tokens,
+        summarized_stories,
+    })
+}
 
-        // Sort files by path for consistent output
-        let mut file_entries: Vec<_> = knowledge.files.iter().collect();
-        file_entries.sort_by(|a, b| a.0.cmp(b.0));
+/// A section of the knowledge context: a heading, then groups of entries.
+/// Groups and sections with no entries left are not rendered.
+struct ContextSection {
+    heading: String,
+    groups: Vec<ContextGroup>,
+}
 
-        for (path, info) in file_entries {
-            let path_str = abbreviate_path(path.to_string_lossy().as_ref());
-            let purpose = truncate_str(&info.purpose, 40);
-            let symbols = if info.key_symbols.is_empty() {
-                "-".to_string()
-            } else {
-                truncate_str(&info.key_symbols.join(", "), 30)
-            };
-            let stories = if info.touched_by.is_empty() {
-                "-".to_string()
-            } else {
-                info.touched_by.join(", ")
-            };
+/// Entries under a common heading, such as a table's header rows.
+struct ContextGroup {
+    heading: String,
+    entries: Vec<ContextEntry>,
+}
 
-            files_section.push_str(&format!(
-                "| {} | {} | {} | {} |\n",
-                path_str, purpose, symbols, stories
-            ));
+/// One line of the knowledge context.
+struct ContextEntry {
+    line: String,
+    /// Relevance to the story; the least relevant entries are left out first
+    score: u32,
+}
+
+impl ContextSection {
+    /// A section whose heading is followed directly by its entries.
+    fn new(heading: &str, entries: Vec<ContextEntry>) -> Self {
+        Self {
+            heading: heading.to_string(),
+            groups: vec![ContextGroup {
+                heading: String::new(),
+                entries,
+            }],
         }
+    }
+}
 
-        sections.push(files_section);
+/// An entry's section, group and position within the group.
+type EntryId = (usize, usize, usize);
+
+/// Every entry in `sections`, in the order they're rendered.
+fn entries(sections: &[ContextSection]) -> impl Iterator<Item = (EntryId, &ContextEntry)> {
+    sections.iter().enumerate().flat_map(|(s, section)| {
+        section
+            .groups
+            .iter()
+            .enumerate()
+            .flat_map(move |(g, group)| {
+                group
+                    .entries
+                    .iter()
+                    .enumerate()
+                    .map(move |(e, entry)| ((s, g, e), entry))
+            })
+    })
+}
+
+/// Render the entries `keep` accepts, in section order.
+fn render_sections(sections: &[ContextSection], keep: impl Fn(EntryId) -> bool) -> String {
+    let mut rendered = Vec::new();
+    for (s, section) in sections.iter().enumerate() {
+        let mut body = String::new();
+        for (g, group) in section.groups.iter().enumerate() {
+            let lines: Vec<&str> = group
+                .entries
+                .iter()
+                .enumerate()
+                .filter(|(e, _)| keep((s, g, *e)))
+                .map(|(_, entry)| entry.line.as_str())
+                .collect();
+            if !lines.is_empty() {
+                body.push_str(&group.heading);
+                body.push_str(&lines.concat());
+            }
+        }
+        if !body.is_empty() {
+            rendered.push(format!("{}{}", section.heading, body));
+        }
     }
+    rendered.join("\n")
+}
 
-    // Architectural Decisions section
-    if !knowledge.decisions.is_empty() {
-        let mut decisions_section = String::from("## Architectural Decisions\n\n");
+/// The most relevant entries that fit in `budget` tokens, counting the
+/// headings they bring along.
+fn select_entries(sections: &[ContextSection], budget: u64) -> HashSet<EntryId> {
+    let mut ranked: Vec<(EntryId, &ContextEntry)> = entries(sections).collect();
+    ranked.sort_by_key(|(_, entry)| std::cmp::Reverse(entry.score));
+
+    let mut kept = HashSet::new();
+    let mut open_sections = HashSet::new();
+    let mut open_groups = HashSet::new();
+    let mut used = 0;
+    for ((s, g, e), entry) in ranked {
+        let mut cost = estimate_tokens(&entry.line);
+        if !open_groups.contains(&(s, g)) {
+            cost += estimate_tokens(&sections[s].groups[g].heading);
+        }
+        if !open_sections.contains(&s) {
+            // The heading and the blank line before it
+            cost += estimate_tokens(&sections[s].heading) + 1;
+        }
+        if used + cost <= budget {
+            used += cost;
+            kept.insert((s, g, e));
+            open_groups.insert((s, g));
+            open_sections.insert(s);
+        }
+    }
+    kept
+}
 
-        for decision in &knowledge.decisions {
-            decisions_section.push_str(&format!(
-                "- **{}**: {} — {}\n",
-                decision.topic,
-                decision.choice,
-                truncate_str(&decision.rationale, 60)
-            ));
+/// The note ending a context that had entries left out.
+fn omission_note(entries: usize, tokens: u64) -> String {
+    format!(
+        "\n_{} less relevant entries (~{} tokens) were left out to fit the knowledge budget._\n",
+        entries, tokens
+    )
+}
+
+/// Tokens to set aside for the omission note.
+fn omission_note_tokens() -> u64 {
+    estimate_tokens(&omission_note(9_999, 999_999))
+}
+
+/// How relevant knowledge entries are to the story a prompt is for.
+struct Relevance<'a> {
+    /// The story's title, description, acceptance criteria and notes, lowercased
+    story_text: String,
+    /// The story and the stories it depends on
+    related: HashSet<&'a str>,
+    /// Position of each story in the order the run completed them
+    order: HashMap<&'a str, usize>,
+}
+
+impl<'a> Relevance<'a> {
+    fn new(knowledge: &'a ProjectKnowledge, story: Option<&'a UserStory>) -> Self {
+        let story_text = story
+            .map(|story| {
+                let mut parts = vec![story.title.as_str(), &story.description, &story.notes];
+                parts.extend(story.acceptance_criteria.iter().map(String::as_str));
+                parts.join("\n").to_lowercase()
+            })
+            .unwrap_or_default();
+        let related = story
+            .map(|story| {
+                std::iter::once(story.id.as_str())
+                    .chain(story.depends_on.iter().map(String::as_str))
+                    .collect()
+            })
+            .unwrap_or_default();
+        let order = knowledge
+            .story_changes
+            .iter()
+            .enumerate()
+            .map(|(i, changes)| (changes.story_id.as_str(), i))
+            .collect();
+        Self {
+            story_text,
+            related,
+            order,
         }
+    }
 
-        sections.push(decisions_section);
+    /// Whether the story mentions `path`, by full path or by file name.
+    fn mentions(&self, path: &Path) -> bool {
+        let full = path.to_string_lossy().to_lowercase();
+        let name = path
+            .file_name()
+            .map(|name| name.to_string_lossy().to_lowercase())
+            .unwrap_or_default();
+        contains_word(&self.story_text, &full)
+            || (name.contains('.') && contains_word(&self.story_text, &name))
     }
 
-    // Patterns to Follow section
-    if !knowledge.patterns.is_empty() {
-        let mut patterns_section = String::from("## Patterns to Follow\n\n");
+    /// Score for entries from `story_id`, by how recently it was completed
+    /// and whether the story is related to the one the prompt is for.
+    fn story_score(&self, story_id: &str) -> u32 {
+        let recency = self.order.get(story_id).map_or(0, |&i| {
+            RECENCY_SCORE * (i as u32 + 1) / self.order.len() as u32
+        });
+        let related = if self.related.contains(story_id) {
+            RELATED_SCORE
+        } else {
+            0
+        };
+        recency + related
+    }
 
-        for pattern in &knowledge.patterns {
-            let example = pattern
-                .example_file
-                .as_ref()
-                .map(|p| format!(" (see {})", abbreviate_path(p.to_string_lossy().as_ref())))
-                .unwrap_or_default();
-            patterns_section.push_str(&format!("- {}{}\n", pattern.description, example));
+    /// The best score among `story_ids`.
+    fn stories_score<'s>(&self, story_ids: impl IntoIterator<Item = &'s String>) -> u32 {
+        story_ids
+            .into_iter()
+            .map(|id| self.story_score(id))
+            .max()
+            .unwrap_or(0)
+    }
+
+    fn mention_score(&self, path: &Path) -> u32 {
+        if self.mentions(path) {
+            MENTIONED_SCORE
+        } else {
+            0
         }
+    }
+}
+
+/// Whether `text` contains `word` with no letter, digit or underscore
+/// directly around it.
+fn contains_word(text: &str, word: &str) -> bool {
+    if word.is_empty() {
+        return false;
+    }
+    let is_word_char = |c: char| c.is_alphanumeric() || c == '_';
+    text.match_indices(word).any(|(i, _)| {
+        let before = text[..i].chars().next_back();
+        let after = text[i + word.len()..].chars().next();
+        !before.is_some_and(is_word_char) && !after.is_some_and(is_word_char)
+    })
+}
+
+/// The sections of the knowledge context, with every entry scored. With
+/// `summarize_older`, the changes of all but the last few stories are
+/// summarized in one line.
+fn knowledge_sections(
+    knowledge: &ProjectKnowledge,
+    relevance: &Relevance,
+    summarize_older: bool,
+) -> Vec<ContextSection> {
+    let mut sections = Vec::new();
+
+    // Files Modified section (as a table)
+    if !knowledge.files.is_empty() {
+        // Sort files by path for consistent output
+        let mut file_entries: Vec<_> = knowledge.files.iter().collect();
+        file_entries.sort_by(|a, b| a.0.cmp(b.0));
+
+        let entries = file_entries
+            .into_iter()
+            .map(|(path, info)| {
+                let path_str = abbreviate_path(path.to_string_lossy().as_ref());
+                let purpose = truncate_str(&info.purpose, 40);
+                let symbols = if info.key_symbols.is_empty() {
+                    "-".to_string()
+                } else {
+                    truncate_str(&info.key_symbols.join(", "), 30)
+                };
+                let stories = if info.touched_by.is_empty() {
+                    "-".to_string()
+                } else {
+                    info.touched_by.join(", ")
+                };
+                ContextEntry {
+                    line: format!(
+                        "| {} | {} | {} | {} |\n",
+                        path_str, purpose, symbols, stories
+                    ),
+                    score: 10
+                        + relevance.mention_score(path)
+                        + relevance.stories_score(&info.touched_by),
+                }
+            })
+            .collect();
 
-        sections.push(patterns_section);
+        sections.push(ContextSection {
+            heading: "## Files Modified in This Run\n\n".to_string(),
+            groups: vec![ContextGroup {
+                heading: "| Path | Purpose | Key Symbols | Stories |\n\
+                          |------|---------|-------------|--------|\n"
+                    .to_string(),
+                entries,
+            }],
+        });
+    }
+
+    // Architectural Decisions section
+    if !knowledge.decisions.is_empty() {
+        let entries = knowledge
+            .decisions
+            .iter()
+            .map(|decision| ContextEntry {
+                line: format!(
+                    "- **{}**: {} — {}\n",
+                    decision.topic,
+                    decision.choice,
+                    truncate_str(&decision.rationale, 60)
+                ),
+                score: 20 + relevance.story_score(&decision.story_id),
+            })
+            .collect();
+        sections.push(ContextSection::new(
+            "## Architectural Decisions\n\n",
+            entries,
+        ));
+    }
+
+    // Patterns to Follow section
+    if !knowledge.patterns.is_empty() {
+        let entries = knowledge
+            .patterns
+            .iter()
+            .map(|pattern| ContextEntry {
+                line: pattern_line(pattern),
+                score: 20
+                    + relevance.story_score(&pattern.story_id)
+                    + pattern
+                        .example_file
+                        .as_deref()
+                        .map_or(0, |path| relevance.mention_score(path)),
+            })
+            .collect();
+        sections.push(ContextSection::new("## Patterns to Follow\n\n", entries));
     }
 
     // Recent Work section (story changes summary)
     if !knowledge.story_changes.is_empty() {
-        let mut work_section = String::from("## Recent Work\n\n");
-
-        for story in &knowledge.story_changes {
+        let older = if summarize_older {
+            knowledge
+                .story_changes
+                .len()
+                .saturating_sub(RECENT_STORIES_IN_FULL)
+        } else {
+            0
+        };
+        let (summarized, listed) = knowledge.story_changes.split_at(older);
+
+        let mut entries = Vec::new();
+        if let (Some(first), Some(last)) = (summarized.first(), summarized.last()) {
+            let count =
+                |files: fn(&StoryChanges) -> usize| -> usize { summarized.iter().map(files).sum() };
+            entries.push(ContextEntry {
+                line: format!(
+                    "- **{} to {}** ({} earlier stories): {} files created, {} modified, {} deleted\n",
+                    first.story_id,
+                    last.story_id,
+                    summarized.len(),
+                    count(|story| story.files_created.len()),
+                    count(|story| story.files_modified.len()),
+                    count(|story| story.files_deleted.len())
+                ),
+                score: 50,
+            });
+        }
+        for story in listed {
             let mut file_list: Vec<String> = Vec::new();
+            let mut mentioned = false;
 
             for fc in &story.files_created {
+                mentioned |= relevance.mentions(&fc.path);
                 file_list.push(format!(
                     "+{}",
                     abbreviate_path(fc.path.to_string_lossy().as_ref())
                 ));
             }
             for fc in &story.files_modified {
+                mentioned |= relevance.mentions(&fc.path);
                 file_list.push(format!(
                     "~{}",
                     abbreviate_path(fc.path.to_string_lossy().as_ref())
                 ));
             }
             for path in &story.files_deleted {
+                mentioned |= relevance.mentions(path);
                 file_list.push(format!(
                     "-{}",
                     abbreviate_path(path.to_string_lossy().as_ref())
@@ -394,17 +811,143 @@ pub fn build_knowledge_context(knowledge: &ProjectKnowledge) -> Option<String> {
                 truncate_str(&file_list.join(", "), 80)
             };
 
-            work_section.push_str(&format!("- **{}**: {}\n", story.story_id, files_str));
+            entries.push(ContextEntry {
+                line: format!("- **{}**: {}\n", story.story_id, files_str),
+                score: 20
+                    + relevance.story_score(&story.story_id)
+                    + if mentioned { MENTIONED_SCORE } else { 0 },
+            });
         }
-
-        sections.push(work_section);
+        sections.push(ContextSection::new("## Recent Work\n\n", entries));
     }
 
-    if sections.is_empty() {
-        None
-    } else {
-        Some(sections.join("\n"))
+    sections.extend(inherited_section(knowledge, relevance));
+    sections
+}
+
+/// A pattern as a list item, with its example file if it has one.
+fn pattern_line(pattern: &crate::knowledge::Pattern) -> String {
+    let example = pattern
+        .example_file
+        .as_ref()
+        .map(|p| format!(" (see {})", abbreviate_path(p.to_string_lossy().as_ref())))
+        .unwrap_or_default();
+    format!("- {}{}\n", pattern.description, example)
+}
+
+/// The "Inherited from Earlier Runs" section of the knowledge context, with
+/// entries this run learned again left out.
+///
+/// Inherited entries rank below this run's own unless the story mentions
+/// their file; the store lists decisions and patterns most recent first.
+fn inherited_section(
+    knowledge: &ProjectKnowledge,
+    relevance: &Relevance,
+) -> Option<ContextSection> {
+    let inherited = &knowledge.inherited;
+    let same = |a: &str, b: &str| a.trim().eq_ignore_ascii_case(b.trim());
+    let by_age = |i: usize, len: usize| 10 * (len - i) as u32 / len as u32;
+
+    let mut files: Vec<_> = inherited
+        .files
+        .iter()
+        .filter(|(path, _)| !knowledge.files.contains_key(*path))
+        .collect();
+    files.sort_by(|a, b| a.0.cmp(b.0));
+    let decisions: Vec<_> = inherited
+        .decisions
+        .iter()
+        .filter(|d| {
+            !knowledge
+                .decisions
+                .iter()
+                .any(|own| same(&own.topic, &d.topic))
+        })
+        .collect();
+    let patterns: Vec<_> = inherited
+        .patterns
+        .iter()
+        .filter(|p| {
+            !knowledge
+                .patterns
+                .iter()
+                .any(|own| same(&own.description, &p.description))
+        })
+        .collect();
+    if files.is_empty() && decisions.is_empty() && patterns.is_empty() {
+        return None;
     }
+
+    let files = files
+        .into_iter()
+        .map(|(path, info)| {
+            let symbols = if info.key_symbols.is_empty() {
+                "-".to_string()
+            } else {
+                truncate_str(&info.key_symbols.join(", "), 30)
+            };
+            ContextEntry {
+                line: format!(
+                    "| {} | {} | {} |\n",
+                    abbreviate_path(path.to_string_lossy().as_ref()),
+                    truncate_str(&info.purpose, 40),
+                    symbols
+                ),
+                score: relevance.mention_score(path),
+            }
+        })
+        .collect();
+    let decision_count = decisions.len();
+    let decisions = decisions
+        .into_iter()
+        .enumerate()
+        .map(|(i, decision)| ContextEntry {
+            line: format!(
+                "- **{}**: {} — {}\n",
+                decision.topic,
+                decision.choice,
+                truncate_str(&decision.rationale, 60)
+            ),
+            score: by_age(i, decision_count),
+        })
+        .collect();
+    let pattern_count = patterns.len();
+    let patterns = patterns
+        .into_iter()
+        .enumerate()
+        .map(|(i, pattern)| ContextEntry {
+            line: pattern_line(pattern),
+            score: by_age(i, pattern_count)
+                + pattern
+                    .example_file
+                    .as_deref()
+                    .map_or(0, |path| relevance.mention_score(path)),
+        })
+        .collect();
+
+    Some(ContextSection {
+        heading: "## Inherited from Earlier Runs\n\n\
+                  Learned while building earlier features of this project. \
+                  Verify against the code before relying on it.\n"
+            .to_string(),
+        groups: vec![
+            ContextGroup {
+                heading: "\n### Known Files\n\n\
+                          | Path | Purpose | Key Symbols |\n\
+                          |------|---------|-------------|\n"
+                    .to_string(),
+                entries: files,
+            },
+            ContextGroup {
+                heading: "\n### Decisions\n\n".to_string(),
+                entries: decisions,
+            },
+            ContextGroup {
+                heading: "\n### Patterns\n\n".to_string(),
+                entries: patterns,
+            },
+        ],
+    })
 }
 
 /// Abbreviate a file path for concise display.
@@ -1004,6 +1547,64 @@ Done!"#;
         assert!(patterns.is_empty());
     }
 
+    // ===========================================
+    // build_previous_context tests
+    // ===========================================
+
+    fn make_iteration(
+        story_id: &str,
+        work_summary: Option<&str>,
+        verify_failure: Option<&str>,
+    ) -> IterationRecord {
+        IterationRecord {
+            number: 1,
+            story_id: story_id.to_string(),
+            started_at: chrono::Utc::now(),
+            finished_at: None,
+            status: crate::state::IterationStatus::Success,
+            output_snippet: String::new(),
+            work_summary: work_summary.map(String::from),
+            usage: None,
+            verify_failure: verify_failure.map(String::from),
+            retries: Vec::new(),
+            model: None,
+        }
+    }
+
+    #[test]
+    fn test_build_previous_context_empty_returns_none() {
+        assert!(build_previous_context(&[]).is_none());
+        assert!(build_previous_context(&[make_iteration("US-001", None, None)]).is_none());
+    }
+
+    #[test]
+    fn test_build_previous_context_lists_summaries() {
+        let iterations = vec![
+            make_iteration("US-001", Some("Added login"), None),
+            make_iteration("US-002", Some("Added logout"), None),
+        ];
+        assert_eq!(
+            build_previous_context(&iterations).unwrap(),
+            "US-001: Added login\nUS-002: Added logout"
+        );
+    }
+
+    #[test]
+    fn test_build_previous_context_includes_latest_verify_failure() {
+        let iterations = vec![
+            make_iteration("US-001", None, Some("`cargo test` exited with code 101")),
+            make_iteration("US-002", None, Some("`npm test` exited with code 1")),
+            make_iteration("US-002", Some("Fixed tests"), None),
+        ];
+        let context = build_previous_context(&iterations).unwrap();
+
+        assert!(context.starts_with("US-002: Fixed tests"));
+        assert!(context.contains("last attempt at US-001"));
+        assert!(context.contains("`cargo test` exited with code 101"));
+        // US-002's failure was superseded by a later attempt
+        assert!(!context.contains("npm test"));
+    }
+
     // ===========================================
     // build_knowledge_context tests
     // ===========================================
@@ -1012,6 +1613,7 @@ Done!"#;
         Decision as KnowledgeDecision, FileChange, FileInfo, Pattern as KnowledgePattern,
         ProjectKnowledge, StoryChanges,
     };
+    use crate::spec::UserStory;
 
     #[test]
     fn test_build_knowledge_context_empty_returns_none() {
@@ -1121,6 +1723,7 @@ Done!"#;
             }],
             files_deleted: vec![PathBuf::from("src/old.rs")],
             commit_hash: Some("abc123".to_string()),
+            base_commit: None,
         });
 
         let result = build_knowledge_context(&knowledge);
@@ -1143,6 +1746,7 @@ Done!"#;
             files_modified: vec![],
             files_deleted: vec![],
             commit_hash: None,
+            base_commit: None,
         });
 
         let result = build_knowledge_context(&knowledge);
@@ -1195,6 +1799,7 @@ Done!"#;
             files_modified: vec![],
             files_deleted: vec![],
             commit_hash: None,
+            base_commit: None,
         });
 
         let result = build_knowledge_context(&knowledge);
@@ -1362,6 +1967,7 @@ Done!"#;
             files_modified: vec![],
             files_deleted: vec![],
             commit_hash: None,
+            base_commit: None,
         });
 
         knowledge.story_changes.push(StoryChanges {
@@ -1376,6 +1982,7 @@ Done!"#;
             files_created: vec![],
             files_deleted: vec![],
             commit_hash: None,
+            base_commit: None,
         });
 
         let result = build_knowledge_context(&knowledge).unwrap();
@@ -1385,4 +1992,181 @@ Done!"#;
         assert!(result.contains("+s/first.rs"));
         assert!(result.contains("~s/second.rs"));
     }
+
+    #[test]
+    fn test_build_knowledge_context_inherited_section() {
+        let mut knowledge = ProjectKnowledge::default();
+        let decision = |topic: &str, choice: &str| KnowledgeDecision {
+            story_id: "US-001".to_string(),
+            topic: topic.to_string(),
+            choice: choice.to_string(),
+            rationale: "Simple".to_string(),
+        };
+        knowledge.inherited.files.insert(
+            PathBuf::from("src/db.rs"),
+            FileInfo {
+                purpose: "Database access".to_string(),
+                key_symbols: vec!["Pool".to_string()],
+                touched_by: vec!["US-003".to_string()],
+                line_count: 80,
+            },
+        );
+        knowledge
+            .inherited
+            .decisions
+            .push(decision("Storage", "SQLite"));
+        knowledge
+            .inherited
+            .decisions
+            .push(decision("Logging", "tracing"));
+        knowledge.decisions.push(decision("storage", "Postgres"));
+
+        let context = build_knowledge_context(&knowledge).unwrap();
+
+        assert!(context.contains("## Inherited from Earlier Runs"));
+        assert!(context.contains("| s/db.rs | Database access | Pool |"));
+        assert!(context.contains("**Logging**: tracing"));
+        assert!(context.contains("Postgres"));
+        assert!(!context.contains("SQLite"));
+
+        let mut only_inherited = ProjectKnowledge::default();
+        only_inherited
+            .inherited
+            .decisions
+            .push(decision("Logging", "tracing"));
+        let context = build_knowledge_context(&only_inherited).unwrap();
+        assert!(!context.contains("## Architectural Decisions"));
+        assert!(context.contains("## Inherited from Earlier Runs"));
+    }
+
+    // ========================================================================
+    // build_story_knowledge_context tests
+    // ========================================================================
+
+    /// A run of `stories` stories, each creating one module and making one
+    /// decision.
+    fn long_run(stories: u32) -> ProjectKnowledge {
+        let mut knowledge = ProjectKnowledge::default();
+        for n in 1..=stories {
+            let id = format!("US-{:03}", n);
+            let path = PathBuf::from(format!("src/module_{:02}.rs", n));
+            knowledge.files.insert(
+                path.clone(),
+                FileInfo {
+                    purpose: format!("Handles the feature added by {}", id),
+                    key_symbols: vec![format!("Feature{}", n)],
+                    touched_by: vec![id.clone()],
+                    line_count: 120,
+                },
+            );
+            knowledge.decisions.push(KnowledgeDecision {
+                story_id: id.clone(),
+                topic: format!("Feature {} storage", n),
+                choice: "In-memory map".to_string(),
+                rationale: "Nothing needs to survive a restart yet".to_string(),
+            });
+            knowledge.story_changes.push(StoryChanges {
+                story_id: id,
+                files_created: vec![FileChange {
+                    path,
+                    additions: 120,
+                    deletions: 0,
+                    purpose: None,
+                    key_symbols: vec![],
+                }],
+                files_modified: vec![],
+                files_deleted: vec![],
+                commit_hash: None,
+                base_commit: None,
+            });
+        }
+        knowledge
+    }
+
+    fn next_story(description: &str, depends_on: &[&str]) -> UserStory {
+        UserStory {
+            id: "US-099".to_string(),
+            title: "Next feature".to_string(),
+            description: description.to_string(),
+            acceptance_criteria: vec![],
+            priority: 1,
+            status: Default::default(),
+            notes: String::new(),
+            depends_on: depends_on.iter().map(|s| s.to_string()).collect(),
+            verify: None,
+        }
+    }
+
+    #[test]
+    fn test_story_knowledge_context_within_budget_is_untouched() {
+        let knowledge = long_run(3);
+        let story = next_story("Extend src/module_01.rs", &[]);
+
+        let context =
+            build_story_knowledge_context(&knowledge, Some(&story), Some(10_000)).unwrap();
+
+        assert!(!context.is_trimmed());
+        assert_eq!(Some(context.text), build_knowledge_context(&knowledge));
+        assert!(
+            build_story_knowledge_context(&ProjectKnowledge::default(), None, Some(10)).is_none()
+        );
+    }
+
+    #[test]
+    fn test_story_knowledge_context_summarizes_older_stories() {
+        let mut knowledge = long_run(12);
+        knowledge.files.clear();
+        knowledge.decisions.clear();
+        let full = build_knowledge_context(&knowledge).unwrap();
+
+        let budget = estimate_tokens(&full) * 2 / 3;
+        let context = build_story_knowledge_context(&knowledge, None, Some(budget)).unwrap();
+
+        assert_eq!(context.summarized_stories, 9);
+        assert_eq!(context.dropped_entries, 0);
+        assert!(context.text.contains(
+            "- **US-001 to US-009** (9 earlier stories): 9 files created, 0 modified, 0 deleted"
+        ));
+        assert!(context.text.contains("- **US-010**: +s/module_10.rs"));
+        assert!(!context.text.contains("- **US-001**:"));
+        assert!(estimate_tokens(&context.text) <= budget);
+        assert_eq!(context.trim_summary(), "summarized 9 earlier stories");
+    }
+
+    #[test]
+    fn test_story_knowledge_context_keeps_most_relevant_entries() {
+        let knowledge = long_run(20);
+        let story = next_story("Add caching to module_02.rs", &["US-005"]);
+
+        let context = build_story_knowledge_context(&knowledge, Some(&story), Some(300)).unwrap();
+        let text = &context.text;
+
+        assert!(estimate_tokens(text) <= 300);
+        assert_eq!(context.summarized_stories, 17);
+        assert!(context.dropped_entries > 0);
+        assert!(context.dropped_tokens > 0);
+        // Mentioned by the story
+        assert!(text.contains("| s/module_02.rs |"));
+        // From a story it depends on
+        assert!(text.contains("**Feature 5 storage**"));
+        // Most recent
+        assert!(text.contains("**Feature 20 storage**"));
+        assert!(text.contains("- **US-020**: +s/module_20.rs"));
+        // Old and unrelated
+        assert!(!text.contains("| s/module_03.rs |"));
+        assert!(!text.contains("**Feature 3 storage**"));
+        assert!(text.ends_with(&format!(
+            "\n_{} less relevant entries (~{} tokens) were left out to fit the knowledge budget._\n",
+            context.dropped_entries, context.dropped_tokens
+        )));
+    }
+
+    #[test]
+    fn test_contains_word() {
+        assert!(contains_word("update src/db.rs first", "src/db.rs"));
+        assert!(contains_word("(db.rs)", "db.rs"));
+        assert!(!contains_word("update metadata.rs", "data.rs"));
+        assert!(!contains_word("db.rsx", "db.rs"));
+        assert!(!contains_word("anything", ""));
+    }
 }
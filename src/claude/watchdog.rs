@@ -0,0 +1,285 @@
+//! Wall-clock and inactivity limits for Claude subprocesses.
+//!
+//! A hung `claude` process blocks its caller on stdout forever. A
+//! [`Watchdog`] runs on its own thread and fires a callback (normally
+//! [`ClaudeRunner::kill`](super::ClaudeRunner::kill)) once the process runs
+//! past its time limit, or goes too long without writing a line of output.
+//! Killing the process closes stdout and lets the caller turn the result into
+//! [`Autom8Error::ClaudeTimeout`] or [`Autom8Error::ClaudeStalled`].
+
+use std::sync::mpsc::{self, RecvTimeoutError, Sender};
+use std::sync::{Arc, Mutex};
+use std::thread::JoinHandle;
+use std::time::{Duration, Instant};
+
+use chrono::{DateTime, Utc};
+
+use crate::error::{Autom8Error, Result};
+
+/// How often the watchdog checks its limits, at most.
+const WATCHDOG_POLL_INTERVAL: Duration = Duration::from_secs(1);
+
+/// What a Claude subprocess last did, shared between the thread reading its
+/// output, the watchdog and the live display.
+#[derive(Debug, Clone)]
+pub struct ClaudeActivity {
+    inner: Arc<Mutex<ActivitySnapshot>>,
+}
+
+/// A copy of [`ClaudeActivity`] at one point in time.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ActivitySnapshot {
+    /// When the last line of output arrived (or output started).
+    pub last_line_at: DateTime<Utc>,
+    /// The last tool call, e.g. `Bash: cargo test`.
+    pub last_tool: Option<String>,
+}
+
+impl ClaudeActivity {
+    pub fn new() -> Self {
+        Self {
+            inner: Arc::new(Mutex::new(ActivitySnapshot {
+                last_line_at: Utc::now(),
+                last_tool: None,
+            })),
+        }
+    }
+
+    /// Start tracking a new subprocess.
+    pub(crate) fn reset(&self) {
+        if let Ok(mut inner) = self.inner.lock() {
+            inner.last_line_at = Utc::now();
+            inner.last_tool = None;
+        }
+    }
+
+    /// Record a line of output from the subprocess.
+    pub(crate) fn record_line(&self) {
+        if let Ok(mut inner) = self.inner.lock() {
+            inner.last_line_at = Utc::now();
+        }
+    }
+
+    /// Record a tool call the subprocess made.
+    pub(crate) fn record_tool(&self, tool: &str) {
+        if let Ok(mut inner) = self.inner.lock() {
+            inner.last_tool = Some(tool.to_string());
+        }
+    }
+
+    pub fn snapshot(&self) -> ActivitySnapshot {
+        self.inner
+            .lock()
+            .map(|inner| inner.clone())
+            .unwrap_or_else(|e| e.into_inner().clone())
+    }
+
+    /// Time since the last line of output.
+    pub fn idle_for(&self) -> Duration {
+        Utc::now()
+            .signed_duration_since(self.snapshot().last_line_at)
+            .to_std()
+            .unwrap_or_default()
+    }
+}
+
+impl Default for ClaudeActivity {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Which limit a watchdog hit.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Expiry {
+    Timeout,
+    Stalled,
+}
+
+/// Fires a callback if it isn't stopped within a time limit, or if the
+/// watched activity goes quiet for too long.
+///
+/// Dropping the watchdog stops it.
+pub(crate) struct Watchdog {
+    timeout: Option<Duration>,
+    stall_limit: Option<Duration>,
+    expired: Arc<Mutex<Option<Expiry>>>,
+    stop: Option<Sender<()>>,
+    handle: Option<JoinHandle<()>>,
+}
+
+impl Watchdog {
+    /// Call `on_expire` once `timeout` has passed, or once `activity` has
+    /// been idle for `stall_limit`, unless stopped first.
+    ///
+    /// With neither limit the watchdog never fires and no thread is started.
+    pub(crate) fn start<F>(
+        timeout: Option<Duration>,
+        stall_limit: Option<Duration>,
+        activity: ClaudeActivity,
+        on_expire: F,
+    ) -> Self
+    where
+        F: FnOnce() + Send + 'static,
+    {
+        let expired = Arc::new(Mutex::new(None));
+        let mut watchdog = Self {
+            timeout,
+            stall_limit,
+            expired: Arc::clone(&expired),
+            stop: None,
+            handle: None,
+        };
+        let Some(shortest) = timeout.into_iter().chain(stall_limit).min() else {
+            return watchdog;
+        };
+        let poll = shortest.min(WATCHDOG_POLL_INTERVAL);
+
+        let (stop, stopped) = mpsc::channel::<()>();
+        let started = Instant::now();
+        let handle = std::thread::spawn(move || loop {
+            // A message or a dropped sender both mean the work finished in time
+            if !matches!(stopped.recv_timeout(poll), Err(RecvTimeoutError::Timeout)) {
+                return;
+            }
+            let expiry = if timeout.is_some_and(|limit| started.elapsed() >= limit) {
+                Expiry::Timeout
+            } else if stall_limit.is_some_and(|limit| activity.idle_for() >= limit) {
+                Expiry::Stalled
+            } else {
+                continue;
+            };
+            if let Ok(mut expired) = expired.lock() {
+                *expired = Some(expiry);
+            }
+            on_expire();
+            return;
+        });
+
+        watchdog.stop = Some(stop);
+        watchdog.handle = Some(handle);
+        watchdog
+    }
+
+    /// Stop the watchdog, returning `ClaudeTimeout` or `ClaudeStalled` if it
+    /// already fired.
+    pub(crate) fn finish(mut self) -> Result<()> {
+        self.stop();
+        let expired = self.expired.lock().ok().and_then(|e| *e);
+        let secs = |limit: Option<Duration>| limit.map(|l| l.as_secs()).unwrap_or_default();
+        match expired {
+            Some(Expiry::Timeout) => Err(Autom8Error::ClaudeTimeout(secs(self.timeout))),
+            Some(Expiry::Stalled) => Err(Autom8Error::ClaudeStalled(secs(self.stall_limit))),
+            None => Ok(()),
+        }
+    }
+
+    fn stop(&mut self) {
+        if let Some(stop) = self.stop.take() {
+            let _ = stop.send(());
+        }
+        if let Some(handle) = self.handle.take() {
+            let _ = handle.join();
+        }
+    }
+}
+
+impl Drop for Watchdog {
+    fn drop(&mut self) {
+        self.stop();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    fn counting_callback() -> (Arc<AtomicUsize>, impl FnOnce() + Send + 'static) {
+        let calls = Arc::new(AtomicUsize::new(0));
+        let counter = Arc::clone(&calls);
+        (calls, move || {
+            counter.fetch_add(1, Ordering::SeqCst);
+        })
+    }
+
+    #[test]
+    fn test_watchdog_fires_after_timeout() {
+        let (calls, on_expire) = counting_callback();
+        let watchdog = Watchdog::start(
+            Some(Duration::from_millis(10)),
+            None,
+            ClaudeActivity::new(),
+            on_expire,
+        );
+
+        std::thread::sleep(Duration::from_millis(100));
+
+        let err = watchdog.finish().unwrap_err();
+        assert!(matches!(err, Autom8Error::ClaudeTimeout(0)));
+        assert_eq!(calls.load(Ordering::SeqCst), 1);
+    }
+
+    #[test]
+    fn test_watchdog_stopped_in_time_does_not_fire() {
+        let (calls, on_expire) = counting_callback();
+        let watchdog = Watchdog::start(
+            Some(Duration::from_secs(60)),
+            Some(Duration::from_secs(60)),
+            ClaudeActivity::new(),
+            on_expire,
+        );
+
+        assert!(watchdog.finish().is_ok());
+        assert_eq!(calls.load(Ordering::SeqCst), 0);
+    }
+
+    #[test]
+    fn test_watchdog_without_limits_never_fires() {
+        let watchdog = Watchdog::start(None, None, ClaudeActivity::new(), || {
+            panic!("should not fire")
+        });
+        assert!(watchdog.handle.is_none());
+        assert!(watchdog.finish().is_ok());
+    }
+
+    #[test]
+    fn test_watchdog_fires_when_activity_stalls() {
+        let (calls, on_expire) = counting_callback();
+        let activity = ClaudeActivity::new();
+        let watchdog = Watchdog::start(
+            Some(Duration::from_secs(60)),
+            Some(Duration::from_millis(50)),
+            activity.clone(),
+            on_expire,
+        );
+
+        // Output keeps the watchdog quiet...
+        for _ in 0..4 {
+            std::thread::sleep(Duration::from_millis(20));
+            activity.record_line();
+        }
+        assert_eq!(calls.load(Ordering::SeqCst), 0);
+
+        // ...until it stops
+        std::thread::sleep(Duration::from_millis(150));
+        let err = watchdog.finish().unwrap_err();
+        assert!(matches!(err, Autom8Error::ClaudeStalled(0)));
+        assert_eq!(calls.load(Ordering::SeqCst), 1);
+    }
+
+    #[test]
+    fn test_activity_keeps_last_tool_call() {
+        let activity = ClaudeActivity::new();
+        activity.record_line();
+        activity.record_tool("Read: src/lib.rs");
+        activity.record_line();
+        assert_eq!(
+            activity.snapshot().last_tool.as_deref(),
+            Some("Read: src/lib.rs")
+        );
+
+        activity.reset();
+        assert_eq!(activity.snapshot().last_tool, None);
+    }
+}
@@ -1622,10 +1622,7 @@ mod tests {
         let result = remove_project_direct("any-project-name");
 
         // Type check: should be Result<RemovalSummary>
-        let _summary: RemovalSummary = match result {
-            Ok(s) => s,
-            Err(_) => RemovalSummary::default(),
-        };
+        let _summary: RemovalSummary = result.unwrap_or_default();
     }
 
     #[test]
@@ -4,7 +4,8 @@
 
 use crate::config::{
     global_config_path, load_global_config, load_project_config, project_config_path,
-    save_global_config, save_project_config, validate_config, Config,
+    save_global_config, save_project_config, validate_config, CommitStrategy, Config, SandboxKind,
+    StoryFailurePolicy,
 };
 use crate::error::{Autom8Error, Result};
 use crate::git::is_git_repo;
@@ -16,11 +17,31 @@ use std::fs;
 pub const VALID_CONFIG_KEYS: &[&str] = &[
     "review",
     "commit",
+    "commit_strategy",
     "pull_request",
     "pull_request_draft",
     "worktree",
     "worktree_path_pattern",
     "worktree_cleanup",
+    "max_parallel_stories",
+    "spec_migration_backup",
+    "max_iterations_per_story",
+    "max_total_iterations",
+    "on_story_failure",
+    "story_timeout_minutes",
+    "review_timeout_minutes",
+    "correct_timeout_minutes",
+    "commit_timeout_minutes",
+    "pr_timeout_minutes",
+    "stall_timeout_minutes",
+    "kill_on_stall",
+    "claude_retry_attempts",
+    "max_run_tokens",
+    "max_story_tokens",
+    "max_run_cost_usd",
+    "sandbox",
+    "knowledge_decay_runs",
+    "knowledge_context_tokens",
 ];
 
 /// Scope for config operations.
@@ -46,14 +67,37 @@ pub enum ConfigSubcommand {
 VALID KEYS:
     review              - Enable code review step (true/false)
     commit              - Enable auto-commit (true/false)
+    commit_strategy     - When to commit (end/per_story)
     pull_request        - Enable auto-PR creation (true/false, requires commit=true)
     pull_request_draft  - Create PRs as drafts (true/false, requires pull_request=true)
     worktree            - Enable worktree mode (true/false)
     worktree_path_pattern - Pattern for worktree directory names (string)
     worktree_cleanup    - Auto-cleanup worktrees after completion (true/false)
+    max_parallel_stories - Independent stories to run at once (number, 1 = sequential)
+    spec_migration_backup - Back up older spec files when upgrading them (true/false)
+    max_iterations_per_story - Iterations allowed per story before it fails (number)
+    max_total_iterations - Story iterations allowed in a whole run (number)
+    on_story_failure    - What to do when a story fails (fail/skip/pause)
+    story_timeout_minutes - Time limit for one story iteration (minutes, default 0 = none)
+    review_timeout_minutes - Time limit for a review (minutes, default 0 = none)
+    correct_timeout_minutes - Time limit for a correction (minutes, 0 = none)
+    commit_timeout_minutes - Time limit for the commit agent (minutes, 0 = none)
+    pr_timeout_minutes  - Time limit for the PR template agent (minutes, 0 = none)
+    stall_timeout_minutes - Minutes without output before Claude counts as stalled (0 = never)
+    kill_on_stall       - Kill and retry stalled Claude processes (true/false)
+    claude_retry_attempts - Retries after a transient Claude failure (number, 0 = none)
+    max_run_tokens      - Tokens a run may use before pausing (number, 0 = no limit)
+    max_story_tokens    - Tokens one story may use before pausing (number, 0 = no limit)
+    max_run_cost_usd    - Estimated USD a run may spend before pausing (0 = no limit)
+    sandbox             - What agent processes are confined to (none/bwrap)
+    knowledge_decay_runs - Runs a stored knowledge entry survives unless relearned (number, 0 = forever)
+    knowledge_context_tokens - Tokens of project knowledge per story prompt (number, 0 = no limit)
 
 VALUE FORMATS:
     Boolean: true, false (case-insensitive)
+    Number:  A positive integer (timeouts also accept 0)
+    Policy:  fail, skip, pause (case-insensitive)
+    Strategy: end, per_story (case-insensitive)
     String:  Quoted or unquoted text
 
 VALIDATION:
@@ -81,11 +125,31 @@ VALIDATION:
 DEFAULT VALUES:
     review              = true
     commit              = true
+    commit_strategy     = \"end\"
     pull_request        = true
     pull_request_draft  = false
     worktree            = true
     worktree_path_pattern = \"{repo}-wt-{branch}\"
     worktree_cleanup    = false
+    max_parallel_stories = 1
+    spec_migration_backup = true
+    max_iterations_per_story = 10
+    max_total_iterations = 100
+    on_story_failure    = \"fail\"
+    story_timeout_minutes = 0
+    review_timeout_minutes = 0
+    correct_timeout_minutes = 30
+    commit_timeout_minutes = 10
+    pr_timeout_minutes  = 10
+    stall_timeout_minutes = 10
+    kill_on_stall       = false
+    claude_retry_attempts = 3
+    max_run_tokens      = 0
+    max_story_tokens    = 0
+    max_run_cost_usd    = 0
+    sandbox             = \"none\"
+    knowledge_decay_runs = 5
+    knowledge_context_tokens = 2000
 
 BEHAVIOR:
     - Prompts for confirmation before resetting (unless -y/--yes is used)
@@ -289,6 +353,72 @@ fn set_config_value(config: &mut Config, key: &str, value: &str) -> Result<()> {
         "worktree_cleanup" => {
             config.worktree_cleanup = parse_bool_value(value, key)?;
         }
+        "max_parallel_stories" => {
+            config.max_parallel_stories = parse_positive_u32_value(value, key)?;
+        }
+        "spec_migration_backup" => {
+            config.spec_migration_backup = parse_bool_value(value, key)?;
+        }
+        "max_iterations_per_story" => {
+            config.max_iterations_per_story = parse_positive_u32_value(value, key)?;
+        }
+        "max_total_iterations" => {
+            config.max_total_iterations = parse_positive_u32_value(value, key)?;
+        }
+        "commit_strategy" => {
+            config.commit_strategy = value
+                .parse::<CommitStrategy>()
+                .map_err(|e| Autom8Error::Config(format!("Invalid value for '{}': {}", key, e)))?;
+        }
+        "on_story_failure" => {
+            config.on_story_failure = value
+                .parse::<StoryFailurePolicy>()
+                .map_err(|e| Autom8Error::Config(format!("Invalid value for '{}': {}", key, e)))?;
+        }
+        "story_timeout_minutes" => {
+            config.story_timeout_minutes = parse_timeout_minutes_value(value, key)?;
+        }
+        "review_timeout_minutes" => {
+            config.review_timeout_minutes = parse_timeout_minutes_value(value, key)?;
+        }
+        "correct_timeout_minutes" => {
+            config.correct_timeout_minutes = parse_timeout_minutes_value(value, key)?;
+        }
+        "commit_timeout_minutes" => {
+            config.commit_timeout_minutes = parse_timeout_minutes_value(value, key)?;
+        }
+        "pr_timeout_minutes" => {
+            config.pr_timeout_minutes = parse_timeout_minutes_value(value, key)?;
+        }
+        "stall_timeout_minutes" => {
+            config.stall_timeout_minutes = parse_timeout_minutes_value(value, key)?;
+        }
+        "kill_on_stall" => {
+            config.kill_on_stall = parse_bool_value(value, key)?;
+        }
+        "claude_retry_attempts" => {
+            config.claude_retry_attempts = parse_count_value(value, key)?;
+        }
+        "max_run_tokens" => {
+            config.max_run_tokens = parse_token_budget_value(value, key)?;
+        }
+        "max_story_tokens" => {
+            config.max_story_tokens = parse_token_budget_value(value, key)?;
+        }
+        "max_run_cost_usd" => {
+            config.max_run_cost_usd = parse_cost_budget_value(value, key)?;
+        }
+        "sandbox" => {
+            config.sandbox = value
+                .parse::<SandboxKind>()
+                .map_err(|e| Autom8Error::Config(format!("Invalid value for '{}': {}", key, e)))?;
+        }
+        "knowledge_decay_runs" => {
+            config.knowledge_decay_runs = parse_count_value(value, key)?;
+        }
+        "knowledge_context_tokens" => {
+            config.knowledge_context_tokens = parse_token_budget_value(value, key)?;
+        }
         _ => {
             // This shouldn't happen if VALID_CONFIG_KEYS is kept in sync
             return Err(Autom8Error::Config(format!("Unknown key: {}", key)));
@@ -309,6 +439,59 @@ fn parse_bool_value(value: &str, key: &str) -> Result<bool> {
     }
 }
 
+/// Parse a positive integer value from a string.
+fn parse_positive_u32_value(value: &str, key: &str) -> Result<u32> {
+    match value.trim().parse::<u32>() {
+        Ok(n) if n > 0 => Ok(n),
+        _ => Err(Autom8Error::Config(format!(
+            "Invalid value for '{}': expected a positive integer, got '{}'",
+            key, value
+        ))),
+    }
+}
+
+/// Parse a timeout in minutes, where 0 means no limit.
+fn parse_timeout_minutes_value(value: &str, key: &str) -> Result<u32> {
+    value.trim().parse::<u32>().map_err(|_| {
+        Autom8Error::Config(format!(
+            "Invalid value for '{}': expected a number of minutes (0 for no limit), got '{}'",
+            key, value
+        ))
+    })
+}
+
+/// Parse a count where 0 is allowed (e.g., 0 retries).
+fn parse_count_value(value: &str, key: &str) -> Result<u32> {
+    value.trim().parse::<u32>().map_err(|_| {
+        Autom8Error::Config(format!(
+            "Invalid value for '{}': expected a non-negative integer, got '{}'",
+            key, value
+        ))
+    })
+}
+
+/// Parse a token budget, where 0 means no limit.
+fn parse_token_budget_value(value: &str, key: &str) -> Result<u64> {
+    value.trim().replace('_', "").parse::<u64>().map_err(|_| {
+        Autom8Error::Config(format!(
+            "Invalid value for '{}': expected a number of tokens (0 for no limit), got '{}'",
+            key, value
+        ))
+    })
+}
+
+/// Parse a budget in USD (an optional leading `$` is allowed), where 0 means no limit.
+fn parse_cost_budget_value(value: &str, key: &str) -> Result<f64> {
+    let trimmed = value.trim();
+    match trimmed.strip_prefix('$').unwrap_or(trimmed).parse::<f64>() {
+        Ok(usd) if usd.is_finite() && usd >= 0.0 => Ok(usd),
+        _ => Err(Autom8Error::Config(format!(
+            "Invalid value for '{}': expected an amount in USD (0 for no limit), got '{}'",
+            key, value
+        ))),
+    }
+}
+
 /// Format a config value for display in the confirmation message.
 fn format_value_for_display(key: &str, config: &Config) -> String {
     match key {
@@ -319,6 +502,26 @@ fn format_value_for_display(key: &str, config: &Config) -> String {
         "worktree" => config.worktree.to_string(),
         "worktree_path_pattern" => format!("\"{}\"", config.worktree_path_pattern),
         "worktree_cleanup" => config.worktree_cleanup.to_string(),
+        "max_parallel_stories" => config.max_parallel_stories.to_string(),
+        "spec_migration_backup" => config.spec_migration_backup.to_string(),
+        "max_iterations_per_story" => config.max_iterations_per_story.to_string(),
+        "max_total_iterations" => config.max_total_iterations.to_string(),
+        "commit_strategy" => format!("\"{}\"", config.commit_strategy),
+        "on_story_failure" => format!("\"{}\"", config.on_story_failure),
+        "story_timeout_minutes" => config.story_timeout_minutes.to_string(),
+        "review_timeout_minutes" => config.review_timeout_minutes.to_string(),
+        "correct_timeout_minutes" => config.correct_timeout_minutes.to_string(),
+        "commit_timeout_minutes" => config.commit_timeout_minutes.to_string(),
+        "pr_timeout_minutes" => config.pr_timeout_minutes.to_string(),
+        "stall_timeout_minutes" => config.stall_timeout_minutes.to_string(),
+        "kill_on_stall" => config.kill_on_stall.to_string(),
+        "claude_retry_attempts" => config.claude_retry_attempts.to_string(),
+        "max_run_tokens" => config.max_run_tokens.to_string(),
+        "max_story_tokens" => config.max_story_tokens.to_string(),
+        "max_run_cost_usd" => config.max_run_cost_usd.to_string(),
+        "sandbox" => format!("\"{}\"", config.sandbox),
+        "knowledge_decay_runs" => config.knowledge_decay_runs.to_string(),
+        "knowledge_context_tokens" => config.knowledge_context_tokens.to_string(),
         _ => "unknown".to_string(),
     }
 }
@@ -408,6 +611,10 @@ pub fn config_reset_command(global: bool, yes: bool) -> Result<()> {
 fn print_config_as_toml(config: &Config) {
     println!("{CYAN}review{RESET} = {}", config.review);
     println!("{CYAN}commit{RESET} = {}", config.commit);
+    println!(
+        "{CYAN}commit_strategy{RESET} = \"{}\"",
+        config.commit_strategy
+    );
     println!("{CYAN}pull_request{RESET} = {}", config.pull_request);
     println!(
         "{CYAN}pull_request_draft{RESET} = {}",
@@ -422,27 +629,244 @@ fn print_config_as_toml(config: &Config) {
         "{CYAN}worktree_cleanup{RESET} = {}",
         config.worktree_cleanup
     );
+    println!(
+        "{CYAN}max_parallel_stories{RESET} = {}",
+        config.max_parallel_stories
+    );
+    println!(
+        "{CYAN}spec_migration_backup{RESET} = {}",
+        config.spec_migration_backup
+    );
+    println!(
+        "{CYAN}max_iterations_per_story{RESET} = {}",
+        config.max_iterations_per_story
+    );
+    println!(
+        "{CYAN}max_total_iterations{RESET} = {}",
+        config.max_total_iterations
+    );
+    println!(
+        "{CYAN}on_story_failure{RESET} = \"{}\"",
+        config.on_story_failure
+    );
+    println!(
+        "{CYAN}story_timeout_minutes{RESET} = {}",
+        config.story_timeout_minutes
+    );
+    println!(
+        "{CYAN}review_timeout_minutes{RESET} = {}",
+        config.review_timeout_minutes
+    );
+    println!(
+        "{CYAN}correct_timeout_minutes{RESET} = {}",
+        config.correct_timeout_minutes
+    );
+    println!(
+        "{CYAN}commit_timeout_minutes{RESET} = {}",
+        config.commit_timeout_minutes
+    );
+    println!(
+        "{CYAN}pr_timeout_minutes{RESET} = {}",
+        config.pr_timeout_minutes
+    );
+    println!(
+        "{CYAN}stall_timeout_minutes{RESET} = {}",
+        config.stall_timeout_minutes
+    );
+    println!("{CYAN}kill_on_stall{RESET} = {}", config.kill_on_stall);
+    println!(
+        "{CYAN}claude_retry_attempts{RESET} = {}",
+        config.claude_retry_attempts
+    );
+    println!("{CYAN}max_run_tokens{RESET} = {}", config.max_run_tokens);
+    println!(
+        "{CYAN}max_story_tokens{RESET} = {}",
+        config.max_story_tokens
+    );
+    println!(
+        "{CYAN}max_run_cost_usd{RESET} = {}",
+        config.max_run_cost_usd
+    );
+    println!("{CYAN}sandbox{RESET} = \"{}\"", config.sandbox);
+    println!(
+        "{CYAN}knowledge_decay_runs{RESET} = {}",
+        config.knowledge_decay_runs
+    );
+    println!(
+        "{CYAN}knowledge_context_tokens{RESET} = {}",
+        config.knowledge_context_tokens
+    );
+
+    let hooks = config.hooks.entries();
+    if !hooks.is_empty() {
+        println!();
+        println!("{CYAN}[hooks]{RESET}");
+        for (key, command) in hooks {
+            println!(
+                "{CYAN}{}{RESET} = {}",
+                key,
+                toml::Value::String(command.to_string())
+            );
+        }
+    }
+
+    let fixtures = config.fixtures.entries();
+    if !fixtures.is_empty() {
+        println!();
+        println!("{CYAN}[fixtures]{RESET}");
+        for (key, dir) in fixtures {
+            println!(
+                "{CYAN}{}{RESET} = {}",
+                key,
+                toml::Value::String(dir.to_string())
+            );
+        }
+    }
+
+    let agent = config.agent.entries();
+    if !agent.is_empty() {
+        println!();
+        println!("{CYAN}[agent]{RESET}");
+        for (key, value) in agent {
+            println!("{CYAN}{}{RESET} = {}", key, value);
+        }
+    }
+
+    let claude = config.claude.entries();
+    if !claude.is_empty() {
+        println!();
+        println!("{CYAN}[claude]{RESET}");
+        for (key, value) in claude {
+            println!("{CYAN}{}{RESET} = {}", key, value);
+        }
+    }
+    for (table, entries) in config.claude.subtables() {
+        println!();
+        println!("{CYAN}[{}]{RESET}", table);
+        for (key, value) in entries {
+            println!("{CYAN}{}{RESET} = {}", key, value);
+        }
+    }
+
+    let bwrap = config.bwrap.entries();
+    if !bwrap.is_empty() {
+        println!();
+        println!("{CYAN}[bwrap]{RESET}");
+        for (key, value) in bwrap {
+            println!("{CYAN}{}{RESET} = {}", key, value);
+        }
+    }
 }
 
 /// Convert a Config to a TOML string (for testing).
 #[cfg(test)]
 fn config_to_toml_string(config: &Config) -> String {
-    format!(
+    let mut out = format!(
         "review = {}\n\
          commit = {}\n\
+         commit_strategy = \"{}\"\n\
          pull_request = {}\n\
          pull_request_draft = {}\n\
          worktree = {}\n\
          worktree_path_pattern = \"{}\"\n\
-         worktree_cleanup = {}",
+         worktree_cleanup = {}\n\
+         max_parallel_stories = {}\n\
+         spec_migration_backup = {}\n\
+         max_iterations_per_story = {}\n\
+         max_total_iterations = {}\n\
+         on_story_failure = \"{}\"\n\
+         story_timeout_minutes = {}\n\
+         review_timeout_minutes = {}\n\
+         correct_timeout_minutes = {}\n\
+         commit_timeout_minutes = {}\n\
+         pr_timeout_minutes = {}\n\
+         stall_timeout_minutes = {}\n\
+         kill_on_stall = {}\n\
+         claude_retry_attempts = {}\n\
+         max_run_tokens = {}\n\
+         max_story_tokens = {}\n\
+         max_run_cost_usd = {}\n\
+         sandbox = \"{}\"\n\
+         knowledge_decay_runs = {}\n\
+         knowledge_context_tokens = {}",
         config.review,
         config.commit,
+        config.commit_strategy,
         config.pull_request,
         config.pull_request_draft,
         config.worktree,
         config.worktree_path_pattern,
-        config.worktree_cleanup
-    )
+        config.worktree_cleanup,
+        config.max_parallel_stories,
+        config.spec_migration_backup,
+        config.max_iterations_per_story,
+        config.max_total_iterations,
+        config.on_story_failure,
+        config.story_timeout_minutes,
+        config.review_timeout_minutes,
+        config.correct_timeout_minutes,
+        config.commit_timeout_minutes,
+        config.pr_timeout_minutes,
+        config.stall_timeout_minutes,
+        config.kill_on_stall,
+        config.claude_retry_attempts,
+        config.max_run_tokens,
+        config.max_story_tokens,
+        config.max_run_cost_usd,
+        config.sandbox,
+        config.knowledge_decay_runs,
+        config.knowledge_context_tokens
+    );
+    let hooks = config.hooks.entries();
+    if !hooks.is_empty() {
+        out.push_str("\n\n[hooks]");
+        for (key, command) in hooks {
+            out.push_str(&format!(
+                "\n{} = {}",
+                key,
+                toml::Value::String(command.to_string())
+            ));
+        }
+    }
+    let fixtures = config.fixtures.entries();
+    if !fixtures.is_empty() {
+        out.push_str("\n\n[fixtures]");
+        for (key, dir) in fixtures {
+            out.push_str(&format!(
+                "\n{} = {}",
+                key,
+                toml::Value::String(dir.to_string())
+            ));
+        }
+    }
+    let agent = config.agent.entries();
+    if !agent.is_empty() {
+        out.push_str("\n\n[agent]");
+        for (key, value) in agent {
+            out.push_str(&format!("\n{} = {}", key, value));
+        }
+    }
+    let claude = config.claude.entries();
+    if !claude.is_empty() {
+        out.push_str("\n\n[claude]");
+        for (key, value) in claude {
+            out.push_str(&format!("\n{} = {}", key, value));
+        }
+    }
+    for (table, entries) in config.claude.subtables() {
+        out.push_str(&format!("\n\n[{}]", table));
+        for (key, value) in entries {
+            out.push_str(&format!("\n{} = {}", key, value));
+        }
+    }
+    let bwrap = config.bwrap.entries();
+    if !bwrap.is_empty() {
+        out.push_str("\n\n[bwrap]");
+        for (key, value) in bwrap {
+            out.push_str(&format!("\n{} = {}", key, value));
+        }
+    }
+    out
 }
 
 #[cfg(test)]
@@ -476,21 +900,103 @@ mod tests {
         let config = Config {
             review: false,
             commit: true,
+            commit_strategy: CommitStrategy::PerStory,
             pull_request: false,
             pull_request_draft: true,
             worktree: false,
             worktree_path_pattern: "custom-{branch}".to_string(),
             worktree_cleanup: true,
+            max_parallel_stories: 3,
+            spec_migration_backup: false,
+            max_iterations_per_story: 5,
+            max_total_iterations: 40,
+            on_story_failure: StoryFailurePolicy::Skip,
+            story_timeout_minutes: 90,
+            review_timeout_minutes: 15,
+            correct_timeout_minutes: 25,
+            commit_timeout_minutes: 5,
+            pr_timeout_minutes: 0,
+            stall_timeout_minutes: 5,
+            kill_on_stall: true,
+            claude_retry_attempts: 0,
+            max_run_tokens: 1_000_000,
+            max_story_tokens: 0,
+            max_run_cost_usd: 7.5,
+            sandbox: SandboxKind::Bwrap,
+            knowledge_decay_runs: 0,
+            knowledge_context_tokens: 500,
+            hooks: crate::config::HooksConfig {
+                pre_story: Some("docker compose up -d db".to_string()),
+                ..Default::default()
+            },
+            fixtures: crate::config::FixturesConfig {
+                record: None,
+                replay: Some("fixtures/auth".to_string()),
+            },
+            agent: crate::config::AgentConfig {
+                backend: crate::config::AgentKind::Command,
+                command: Some("my-agent".to_string()),
+                ..Default::default()
+            },
+            claude: crate::config::ClaudeConfig {
+                model: Some("opus".to_string()),
+                commit: crate::config::ClaudePhaseConfig {
+                    model: Some("haiku".to_string()),
+                    args: None,
+                    permissions: Some("ci".to_string()),
+                },
+                profiles: [(
+                    "ci".to_string(),
+                    crate::config::PermissionProfile {
+                        tools: vec!["Read".to_string()],
+                        bash: vec!["git diff:*".to_string()],
+                    },
+                )]
+                .into(),
+                ..Default::default()
+            },
+            bwrap: crate::config::BwrapConfig {
+                read_only: vec!["~/.rustup".to_string()],
+                writable: Vec::new(),
+                network: false,
+            },
         };
         let toml_str = config_to_toml_string(&config);
 
         assert!(toml_str.contains("review = false"));
         assert!(toml_str.contains("commit = true"));
+        assert!(toml_str.contains("commit_strategy = \"per_story\""));
         assert!(toml_str.contains("pull_request = false"));
         assert!(toml_str.contains("pull_request_draft = true"));
         assert!(toml_str.contains("worktree = false"));
         assert!(toml_str.contains("worktree_path_pattern = \"custom-{branch}\""));
         assert!(toml_str.contains("worktree_cleanup = true"));
+        assert!(toml_str.contains("max_parallel_stories = 3"));
+        assert!(toml_str.contains("spec_migration_backup = false"));
+        assert!(toml_str.contains("max_iterations_per_story = 5"));
+        assert!(toml_str.contains("max_total_iterations = 40"));
+        assert!(toml_str.contains("on_story_failure = \"skip\""));
+        assert!(toml_str.contains("story_timeout_minutes = 90"));
+        assert!(toml_str.contains("pr_timeout_minutes = 0"));
+        assert!(toml_str.contains("stall_timeout_minutes = 5"));
+        assert!(toml_str.contains("kill_on_stall = true"));
+        assert!(toml_str.contains("claude_retry_attempts = 0"));
+        assert!(toml_str.contains("max_run_tokens = 1000000"));
+        assert!(toml_str.contains("max_story_tokens = 0"));
+        assert!(toml_str.contains("max_run_cost_usd = 7.5"));
+        assert!(toml_str.contains("sandbox = \"bwrap\""));
+        assert!(toml_str.contains("knowledge_decay_runs = 0"));
+        assert!(toml_str.contains("knowledge_context_tokens = 500"));
+        assert!(toml_str.contains("[hooks]\npre_story = \"docker compose up -d db\""));
+        assert!(toml_str.contains("[fixtures]\nreplay = \"fixtures/auth\""));
+        assert!(toml_str.contains("[agent]\nbackend = \"command\"\ncommand = \"my-agent\""));
+        assert!(
+            toml_str.contains("[claude]\nmodel = \"opus\"\n\n[claude.commit]\nmodel = \"haiku\"\npermissions = \"ci\"")
+        );
+        assert!(
+            toml_str.contains("[claude.profiles.ci]\ntools = [\"Read\"]\nbash = [\"git diff:*\"]")
+        );
+        assert!(toml_str.contains("[bwrap]\nread_only = [\"~/.rustup\"]\nnetwork = false"));
     }
 
     #[test]
@@ -524,15 +1030,35 @@ mod tests {
         // Verify all expected keys are in the VALID_CONFIG_KEYS constant
         assert!(VALID_CONFIG_KEYS.contains(&"review"));
         assert!(VALID_CONFIG_KEYS.contains(&"commit"));
+        assert!(VALID_CONFIG_KEYS.contains(&"commit_strategy"));
         assert!(VALID_CONFIG_KEYS.contains(&"pull_request"));
         assert!(VALID_CONFIG_KEYS.contains(&"pull_request_draft"));
         assert!(VALID_CONFIG_KEYS.contains(&"worktree"));
         assert!(VALID_CONFIG_KEYS.contains(&"worktree_path_pattern"));
         assert!(VALID_CONFIG_KEYS.contains(&"worktree_cleanup"));
+        assert!(VALID_CONFIG_KEYS.contains(&"max_parallel_stories"));
+        assert!(VALID_CONFIG_KEYS.contains(&"spec_migration_backup"));
+        assert!(VALID_CONFIG_KEYS.contains(&"max_iterations_per_story"));
+        assert!(VALID_CONFIG_KEYS.contains(&"max_total_iterations"));
+        assert!(VALID_CONFIG_KEYS.contains(&"on_story_failure"));
+        assert!(VALID_CONFIG_KEYS.contains(&"story_timeout_minutes"));
+        assert!(VALID_CONFIG_KEYS.contains(&"review_timeout_minutes"));
+        assert!(VALID_CONFIG_KEYS.contains(&"correct_timeout_minutes"));
+        assert!(VALID_CONFIG_KEYS.contains(&"commit_timeout_minutes"));
+        assert!(VALID_CONFIG_KEYS.contains(&"pr_timeout_minutes"));
+        assert!(VALID_CONFIG_KEYS.contains(&"stall_timeout_minutes"));
+        assert!(VALID_CONFIG_KEYS.contains(&"kill_on_stall"));
+        assert!(VALID_CONFIG_KEYS.contains(&"claude_retry_attempts"));
+        assert!(VALID_CONFIG_KEYS.contains(&"max_run_tokens"));
+        assert!(VALID_CONFIG_KEYS.contains(&"max_story_tokens"));
+        assert!(VALID_CONFIG_KEYS.contains(&"max_run_cost_usd"));
+        assert!(VALID_CONFIG_KEYS.contains(&"sandbox"));
+        assert!(VALID_CONFIG_KEYS.contains(&"knowledge_decay_runs"));
+        assert!(VALID_CONFIG_KEYS.contains(&"knowledge_context_tokens"));
         assert_eq!(
             VALID_CONFIG_KEYS.len(),
-            7,
-            "Should have exactly 7 valid keys"
+            27,
+            "Should have exactly 27 valid keys"
         );
     }
 
@@ -616,6 +1142,18 @@ mod tests {
         assert!(config.worktree_cleanup);
     }
 
+    #[test]
+    fn test_set_config_value_max_parallel_stories() {
+        let mut config = Config::default();
+        assert_eq!(config.max_parallel_stories, 1); // default is sequential
+
+        set_config_value(&mut config, "max_parallel_stories", "4").unwrap();
+        assert_eq!(config.max_parallel_stories, 4);
+
+        assert!(set_config_value(&mut config, "max_parallel_stories", "0").is_err());
+        assert!(set_config_value(&mut config, "max_parallel_stories", "many").is_err());
+    }
+
     #[test]
     fn test_us002_set_config_value_worktree_path_pattern() {
         let mut config = Config::default();
@@ -636,9 +1174,11 @@ mod tests {
 
     #[test]
     fn test_us002_format_value_for_display_boolean() {
-        let mut config = Config::default();
-        config.review = true;
-        config.commit = false;
+        let config = Config {
+            review: true,
+            commit: false,
+            ..Default::default()
+        };
 
         assert_eq!(format_value_for_display("review", &config), "true");
         assert_eq!(format_value_for_display("commit", &config), "false");
@@ -646,8 +1186,10 @@ mod tests {
 
     #[test]
     fn test_us002_format_value_for_display_string() {
-        let mut config = Config::default();
-        config.worktree_path_pattern = "custom-{branch}".to_string();
+        let config = Config {
+            worktree_path_pattern: "custom-{branch}".to_string(),
+            ..Default::default()
+        };
 
         // String values should be quoted
         assert_eq!(
@@ -671,6 +1213,13 @@ mod tests {
             let mut config = Config::default();
             let value = match *key {
                 "worktree_path_pattern" => "custom-pattern",
+                "max_parallel_stories" | "max_iterations_per_story" | "max_total_iterations" => "2",
+                "on_story_failure" => "skip",
+                "commit_strategy" => "per_story",
+                "sandbox" => "bwrap",
+                key if key.ends_with("_timeout_minutes") => "0",
+                "claude_retry_attempts" | "knowledge_decay_runs" => "0",
+                key if key.starts_with("max_run_") || key.ends_with("_tokens") => "0",
                 _ => "false", // Boolean keys
             };
             let result = set_config_value(&mut config, key, value);
@@ -678,6 +1227,93 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_us002_on_story_failure_values() {
+        let mut config = Config::default();
+        set_config_value(&mut config, "on_story_failure", "Pause").unwrap();
+        assert_eq!(config.on_story_failure, StoryFailurePolicy::Pause);
+
+        let err = set_config_value(&mut config, "on_story_failure", "retry").unwrap_err();
+        assert!(err.to_string().contains("expected fail, skip or pause"));
+        assert!(set_config_value(&mut config, "max_total_iterations", "0").is_err());
+    }
+
+    #[test]
+    fn test_us002_commit_strategy_values() {
+        let mut config = Config::default();
+        set_config_value(&mut config, "commit_strategy", "Per_Story").unwrap();
+        assert_eq!(config.commit_strategy, CommitStrategy::PerStory);
+        assert_eq!(
+            format_value_for_display("commit_strategy", &config),
+            "\"per_story\""
+        );
+
+        let err = set_config_value(&mut config, "commit_strategy", "always").unwrap_err();
+        assert!(err.to_string().contains("expected end or per_story"));
+    }
+
+    #[test]
+    fn test_us002_sandbox_values() {
+        let mut config = Config::default();
+        assert_eq!(format_value_for_display("sandbox", &config), "\"none\"");
+        set_config_value(&mut config, "sandbox", "BWRAP").unwrap();
+        assert_eq!(config.sandbox, SandboxKind::Bwrap);
+
+        let err = set_config_value(&mut config, "sandbox", "docker").unwrap_err();
+        assert!(err.to_string().contains("expected none or bwrap"));
+    }
+
+    #[test]
+    fn test_us002_knowledge_decay_runs_values() {
+        let mut config = Config::default();
+        assert_eq!(
+            format_value_for_display("knowledge_decay_runs", &config),
+            "5"
+        );
+        set_config_value(&mut config, "knowledge_decay_runs", "0").unwrap();
+        assert_eq!(config.knowledge_decay_runs, 0);
+        assert!(set_config_value(&mut config, "knowledge_decay_runs", "-1").is_err());
+    }
+
+    #[test]
+    fn test_us002_timeout_values() {
+        let mut config = Config::default();
+        set_config_value(&mut config, "story_timeout_minutes", "0").unwrap();
+        assert_eq!(config.story_timeout_minutes, 0);
+        set_config_value(&mut config, "review_timeout_minutes", " 45 ").unwrap();
+        assert_eq!(config.review_timeout_minutes, 45);
+
+        let err = set_config_value(&mut config, "commit_timeout_minutes", "10m").unwrap_err();
+        assert!(err.to_string().contains("0 for no limit"));
+    }
+
+    #[test]
+    fn test_us002_claude_retry_attempts_values() {
+        let mut config = Config::default();
+        set_config_value(&mut config, "claude_retry_attempts", "0").unwrap();
+        assert_eq!(config.claude_retry_attempts, 0);
+        set_config_value(&mut config, "claude_retry_attempts", "5").unwrap();
+        assert_eq!(config.claude_retry_attempts, 5);
+        assert!(set_config_value(&mut config, "claude_retry_attempts", "-1").is_err());
+    }
+
+    #[test]
+    fn test_us002_budget_values() {
+        let mut config = Config::default();
+        set_config_value(&mut config, "max_run_tokens", "2_000_000").unwrap();
+        assert_eq!(config.max_run_tokens, 2_000_000);
+        set_config_value(&mut config, "max_run_cost_usd", "$12.50").unwrap();
+        assert_eq!(config.max_run_cost_usd, 12.5);
+        set_config_value(&mut config, "max_run_cost_usd", "0").unwrap();
+        assert_eq!(config.max_run_cost_usd, 0.0);
+
+        assert!(set_config_value(&mut config, "max_story_tokens", "lots").is_err());
+        set_config_value(&mut config, "knowledge_context_tokens", "4_000").unwrap();
+        assert_eq!(config.knowledge_context_tokens, 4000);
+        let err = set_config_value(&mut config, "max_run_cost_usd", "-5").unwrap_err();
+        assert!(err.to_string().contains("amount in USD"));
+    }
+
     #[test]
     fn test_us002_validation_enforced_pr_without_commit() {
         // Test that setting pull_request=true when commit=false would fail validation
@@ -864,10 +1500,12 @@ mod tests {
     #[test]
     fn test_us003_modified_config_differs_from_default() {
         // Verify we can detect when a config differs from default
-        let mut modified = Config::default();
-        modified.review = false;
-        modified.commit = false;
-        modified.worktree_path_pattern = "custom-{branch}".to_string();
+        let modified = Config {
+            review: false,
+            commit: false,
+            worktree_path_pattern: "custom-{branch}".to_string(),
+            ..Default::default()
+        };
 
         let default = Config::default();
 
@@ -3,6 +3,8 @@
 //! Handles the default behavior when autom8 is run with no arguments.
 //! Checks for existing state and either resumes work or starts spec creation.
 
+use crate::claude::interactive_claude;
+use crate::config::get_effective_config;
 use crate::error::{Autom8Error, Result};
 use crate::output::{print_error, print_header, BOLD, CYAN, GRAY, GREEN, RESET, YELLOW};
 use crate::prompt;
@@ -150,8 +152,6 @@ fn handle_existing_state(state: RunState, verbose: bool) -> Result<()> {
 /// * `Ok(())` on success
 /// * `Err(Autom8Error)` if spec creation or implementation fails
 fn start_spec_creation(verbose: bool) -> Result<()> {
-    use std::process::Command;
-
     print_header();
 
     // Print explanation of what will happen
@@ -175,12 +175,16 @@ fn start_spec_creation(verbose: bool) -> Result<()> {
     let snapshot = SpecSnapshot::capture()?;
 
     // Spawn interactive Claude session with the spec skill prompt
-    let status = Command::new("claude")
-        .arg(prompts::SPEC_SKILL_PROMPT)
-        .stdin(std::process::Stdio::inherit())
-        .stdout(std::process::Stdio::inherit())
-        .stderr(std::process::Stdio::inherit())
-        .status();
+    let spec_dir = crate::config::spec_dir()?;
+    let status = interactive_claude(
+        &get_effective_config()?,
+        prompts::SPEC_SKILL_PROMPT,
+        Some(&spec_dir),
+    )?
+    .stdin(std::process::Stdio::inherit())
+    .stdout(std::process::Stdio::inherit())
+    .stderr(std::process::Stdio::inherit())
+    .status();
 
     match status {
         Ok(exit_status) => {
@@ -12,7 +12,7 @@ use crate::knowledge::ProjectKnowledge;
 use crate::output::improve::print_context_summary;
 use crate::spec::Spec;
 use crate::state::StateManager;
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 
 // ============================================================================
 // US-008: Command Handler
@@ -57,8 +57,12 @@ pub fn improve_command(_verbose: bool) -> Result<()> {
     // Step 3: Build the context prompt
     let prompt = build_improve_prompt(&context);
 
-    // Step 4: Spawn interactive Claude session
-    run_improve_session(&prompt)?;
+    // Step 4: Spawn interactive Claude session, free to update the spec
+    let spec_dir = match context.spec_path.as_deref().and_then(Path::parent) {
+        Some(dir) => dir.to_path_buf(),
+        None => crate::config::spec_dir()?,
+    };
+    run_improve_session(&prompt, Some(&spec_dir))?;
 
     Ok(())
 }
@@ -455,7 +459,7 @@ pub fn load_follow_up_context() -> Result<FollowUpContext> {
         // Layer 2: Try to load spec from session's spec_json_path first
         if let Some(ref path) = metadata.spec_json_path {
             if path.exists() {
-                if let Ok(loaded_spec) = Spec::load(path) {
+                if let Ok(loaded_spec) = Spec::read(path) {
                     spec = Some(loaded_spec);
                     spec_path = Some(path.clone());
                 }
@@ -500,7 +504,7 @@ mod tests {
     use super::*;
     use crate::git::{DiffEntry, DiffStatus};
     use crate::knowledge::{Decision, Pattern};
-    use crate::spec::UserStory;
+    use crate::spec::{StoryStatus, UserStory};
     use std::path::PathBuf;
 
     // ========================================================================
@@ -745,6 +749,7 @@ mod tests {
 
     fn make_spec() -> Spec {
         Spec {
+            schema_version: crate::spec::SPEC_SCHEMA_VERSION,
             project: "TestProject".to_string(),
             branch_name: "feature/test".to_string(),
             description: "Test description".to_string(),
@@ -754,9 +759,12 @@ mod tests {
                 description: "Test".to_string(),
                 acceptance_criteria: vec![],
                 priority: 1,
-                passes: false,
+                status: StoryStatus::default(),
                 notes: String::new(),
+                depends_on: Vec::new(),
+                verify: None,
             }],
+            verify: Vec::new(),
         }
     }
 
@@ -966,12 +974,14 @@ mod tests {
             }],
             files_deleted: vec![],
             commit_hash: None,
+            base_commit: None,
         });
         knowledge
     }
 
     fn make_complete_spec() -> Spec {
         Spec {
+            schema_version: crate::spec::SPEC_SCHEMA_VERSION,
             project: "TestProject".to_string(),
             branch_name: "feature/test".to_string(),
             description: "Test description".to_string(),
@@ -982,8 +992,10 @@ mod tests {
                     description: "Test".to_string(),
                     acceptance_criteria: vec![],
                     priority: 1,
-                    passes: true,
+                    status: StoryStatus::done(),
                     notes: String::new(),
+                    depends_on: Vec::new(),
+                    verify: None,
                 },
                 UserStory {
                     id: "US-002".to_string(),
@@ -991,10 +1003,13 @@ mod tests {
                     description: "Test 2".to_string(),
                     acceptance_criteria: vec![],
                     priority: 2,
-                    passes: true,
+                    status: StoryStatus::done(),
                     notes: String::new(),
+                    depends_on: Vec::new(),
+                    verify: None,
                 },
             ],
+            verify: Vec::new(),
         }
     }
 
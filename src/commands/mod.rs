@@ -8,6 +8,7 @@
 //! - [`run`] - Run implementation from a spec file
 //! - [`status`] - Check current run status
 //! - [`resume`] - Resume an interrupted run
+//! - [`rollback`] - Undo a story's changes
 //! - [`clean`] - Clean up spec files
 //! - [`config`] - View and modify configuration
 //! - [`init`] - Initialize project config
@@ -17,6 +18,8 @@
 //! - [`pr_review`] - Analyze and fix PR review comments
 //! - [`monitor`] - TUI dashboard
 //! - [`gui`] - Native GUI application
+//! - [`spec`] - Spec file utilities (lint, show, story editing)
+//! - [`prompt`] - Render prompts without running Claude (dry runs)
 //! - [`default`] - Interactive spec creation flow
 
 mod clean;
@@ -30,8 +33,11 @@ mod list;
 mod monitor;
 mod pr_review;
 mod projects;
+mod prompt;
 mod resume;
+mod rollback;
 mod run;
+mod spec;
 mod status;
 
 pub use clean::{
@@ -54,8 +60,14 @@ pub use list::list_command;
 pub use monitor::monitor_command;
 pub use pr_review::pr_review_command;
 pub use projects::projects_command;
+pub use prompt::{dry_run_command, prompt_show_command, PromptSubcommand};
 pub use resume::resume_command;
+pub use rollback::rollback_command;
 pub use run::{run_command, run_with_file};
+pub use spec::{
+    spec_add_story_command, spec_lint_command, spec_remove_story_command, spec_reset_command,
+    spec_set_priority_command, spec_show_command, AddStoryArgs, SpecSubcommand,
+};
 pub use status::{all_sessions_status_command, global_status_command, status_command};
 
 use std::path::Path;
@@ -3,7 +3,8 @@
 //! Analyzes unresolved PR review comments and uses Claude to fix
 //! legitimate issues while ignoring stylistic preferences.
 
-use crate::claude::{run_pr_review, PRReviewResult};
+use crate::claude::{run_pr_review, AgentSessions, PRReviewResult};
+use crate::config::get_effective_config;
 use crate::error::{Autom8Error, Result};
 use crate::gh::{
     detect_pr_for_current_branch, gather_branch_context, gather_pr_context, list_open_prs,
@@ -139,7 +140,9 @@ pub fn pr_review_command(verbose: bool) -> Result<()> {
     print_pr_review_spawning();
     print_pr_review_streaming();
 
-    let review_result = run_pr_review(&pr_context, &branch_context, |text| {
+    let config = get_effective_config()?;
+    let agent = AgentSessions::from_config(&config)?;
+    let review_result = run_pr_review(&agent.runner(), &pr_context, &branch_context, |text| {
         if verbose {
             print!("{}", text);
         }
@@ -148,7 +151,6 @@ pub fn pr_review_command(verbose: bool) -> Result<()> {
     print_pr_review_streaming_done();
 
     // Step 5: Handle results and commit/push if configured
-    let config = crate::config::get_effective_config().unwrap_or_default();
 
     match review_result {
         PRReviewResult::Complete(summary) => {
@@ -0,0 +1,230 @@
+//! Prompt command handlers.
+//!
+//! `autom8 run --dry-run` and `autom8 prompt show` render the prompts autom8
+//! sends to Claude without running it, to check what a spec and the
+//! project's knowledge turn into before paying for a run.
+
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use clap::Subcommand;
+
+use crate::claude::{build_story_knowledge_context, build_story_prompt};
+use crate::config::{get_effective_config, token_limit};
+use crate::dry_run::{run_prompts, RenderedPrompt};
+use crate::error::{Autom8Error, Result};
+use crate::gh::detect_pr_template;
+use crate::knowledge::ProjectKnowledge;
+use crate::output::{print_info, BOLD, CYAN, GRAY, GREEN, RESET};
+use crate::progress::format_tokens;
+use crate::spec::Spec;
+use crate::spec_parser::parse_markdown_spec;
+use crate::state::StateManager;
+
+use super::spec::resolve_spec_path;
+use super::{detect_input_type, ensure_project_dir, InputType};
+
+/// Subcommands for the prompt command.
+#[derive(Subcommand, Debug, Clone)]
+pub enum PromptSubcommand {
+    /// Print the prompt Claude would get for a story
+    #[command(after_help = "EXAMPLES:
+    autom8 prompt show --story US-003                      # Story of the current spec
+    autom8 prompt show --story US-003 --spec spec-auth.json
+
+The prompt includes what the current run has learned so far (files,
+decisions, patterns and earlier stories' work), as the next iteration would.")]
+    Show {
+        /// ID of the story, e.g. US-003
+        #[arg(long)]
+        story: String,
+
+        /// Spec to use: a path or a file name in the project's spec/ directory
+        /// (defaults to the active run's spec, or the only spec)
+        #[arg(long)]
+        spec: Option<PathBuf>,
+    },
+}
+
+/// Render every prompt a run of `spec` would send, without running it.
+///
+/// Prints the prompts, or with `prompts_dir` writes one numbered file per
+/// prompt there, followed by their estimated sizes.
+pub fn dry_run_command(spec: &Path, skip_review: bool, prompts_dir: Option<&Path>) -> Result<()> {
+    ensure_project_dir()?;
+
+    let mut config = get_effective_config()?;
+    if skip_review {
+        config.review = false;
+    }
+
+    let state_manager = StateManager::new()?;
+    let (spec, spec_path) = load_spec_for_dry_run(&state_manager, spec)?;
+
+    let repo_root = std::env::current_dir()?;
+    let pr_template = detect_pr_template(&repo_root);
+    let prompts = run_prompts(
+        &spec,
+        &spec_path,
+        &config,
+        &ProjectKnowledge::default(),
+        pr_template.as_deref(),
+    );
+
+    match prompts_dir {
+        Some(dir) => {
+            fs::create_dir_all(dir)?;
+            for prompt in &prompts {
+                fs::write(dir.join(&prompt.file_name), &prompt.text)?;
+            }
+            println!(
+                "{GREEN}Wrote {} prompt{} to {}{RESET}",
+                prompts.len(),
+                if prompts.len() == 1 { "" } else { "s" },
+                dir.display()
+            );
+        }
+        None => {
+            for prompt in &prompts {
+                print_prompt(prompt);
+            }
+        }
+    }
+
+    println!();
+    print_prompt_sizes(&prompts, prompts_dir.is_some());
+    if config.commit && config.pull_request && pr_template.is_none() {
+        print_info("No PR template found: the PR description would be written without Claude");
+    }
+    print_info("Dry run: Claude was not started and no git changes were made");
+    Ok(())
+}
+
+/// Print the prompt for one story, with the current run's knowledge and
+/// earlier work injected.
+pub fn prompt_show_command(story_id: &str, spec: Option<&Path>) -> Result<()> {
+    let state_manager = StateManager::new()?;
+    let path = resolve_spec_path(&state_manager, spec)?;
+    let spec = Spec::read(&path)?;
+    let story = spec
+        .user_stories
+        .iter()
+        .find(|s| s.id == story_id)
+        .ok_or_else(|| Autom8Error::StoryNotFound {
+            story_id: story_id.to_string(),
+            path: path.clone(),
+        })?;
+
+    // Knowledge only applies to the spec the current run is working on
+    let state = state_manager
+        .load_current()?
+        .filter(|state| same_file(&state.spec_json_path, &path));
+    let (iterations, knowledge, config) = match &state {
+        Some(state) => (
+            state.iterations.as_slice(),
+            state.knowledge.clone(),
+            state.effective_config(),
+        ),
+        None => (
+            &[][..],
+            ProjectKnowledge::default(),
+            get_effective_config()?,
+        ),
+    };
+    let budget = token_limit(config.knowledge_context_tokens);
+
+    let prompt = RenderedPrompt {
+        label: format!("Story {}: {}", story.id, story.title),
+        file_name: format!("story-{}.md", story.id),
+        text: build_story_prompt(&spec, story, &path, iterations, &knowledge, budget),
+    };
+    print_prompt(&prompt);
+    if state.is_none() {
+        print_info("No run of this spec is active, so no project knowledge was injected");
+    } else if let Some(context) = build_story_knowledge_context(&knowledge, Some(story), budget)
+        .filter(|context| context.is_trimmed())
+    {
+        print_info(&format!(
+            "Project knowledge trimmed to fit knowledge_context_tokens: {}",
+            context.trim_summary()
+        ));
+    }
+    Ok(())
+}
+
+/// Load the spec a dry run renders, with the path a real run would pass to
+/// Claude.
+///
+/// Markdown specs are parsed natively; a real run would ask Claude to
+/// convert one that doesn't parse, which a dry run can't do.
+fn load_spec_for_dry_run(state_manager: &StateManager, spec: &Path) -> Result<(Spec, PathBuf)> {
+    match detect_input_type(spec) {
+        InputType::Json => {
+            let spec_path = spec
+                .canonicalize()
+                .map_err(|_| Autom8Error::SpecNotFound(spec.to_path_buf()))?;
+            Ok((Spec::read(&spec_path)?, spec_path))
+        }
+        InputType::Markdown => {
+            let content = fs::read_to_string(spec)
+                .map_err(|_| Autom8Error::SpecNotFound(spec.to_path_buf()))?;
+            let parsed = parse_markdown_spec(&content).map_err(|diagnostics| {
+                Autom8Error::InvalidSpec(format!(
+                    "{} can't be parsed without Claude, so it can't be dry run:\n{}",
+                    spec.display(),
+                    diagnostics
+                        .iter()
+                        .map(|d| format!("  {}", d))
+                        .collect::<Vec<_>>()
+                        .join("\n")
+                ))
+            })?;
+            let stem = spec.file_stem().and_then(|s| s.to_str()).unwrap_or("spec");
+            let spec_path = state_manager.spec_dir().join(format!("{}.json", stem));
+            Ok((parsed, spec_path))
+        }
+    }
+}
+
+fn same_file(a: &Path, b: &Path) -> bool {
+    match (a.canonicalize(), b.canonicalize()) {
+        (Ok(a), Ok(b)) => a == b,
+        _ => a == b,
+    }
+}
+
+fn print_prompt(prompt: &RenderedPrompt) {
+    println!(
+        "{BOLD}━━━ {} {GRAY}(~{} tokens){RESET}",
+        prompt.label,
+        format_tokens(prompt.estimated_tokens())
+    );
+    println!("{}", prompt.text.trim_end());
+    println!();
+}
+
+fn print_prompt_sizes(prompts: &[RenderedPrompt], with_files: bool) {
+    let name = |prompt: &RenderedPrompt| {
+        if with_files {
+            prompt.file_name.clone()
+        } else {
+            prompt.label.clone()
+        }
+    };
+    let width = prompts.iter().map(|p| name(p).len()).max().unwrap_or(0);
+
+    println!("{BOLD}Estimated prompt sizes{RESET}");
+    for prompt in prompts {
+        println!(
+            "  {CYAN}{:<width$}{RESET}  ~{} tokens",
+            name(prompt),
+            format_tokens(prompt.estimated_tokens())
+        );
+    }
+    let total: u64 = prompts.iter().map(RenderedPrompt::estimated_tokens).sum();
+    println!(
+        "  {BOLD}{:<width$}{RESET}  ~{} tokens",
+        "Total",
+        format_tokens(total)
+    );
+}
@@ -2,11 +2,19 @@
 //!
 //! Resumes a failed or interrupted autom8 run from its last checkpoint.
 //! Supports multi-session resume with --session and --list flags.
+//!
+//! If the spec was edited since the run started, the added, removed and
+//! changed stories are shown before resuming, and the user can accept the
+//! changes or abort.
 
 use crate::error::{Autom8Error, Result};
-use crate::output::{print_header, print_sessions_status, BOLD, CYAN, GRAY, GREEN, RESET, YELLOW};
+use crate::output::{
+    print_header, print_sessions_status, print_spec_diff, BOLD, CYAN, GRAY, GREEN, RESET, YELLOW,
+};
 use crate::prompt;
-use crate::state::{RunStatus, SessionStatus, StateManager};
+use crate::spec::Spec;
+use crate::spec_diff::{SpecDiff, SpecFingerprint};
+use crate::state::{RunState, RunStatus, SessionStatus, StateManager};
 use crate::worktree::is_in_worktree;
 use crate::Runner;
 
@@ -133,8 +141,7 @@ fn resume_specific_session(state_manager: &StateManager, session_id: &str) -> Re
     );
     println!();
 
-    let runner = Runner::new()?;
-    runner.resume()
+    resume_current_session()
 }
 
 /// Auto-detect which session to resume.
@@ -157,8 +164,7 @@ fn resume_auto_detect(state_manager: &StateManager) -> Result<()> {
             "{YELLOW}[resume]{RESET} No active sessions found, scanning for incomplete specs..."
         );
         println!();
-        let runner = Runner::new()?;
-        return runner.resume();
+        return resume_current_session();
     }
 
     // If in a worktree, prefer the current session
@@ -170,8 +176,7 @@ fn resume_auto_detect(state_manager: &StateManager) -> Result<()> {
                 current.metadata.session_id
             );
             println!();
-            let runner = Runner::new()?;
-            return runner.resume();
+            return resume_current_session();
         }
     }
 
@@ -186,8 +191,7 @@ fn resume_auto_detect(state_manager: &StateManager) -> Result<()> {
                 current.metadata.session_id
             );
             println!();
-            let runner = Runner::new()?;
-            return runner.resume();
+            return resume_current_session();
         }
     }
 
@@ -273,11 +277,86 @@ fn resume_session_with_change(metadata: &crate::state::SessionMetadata) -> Resul
     );
     println!();
 
+    resume_current_session()
+}
+
+/// Resume the run of the session for the current directory, after
+/// reconciling any spec edits made since it started.
+fn resume_current_session() -> Result<()> {
+    if !reconcile_spec_changes(&StateManager::new()?)? {
+        println!("Resume aborted. The run was left as it was.");
+        return Ok(());
+    }
+
     // Create a new runner for the new directory/session
     let runner = Runner::new()?;
     runner.resume()
 }
 
+/// Compare the interrupted run's spec fingerprint with the spec on disk and,
+/// if they differ, ask whether to accept the changes.
+///
+/// Refuses while the run is still going, since the changes would be made
+/// underneath it. Returns `false` if the user chose to abort.
+fn reconcile_spec_changes(state_manager: &StateManager) -> Result<bool> {
+    let Some(mut state) = state_manager.load_current()? else {
+        return Ok(true);
+    };
+    if state.status == RunStatus::Running
+        && state_manager
+            .load_live()
+            .is_some_and(|live| live.is_heartbeat_fresh())
+    {
+        return Err(Autom8Error::RunInProgress(state.run_id));
+    }
+    if !matches!(
+        state.status,
+        RunStatus::Running | RunStatus::Failed | RunStatus::Interrupted
+    ) {
+        return Ok(true);
+    }
+    // Runs started before fingerprints were recorded have nothing to compare
+    let Some(fingerprint) = state.spec_fingerprint.clone() else {
+        return Ok(true);
+    };
+
+    let mut spec = Spec::load(&state.spec_json_path)?;
+    let diff = fingerprint.diff(&spec);
+    if diff.is_empty() {
+        return Ok(true);
+    }
+
+    print_spec_diff(&diff);
+    let choice = prompt::select(
+        "How would you like to continue?",
+        &["Accept the changes and resume", "Abort"],
+        0,
+    );
+    if choice != 0 {
+        return Ok(false);
+    }
+
+    apply_spec_changes(&mut state, &mut spec, &diff);
+    spec.save(&state.spec_json_path)?;
+    state_manager.save(&state)?;
+    Ok(true)
+}
+
+/// Accept spec changes into an interrupted run: drop what the run recorded
+/// for removed stories, and re-queue changed ones so they run again against
+/// their new content.
+///
+/// The resumed run carries on from this state, so changed stories also
+/// start over with a fresh iteration limit and no stale verify failures.
+fn apply_spec_changes(state: &mut RunState, spec: &mut Spec, diff: &SpecDiff) {
+    let changed = diff.changed_ids();
+    state.forget_stories(&diff.removed_ids(), &changed);
+    for id in &changed {
+        spec.reset_story(id);
+    }
+    state.spec_fingerprint = Some(SpecFingerprint::of(spec));
+}
+
 /// Check if a session is resumable (has an incomplete run).
 fn is_resumable_session(session: &SessionStatus) -> bool {
     // A session is resumable if it has a state with Running or Failed status
@@ -306,6 +385,7 @@ fn is_resumable_session(session: &SessionStatus) -> bool {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::spec::{StoryStatus, UserStory, SPEC_SCHEMA_VERSION};
     use crate::state::{MachineState, SessionMetadata, SessionStatus};
     use chrono::Utc;
     use std::path::PathBuf;
@@ -376,4 +456,85 @@ mod tests {
         let session = make_session("test", false, None, false);
         assert!(!is_resumable_session(&session));
     }
+
+    // ===========================================
+    // Spec change reconciliation
+    // ===========================================
+
+    fn make_story(id: &str, done: bool) -> UserStory {
+        UserStory {
+            id: id.to_string(),
+            title: format!("Story {}", id),
+            description: String::new(),
+            acceptance_criteria: vec!["Works".to_string()],
+            priority: 1,
+            status: if done {
+                StoryStatus::done()
+            } else {
+                StoryStatus::default()
+            },
+            notes: String::new(),
+            depends_on: Vec::new(),
+            verify: None,
+        }
+    }
+
+    #[test]
+    fn test_apply_spec_changes_requeues_changed_and_forgets_removed() {
+        let mut spec = Spec {
+            schema_version: SPEC_SCHEMA_VERSION,
+            project: "test".to_string(),
+            branch_name: "feature/test".to_string(),
+            description: "Test".to_string(),
+            user_stories: vec![make_story("US-001", true), make_story("US-002", true)],
+            verify: Vec::new(),
+        };
+        let mut state = RunState::new(PathBuf::from("spec.json"), "feature/test".to_string());
+        state.spec_fingerprint = Some(SpecFingerprint::of(&spec));
+        state.start_iteration("US-001");
+        state.start_iteration("US-002");
+
+        // The user drops US-002 and tightens US-001 while the run is stopped
+        spec.remove_story("US-002");
+        spec.user_stories[0]
+            .acceptance_criteria
+            .push("Handles errors".to_string());
+        let diff = state.spec_fingerprint.as_ref().unwrap().diff(&spec);
+
+        apply_spec_changes(&mut state, &mut spec, &diff);
+
+        assert!(!spec.user_stories[0].passes());
+        assert!(state.iterations.is_empty());
+        assert_eq!(state.story_iteration_count("US-001"), 0);
+        assert!(state
+            .spec_fingerprint
+            .as_ref()
+            .unwrap()
+            .diff(&spec)
+            .is_empty());
+
+        // Resuming continues this state, so the changes carry through
+        state.status = RunStatus::Interrupted;
+        state.resume(&crate::config::Config::default());
+        assert_eq!(state.status, RunStatus::Running);
+        assert!(state.iterations.is_empty());
+        assert_eq!(state.iteration, 2);
+    }
+
+    #[test]
+    fn test_reconcile_refuses_while_run_is_live() {
+        let dir = tempfile::TempDir::new().unwrap();
+        let state_manager = StateManager::with_dir(dir.path().to_path_buf());
+        let state = RunState::new(PathBuf::from("spec.json"), "feature/test".to_string());
+        let run_id = state.run_id.clone();
+        state_manager.save(&state).unwrap();
+        state_manager
+            .save_live(&crate::state::LiveState::new(MachineState::RunningClaude))
+            .unwrap();
+
+        match reconcile_spec_changes(&state_manager) {
+            Err(Autom8Error::RunInProgress(id)) => assert_eq!(id, run_id),
+            other => panic!("expected RunInProgress, got {:?}", other.map(|_| ())),
+        }
+    }
 }
@@ -0,0 +1,180 @@
+//! Rollback command handler.
+//!
+//! Undoes a story's changes: resets the working tree to the commit before
+//! the story (or, with `--to`, to right after another story), marks the
+//! undone stories as not started in the spec and drops what the run learned
+//! from them, so the session can be resumed.
+
+use crate::error::{Autom8Error, Result};
+use crate::git;
+use crate::knowledge::KnowledgeStore;
+use crate::output::{print_header, BOLD, CYAN, GRAY, GREEN, RESET, YELLOW};
+use crate::prompt;
+use crate::rollback::plan_rollback;
+use crate::spec::Spec;
+use crate::state::{MachineState, RunState, RunStatus, StateManager};
+
+use super::ensure_project_dir;
+
+/// Roll back `story_id` and the stories after it, or with `keep_story`,
+/// everything after `story_id`.
+///
+/// Asks for confirmation before resetting unless `yes` is set.
+pub fn rollback_command(story_id: &str, keep_story: bool, yes: bool) -> Result<()> {
+    ensure_project_dir()?;
+    print_header();
+
+    if !git::is_git_repo() {
+        return Err(Autom8Error::Rollback(
+            "this directory is not a git repository".to_string(),
+        ));
+    }
+
+    let state_manager = StateManager::new()?;
+    let current = state_manager.load_current()?;
+    if current
+        .as_ref()
+        .is_some_and(|state| state.status == RunStatus::Running)
+        && state_manager
+            .load_live()
+            .is_some_and(|live| live.is_heartbeat_fresh())
+    {
+        return Err(Autom8Error::Rollback(
+            "a run is in progress in this session; stop it (Ctrl+C) or pause it first".to_string(),
+        ));
+    }
+
+    let runs = session_runs(&state_manager, current.as_ref())?;
+    let Some(spec_path) = runs.last().map(|run| run.spec_json_path.clone()) else {
+        return Err(Autom8Error::Rollback(
+            "this session has no runs to roll back".to_string(),
+        ));
+    };
+    if let Some(run) = runs.last() {
+        check_run_branch(&run.branch, &git::current_branch()?)?;
+    }
+    let knowledge: Vec<_> = runs.iter().map(|run| &run.knowledge).collect();
+    let plan = plan_rollback(&knowledge, story_id, keep_story, git::is_ancestor)?;
+
+    println!(
+        "{BOLD}Rolling back to{RESET} {CYAN}{}{RESET}",
+        short_hash(&plan.target_commit)
+    );
+    if plan.story_ids.is_empty() {
+        println!("{GRAY}No stories are undone.{RESET}");
+    } else {
+        println!("{BOLD}Stories undone:{RESET} {}", plan.story_ids.join(", "));
+    }
+    println!(
+        "{YELLOW}Uncommitted changes and untracked files in this worktree will be lost.{RESET}"
+    );
+    println!();
+
+    if !yes && !prompt::confirm("Roll back?", false) {
+        println!("Rollback cancelled.");
+        return Ok(());
+    }
+
+    git::reset_hard(&plan.target_commit)?;
+
+    let mut spec = Spec::load(&spec_path)?;
+    for id in &plan.story_ids {
+        spec.reset_story(id);
+    }
+    spec.save(&spec_path)?;
+
+    forget_in_knowledge_store(&state_manager, &runs, &plan.story_ids)?;
+
+    // Without a current run, the session's last run is brought back so
+    // `autom8 resume` picks up the undone stories with what it learned.
+    let restored = current.is_none();
+    if let Some(mut state) = current.or_else(|| runs.last().cloned()) {
+        state.forget_stories(&[], &plan.story_ids);
+        state.pre_story_commit = None;
+        state.current_story = None;
+        state.machine_state = MachineState::PickingStory;
+        if restored || state.status == RunStatus::Running {
+            state.status = RunStatus::Interrupted;
+            state.finished_at = None;
+        }
+        state_manager.save(&state)?;
+    }
+
+    println!(
+        "{GREEN}Rolled back to {}.{RESET} Continue with {CYAN}autom8 resume{RESET}",
+        short_hash(&plan.target_commit)
+    );
+    Ok(())
+}
+
+/// This session's runs of the spec it worked on last, oldest first: the
+/// archived runs, then the current one.
+fn session_runs(state_manager: &StateManager, current: Option<&RunState>) -> Result<Vec<RunState>> {
+    let session_id = state_manager.session_id();
+    let mut runs: Vec<RunState> = state_manager
+        .list_archived()?
+        .into_iter()
+        .filter(|run| run.session_id.as_deref() == Some(session_id))
+        .filter(|run| current.is_none_or(|current| current.run_id != run.run_id))
+        .collect();
+    runs.reverse();
+    runs.extend(current.cloned());
+
+    if let Some(spec_path) = runs.last().map(|run| run.spec_json_path.clone()) {
+        runs.retain(|run| run.spec_json_path == spec_path);
+    }
+    Ok(runs)
+}
+
+/// Drop what the undone stories taught the project's knowledge store, for
+/// runs that completed and were merged into it.
+fn forget_in_knowledge_store(
+    state_manager: &StateManager,
+    runs: &[RunState],
+    story_ids: &[String],
+) -> Result<()> {
+    let merged: Vec<&RunState> = runs
+        .iter()
+        .filter(|run| run.status == RunStatus::Completed)
+        .collect();
+    if story_ids.is_empty() || merged.is_empty() {
+        return Ok(());
+    }
+
+    let dir = state_manager.knowledge_dir();
+    let mut store = KnowledgeStore::load(&dir)?;
+    for run in merged {
+        store.forget_stories(&run.knowledge, story_ids);
+    }
+    store.save(&dir)
+}
+
+/// Refuse to reset a branch other than the one the run committed to, which
+/// would throw away unrelated work.
+fn check_run_branch(run_branch: &str, current_branch: &str) -> Result<()> {
+    if run_branch == current_branch {
+        return Ok(());
+    }
+    Err(Autom8Error::Rollback(format!(
+        "the run worked on branch '{}' but '{}' is checked out; run `git checkout {}` first",
+        run_branch, current_branch, run_branch
+    )))
+}
+
+fn short_hash(commit: &str) -> &str {
+    &commit[..commit.len().min(7)]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_rollback_refuses_another_branch() {
+        assert!(check_run_branch("feature/login", "feature/login").is_ok());
+
+        let err = check_run_branch("feature/login", "main").unwrap_err();
+        assert!(matches!(err, Autom8Error::Rollback(_)));
+        assert!(err.to_string().contains("git checkout feature/login"));
+    }
+}
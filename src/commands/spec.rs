@@ -0,0 +1,677 @@
+//! Spec command handlers.
+//!
+//! Utilities for working with spec files outside of a run: linting, and
+//! editing the stories of a project's specs without hand-editing JSON.
+
+use std::path::{Path, PathBuf};
+
+use clap::{Args, Subcommand};
+
+use crate::error::{Autom8Error, Result};
+use crate::output::{print_info, BLUE, BOLD, CYAN, GRAY, GREEN, RED, RESET, YELLOW};
+use crate::spec::{Spec, StoryState, StoryStatus, UserStory};
+use crate::spec_lint::{lint_file, LintContext, LintReport, LintSeverity};
+use crate::state::{MachineState, RunState, RunStatus, StateManager};
+
+/// Subcommands for the spec command.
+#[derive(Subcommand, Debug, Clone)]
+pub enum SpecSubcommand {
+    /// Check spec files for structural and quality problems
+    #[command(after_help = "EXAMPLES:
+    autom8 spec lint spec-auth.json              # Lint one spec
+    autom8 spec lint specs/*.json specs/*.md     # Lint several specs
+    autom8 spec lint --json spec-auth.json       # Machine-readable output
+
+CHECKS:
+    Errors:   invalid spec, duplicate story IDs, stories without acceptance
+              criteria, invalid git branch names, branchName equal to the
+              base branch
+    Warnings: duplicate priorities, vague acceptance criteria, project name
+              not matching the current project, outdated schema version
+
+EXIT CODE:
+    0 if no errors were found (warnings are allowed), 1 otherwise.
+    Spec files are never modified.")]
+    Lint {
+        /// Spec files to lint (.json or .md)
+        #[arg(required = true)]
+        files: Vec<PathBuf>,
+
+        /// Print the results as JSON
+        #[arg(long)]
+        json: bool,
+    },
+
+    /// Show a spec's stories with their status
+    #[command(after_help = "EXAMPLES:
+    autom8 spec show                          # The active run's spec
+    autom8 spec show --spec spec-auth.json    # A spec in the project's spec/ directory
+
+Without --spec, the spec of this session's run is used, or the project's
+only spec if there is no run.")]
+    Show {
+        /// Spec file, or the name of a spec in the project's spec/ directory
+        #[arg(long)]
+        spec: Option<PathBuf>,
+    },
+
+    /// Add a story to a spec
+    #[command(after_help = "EXAMPLES:
+    autom8 spec add-story --title \"Add logout button\" \\
+        -c \"Button is shown when signed in\" -c \"Clicking it ends the session\"
+    autom8 spec add-story --title \"Audit log\" --depends-on US-002 --priority 3")]
+    AddStory(AddStoryArgs),
+
+    /// Remove a story from a spec
+    #[command(after_help = "EXAMPLES:
+    autom8 spec remove-story US-004
+
+Other stories stop depending on the removed story. Sessions running this
+spec forget it; a story that a running session is implementing is only
+removed with --force.")]
+    RemoveStory {
+        /// ID of the story to remove
+        id: String,
+
+        /// Remove the story even if a running session is implementing it
+        #[arg(long)]
+        force: bool,
+
+        /// Spec file, or the name of a spec in the project's spec/ directory
+        #[arg(long)]
+        spec: Option<PathBuf>,
+    },
+
+    /// Change a story's priority (lower runs first)
+    SetPriority {
+        /// ID of the story
+        id: String,
+
+        /// New priority
+        priority: u32,
+
+        /// Spec file, or the name of a spec in the project's spec/ directory
+        #[arg(long)]
+        spec: Option<PathBuf>,
+    },
+
+    /// Mark a story as not done so the next run implements it again
+    #[command(after_help = "EXAMPLES:
+    autom8 spec reset US-003
+    autom8 resume                             # Pick the story up again
+
+Failed stories are skipped until they are reset. Stories that were blocked
+on it are reset too, and sessions running this spec stop skipping it and
+give it a fresh iteration limit.")]
+    Reset {
+        /// ID of the story to reset
+        id: String,
+
+        /// Spec file, or the name of a spec in the project's spec/ directory
+        #[arg(long)]
+        spec: Option<PathBuf>,
+    },
+}
+
+/// Arguments for `autom8 spec add-story`.
+#[derive(Args, Debug, Clone)]
+pub struct AddStoryArgs {
+    /// Story title
+    #[arg(long)]
+    pub title: String,
+
+    /// What the story accomplishes
+    #[arg(long, default_value = "")]
+    pub description: String,
+
+    /// Acceptance criterion (repeat for several)
+    #[arg(short = 'c', long = "criterion")]
+    pub criteria: Vec<String>,
+
+    /// Priority (defaults to after the last story)
+    #[arg(long)]
+    pub priority: Option<u32>,
+
+    /// Story ID (defaults to the next free ID, e.g. US-005)
+    #[arg(long)]
+    pub id: Option<String>,
+
+    /// ID of a story that must pass first (repeat for several)
+    #[arg(long = "depends-on")]
+    pub depends_on: Vec<String>,
+
+    /// Spec file, or the name of a spec in the project's spec/ directory
+    #[arg(long)]
+    pub spec: Option<PathBuf>,
+}
+
+/// Lint spec files and print the results.
+///
+/// # Returns
+///
+/// * `Ok(true)` if no file has errors
+/// * `Ok(false)` if at least one file has errors
+pub fn spec_lint_command(files: &[PathBuf], json: bool) -> Result<bool> {
+    let ctx = LintContext::detect();
+    let reports: Vec<LintReport> = files.iter().map(|f| lint_file(f, &ctx)).collect();
+
+    if json {
+        println!("{}", serde_json::to_string_pretty(&reports)?);
+    } else {
+        for report in &reports {
+            print_report(report);
+        }
+        print_summary(&reports);
+    }
+
+    Ok(reports.iter().all(|r| r.errors == 0))
+}
+
+fn print_report(report: &LintReport) {
+    if report.issues.is_empty() {
+        println!("{GREEN}✓{RESET} {}", report.path);
+        return;
+    }
+
+    println!("{BOLD}{}{RESET}", report.path);
+    for issue in &report.issues {
+        let label = match issue.severity {
+            LintSeverity::Error => format!("{RED}error{RESET}"),
+            LintSeverity::Warning => format!("{YELLOW}warning{RESET}"),
+        };
+        let story = issue
+            .story_id
+            .as_deref()
+            .map(|id| format!("{CYAN}{}{RESET}: ", id))
+            .unwrap_or_default();
+        println!(
+            "  {} {}{} {GRAY}[{}]{RESET}",
+            label, story, issue.message, issue.rule
+        );
+    }
+}
+
+fn print_summary(reports: &[LintReport]) {
+    let errors: usize = reports.iter().map(|r| r.errors).sum();
+    let warnings: usize = reports.iter().map(|r| r.warnings).sum();
+    println!();
+    println!(
+        "{GRAY}{} file{}: {} error{}, {} warning{}{RESET}",
+        reports.len(),
+        if reports.len() == 1 { "" } else { "s" },
+        errors,
+        if errors == 1 { "" } else { "s" },
+        warnings,
+        if warnings == 1 { "" } else { "s" }
+    );
+}
+
+/// Show a spec's stories with their status.
+pub fn spec_show_command(spec: Option<&Path>) -> Result<()> {
+    let state_manager = StateManager::new()?;
+    let path = resolve_spec_path(&state_manager, spec)?;
+    let spec = Spec::read(&path)?;
+    print_spec(&spec, &path);
+    Ok(())
+}
+
+/// Add a story to a spec.
+pub fn spec_add_story_command(args: &AddStoryArgs) -> Result<()> {
+    let state_manager = StateManager::new()?;
+    let path = resolve_spec_path(&state_manager, args.spec.as_deref())?;
+    let story = add_story(&path, args)?;
+    println!(
+        "{GREEN}Added {CYAN}{}{RESET}{GREEN}: {} (priority {}){RESET}",
+        story.id, story.title, story.priority
+    );
+    Ok(())
+}
+
+/// Remove a story from a spec.
+pub fn spec_remove_story_command(id: &str, force: bool, spec: Option<&Path>) -> Result<()> {
+    let state_manager = StateManager::new()?;
+    let path = resolve_spec_path(&state_manager, spec)?;
+    let dependents = remove_story(&state_manager, &path, id, force)?;
+    println!("{GREEN}Removed {CYAN}{}{RESET}", id);
+    if !dependents.is_empty() {
+        print_info(&format!(
+            "{} no longer depend{} on {}",
+            dependents.join(", "),
+            if dependents.len() == 1 { "s" } else { "" },
+            id
+        ));
+    }
+    Ok(())
+}
+
+/// Change a story's priority.
+pub fn spec_set_priority_command(id: &str, priority: u32, spec: Option<&Path>) -> Result<()> {
+    let state_manager = StateManager::new()?;
+    let path = resolve_spec_path(&state_manager, spec)?;
+    set_priority(&path, id, priority)?;
+    println!(
+        "{GREEN}Set {CYAN}{}{RESET}{GREEN} priority to {}{RESET}",
+        id, priority
+    );
+    Ok(())
+}
+
+/// Mark a story as not done so the next run implements it again.
+pub fn spec_reset_command(id: &str, spec: Option<&Path>) -> Result<()> {
+    let state_manager = StateManager::new()?;
+    let path = resolve_spec_path(&state_manager, spec)?;
+    reset_story(&state_manager, &path, id)?;
+    println!(
+        "{GREEN}Reset {CYAN}{}{RESET}{GREEN}; it will run again{RESET}",
+        id
+    );
+    Ok(())
+}
+
+/// Find the spec a `spec` subcommand works on.
+///
+/// An explicit `--spec` is used as a path if it exists, otherwise as a file
+/// name in the project's spec/ directory. Without it, the spec of this
+/// session's run wins, then the project's only spec.
+pub(super) fn resolve_spec_path(
+    state_manager: &StateManager,
+    spec: Option<&Path>,
+) -> Result<PathBuf> {
+    if let Some(spec) = spec {
+        if spec.exists() {
+            return Ok(spec.to_path_buf());
+        }
+        let in_spec_dir = state_manager.spec_dir().join(spec);
+        if in_spec_dir.exists() {
+            return Ok(in_spec_dir);
+        }
+        return Err(Autom8Error::SpecNotFound(spec.to_path_buf()));
+    }
+
+    if let Some(state) = state_manager.load_current()? {
+        if state.spec_json_path.exists() {
+            return Ok(state.spec_json_path);
+        }
+    }
+
+    let mut specs = state_manager.list_specs()?;
+    match specs.len() {
+        0 => Err(Autom8Error::SpecNotFound(state_manager.spec_dir())),
+        1 => Ok(specs.remove(0)),
+        _ => Err(Autom8Error::AmbiguousSpec(
+            specs
+                .iter()
+                .filter_map(|p| p.file_name())
+                .map(|n| n.to_string_lossy().into_owned())
+                .collect::<Vec<_>>()
+                .join(", "),
+        )),
+    }
+}
+
+fn add_story(path: &Path, args: &AddStoryArgs) -> Result<UserStory> {
+    let mut spec = Spec::load(path)?;
+    let story = UserStory {
+        id: args.id.clone().unwrap_or_else(|| spec.next_story_id()),
+        title: args.title.clone(),
+        description: args.description.clone(),
+        acceptance_criteria: args.criteria.clone(),
+        priority: args.priority.unwrap_or_else(|| {
+            spec.user_stories
+                .iter()
+                .map(|s| s.priority)
+                .max()
+                .map_or(1, |p| p + 1)
+        }),
+        status: StoryStatus::default(),
+        notes: String::new(),
+        depends_on: args.depends_on.clone(),
+        verify: None,
+    };
+    spec.add_story(story.clone())?;
+    spec.save(path)?;
+    Ok(story)
+}
+
+/// Remove a story and update sessions running the spec.
+///
+/// Returns the IDs of stories that depended on the removed one.
+fn remove_story(
+    state_manager: &StateManager,
+    path: &Path,
+    id: &str,
+    force: bool,
+) -> Result<Vec<String>> {
+    let mut spec = Spec::load(path)?;
+    if spec.story(id).is_none() {
+        return Err(story_not_found(id, path));
+    }
+
+    if !force {
+        let in_progress = sessions_for_spec(state_manager, path)?
+            .iter()
+            .any(|(_, state)| {
+                state.status == RunStatus::Running && state.current_story.as_deref() == Some(id)
+            });
+        if in_progress {
+            return Err(Autom8Error::StoryInProgress(id.to_string()));
+        }
+    }
+
+    let (_, dependents) = spec.remove_story(id).expect("story exists");
+    spec.validate()?;
+    spec.save(path)?;
+
+    update_sessions(state_manager, path, |state| {
+        if state.current_story.as_deref() == Some(id) {
+            state.current_story = None;
+            if state.machine_state == MachineState::RunningClaude {
+                state.machine_state = MachineState::PickingStory;
+            }
+        }
+        state.failed_stories.retain(|f| f.story_id != id);
+        state.sequential_stories.retain(|s| s != id);
+    })?;
+    Ok(dependents)
+}
+
+fn set_priority(path: &Path, id: &str, priority: u32) -> Result<()> {
+    let mut spec = Spec::load(path)?;
+    let story = spec
+        .user_stories
+        .iter_mut()
+        .find(|s| s.id == id)
+        .ok_or_else(|| story_not_found(id, path))?;
+    story.priority = priority;
+    spec.save(path)
+}
+
+/// Reset a story and stop sessions running the spec from skipping it.
+fn reset_story(state_manager: &StateManager, path: &Path, id: &str) -> Result<()> {
+    let mut spec = Spec::load(path)?;
+    if !spec.reset_story(id) {
+        return Err(story_not_found(id, path));
+    }
+    spec.save(path)?;
+
+    update_sessions(state_manager, path, |state| {
+        state.forget_stories(&[], &[id.to_string()]);
+    })
+}
+
+fn story_not_found(id: &str, path: &Path) -> Autom8Error {
+    Autom8Error::StoryNotFound {
+        story_id: id.to_string(),
+        path: path.to_path_buf(),
+    }
+}
+
+/// Load the run state of every session whose run uses the spec at `path`.
+fn sessions_for_spec(
+    state_manager: &StateManager,
+    path: &Path,
+) -> Result<Vec<(StateManager, RunState)>> {
+    let target = path.canonicalize().unwrap_or_else(|_| path.to_path_buf());
+    let mut sessions = Vec::new();
+    for metadata in state_manager.list_sessions()? {
+        let Some(session) = state_manager.get_session(&metadata.session_id) else {
+            continue;
+        };
+        let Some(state) = session.load_current()? else {
+            continue;
+        };
+        let spec_path = state
+            .spec_json_path
+            .canonicalize()
+            .unwrap_or_else(|_| state.spec_json_path.clone());
+        if spec_path == target {
+            sessions.push((session, state));
+        }
+    }
+    Ok(sessions)
+}
+
+/// Apply `update` to the run state of every session using the spec at `path`.
+fn update_sessions(
+    state_manager: &StateManager,
+    path: &Path,
+    mut update: impl FnMut(&mut RunState),
+) -> Result<()> {
+    for (session, mut state) in sessions_for_spec(state_manager, path)? {
+        update(&mut state);
+        session.save(&state)?;
+    }
+    Ok(())
+}
+
+fn print_spec(spec: &Spec, path: &Path) {
+    println!(
+        "{BOLD}{}{RESET} {GRAY}({}){RESET}",
+        spec.project,
+        path.display()
+    );
+    println!("{BLUE}Branch:{RESET} {}", spec.branch_name);
+    println!(
+        "{BLUE}Progress:{RESET} {}/{} stories complete",
+        spec.completed_count(),
+        spec.total_count()
+    );
+    println!();
+
+    let mut stories: Vec<&UserStory> = spec.user_stories.iter().collect();
+    stories.sort_by_key(|s| s.priority);
+    for story in stories {
+        let color = match story.status.state {
+            StoryState::Done => GREEN,
+            StoryState::InProgress => CYAN,
+            StoryState::Blocked => YELLOW,
+            StoryState::Failed => RED,
+            StoryState::NotStarted => GRAY,
+        };
+        let deps = if story.depends_on.is_empty() {
+            String::new()
+        } else {
+            format!(" {GRAY}(after {}){RESET}", story.depends_on.join(", "))
+        };
+        println!(
+            "  {color}{}{RESET} {BOLD}{}{RESET} {GRAY}[{}]{RESET} {}{}",
+            story.status.state.icon(),
+            story.id,
+            story.priority,
+            story.title,
+            deps
+        );
+        if story.status.state != StoryState::NotStarted {
+            println!("      {color}{}{RESET}", story.status.describe());
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    fn make_story(id: &str, priority: u32, depends_on: &[&str]) -> UserStory {
+        UserStory {
+            id: id.to_string(),
+            title: format!("Story {}", id),
+            description: String::new(),
+            acceptance_criteria: vec!["Works".to_string()],
+            priority,
+            status: StoryStatus::default(),
+            notes: String::new(),
+            depends_on: depends_on.iter().map(|d| d.to_string()).collect(),
+            verify: None,
+        }
+    }
+
+    /// A state manager in a temp dir with `spec-<name>.json` specs saved in spec/.
+    fn setup(names: &[&str]) -> (TempDir, StateManager, Vec<PathBuf>) {
+        let temp_dir = TempDir::new().unwrap();
+        let sm = StateManager::with_dir(temp_dir.path().to_path_buf());
+        let spec_dir = sm.ensure_spec_dir().unwrap();
+        let paths = names
+            .iter()
+            .map(|name| {
+                let spec = Spec {
+                    schema_version: crate::spec::SPEC_SCHEMA_VERSION,
+                    project: "test".to_string(),
+                    branch_name: "feature/test".to_string(),
+                    description: String::new(),
+                    user_stories: vec![
+                        make_story("US-001", 1, &[]),
+                        make_story("US-002", 2, &["US-001"]),
+                        make_story("US-003", 3, &["US-002"]),
+                    ],
+                    verify: Vec::new(),
+                };
+                let path = spec_dir.join(format!("spec-{}.json", name));
+                spec.save(&path).unwrap();
+                path
+            })
+            .collect();
+        (temp_dir, sm, paths)
+    }
+
+    fn add_args(title: &str) -> AddStoryArgs {
+        AddStoryArgs {
+            title: title.to_string(),
+            description: String::new(),
+            criteria: Vec::new(),
+            priority: None,
+            id: None,
+            depends_on: Vec::new(),
+            spec: None,
+        }
+    }
+
+    // ========================================================================
+    // Spec resolution
+    // ========================================================================
+
+    #[test]
+    fn test_resolve_spec_path_uses_only_spec_or_asks() {
+        let (_dir, sm, paths) = setup(&["auth"]);
+        assert_eq!(resolve_spec_path(&sm, None).unwrap(), paths[0]);
+
+        let (_dir, sm, paths) = setup(&["auth", "billing"]);
+        let err = resolve_spec_path(&sm, None).unwrap_err();
+        assert!(matches!(err, Autom8Error::AmbiguousSpec(ref names)
+            if names.contains("spec-auth.json") && names.contains("spec-billing.json")));
+
+        // A bare file name resolves in the spec directory
+        let name = Path::new("spec-billing.json");
+        assert_eq!(resolve_spec_path(&sm, Some(name)).unwrap(), paths[1]);
+        assert!(matches!(
+            resolve_spec_path(&sm, Some(Path::new("spec-nope.json"))),
+            Err(Autom8Error::SpecNotFound(_))
+        ));
+    }
+
+    #[test]
+    fn test_resolve_spec_path_prefers_active_run() {
+        let (_dir, sm, paths) = setup(&["auth", "billing"]);
+        sm.save(&RunState::new(paths[1].clone(), "feature/test".to_string()))
+            .unwrap();
+
+        assert_eq!(resolve_spec_path(&sm, None).unwrap(), paths[1]);
+    }
+
+    // ========================================================================
+    // Story editing
+    // ========================================================================
+
+    #[test]
+    fn test_add_story_defaults_id_and_priority() {
+        let (_dir, _sm, paths) = setup(&["auth"]);
+
+        let story = add_story(&paths[0], &add_args("Logout")).unwrap();
+        assert_eq!(story.id, "US-004");
+        assert_eq!(story.priority, 4);
+
+        let mut args = add_args("Audit log");
+        args.depends_on = vec!["US-009".to_string()];
+        assert!(matches!(
+            add_story(&paths[0], &args),
+            Err(Autom8Error::InvalidSpec(_))
+        ));
+
+        let spec = Spec::load(&paths[0]).unwrap();
+        assert_eq!(spec.total_count(), 4);
+    }
+
+    #[test]
+    fn test_set_priority_updates_story() {
+        let (_dir, _sm, paths) = setup(&["auth"]);
+
+        set_priority(&paths[0], "US-003", 0).unwrap();
+        assert_eq!(
+            Spec::load(&paths[0])
+                .unwrap()
+                .story("US-003")
+                .unwrap()
+                .priority,
+            0
+        );
+        assert!(matches!(
+            set_priority(&paths[0], "US-009", 1),
+            Err(Autom8Error::StoryNotFound { .. })
+        ));
+    }
+
+    #[test]
+    fn test_remove_story_updates_spec_and_session() {
+        let (_dir, sm, paths) = setup(&["auth"]);
+        let mut state = RunState::new(paths[0].clone(), "feature/test".to_string());
+        state.start_iteration("US-002");
+        state.mark_story_failed("US-002", "Claude exited with code 1");
+        sm.save(&state).unwrap();
+
+        // A running session is implementing the story
+        assert!(matches!(
+            remove_story(&sm, &paths[0], "US-002", false),
+            Err(Autom8Error::StoryInProgress(_))
+        ));
+
+        let dependents = remove_story(&sm, &paths[0], "US-002", true).unwrap();
+        assert_eq!(dependents, vec!["US-003"]);
+
+        let spec = Spec::load(&paths[0]).unwrap();
+        assert!(spec.story("US-002").is_none());
+        assert!(spec.story("US-003").unwrap().depends_on.is_empty());
+
+        let state = sm.load_current().unwrap().unwrap();
+        assert!(state.current_story.is_none());
+        assert_eq!(state.machine_state, MachineState::PickingStory);
+        assert!(state.failed_stories.is_empty());
+    }
+
+    #[test]
+    fn test_reset_story_unblocks_dependents_and_session() {
+        let (_dir, sm, paths) = setup(&["auth"]);
+        let mut spec = Spec::load(&paths[0]).unwrap();
+        spec.set_story_status("US-001", StoryStatus::new(StoryState::Failed, None));
+        spec.block_dependents("US-001");
+        spec.save(&paths[0]).unwrap();
+
+        let mut state = RunState::new(paths[0].clone(), "feature/test".to_string());
+        state.mark_story_failed("US-001", "still not passing after 10 iterations");
+        sm.save(&state).unwrap();
+
+        reset_story(&sm, &paths[0], "US-001").unwrap();
+
+        let spec = Spec::load(&paths[0]).unwrap();
+        assert_eq!(spec.count_in_state(StoryState::NotStarted), 3);
+        assert!(sm
+            .load_current()
+            .unwrap()
+            .unwrap()
+            .failed_stories
+            .is_empty());
+        assert!(matches!(
+            reset_story(&sm, &paths[0], "US-009"),
+            Err(Autom8Error::StoryNotFound { .. })
+        ));
+    }
+}
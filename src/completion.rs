@@ -248,6 +248,18 @@ fn build_cli() -> Command {
                         .long("skip-review")
                         .help("Skip the review loop and go directly to committing")
                         .action(clap::ArgAction::SetTrue),
+                )
+                .arg(
+                    clap::Arg::new("dry-run")
+                        .long("dry-run")
+                        .help("Render the prompts the run would send without running Claude")
+                        .action(clap::ArgAction::SetTrue),
+                )
+                .arg(
+                    clap::Arg::new("prompts-dir")
+                        .long("prompts-dir")
+                        .help("With --dry-run, write the prompts to this directory")
+                        .value_hint(clap::ValueHint::DirPath),
                 ),
         )
         .subcommand(
@@ -269,6 +281,23 @@ fn build_cli() -> Command {
                 ),
         )
         .subcommand(Command::new("resume").about("Resume a failed or interrupted run"))
+        .subcommand(
+            Command::new("rollback")
+                .about("Undo a story's changes so it can run again")
+                .arg(clap::Arg::new("story_id").help("Story to undo, along with the stories after it"))
+                .arg(
+                    clap::Arg::new("to")
+                        .long("to")
+                        .help("Keep this story and undo only the stories after it"),
+                )
+                .arg(
+                    clap::Arg::new("yes")
+                        .short('y')
+                        .long("yes")
+                        .help("Skip confirmation prompt")
+                        .action(clap::ArgAction::SetTrue),
+                ),
+        )
         .subcommand(Command::new("clean").about("Clean up spec files from config directory"))
         .subcommand(
             Command::new("init")
@@ -312,6 +341,157 @@ fn build_cli() -> Command {
                 "Continue iterating on a feature with Claude using context from previous runs",
             ),
         )
+        .subcommand(
+            Command::new("spec")
+                .about("Work with spec files")
+                .subcommand(
+                    Command::new("lint")
+                        .about("Check spec files for structural and quality problems")
+                        .arg(
+                            clap::Arg::new("files")
+                                .help("Spec files to lint (.json or .md)")
+                                .required(true)
+                                .num_args(1..)
+                                .value_hint(clap::ValueHint::FilePath),
+                        )
+                        .arg(
+                            clap::Arg::new("json")
+                                .long("json")
+                                .help("Print the results as JSON")
+                                .action(clap::ArgAction::SetTrue),
+                        ),
+                )
+                .subcommand(
+                    Command::new("show")
+                        .about("Show a spec's stories with their status")
+                        .arg(
+                            clap::Arg::new("spec")
+                                .long("spec")
+                                .help("Spec file, or the name of a spec in the project's spec/ directory")
+                                .value_hint(clap::ValueHint::FilePath),
+                        ),
+                )
+                .subcommand(
+                    Command::new("add-story")
+                        .about("Add a story to a spec")
+                        .arg(
+                            clap::Arg::new("title")
+                                .long("title")
+                                .help("Story title")
+                                .required(true),
+                        )
+                        .arg(
+                            clap::Arg::new("description")
+                                .long("description")
+                                .help("What the story accomplishes"),
+                        )
+                        .arg(
+                            clap::Arg::new("criterion")
+                                .short('c')
+                                .long("criterion")
+                                .help("Acceptance criterion (repeat for several)")
+                                .action(clap::ArgAction::Append),
+                        )
+                        .arg(
+                            clap::Arg::new("priority")
+                                .long("priority")
+                                .help("Priority (defaults to after the last story)"),
+                        )
+                        .arg(
+                            clap::Arg::new("id")
+                                .long("id")
+                                .help("Story ID (defaults to the next free ID, e.g. US-005)"),
+                        )
+                        .arg(
+                            clap::Arg::new("depends-on")
+                                .long("depends-on")
+                                .help("ID of a story that must pass first (repeat for several)")
+                                .action(clap::ArgAction::Append),
+                        )
+                        .arg(
+                            clap::Arg::new("spec")
+                                .long("spec")
+                                .help("Spec file, or the name of a spec in the project's spec/ directory")
+                                .value_hint(clap::ValueHint::FilePath),
+                        ),
+                )
+                .subcommand(
+                    Command::new("remove-story")
+                        .about("Remove a story from a spec")
+                        .arg(
+                            clap::Arg::new("id")
+                                .help("ID of the story to remove")
+                                .required(true),
+                        )
+                        .arg(
+                            clap::Arg::new("force")
+                                .long("force")
+                                .help("Remove the story even if a running session is implementing it")
+                                .action(clap::ArgAction::SetTrue),
+                        )
+                        .arg(
+                            clap::Arg::new("spec")
+                                .long("spec")
+                                .help("Spec file, or the name of a spec in the project's spec/ directory")
+                                .value_hint(clap::ValueHint::FilePath),
+                        ),
+                )
+                .subcommand(
+                    Command::new("set-priority")
+                        .about("Change a story's priority (lower runs first)")
+                        .arg(
+                            clap::Arg::new("id")
+                                .help("ID of the story")
+                                .required(true),
+                        )
+                        .arg(
+                            clap::Arg::new("priority")
+                                .help("New priority")
+                                .required(true),
+                        )
+                        .arg(
+                            clap::Arg::new("spec")
+                                .long("spec")
+                                .help("Spec file, or the name of a spec in the project's spec/ directory")
+                                .value_hint(clap::ValueHint::FilePath),
+                        ),
+                )
+                .subcommand(
+                    Command::new("reset")
+                        .about("Mark a story as not done so the next run implements it again")
+                        .arg(
+                            clap::Arg::new("id")
+                                .help("ID of the story to reset")
+                                .required(true),
+                        )
+                        .arg(
+                            clap::Arg::new("spec")
+                                .long("spec")
+                                .help("Spec file, or the name of a spec in the project's spec/ directory")
+                                .value_hint(clap::ValueHint::FilePath),
+                        ),
+                ),
+        )
+        .subcommand(
+            Command::new("prompt")
+                .about("Show the prompts autom8 sends to Claude")
+                .subcommand(
+                    Command::new("show")
+                        .about("Print the prompt Claude would get for a story")
+                        .arg(
+                            clap::Arg::new("story")
+                                .long("story")
+                                .help("ID of the story")
+                                .required(true),
+                        )
+                        .arg(
+                            clap::Arg::new("spec")
+                                .long("spec")
+                                .help("Spec file, or the name of a spec in the project's spec/ directory")
+                                .value_hint(clap::ValueHint::FilePath),
+                        ),
+                ),
+        )
         .subcommand(
             Command::new("config")
                 .about("View, modify, or reset configuration")
@@ -348,10 +528,30 @@ fn build_cli() -> Command {
                                 .value_parser([
                                     "review",
                                     "commit",
+                                    "commit_strategy",
                                     "pull_request",
                                     "worktree",
                                     "worktree_path_pattern",
                                     "worktree_cleanup",
+                                    "max_parallel_stories",
+                                    "spec_migration_backup",
+                                    "max_iterations_per_story",
+                                    "max_total_iterations",
+                                    "on_story_failure",
+                                    "story_timeout_minutes",
+                                    "review_timeout_minutes",
+                                    "correct_timeout_minutes",
+                                    "commit_timeout_minutes",
+                                    "pr_timeout_minutes",
+                                    "stall_timeout_minutes",
+                                    "kill_on_stall",
+                                    "claude_retry_attempts",
+                                    "max_run_tokens",
+                                    "max_story_tokens",
+                                    "max_run_cost_usd",
+                                    "sandbox",
+                                    "knowledge_decay_runs",
+                                    "knowledge_context_tokens",
                                 ]),
                         )
                         .arg(
@@ -461,7 +661,7 @@ _autom8_complete() {
     # Check if completing first positional arg (not a subcommand)
     if [[ $cword -eq 1 && "$cur" != -* ]]; then
         # Get subcommands
-        local subcommands="run status resume clean config init projects list describe pr-review monitor gui improve"
+        local subcommands="run status resume rollback clean config init projects list describe pr-review monitor gui improve spec prompt"
         # Get spec files
         local specs=$(_autom8_spec_files)
         COMPREPLY=($(compgen -W "$subcommands $specs" -- "$cur"))
@@ -472,7 +672,16 @@ _autom8_complete() {
     if [[ "${words[1]}" == "config" && "${words[2]}" == "set" ]]; then
         if [[ $cword -eq 3 ]]; then
             # Complete config keys
-            COMPREPLY=($(compgen -W "review commit pull_request worktree worktree_path_pattern worktree_cleanup" -- "$cur"))
+            COMPREPLY=($(compgen -W "review commit commit_strategy pull_request worktree worktree_path_pattern worktree_cleanup max_parallel_stories spec_migration_backup max_iterations_per_story max_total_iterations on_story_failure story_timeout_minutes review_timeout_minutes correct_timeout_minutes commit_timeout_minutes pr_timeout_minutes stall_timeout_minutes kill_on_stall claude_retry_attempts max_run_tokens max_story_tokens max_run_cost_usd sandbox knowledge_decay_runs knowledge_context_tokens" -- "$cur"))
+            return
+        elif [[ $cword -eq 4 && "${words[3]}" == "on_story_failure" ]]; then
+            COMPREPLY=($(compgen -W "fail skip pause" -- "$cur"))
+            return
+        elif [[ $cword -eq 4 && "${words[3]}" == "commit_strategy" ]]; then
+            COMPREPLY=($(compgen -W "end per_story" -- "$cur"))
+            return
+        elif [[ $cword -eq 4 && "${words[3]}" == "sandbox" ]]; then
+            COMPREPLY=($(compgen -W "none bwrap" -- "$cur"))
             return
         elif [[ $cword -eq 4 && "${words[3]}" != "worktree_path_pattern" ]]; then
             # Complete boolean values for non-string keys
@@ -548,6 +757,7 @@ else
                 'run:Run the agent loop to implement spec stories'
                 'status:Check the current run status'
                 'resume:Resume a failed or interrupted run'
+                'rollback:Undo a story so it can run again'
                 'clean:Clean up spec files from config directory'
                 'config:View, modify, or reset configuration'
                 'init:Initialize autom8 config directory structure'
@@ -558,6 +768,8 @@ else
                 'monitor:Monitor autom8 activity across all projects'
                 'gui:Launch the native GUI to monitor autom8 activity'
                 'improve:Continue iterating on a feature with Claude using context from previous runs'
+                'spec:Work with spec files'
+                'prompt:Show the prompts autom8 sends to Claude'
             )
             for spec in "${spec_files[@]}"; do
                 [[ -n "$spec" ]] && completions+=("$spec:Spec file")
@@ -573,13 +785,48 @@ else
                 config_keys=(
                     'review:Enable code review step'
                     'commit:Enable auto-commit'
+                    'commit_strategy:When to commit (end or per_story)'
                     'pull_request:Enable auto-PR creation'
                     'worktree:Enable worktree mode'
                     'worktree_path_pattern:Pattern for worktree names'
                     'worktree_cleanup:Auto-cleanup worktrees'
+                    'max_parallel_stories:Independent stories to run at once'
+                    'spec_migration_backup:Back up specs when upgrading'
+                    'max_iterations_per_story:Iterations allowed per story'
+                    'max_total_iterations:Story iterations allowed per run'
+                    'on_story_failure:What to do when a story fails'
+                    'story_timeout_minutes:Time limit per story iteration'
+                    'review_timeout_minutes:Time limit per review'
+                    'correct_timeout_minutes:Time limit per correction'
+                    'commit_timeout_minutes:Time limit for the commit agent'
+                    'pr_timeout_minutes:Time limit for the PR template agent'
+                    'stall_timeout_minutes:Minutes without output before Claude counts as stalled'
+                    'kill_on_stall:Kill and retry stalled Claude processes'
+                    'claude_retry_attempts:Retries after a transient Claude failure'
+                    'max_run_tokens:Tokens a run may use before pausing'
+                    'max_story_tokens:Tokens one story may use before pausing'
+                    'max_run_cost_usd:Estimated USD a run may spend before pausing'
+                    'sandbox:What agent processes are confined to (none or bwrap)'
+                    'knowledge_decay_runs:Runs a stored knowledge entry survives unless relearned'
+                    'knowledge_context_tokens:Tokens of project knowledge per story prompt'
                 )
                 _describe 'config key' config_keys
                 return
+            elif [[ $CURRENT -eq 5 && "${words[4]}" == "on_story_failure" ]]; then
+                local -a policy_values
+                policy_values=('fail' 'skip' 'pause')
+                _describe 'value' policy_values
+                return
+            elif [[ $CURRENT -eq 5 && "${words[4]}" == "commit_strategy" ]]; then
+                local -a strategy_values
+                strategy_values=('end' 'per_story')
+                _describe 'value' strategy_values
+                return
+            elif [[ $CURRENT -eq 5 && "${words[4]}" == "sandbox" ]]; then
+                local -a sandbox_values
+                sandbox_values=('none' 'bwrap')
+                _describe 'value' sandbox_values
+                return
             elif [[ $CURRENT -eq 5 && "${words[4]}" != "worktree_path_pattern" ]]; then
                 local -a bool_values
                 bool_values=('true' 'false')
@@ -641,13 +888,16 @@ end
 complete -c autom8 -l spec -xa '(__autom8_spec_files)'
 
 # Add spec file completions for positional argument (first arg that's not a flag)
-complete -c autom8 -n '__fish_is_first_arg; and not __fish_seen_subcommand_from run status resume clean config init projects list describe pr-review monitor gui improve' -xa '(__autom8_spec_files)'
+complete -c autom8 -n '__fish_is_first_arg; and not __fish_seen_subcommand_from run status resume rollback clean config init projects list describe pr-review monitor gui improve spec prompt' -xa '(__autom8_spec_files)'
 
 # Config set key completion
-complete -c autom8 -n '__fish_seen_subcommand_from config; and __fish_seen_subcommand_from set; and test (count (commandline -opc)) -eq 3' -xa 'review commit pull_request worktree worktree_path_pattern worktree_cleanup'
+complete -c autom8 -n '__fish_seen_subcommand_from config; and __fish_seen_subcommand_from set; and test (count (commandline -opc)) -eq 3' -xa 'review commit commit_strategy pull_request worktree worktree_path_pattern worktree_cleanup max_parallel_stories spec_migration_backup max_iterations_per_story max_total_iterations on_story_failure story_timeout_minutes review_timeout_minutes correct_timeout_minutes commit_timeout_minutes pr_timeout_minutes stall_timeout_minutes kill_on_stall claude_retry_attempts max_run_tokens max_story_tokens max_run_cost_usd sandbox knowledge_decay_runs knowledge_context_tokens'
 
 # Config set value completion (true/false for boolean keys)
-complete -c autom8 -n '__fish_seen_subcommand_from config; and __fish_seen_subcommand_from set; and test (count (commandline -opc)) -eq 4; and not string match -q worktree_path_pattern (commandline -opc)[-1]' -xa 'true false'
+complete -c autom8 -n '__fish_seen_subcommand_from config; and __fish_seen_subcommand_from set; and test (count (commandline -opc)) -eq 4; and not string match -q -r "worktree_path_pattern|on_story_failure|commit_strategy|sandbox" (commandline -opc)[-1]' -xa 'true false'
+complete -c autom8 -n '__fish_seen_subcommand_from config; and __fish_seen_subcommand_from set; and test (count (commandline -opc)) -eq 4; and string match -q on_story_failure (commandline -opc)[-1]' -xa 'fail skip pause'
+complete -c autom8 -n '__fish_seen_subcommand_from config; and __fish_seen_subcommand_from set; and test (count (commandline -opc)) -eq 4; and string match -q commit_strategy (commandline -opc)[-1]' -xa 'end per_story'
+complete -c autom8 -n '__fish_seen_subcommand_from config; and __fish_seen_subcommand_from set; and test (count (commandline -opc)) -eq 4; and string match -q sandbox (commandline -opc)[-1]' -xa 'none bwrap'
 "#
 }
 
@@ -1515,7 +1765,7 @@ mod tests {
 
         // Should include config in subcommands list
         assert!(
-            script.contains("run status resume clean config init projects list describe pr-review monitor gui improve"),
+            script.contains("run status resume rollback clean config init projects list describe pr-review monitor gui improve"),
             "Bash script should include all commands in dynamic subcommands list"
         );
     }
@@ -1543,7 +1793,7 @@ mod tests {
 
         // Should include config in the exclusion list for spec file completion
         assert!(
-            script.contains("run status resume clean config init projects list describe pr-review monitor gui improve"),
+            script.contains("run status resume rollback clean config init projects list describe pr-review monitor gui improve"),
             "Fish script should include all commands in dynamic subcommands list"
         );
     }
@@ -1836,7 +2086,7 @@ mod tests {
         // Fish: gui and improve in subcommand exclusion list
         let fish_script = generate_completion_script(ShellType::Fish);
         assert!(
-            fish_script.contains("__fish_seen_subcommand_from run status resume clean config init projects list describe pr-review monitor gui improve"),
+            fish_script.contains("__fish_seen_subcommand_from run status resume rollback clean config init projects list describe pr-review monitor gui improve"),
             "Fish script should include gui and improve in subcommand list"
         );
     }
@@ -1,8 +1,12 @@
+use crate::claude::ClaudePhase;
 use crate::error::{Autom8Error, Result};
+use crate::spec::{StoryState, StoryStatus};
 use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
 use std::env;
 use std::fs;
 use std::path::PathBuf;
+use std::time::Duration;
 
 /// The base config directory name under ~/.config/
 const CONFIG_DIR_NAME: &str = "autom8";
@@ -38,7 +42,7 @@ const CONFIG_DIR_NAME: &str = "autom8";
 /// # Enable/disable the pull request state (creating PRs)
 /// pull_request = true
 /// ```
-#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct Config {
     /// Whether to run the review state.
     ///
@@ -54,6 +58,14 @@ pub struct Config {
     #[serde(default = "default_true")]
     pub commit: bool,
 
+    /// When changes are committed.
+    ///
+    /// `end` commits everything once all stories pass; `per_story` commits
+    /// after each passing story and commits review fixes on top at the end.
+    /// Only applies when `commit = true`.
+    #[serde(default)]
+    pub commit_strategy: CommitStrategy,
+
     /// Whether to run the pull request state.
     ///
     /// When `true`, a pull request is created after committing.
@@ -100,6 +112,712 @@ pub struct Config {
     /// Note: Only applies when `worktree = true`. Has no effect otherwise.
     #[serde(default = "default_false")]
     pub worktree_cleanup: bool,
+
+    /// Maximum number of independent stories to run at the same time.
+    ///
+    /// Stories whose dependencies are all complete are considered independent.
+    /// When greater than 1, each story in a batch runs in its own temporary
+    /// worktree and its branch is merged back into the run branch afterwards.
+    /// Stories that hit a merge conflict are re-queued to run sequentially.
+    ///
+    /// Note: Requires a git repository and `commit = true`, since parallel
+    /// stories are merged back as commits. Default: 1 (sequential).
+    #[serde(default = "default_max_parallel_stories")]
+    pub max_parallel_stories: u32,
+
+    /// Whether to keep a backup when an older spec file is upgraded.
+    ///
+    /// Spec files written by older versions of autom8 are upgraded to the
+    /// current format when loaded. When `true`, the original file is kept
+    /// next to it as `<file>.v<old version>.bak`.
+    #[serde(default = "default_true")]
+    pub spec_migration_backup: bool,
+
+    /// Maximum number of iterations spent on a single story.
+    ///
+    /// A story that still doesn't pass after this many iterations has
+    /// failed, and `on_story_failure` decides what happens next.
+    #[serde(default = "default_max_iterations_per_story")]
+    pub max_iterations_per_story: u32,
+
+    /// Maximum number of story iterations in a whole run.
+    ///
+    /// When reached, the run stops (or pauses when `on_story_failure = "pause"`).
+    #[serde(default = "default_max_total_iterations")]
+    pub max_total_iterations: u32,
+
+    /// What to do when a story fails.
+    ///
+    /// A story fails when Claude returns an error while implementing it, or
+    /// when it reaches `max_iterations_per_story` without passing.
+    #[serde(default)]
+    pub on_story_failure: StoryFailurePolicy,
+
+    /// Minutes Claude may spend on one story iteration before it is killed.
+    ///
+    /// A timed-out iteration is recorded as failed and handled by
+    /// `on_story_failure`. `0` disables the limit. Default: 0, so hung-story
+    /// protection is opt-in (a long story can legitimately take hours); set
+    /// e.g. `60`, or `kill_on_stall`, to have hung processes killed.
+    #[serde(default)]
+    pub story_timeout_minutes: u32,
+
+    /// Minutes the reviewer may run before it is killed (`0` = no limit).
+    /// Default: 0; set e.g. `20` to opt in.
+    #[serde(default)]
+    pub review_timeout_minutes: u32,
+
+    /// Minutes the corrector may run before it is killed (`0` = no limit).
+    #[serde(default = "default_correct_timeout_minutes")]
+    pub correct_timeout_minutes: u32,
+
+    /// Minutes the commit agent may run before it is killed (`0` = no limit).
+    #[serde(default = "default_commit_timeout_minutes")]
+    pub commit_timeout_minutes: u32,
+
+    /// Minutes the PR template agent may run before it is killed (`0` = no limit).
+    ///
+    /// On timeout the PR is created with the generated description instead.
+    #[serde(default = "default_pr_timeout_minutes")]
+    pub pr_timeout_minutes: u32,
+
+    /// Minutes Claude may go without output before its session is flagged
+    /// as stalled in the monitor. `0` disables stall detection. Default: 10.
+    #[serde(default = "default_stall_timeout_minutes")]
+    pub stall_timeout_minutes: u32,
+
+    /// Whether to kill a stalled Claude process instead of only flagging it.
+    ///
+    /// A killed story iteration is recorded as failed and retried, up to
+    /// `max_iterations_per_story`. Default: false.
+    #[serde(default = "default_false")]
+    pub kill_on_stall: bool,
+
+    /// How many times a story iteration, review, correction, commit or PR
+    /// description is retried after a transient Claude failure (overload,
+    /// server error, network problem), with exponential backoff.
+    ///
+    /// Each starts with a fresh count. Usage-limit waits don't count. `0`
+    /// disables retries. Default: 3.
+    #[serde(default = "default_claude_retry_attempts")]
+    pub claude_retry_attempts: u32,
+
+    /// Input + output tokens a run may use before it pauses. `0` means no
+    /// limit. Default: 0.
+    #[serde(default)]
+    pub max_run_tokens: u64,
+
+    /// Input + output tokens a single story may use before the run pauses.
+    /// `0` means no limit. Default: 0.
+    #[serde(default)]
+    pub max_story_tokens: u64,
+
+    /// Estimated spend in USD a run may reach before it pauses, priced per
+    /// model (see [`crate::claude::estimate_cost_usd`]). `0` means no limit.
+    /// Default: 0.
+    #[serde(default)]
+    pub max_run_cost_usd: f64,
+
+    /// Sandbox agent processes run in: `none` (default), or `bwrap` to
+    /// confine them to the run's worktree with bubblewrap (Linux only,
+    /// configured in the `[bwrap]` table).
+    #[serde(default)]
+    pub sandbox: SandboxKind,
+
+    /// Completed runs after which an entry in the project's knowledge store
+    /// is dropped unless a run learned it again. `0` keeps entries forever.
+    /// Default: 5.
+    #[serde(default = "default_knowledge_decay_runs")]
+    pub knowledge_decay_runs: u32,
+
+    /// Estimated tokens of project knowledge injected into a story prompt.
+    /// Over budget, the entries least relevant to the story are left out.
+    /// `0` means no limit. Default: 2000.
+    #[serde(default = "default_knowledge_context_tokens")]
+    pub knowledge_context_tokens: u64,
+
+    /// Shell commands run at points in a run (the `[hooks]` table).
+    #[serde(default)]
+    pub hooks: HooksConfig,
+
+    /// Recording and replaying Claude sessions (the `[fixtures]` table).
+    #[serde(default)]
+    pub fixtures: FixturesConfig,
+
+    /// The coding agent sessions are run with (the `[agent]` table).
+    #[serde(default)]
+    pub agent: AgentConfig,
+
+    /// How the Claude CLI is run, per phase (the `[claude]` table).
+    #[serde(default)]
+    pub claude: ClaudeConfig,
+
+    /// What a `bwrap` sandbox lets agents see (the `[bwrap]` table).
+    #[serde(default)]
+    pub bwrap: BwrapConfig,
+}
+
+/// Lifecycle hook commands, run through `sh -c`. See [`crate::hooks`].
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct HooksConfig {
+    /// Once the run's state has been created.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub on_run_start: Option<String>,
+    /// Before Claude starts working on a story. A failure stops the run.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub pre_story: Option<String>,
+    /// After Claude finishes a story iteration.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub post_story: Option<String>,
+    /// Before the review phase. A failure stops the run.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub pre_review: Option<String>,
+    /// Before changes are committed. A failure stops the run.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub pre_commit: Option<String>,
+    /// After a pull request is created or updated.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub post_pr: Option<String>,
+    /// When the run fails.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub on_failure: Option<String>,
+    /// When the run completes.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub on_complete: Option<String>,
+}
+
+impl HooksConfig {
+    /// Configured hooks as (key, command), in the order they run.
+    pub fn entries(&self) -> Vec<(&'static str, &str)> {
+        [
+            ("on_run_start", &self.on_run_start),
+            ("pre_story", &self.pre_story),
+            ("post_story", &self.post_story),
+            ("pre_review", &self.pre_review),
+            ("pre_commit", &self.pre_commit),
+            ("post_pr", &self.post_pr),
+            ("on_failure", &self.on_failure),
+            ("on_complete", &self.on_complete),
+        ]
+        .into_iter()
+        .filter_map(|(key, command)| command.as_deref().map(|c| (key, c)))
+        .collect()
+    }
+}
+
+/// Where Claude sessions are recorded to or replayed from. See
+/// [`crate::claude::fixture`].
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct FixturesConfig {
+    /// Directory every Claude session is recorded to.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub record: Option<String>,
+    /// Directory of recorded sessions to replay instead of running Claude.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub replay: Option<String>,
+}
+
+impl FixturesConfig {
+    /// Configured settings as (key, directory).
+    pub fn entries(&self) -> Vec<(&'static str, &str)> {
+        [("record", &self.record), ("replay", &self.replay)]
+            .into_iter()
+            .filter_map(|(key, dir)| dir.as_deref().map(|d| (key, d)))
+            .collect()
+    }
+}
+
+/// The coding agent autom8 runs. See [`crate::claude::backend`].
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct AgentConfig {
+    /// `claude` for the Claude CLI, or `command` for `command`.
+    #[serde(default)]
+    pub backend: AgentKind,
+    /// Agent command to run when `backend = "command"`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub command: Option<String>,
+    /// Arguments for `command`. `{prompt}` is replaced with the prompt;
+    /// without it the prompt is written to stdin.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub args: Vec<String>,
+    /// What `command` writes to stdout.
+    #[serde(default)]
+    pub protocol: LineProtocol,
+}
+
+impl AgentConfig {
+    /// Settings that differ from the defaults, as (key, value).
+    pub fn entries(&self) -> Vec<(&'static str, toml::Value)> {
+        let mut entries = Vec::new();
+        if self.backend != AgentKind::default() {
+            entries.push(("backend", toml::Value::String(self.backend.to_string())));
+        }
+        if let Some(command) = &self.command {
+            entries.push(("command", toml::Value::String(command.clone())));
+        }
+        if !self.args.is_empty() {
+            entries.push(("args", toml_strings(&self.args)));
+        }
+        if self.protocol != LineProtocol::default() {
+            entries.push(("protocol", toml::Value::String(self.protocol.to_string())));
+        }
+        entries
+    }
+}
+
+/// Which coding agent autom8 runs.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum AgentKind {
+    /// The `claude` CLI (default).
+    #[default]
+    Claude,
+    /// The command in `[agent] command`.
+    Command,
+}
+
+impl fmt::Display for AgentKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            AgentKind::Claude => write!(f, "claude"),
+            AgentKind::Command => write!(f, "command"),
+        }
+    }
+}
+
+/// The line protocol an agent command writes to stdout.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum LineProtocol {
+    /// Claude CLI `--output-format stream-json` events (default).
+    #[default]
+    StreamJson,
+    /// One JSON object per line: `{"type": "text", "text": ...}`, `tool`
+    /// with `name`, `usage` with token counts and `error` with `message`.
+    Jsonl,
+    /// Plain text; every line is output.
+    Text,
+}
+
+impl fmt::Display for LineProtocol {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            LineProtocol::StreamJson => write!(f, "stream_json"),
+            LineProtocol::Jsonl => write!(f, "jsonl"),
+            LineProtocol::Text => write!(f, "text"),
+        }
+    }
+}
+
+/// How the Claude CLI is run: the binary, the model and extra arguments,
+/// with overrides per phase in `[claude.<phase>]` tables.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ClaudeConfig {
+    /// Path to the `claude` binary. Default: `claude` on the PATH.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub binary: Option<String>,
+    /// Model for every phase, e.g. `opus`. Default: the CLI's default model.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub model: Option<String>,
+    /// Extra arguments for every phase.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub args: Vec<String>,
+    /// Permission profile for every phase but review, which defaults to
+    /// `read_only`. Default: `full`, which skips permission checks.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub permissions: Option<String>,
+    /// Named permission profiles, in `[claude.profiles.<name>]` tables.
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub profiles: BTreeMap<String, PermissionProfile>,
+    /// Converting a markdown spec to JSON.
+    #[serde(default, skip_serializing_if = "ClaudePhaseConfig::is_empty")]
+    pub spec: ClaudePhaseConfig,
+    /// Implementing a story.
+    #[serde(default, skip_serializing_if = "ClaudePhaseConfig::is_empty")]
+    pub story: ClaudePhaseConfig,
+    /// Reviewing the finished work.
+    #[serde(default, skip_serializing_if = "ClaudePhaseConfig::is_empty")]
+    pub review: ClaudePhaseConfig,
+    /// Fixing what the review found.
+    #[serde(default, skip_serializing_if = "ClaudePhaseConfig::is_empty")]
+    pub correct: ClaudePhaseConfig,
+    /// Writing the commit.
+    #[serde(default, skip_serializing_if = "ClaudePhaseConfig::is_empty")]
+    pub commit: ClaudePhaseConfig,
+    /// Filling in the PR template.
+    #[serde(default, skip_serializing_if = "ClaudePhaseConfig::is_empty")]
+    pub pr_template: ClaudePhaseConfig,
+    /// Addressing PR review comments.
+    #[serde(default, skip_serializing_if = "ClaudePhaseConfig::is_empty")]
+    pub pr_review: ClaudePhaseConfig,
+}
+
+/// Overrides for one phase in `[claude.<phase>]`.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ClaudePhaseConfig {
+    /// Model for this phase instead of `[claude] model`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub model: Option<String>,
+    /// Extra arguments for this phase instead of `[claude] args`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub args: Option<Vec<String>>,
+    /// Permission profile for this phase instead of `[claude] permissions`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub permissions: Option<String>,
+}
+
+impl ClaudePhaseConfig {
+    pub fn is_empty(&self) -> bool {
+        self.model.is_none() && self.args.is_none() && self.permissions.is_none()
+    }
+
+    /// Configured settings as (key, value).
+    pub fn entries(&self) -> Vec<(&'static str, toml::Value)> {
+        let mut entries = Vec::new();
+        if let Some(model) = &self.model {
+            entries.push(("model", toml::Value::String(model.clone())));
+        }
+        if let Some(args) = &self.args {
+            entries.push(("args", toml_strings(args)));
+        }
+        if let Some(permissions) = &self.permissions {
+            entries.push(("permissions", toml::Value::String(permissions.clone())));
+        }
+        entries
+    }
+}
+
+/// Profile that runs Claude with `--dangerously-skip-permissions`.
+pub const FULL_ACCESS_PROFILE: &str = "full";
+
+/// Built-in profile the reviewer runs with: reading the code, writing the
+/// review file, and running tests, linters and read-only git commands.
+pub const READ_ONLY_PROFILE: &str = "read_only";
+
+/// What Claude may do in a phase, passed to the CLI as `--allowedTools`.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct PermissionProfile {
+    /// Tools or tool rules, e.g. `Read` or `Edit(docs/**)`.
+    #[serde(default)]
+    pub tools: Vec<String>,
+    /// Bash command patterns, e.g. `cargo test:*`.
+    #[serde(default)]
+    pub bash: Vec<String>,
+}
+
+impl PermissionProfile {
+    /// The built-in [`READ_ONLY_PROFILE`].
+    pub fn read_only() -> Self {
+        let strings = |values: &[&str]| values.iter().map(|v| v.to_string()).collect();
+        Self {
+            tools: strings(&[
+                "Read",
+                "Glob",
+                "Grep",
+                "Write(autom8_review.md)",
+                "Edit(autom8_review.md)",
+            ]),
+            bash: strings(&[
+                "git status:*",
+                "git diff:*",
+                "git log:*",
+                "git show:*",
+                "cargo test:*",
+                "cargo check:*",
+                "cargo clippy:*",
+                "cargo fmt --check:*",
+                "npm test:*",
+                "npm run test:*",
+                "npm run lint:*",
+                "pnpm test:*",
+                "yarn test:*",
+                "pytest:*",
+                "ruff check:*",
+                "go test:*",
+                "go vet:*",
+                "make test:*",
+                "make lint:*",
+            ]),
+        }
+    }
+
+    /// The `--allowedTools` rules: the tools, then `Bash(<pattern>)` for
+    /// each Bash pattern.
+    pub fn allowed_tools(&self) -> Vec<String> {
+        let bash = self.bash.iter().map(|pattern| format!("Bash({})", pattern));
+        self.tools.iter().cloned().chain(bash).collect()
+    }
+
+    /// Settings as (key, value).
+    pub fn entries(&self) -> Vec<(&'static str, toml::Value)> {
+        vec![
+            ("tools", toml_strings(&self.tools)),
+            ("bash", toml_strings(&self.bash)),
+        ]
+    }
+}
+
+impl ClaudeConfig {
+    /// The `claude` binary to run.
+    pub fn binary(&self) -> &str {
+        self.binary.as_deref().unwrap_or("claude")
+    }
+
+    /// The model for `phase`, if one is configured.
+    pub fn model_for(&self, phase: ClaudePhase) -> Option<&str> {
+        self.phase(phase).model.as_deref().or(self.model.as_deref())
+    }
+
+    /// The extra arguments for `phase`.
+    pub fn args_for(&self, phase: ClaudePhase) -> &[String] {
+        self.phase(phase).args.as_deref().unwrap_or(&self.args)
+    }
+
+    /// The name of the permission profile `phase` runs with.
+    pub fn permissions_name_for(&self, phase: ClaudePhase) -> &str {
+        if let Some(name) = &self.phase(phase).permissions {
+            return name;
+        }
+        if phase == ClaudePhase::Review {
+            return READ_ONLY_PROFILE;
+        }
+        self.permissions.as_deref().unwrap_or(FULL_ACCESS_PROFILE)
+    }
+
+    /// The permission profile `phase` runs with, or `None` for full access.
+    ///
+    /// An unknown profile name allows nothing; [`validate_config`] rejects
+    /// those before a run starts.
+    pub fn permissions_for(&self, phase: ClaudePhase) -> Option<PermissionProfile> {
+        let name = self.permissions_name_for(phase);
+        if name == FULL_ACCESS_PROFILE {
+            return None;
+        }
+        let profile = match self.profiles.get(name) {
+            Some(profile) => profile.clone(),
+            None if name == READ_ONLY_PROFILE => PermissionProfile::read_only(),
+            None => PermissionProfile::default(),
+        };
+        Some(profile)
+    }
+
+    /// The `[claude.<phase>]` overrides for `phase`.
+    fn phase(&self, phase: ClaudePhase) -> &ClaudePhaseConfig {
+        match phase {
+            ClaudePhase::Spec => &self.spec,
+            ClaudePhase::Story => &self.story,
+            ClaudePhase::Review => &self.review,
+            ClaudePhase::Correct => &self.correct,
+            ClaudePhase::Commit => &self.commit,
+            ClaudePhase::PrTemplate => &self.pr_template,
+            ClaudePhase::PrReview => &self.pr_review,
+        }
+    }
+
+    /// Configured top-level settings as (key, value).
+    pub fn entries(&self) -> Vec<(&'static str, toml::Value)> {
+        let mut entries = Vec::new();
+        if let Some(binary) = &self.binary {
+            entries.push(("binary", toml::Value::String(binary.clone())));
+        }
+        if let Some(model) = &self.model {
+            entries.push(("model", toml::Value::String(model.clone())));
+        }
+        if !self.args.is_empty() {
+            entries.push(("args", toml_strings(&self.args)));
+        }
+        if let Some(permissions) = &self.permissions {
+            entries.push(("permissions", toml::Value::String(permissions.clone())));
+        }
+        entries
+    }
+
+    /// The `[claude.<phase>]` and `[claude.profiles.<name>]` tables that
+    /// have settings, as (table name, settings).
+    pub fn subtables(&self) -> Vec<(String, Vec<(&'static str, toml::Value)>)> {
+        let phases = self
+            .phase_overrides()
+            .into_iter()
+            .map(|(phase, overrides)| (format!("claude.{}", phase), overrides.entries()));
+        let profiles = self
+            .profiles
+            .iter()
+            .map(|(name, profile)| (format!("claude.profiles.{}", name), profile.entries()));
+        phases.chain(profiles).collect()
+    }
+
+    /// Phases with overrides, as (phase name, overrides).
+    fn phase_overrides(&self) -> Vec<(&'static str, &ClaudePhaseConfig)> {
+        [
+            ("spec", &self.spec),
+            ("story", &self.story),
+            ("review", &self.review),
+            ("correct", &self.correct),
+            ("commit", &self.commit),
+            ("pr_template", &self.pr_template),
+            ("pr_review", &self.pr_review),
+        ]
+        .into_iter()
+        .filter(|(_, overrides)| !overrides.is_empty())
+        .collect()
+    }
+}
+
+fn toml_strings(values: &[String]) -> toml::Value {
+    toml::Value::Array(values.iter().cloned().map(toml::Value::String).collect())
+}
+
+/// Convert a `*_timeout_minutes` setting to a duration, where `0` means no limit.
+pub fn timeout_from_minutes(minutes: u32) -> Option<Duration> {
+    (minutes > 0).then(|| Duration::from_secs(u64::from(minutes) * 60))
+}
+
+/// Convert a token budget setting to a limit, where `0` means no limit.
+pub fn token_limit(tokens: u64) -> Option<u64> {
+    (tokens > 0).then_some(tokens)
+}
+
+/// When the runner commits a run's changes.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum CommitStrategy {
+    /// Commit everything once all stories pass (default).
+    #[default]
+    End,
+    /// Commit after each passing story, then commit review fixes on top.
+    PerStory,
+}
+
+impl fmt::Display for CommitStrategy {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            CommitStrategy::End => write!(f, "end"),
+            CommitStrategy::PerStory => write!(f, "per_story"),
+        }
+    }
+}
+
+impl std::str::FromStr for CommitStrategy {
+    type Err = String;
+
+    fn from_str(s: &str) -> std::result::Result<Self, Self::Err> {
+        match s.trim().to_lowercase().as_str() {
+            "end" => Ok(CommitStrategy::End),
+            "per_story" | "per-story" => Ok(CommitStrategy::PerStory),
+            _ => Err(format!("expected end or per_story, got '{}'", s)),
+        }
+    }
+}
+
+/// What agent processes are confined to.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum SandboxKind {
+    /// Run agents unconfined (default).
+    #[default]
+    None,
+    /// Run agents inside a bubblewrap namespace, see [`crate::claude::sandbox`].
+    Bwrap,
+}
+
+impl fmt::Display for SandboxKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            SandboxKind::None => write!(f, "none"),
+            SandboxKind::Bwrap => write!(f, "bwrap"),
+        }
+    }
+}
+
+impl std::str::FromStr for SandboxKind {
+    type Err = String;
+
+    fn from_str(s: &str) -> std::result::Result<Self, Self::Err> {
+        match s.trim().to_lowercase().as_str() {
+            "none" => Ok(SandboxKind::None),
+            "bwrap" => Ok(SandboxKind::Bwrap),
+            _ => Err(format!("expected none or bwrap, got '{}'", s)),
+        }
+    }
+}
+
+/// What the processes in a `bwrap` sandbox can see, besides the worktree,
+/// `/tmp` and the system directories. `~` stands for the home directory.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct BwrapConfig {
+    /// Paths visible read-only, e.g. toolchains such as `~/.rustup`.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub read_only: Vec<String>,
+    /// Paths visible and writable, e.g. a shared build cache.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub writable: Vec<String>,
+    /// Whether agents have network access. Default: true, as the Claude CLI
+    /// needs it to reach the API.
+    #[serde(default = "default_true")]
+    pub network: bool,
+}
+
+impl Default for BwrapConfig {
+    fn default() -> Self {
+        Self {
+            read_only: Vec::new(),
+            writable: Vec::new(),
+            network: true,
+        }
+    }
+}
+
+impl BwrapConfig {
+    /// Settings that differ from the defaults, as (key, value).
+    pub fn entries(&self) -> Vec<(&'static str, toml::Value)> {
+        let mut entries = Vec::new();
+        if !self.read_only.is_empty() {
+            entries.push(("read_only", toml_strings(&self.read_only)));
+        }
+        if !self.writable.is_empty() {
+            entries.push(("writable", toml_strings(&self.writable)));
+        }
+        if !self.network {
+            entries.push(("network", toml::Value::Boolean(false)));
+        }
+        entries
+    }
+}
+
+/// What the runner does when a story fails.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum StoryFailurePolicy {
+    /// Stop the run with a failed state (default).
+    #[default]
+    Fail,
+    /// Give up on the story and continue with stories that don't depend on it.
+    Skip,
+    /// Pause the run so it can be resumed after a manual fix.
+    Pause,
+}
+
+impl fmt::Display for StoryFailurePolicy {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            StoryFailurePolicy::Fail => write!(f, "fail"),
+            StoryFailurePolicy::Skip => write!(f, "skip"),
+            StoryFailurePolicy::Pause => write!(f, "pause"),
+        }
+    }
+}
+
+impl std::str::FromStr for StoryFailurePolicy {
+    type Err = String;
+
+    fn from_str(s: &str) -> std::result::Result<Self, Self::Err> {
+        match s.trim().to_lowercase().as_str() {
+            "fail" => Ok(StoryFailurePolicy::Fail),
+            "skip" => Ok(StoryFailurePolicy::Skip),
+            "pause" => Ok(StoryFailurePolicy::Pause),
+            _ => Err(format!("expected fail, skip or pause, got '{}'", s)),
+        }
+    }
 }
 
 /// Default worktree path pattern.
@@ -107,6 +825,56 @@ fn default_worktree_path_pattern() -> String {
     "{repo}-wt-{branch}".to_string()
 }
 
+/// Default number of stories to run at once (sequential).
+fn default_max_parallel_stories() -> u32 {
+    1
+}
+
+/// Default iteration limit for a single story.
+fn default_max_iterations_per_story() -> u32 {
+    10
+}
+
+/// Default iteration limit for a whole run.
+fn default_max_total_iterations() -> u32 {
+    100
+}
+
+/// Default time limit for a correction, in minutes.
+fn default_correct_timeout_minutes() -> u32 {
+    30
+}
+
+/// Default time limit for the commit agent, in minutes.
+fn default_commit_timeout_minutes() -> u32 {
+    10
+}
+
+/// Default time limit for the PR template agent, in minutes.
+fn default_pr_timeout_minutes() -> u32 {
+    10
+}
+
+/// Default time without Claude output before a session counts as stalled, in minutes.
+fn default_stall_timeout_minutes() -> u32 {
+    10
+}
+
+/// Default number of retries after a transient Claude failure.
+fn default_claude_retry_attempts() -> u32 {
+    3
+}
+
+/// Default value for `knowledge_decay_runs`.
+fn default_knowledge_decay_runs() -> u32 {
+    5
+}
+
+/// Default value for `knowledge_context_tokens`.
+fn default_knowledge_context_tokens() -> u64 {
+    2000
+}
+
 /// Helper function for serde default values (true).
 fn default_true() -> bool {
     true
@@ -122,11 +890,36 @@ impl Default for Config {
         Self {
             review: true,
             commit: true,
+            commit_strategy: CommitStrategy::default(),
             pull_request: true,
             pull_request_draft: false,
             worktree: true,
             worktree_path_pattern: default_worktree_path_pattern(),
             worktree_cleanup: false,
+            max_parallel_stories: default_max_parallel_stories(),
+            spec_migration_backup: true,
+            max_iterations_per_story: default_max_iterations_per_story(),
+            max_total_iterations: default_max_total_iterations(),
+            on_story_failure: StoryFailurePolicy::default(),
+            story_timeout_minutes: 0,
+            review_timeout_minutes: 0,
+            correct_timeout_minutes: default_correct_timeout_minutes(),
+            commit_timeout_minutes: default_commit_timeout_minutes(),
+            pr_timeout_minutes: default_pr_timeout_minutes(),
+            stall_timeout_minutes: default_stall_timeout_minutes(),
+            kill_on_stall: false,
+            claude_retry_attempts: default_claude_retry_attempts(),
+            max_run_tokens: 0,
+            max_story_tokens: 0,
+            max_run_cost_usd: 0.0,
+            sandbox: SandboxKind::default(),
+            knowledge_decay_runs: default_knowledge_decay_runs(),
+            knowledge_context_tokens: default_knowledge_context_tokens(),
+            hooks: HooksConfig::default(),
+            fixtures: FixturesConfig::default(),
+            agent: AgentConfig::default(),
+            claude: ClaudeConfig::default(),
+            bwrap: BwrapConfig::default(),
         }
     }
 }
@@ -150,6 +943,13 @@ pub enum ConfigError {
     /// Creating a pull request requires commits to exist, so this
     /// configuration combination is invalid.
     PullRequestWithoutCommit,
+
+    /// A phase uses a permission profile that isn't built in or defined in
+    /// `[claude.profiles]`.
+    UnknownPermissionProfile(String),
+
+    /// `[claude.profiles]` redefines the built-in full access profile.
+    ReservedPermissionProfile,
 }
 
 impl fmt::Display for ConfigError {
@@ -162,6 +962,22 @@ impl fmt::Display for ConfigError {
                     Either set `commit = true` or set `pull_request = false`"
                 )
             }
+            ConfigError::UnknownPermissionProfile(name) => {
+                write!(
+                    f,
+                    "Unknown permission profile \"{}\". \
+                    Use \"{}\", \"{}\" or a profile defined in a [claude.profiles.{}] table",
+                    name, FULL_ACCESS_PROFILE, READ_ONLY_PROFILE, name
+                )
+            }
+            ConfigError::ReservedPermissionProfile => {
+                write!(
+                    f,
+                    "[claude.profiles.{}] can't be defined: \"{}\" always skips permission checks. \
+                    Give the profile another name",
+                    FULL_ACCESS_PROFILE, FULL_ACCESS_PROFILE
+                )
+            }
         }
     }
 }
@@ -178,6 +994,8 @@ impl Error for ConfigError {}
 ///
 /// - `pull_request = true` requires `commit = true`
 ///   (Cannot create a PR without commits)
+/// - Every permission profile a phase uses must exist, and
+///   `[claude.profiles.full]` can't be defined
 ///
 /// # Arguments
 ///
@@ -210,6 +1028,20 @@ pub fn validate_config(config: &Config) -> std::result::Result<(), ConfigError>
         return Err(ConfigError::PullRequestWithoutCommit);
     }
 
+    let claude = &config.claude;
+    if claude.profiles.contains_key(FULL_ACCESS_PROFILE) {
+        return Err(ConfigError::ReservedPermissionProfile);
+    }
+    for phase in ClaudePhase::ALL {
+        let name = claude.permissions_name_for(phase);
+        let known = name == FULL_ACCESS_PROFILE
+            || name == READ_ONLY_PROFILE
+            || claude.profiles.contains_key(name);
+        if !known {
+            return Err(ConfigError::UnknownPermissionProfile(name.to_string()));
+        }
+    }
+
     Ok(())
 }
 
@@ -237,6 +1069,11 @@ review = true
 # - false: Leave changes uncommitted (manual commit required)
 commit = true
 
+# Commit strategy: When changes are committed (requires commit = true)
+# - "end": One commit once all stories pass (default)
+# - "per_story": A commit after each passing story, review fixes on top
+commit_strategy = "end"
+
 # Pull request state: Creating pull requests
 # - true: Automatically create a PR after committing
 # - false: Skip PR creation (commits remain on local branch)
@@ -265,6 +1102,144 @@ worktree_path_pattern = "{repo}-wt-{branch}"
 # - false: Preserve worktrees for manual inspection/cleanup (default)
 # Note: Failed runs always keep their worktrees. Only applies when worktree = true.
 worktree_cleanup = false
+
+# Parallel stories: Maximum number of independent stories to run at once
+# - 1: Run stories one at a time (default)
+# - N > 1: Run up to N stories whose dependencies are complete in parallel,
+#   each in a temporary worktree, merging them back into the run branch
+# Note: Requires commit = true. Stories with merge conflicts are re-run sequentially.
+max_parallel_stories = 1
+
+# Spec migration backup: Keep the original when an older spec file is upgraded
+# - true: Save the original as <file>.v<old version>.bak (default)
+# - false: Upgrade spec files in place without a backup
+spec_migration_backup = true
+
+# Story iteration limit: Iterations allowed per story before it counts as failed
+max_iterations_per_story = 10
+
+# Run iteration limit: Story iterations allowed in a whole run
+max_total_iterations = 100
+
+# Story failure policy: What to do when a story errors or hits its iteration limit
+# - "fail": Stop the run (default)
+# - "skip": Give up on the story and continue with stories that don't depend on it
+# - "pause": Pause the run so it can be resumed with `autom8 resume`
+on_story_failure = "fail"
+
+# Claude time limits, in minutes (0 = no limit)
+# A hung Claude process is killed once its phase runs past the limit.
+# A timed-out story iteration counts as failed and is retried on resume.
+# Stories and reviews have no limit by default, so a hung story is never
+# killed unless you opt in here (e.g. 60 and 20) or set kill_on_stall.
+story_timeout_minutes = 0
+review_timeout_minutes = 0
+correct_timeout_minutes = 30
+commit_timeout_minutes = 10
+pr_timeout_minutes = 10
+
+# Stall detection: Minutes without Claude output before a session is flagged
+# as stalled in the monitor (0 = never)
+stall_timeout_minutes = 10
+
+# Kill on stall: Kill a stalled Claude process instead of only flagging it
+# - true: Kill it; a stalled story iteration counts as failed and is retried
+# - false: Only flag it (default)
+kill_on_stall = false
+
+# Claude retries: Retries for each story iteration, review, correction, commit
+# and PR description after a transient Claude failure (overload, server error,
+# network problem), with exponential backoff (0 = none)
+# A usage limit with a reset time is always waited out, without counting here.
+claude_retry_attempts = 3
+
+# Budgets: Pause the run once it has used this much (0 = no limit)
+# Token counts are input + output tokens; cost is estimated from model pricing.
+# Resuming a paused run starts a fresh budget.
+max_run_tokens = 0
+max_story_tokens = 0
+max_run_cost_usd = 0
+
+# Sandbox: What agent processes are confined to
+# - none: Nothing, they run as you (default)
+# - bwrap: A bubblewrap namespace (Linux only) that only sees the run's
+#   worktree, its git directory, a private /tmp, system directories and the
+#   paths in [bwrap]. autom8 checks that bwrap is installed at startup.
+sandbox = "none"
+
+# Knowledge: What completed runs learned (files, decisions, patterns) is kept in
+# ~/.config/autom8/<project>/knowledge/ and given to later runs. Entries no run
+# has learned again for this many runs are dropped (0 = keep forever).
+knowledge_decay_runs = 5
+
+# Knowledge budget: Estimated tokens of project knowledge injected into each
+# story prompt (0 = no limit). Over budget, older stories' changes are
+# summarized and the entries least relevant to the story are left out.
+knowledge_context_tokens = 2000
+
+# Hooks: Shell commands run at points in a run (see README)
+# Each gets AUTOM8_HOOK, AUTOM8_RUN_ID, AUTOM8_BRANCH and, when known,
+# AUTOM8_STORY_ID, AUTOM8_WORKTREE_PATH and AUTOM8_PR_URL.
+# A failing pre_story, pre_review or pre_commit hook stops the run;
+# on_run_start, post_story, post_pr, on_failure and on_complete only warn.
+[hooks]
+# pre_story = "docker compose up -d db"
+# pre_commit = "cargo fmt"
+# post_pr = "./scripts/notify.sh \"$AUTOM8_PR_URL\""
+
+# Fixtures: Record every Claude session (output, prompt and file edits) to a
+# directory, or replay recorded sessions instead of running Claude.
+# The AUTOM8_RECORD_DIR and AUTOM8_REPLAY_DIR environment variables win.
+[fixtures]
+# record = "fixtures/my-feature"
+# replay = "fixtures/my-feature"
+
+# Agent: The coding agent CLI to run (see README)
+# - backend = "claude": The Claude CLI (default)
+# - backend = "command": Run `command` with `args`; {prompt} in an argument is
+#   replaced with the prompt, otherwise the prompt goes to stdin
+# protocol is what the command writes: "stream_json" (Claude's format,
+# default), "jsonl" (one {"type": "text"|"tool"|"usage"|"error"} per line)
+# or "text"
+[agent]
+# backend = "command"
+# command = "my-agent"
+# args = ["run", "--json"]
+# protocol = "jsonl"
+
+# Claude: How the Claude CLI is run (see README)
+# - binary: Path to the claude binary (default: claude on the PATH)
+# - model: Model for every phase (default: the CLI's default)
+# - args: Extra arguments for every phase
+# - permissions: Permission profile for every phase but review (default: "full",
+#   which skips permission checks). Review defaults to "read_only": reading
+#   code, writing the review file, tests, linters and read-only git commands.
+# [claude.<phase>] tables override model, args and permissions for one phase:
+# spec, story, review, correct, commit, pr_template or pr_review.
+# [claude.profiles.<name>] tables define profiles from allowed tools and Bash
+# command patterns.
+[claude]
+# model = "opus"
+# args = ["--append-system-prompt", "Prefer small, focused changes."]
+#
+# [claude.commit]
+# model = "haiku"
+#
+# [claude.story]
+# permissions = "implement"
+#
+# [claude.profiles.implement]
+# tools = ["Read", "Glob", "Grep", "Edit", "Write"]
+# bash = ["cargo build:*", "cargo test:*", "git status:*", "git diff:*"]
+
+# Bwrap: What agents in a sandbox = "bwrap" can see (see README)
+# - read_only: Extra read-only paths, e.g. toolchains (~ is your home directory)
+# - writable: Extra writable paths, e.g. build caches
+# - network: Whether agents have network access (default: true; Claude needs it)
+[bwrap]
+# read_only = ["~/.local", "~/.cargo", "~/.rustup"]
+# writable = ["~/.cache/sccache"]
+# network = true
 "#;
 
 /// Get the path to the global config file.
@@ -363,6 +1338,11 @@ review = {}
 # - false: Leave changes uncommitted (manual commit required)
 commit = {}
 
+# Commit strategy: When changes are committed (requires commit = true)
+# - "end": One commit once all stories pass (default)
+# - "per_story": A commit after each passing story, review fixes on top
+commit_strategy = "{}"
+
 # Pull request state: Creating pull requests
 # - true: Automatically create a PR after committing
 # - false: Skip PR creation (commits remain on local branch)
@@ -391,17 +1371,266 @@ worktree_path_pattern = "{}"
 # - false: Preserve worktrees for manual inspection/cleanup (default)
 # Note: Failed runs always keep their worktrees. Only applies when worktree = true.
 worktree_cleanup = {}
-"#,
+
+# Parallel stories: Maximum number of independent stories to run at once
+# - 1: Run stories one at a time (default)
+# - N > 1: Run up to N stories whose dependencies are complete in parallel,
+#   each in a temporary worktree, merging them back into the run branch
+# Note: Requires commit = true. Stories with merge conflicts are re-run sequentially.
+max_parallel_stories = {}
+
+# Spec migration backup: Keep the original when an older spec file is upgraded
+# - true: Save the original as <file>.v<old version>.bak (default)
+# - false: Upgrade spec files in place without a backup
+spec_migration_backup = {}
+
+# Story iteration limit: Iterations allowed per story before it counts as failed
+max_iterations_per_story = {}
+
+# Run iteration limit: Story iterations allowed in a whole run
+max_total_iterations = {}
+
+# Story failure policy: What to do when a story errors or hits its iteration limit
+# - "fail": Stop the run (default)
+# - "skip": Give up on the story and continue with stories that don't depend on it
+# - "pause": Pause the run so it can be resumed with `autom8 resume`
+on_story_failure = "{}"
+
+# Claude time limits, in minutes (0 = no limit)
+# A hung Claude process is killed once its phase runs past the limit.
+# A timed-out story iteration counts as failed and is retried on resume.
+# Stories and reviews have no limit by default, so a hung story is never
+# killed unless you opt in here (e.g. 60 and 20) or set kill_on_stall.
+story_timeout_minutes = {}
+review_timeout_minutes = {}
+correct_timeout_minutes = {}
+commit_timeout_minutes = {}
+pr_timeout_minutes = {}
+
+# Stall detection: Minutes without Claude output before a session is flagged
+# as stalled in the monitor (0 = never)
+stall_timeout_minutes = {}
+
+# Kill on stall: Kill a stalled Claude process instead of only flagging it
+# - true: Kill it; a stalled story iteration counts as failed and is retried
+# - false: Only flag it (default)
+kill_on_stall = {}
+
+# Claude retries: Retries for each story iteration, review, correction, commit
+# and PR description after a transient Claude failure (overload, server error,
+# network problem), with exponential backoff (0 = none)
+# A usage limit with a reset time is always waited out, without counting here.
+claude_retry_attempts = {}
+
+# Budgets: Pause the run once it has used this much (0 = no limit)
+# Token counts are input + output tokens; cost is estimated from model pricing.
+# Resuming a paused run starts a fresh budget.
+max_run_tokens = {}
+max_story_tokens = {}
+max_run_cost_usd = {}
+
+# Sandbox: What agent processes are confined to
+# - none: Nothing, they run as you (default)
+# - bwrap: A bubblewrap namespace (Linux only) that only sees the run's
+#   worktree, its git directory, a private /tmp, system directories and the
+#   paths in [bwrap]. autom8 checks that bwrap is installed at startup.
+sandbox = "{}"
+
+# Knowledge: What completed runs learned (files, decisions, patterns) is kept in
+# ~/.config/autom8/<project>/knowledge/ and given to later runs. Entries no run
+# has learned again for this many runs are dropped (0 = keep forever).
+knowledge_decay_runs = {}
+
+# Knowledge budget: Estimated tokens of project knowledge injected into each
+# story prompt (0 = no limit). Over budget, older stories' changes are
+# summarized and the entries least relevant to the story are left out.
+knowledge_context_tokens = {}
+
+# Hooks: Shell commands run at points in a run (see README)
+# Each gets AUTOM8_HOOK, AUTOM8_RUN_ID, AUTOM8_BRANCH and, when known,
+# AUTOM8_STORY_ID, AUTOM8_WORKTREE_PATH and AUTOM8_PR_URL.
+# A failing pre_story, pre_review or pre_commit hook stops the run;
+# on_run_start, post_story, post_pr, on_failure and on_complete only warn.
+[hooks]
+{}
+# Fixtures: Record every Claude session (output, prompt and file edits) to a
+# directory, or replay recorded sessions instead of running Claude.
+# The AUTOM8_RECORD_DIR and AUTOM8_REPLAY_DIR environment variables win.
+[fixtures]
+{}
+# Agent: The coding agent CLI to run (see README)
+# - backend = "claude": The Claude CLI (default)
+# - backend = "command": Run `command` with `args`; {{prompt}} in an argument is
+#   replaced with the prompt, otherwise the prompt goes to stdin
+# protocol is what the command writes: "stream_json" (Claude's format,
+# default), "jsonl" (one {{"type": "text"|"tool"|"usage"|"error"}} per line)
+# or "text"
+[agent]
+{}
+# Claude: How the Claude CLI is run (see README)
+# - binary: Path to the claude binary (default: claude on the PATH)
+# - model: Model for every phase (default: the CLI's default)
+# - args: Extra arguments for every phase
+# - permissions: Permission profile for every phase but review (default: "full",
+#   which skips permission checks). Review defaults to "read_only": reading
+#   code, writing the review file, tests, linters and read-only git commands.
+# [claude.<phase>] tables override model, args and permissions for one phase:
+# spec, story, review, correct, commit, pr_template or pr_review.
+# [claude.profiles.<name>] tables define profiles from allowed tools and Bash
+# command patterns.
+[claude]
+{}
+# Bwrap: What agents in a sandbox = "bwrap" can see (see README)
+# - read_only: Extra read-only paths, e.g. toolchains (~ is your home directory)
+# - writable: Extra writable paths, e.g. build caches
+# - network: Whether agents have network access (default: true; Claude needs it)
+[bwrap]
+{}"#,
         config.review,
         config.commit,
+        config.commit_strategy,
         config.pull_request,
         config.pull_request_draft,
         config.worktree,
         config.worktree_path_pattern,
-        config.worktree_cleanup
+        config.worktree_cleanup,
+        config.max_parallel_stories,
+        config.spec_migration_backup,
+        config.max_iterations_per_story,
+        config.max_total_iterations,
+        config.on_story_failure,
+        config.story_timeout_minutes,
+        config.review_timeout_minutes,
+        config.correct_timeout_minutes,
+        config.commit_timeout_minutes,
+        config.pr_timeout_minutes,
+        config.stall_timeout_minutes,
+        config.kill_on_stall,
+        config.claude_retry_attempts,
+        config.max_run_tokens,
+        config.max_story_tokens,
+        config.max_run_cost_usd,
+        config.sandbox,
+        config.knowledge_decay_runs,
+        config.knowledge_context_tokens,
+        hooks_toml(&config.hooks),
+        fixtures_toml(&config.fixtures),
+        agent_toml(&config.agent),
+        claude_toml(&config.claude),
+        bwrap_toml(&config.bwrap)
     )
 }
 
+/// Examples written to an empty `[hooks]` table.
+const HOOK_EXAMPLES: &str = r#"# pre_story = "docker compose up -d db"
+# pre_commit = "cargo fmt"
+# post_pr = "./scripts/notify.sh \"$AUTOM8_PR_URL\""
+"#;
+
+/// The body of the `[hooks]` table, or commented-out examples if none are set.
+fn hooks_toml(hooks: &HooksConfig) -> String {
+    let entries = hooks.entries();
+    if entries.is_empty() {
+        return HOOK_EXAMPLES.to_string();
+    }
+    entries
+        .into_iter()
+        .map(|(key, command)| format!("{} = {}\n", key, toml::Value::String(command.to_string())))
+        .collect()
+}
+
+/// Examples written to an empty `[fixtures]` table.
+const FIXTURE_EXAMPLES: &str = r#"# record = "fixtures/my-feature"
+# replay = "fixtures/my-feature"
+"#;
+
+/// The body of the `[fixtures]` table, or commented-out examples if none are set.
+fn fixtures_toml(fixtures: &FixturesConfig) -> String {
+    let entries = fixtures.entries();
+    if entries.is_empty() {
+        return FIXTURE_EXAMPLES.to_string();
+    }
+    entries
+        .into_iter()
+        .map(|(key, dir)| format!("{} = {}\n", key, toml::Value::String(dir.to_string())))
+        .collect()
+}
+
+/// Examples written to an empty `[agent]` table.
+const AGENT_EXAMPLES: &str = r#"# backend = "command"
+# command = "my-agent"
+# args = ["run", "--json"]
+# protocol = "jsonl"
+"#;
+
+/// The body of the `[agent]` table, or commented-out examples if it has
+/// only defaults.
+fn agent_toml(agent: &AgentConfig) -> String {
+    let entries = agent.entries();
+    if entries.is_empty() {
+        return AGENT_EXAMPLES.to_string();
+    }
+    entries
+        .into_iter()
+        .map(|(key, value)| format!("{} = {}\n", key, value))
+        .collect()
+}
+
+/// Examples written to an empty `[bwrap]` table.
+const BWRAP_EXAMPLES: &str = r#"# read_only = ["~/.local", "~/.cargo", "~/.rustup"]
+# writable = ["~/.cache/sccache"]
+# network = true
+"#;
+
+/// The body of the `[bwrap]` table, or commented-out examples if nothing
+/// differs from the defaults.
+fn bwrap_toml(bwrap: &BwrapConfig) -> String {
+    let entries = bwrap.entries();
+    if entries.is_empty() {
+        return BWRAP_EXAMPLES.to_string();
+    }
+    entries
+        .into_iter()
+        .map(|(key, value)| format!("{} = {}\n", key, value))
+        .collect()
+}
+
+/// Examples written to an empty `[claude]` table.
+const CLAUDE_EXAMPLES: &str = r#"# model = "opus"
+# args = ["--append-system-prompt", "Prefer small, focused changes."]
+#
+# [claude.commit]
+# model = "haiku"
+#
+# [claude.story]
+# permissions = "implement"
+#
+# [claude.profiles.implement]
+# tools = ["Read", "Glob", "Grep", "Edit", "Write"]
+# bash = ["cargo build:*", "cargo test:*", "git status:*", "git diff:*"]
+"#;
+
+/// The body of the `[claude]` table and its `[claude.<phase>]` tables, or
+/// commented-out examples if nothing is set.
+fn claude_toml(claude: &ClaudeConfig) -> String {
+    let entries = claude.entries();
+    let subtables = claude.subtables();
+    if entries.is_empty() && subtables.is_empty() {
+        return CLAUDE_EXAMPLES.to_string();
+    }
+    let mut out: String = entries
+        .into_iter()
+        .map(|(key, value)| format!("{} = {}\n", key, value))
+        .collect();
+    for (table, entries) in subtables {
+        out.push_str(&format!("\n[{}]\n", table));
+        for (key, value) in entries {
+            out.push_str(&format!("{} = {}\n", key, value));
+        }
+    }
+    out
+}
+
 // ============================================================================
 // Project Config File Management
 // ============================================================================
@@ -954,7 +2183,7 @@ pub fn list_projects_tree() -> Result<Vec<ProjectTreeInfo>> {
                     let path = entry.path();
                     if path.extension().is_some_and(|e| e == "json") {
                         specs.push(path.clone());
-                        if let Ok(spec) = Spec::load(&path) {
+                        if let Ok(spec) = Spec::read(&path) {
                             if spec.is_incomplete() {
                                 incomplete_count += 1;
                             }
@@ -1000,7 +2229,7 @@ pub fn list_projects_tree() -> Result<Vec<ProjectTreeInfo>> {
             }
         }
         // Sort by start date, newest first
-        archived_runs.sort_by(|a, b| b.started_at.cmp(&a.started_at));
+        archived_runs.sort_by_key(|r| std::cmp::Reverse(r.started_at));
         let runs_count = archived_runs.len();
 
         // Determine last run date from archived runs or current run.
@@ -1077,6 +2306,16 @@ pub struct SpecSummary {
     pub is_active: bool,
 }
 
+impl SpecSummary {
+    /// Number of stories in the given state.
+    pub fn count_in_state(&self, state: StoryState) -> usize {
+        self.stories
+            .iter()
+            .filter(|s| s.status.state == state)
+            .count()
+    }
+}
+
 /// Summary of a user story.
 #[derive(Debug, Clone)]
 pub struct StorySummary {
@@ -1084,8 +2323,8 @@ pub struct StorySummary {
     pub id: String,
     /// Story title.
     pub title: String,
-    /// Whether the story passes.
-    pub passes: bool,
+    /// Where the story is in its lifecycle, and why.
+    pub status: StoryStatus,
 }
 
 /// Check if a project exists in the config directory.
@@ -1124,18 +2363,18 @@ pub fn get_project_description(project_name: &str) -> Result<Option<ProjectDescr
     let mut specs = Vec::new();
 
     for spec_path in spec_paths {
-        if let Ok(spec) = Spec::load(&spec_path) {
+        if let Ok(spec) = Spec::read(&spec_path) {
             let stories: Vec<StorySummary> = spec
                 .user_stories
                 .iter()
                 .map(|s| StorySummary {
                     id: s.id.clone(),
                     title: s.title.clone(),
-                    passes: s.passes,
+                    status: s.status.clone(),
                 })
                 .collect();
 
-            let completed_count = stories.iter().filter(|s| s.passes).count();
+            let completed_count = stories.iter().filter(|s| s.status.is_done()).count();
             let total_count = stories.len();
 
             let filename = spec_path
@@ -1225,7 +2464,7 @@ pub fn global_status() -> Result<Vec<ProjectStatus>> {
         let mut total_count = 0;
 
         for spec_path in &specs {
-            if let Ok(spec) = Spec::load(spec_path) {
+            if let Ok(spec) = Spec::read(spec_path) {
                 total_count += 1;
                 if spec.is_incomplete() {
                     incomplete_count += 1;
@@ -1272,7 +2511,7 @@ fn global_status_at(base_config_dir: &std::path::Path) -> Result<Vec<ProjectStat
         let mut total_count = 0;
 
         for spec_path in &specs {
-            if let Ok(spec) = Spec::load(spec_path) {
+            if let Ok(spec) = Spec::read(spec_path) {
                 total_count += 1;
                 if spec.is_incomplete() {
                     incomplete_count += 1;
@@ -2094,6 +3333,13 @@ mod tests {
         assert_eq!(statuses.len(), 1);
         assert_eq!(statuses[0].incomplete_spec_count, 1);
         assert_eq!(statuses[0].total_spec_count, 2);
+
+        // Listing doesn't upgrade older specs on disk
+        assert_eq!(
+            fs::read_to_string(spec_dir.join("spec-test.json")).unwrap(),
+            incomplete_prd
+        );
+        assert!(!spec_dir.join("spec-test.json.v1.bak").exists());
     }
 
     // ========================================================================
@@ -2254,7 +3500,7 @@ mod tests {
             stories: vec![StorySummary {
                 id: "US-001".to_string(),
                 title: "Test Story".to_string(),
-                passes: true,
+                status: StoryStatus::done(),
             }],
             completed_count: 1,
             total_count: 1,
@@ -2275,12 +3521,12 @@ mod tests {
         let story = StorySummary {
             id: "US-001".to_string(),
             title: "Test Story".to_string(),
-            passes: false,
+            status: StoryStatus::default(),
         };
 
         assert_eq!(story.id, "US-001");
         assert_eq!(story.title, "Test Story");
-        assert!(!story.passes);
+        assert!(!story.status.is_done());
     }
 
     // ========================================================================
@@ -2467,6 +3713,203 @@ mod tests {
         assert!(config.commit);
         assert!(config.pull_request);
         assert!(config.worktree);
+        assert_eq!(config.max_parallel_stories, 1);
+    }
+
+    #[test]
+    fn test_generate_config_with_comments_roundtrips_all_fields() {
+        let config = Config {
+            review: false,
+            worktree_path_pattern: "{repo}-{branch}".to_string(),
+            max_parallel_stories: 4,
+            spec_migration_backup: false,
+            max_iterations_per_story: 3,
+            max_total_iterations: 20,
+            commit_strategy: CommitStrategy::PerStory,
+            on_story_failure: StoryFailurePolicy::Skip,
+            story_timeout_minutes: 0,
+            pr_timeout_minutes: 5,
+            stall_timeout_minutes: 3,
+            kill_on_stall: true,
+            claude_retry_attempts: 0,
+            max_run_tokens: 2_000_000,
+            max_story_tokens: 250_000,
+            max_run_cost_usd: 12.5,
+            hooks: HooksConfig {
+                pre_commit: Some("cargo fmt".to_string()),
+                post_pr: Some("./notify.sh \"$AUTOM8_PR_URL\" 'opened'".to_string()),
+                ..Default::default()
+            },
+            fixtures: FixturesConfig {
+                record: Some("fixtures/auth".to_string()),
+                replay: None,
+            },
+            agent: AgentConfig {
+                backend: AgentKind::Command,
+                command: Some("my-agent".to_string()),
+                args: vec!["run".to_string(), "--prompt={prompt}".to_string()],
+                protocol: LineProtocol::Jsonl,
+            },
+            claude: ClaudeConfig {
+                binary: Some("/opt/claude/bin/claude".to_string()),
+                model: Some("opus".to_string()),
+                args: vec!["--verbose".to_string()],
+                permissions: Some("implement".to_string()),
+                profiles: [(
+                    "implement".to_string(),
+                    PermissionProfile {
+                        tools: vec!["Read".to_string(), "Edit".to_string()],
+                        bash: vec!["cargo test:*".to_string()],
+                    },
+                )]
+                .into(),
+                commit: ClaudePhaseConfig {
+                    model: Some("haiku".to_string()),
+                    args: Some(Vec::new()),
+                    permissions: Some("full".to_string()),
+                },
+                ..Default::default()
+            },
+            sandbox: SandboxKind::Bwrap,
+            knowledge_decay_runs: 0,
+            knowledge_context_tokens: 0,
+            bwrap: BwrapConfig {
+                read_only: vec!["~/.cargo".to_string(), "/opt/node".to_string()],
+                writable: vec!["~/.cache/sccache".to_string()],
+                network: false,
+            },
+            ..Default::default()
+        };
+        let content = generate_config_with_comments(&config);
+        let parsed: Config = toml::from_str(&content).unwrap();
+
+        assert_eq!(parsed, config);
+    }
+
+    #[test]
+    fn test_story_failure_policy_parsing() {
+        assert_eq!("skip".parse(), Ok(StoryFailurePolicy::Skip));
+        assert_eq!(" Pause ".parse(), Ok(StoryFailurePolicy::Pause));
+        assert!("retry".parse::<StoryFailurePolicy>().is_err());
+
+        let config: Config = toml::from_str("on_story_failure = \"pause\"").unwrap();
+        assert_eq!(config.on_story_failure, StoryFailurePolicy::Pause);
+        assert_eq!(config.max_iterations_per_story, 10);
+        assert_eq!(config.max_total_iterations, 100);
+        assert!(toml::from_str::<Config>("on_story_failure = \"retry\"").is_err());
+    }
+
+    #[test]
+    fn test_claude_phase_overrides() {
+        let config: Config = toml::from_str(
+            r#"
+[claude]
+model = "opus"
+args = ["--effort", "high"]
+
+[claude.commit]
+model = "haiku"
+args = []
+
+[claude.spec]
+model = "haiku"
+"#,
+        )
+        .unwrap();
+        let claude = &config.claude;
+
+        assert_eq!(claude.binary(), "claude");
+        assert_eq!(claude.model_for(ClaudePhase::Story), Some("opus"));
+        assert_eq!(claude.args_for(ClaudePhase::Story), ["--effort", "high"]);
+        assert_eq!(claude.model_for(ClaudePhase::Commit), Some("haiku"));
+        assert!(claude.args_for(ClaudePhase::Commit).is_empty());
+        // Only the model is overridden, so the default args still apply
+        assert_eq!(claude.model_for(ClaudePhase::Spec), Some("haiku"));
+        assert_eq!(claude.args_for(ClaudePhase::Spec), ["--effort", "high"]);
+
+        let default = ClaudeConfig::default();
+        assert_eq!(default.model_for(ClaudePhase::Review), None);
+    }
+
+    #[test]
+    fn test_claude_permission_profiles() {
+        let default = ClaudeConfig::default();
+        assert_eq!(default.permissions_for(ClaudePhase::Story), None);
+        assert_eq!(
+            default.permissions_for(ClaudePhase::Review),
+            Some(PermissionProfile::read_only())
+        );
+        assert!(PermissionProfile::read_only()
+            .allowed_tools()
+            .contains(&"Bash(cargo test:*)".to_string()));
+
+        let config: Config = toml::from_str(
+            r#"
+[claude]
+permissions = "implement"
+
+[claude.commit]
+permissions = "full"
+
+[claude.profiles.implement]
+tools = ["Read", "Edit"]
+bash = ["cargo build:*"]
+"#,
+        )
+        .unwrap();
+        let claude = &config.claude;
+
+        assert!(validate_config(&config).is_ok());
+        assert_eq!(
+            claude
+                .permissions_for(ClaudePhase::Story)
+                .map(|p| p.allowed_tools()),
+            Some(vec![
+                "Read".to_string(),
+                "Edit".to_string(),
+                "Bash(cargo build:*)".to_string()
+            ])
+        );
+        assert_eq!(claude.permissions_for(ClaudePhase::Commit), None);
+        assert_eq!(
+            claude.permissions_name_for(ClaudePhase::Review),
+            "read_only"
+        );
+
+        let unknown: Config = toml::from_str("[claude.review]\npermissions = \"strict\"").unwrap();
+        assert_eq!(
+            validate_config(&unknown),
+            Err(ConfigError::UnknownPermissionProfile("strict".to_string()))
+        );
+        let reserved: Config = toml::from_str("[claude.profiles.full]\ntools = []").unwrap();
+        assert_eq!(
+            validate_config(&reserved),
+            Err(ConfigError::ReservedPermissionProfile)
+        );
+    }
+
+    #[test]
+    fn test_commit_strategy_parsing() {
+        assert_eq!("per_story".parse(), Ok(CommitStrategy::PerStory));
+        assert_eq!(" End ".parse(), Ok(CommitStrategy::End));
+        assert!("per_commit".parse::<CommitStrategy>().is_err());
+
+        let config: Config = toml::from_str("commit_strategy = \"per_story\"").unwrap();
+        assert_eq!(config.commit_strategy, CommitStrategy::PerStory);
+        assert_eq!(Config::default().commit_strategy, CommitStrategy::End);
+    }
+
+    #[test]
+    fn test_timeouts_default_and_zero_disables() {
+        let config: Config = toml::from_str("story_timeout_minutes = 60").unwrap();
+        assert_eq!(config.review_timeout_minutes, 0);
+        assert_eq!(config.correct_timeout_minutes, 30);
+        assert_eq!(timeout_from_minutes(config.review_timeout_minutes), None);
+        assert_eq!(
+            timeout_from_minutes(config.story_timeout_minutes),
+            Some(Duration::from_secs(3600))
+        );
+        assert_eq!(Config::default().story_timeout_minutes, 0);
     }
 
     #[test]
@@ -0,0 +1,232 @@
+//! Dry runs.
+//!
+//! `autom8 run --dry-run` renders the prompts a run would send, in the order
+//! it would send them, without starting Claude or touching git. This is for
+//! checking what a spec (and the project's knowledge) turns into before
+//! paying for a run.
+//!
+//! A dry run assumes every story passes on its first attempt and the review
+//! comes back clean, so each story gets one prompt and the review phase one
+//! reviewer and one corrector prompt. Later story prompts don't include what
+//! a real run would have learned from earlier stories by then.
+
+use std::path::Path;
+
+use crate::claude::{
+    build_commit_prompt, build_corrector_prompt, build_reviewer_prompt, build_story_prompt,
+    estimate_tokens,
+};
+use crate::config::{token_limit, Config};
+use crate::gh::{build_template_prompt, format_pr_title};
+use crate::knowledge::ProjectKnowledge;
+use crate::runner::MAX_REVIEW_ITERATIONS;
+use crate::spec::Spec;
+
+/// A prompt as it would be sent to Claude.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RenderedPrompt {
+    /// What the prompt is for, e.g. `Story US-002` or `Reviewer`.
+    pub label: String,
+    /// File name used when prompts are written to a directory.
+    pub file_name: String,
+    pub text: String,
+}
+
+impl RenderedPrompt {
+    fn new(index: usize, label: String, slug: &str, text: String) -> Self {
+        Self {
+            label,
+            file_name: format!("{:02}-{}.md", index, slug),
+            text,
+        }
+    }
+
+    /// Rough prompt size in tokens, at about four characters per token.
+    pub fn estimated_tokens(&self) -> u64 {
+        estimate_tokens(&self.text)
+    }
+}
+
+/// The prompts a run of `spec` would send, in order.
+///
+/// Stories that already pass are skipped, as in a real run. Review, commit
+/// and PR prompts follow `config`; the PR prompt is only sent when the
+/// repository has a PR template (`pr_template`), otherwise the PR body is
+/// written without Claude.
+pub fn run_prompts(
+    spec: &Spec,
+    spec_path: &Path,
+    config: &Config,
+    knowledge: &ProjectKnowledge,
+    pr_template: Option<&str>,
+) -> Vec<RenderedPrompt> {
+    let mut prompts = Vec::new();
+    let mut push = |label: String, slug: String, text: String| {
+        let index = prompts.len() + 1;
+        prompts.push(RenderedPrompt::new(index, label, &slug, text));
+    };
+
+    let mut remaining = spec.clone();
+    while let Some(story) = remaining.next_incomplete_story().cloned() {
+        push(
+            format!("Story {}: {}", story.id, story.title),
+            format!("story-{}", story.id),
+            build_story_prompt(
+                spec,
+                &story,
+                spec_path,
+                &[],
+                knowledge,
+                token_limit(config.knowledge_context_tokens),
+            ),
+        );
+        remaining.mark_story_complete(&story.id);
+    }
+
+    if config.review {
+        push(
+            "Reviewer".to_string(),
+            "reviewer".to_string(),
+            build_reviewer_prompt(spec, 1, MAX_REVIEW_ITERATIONS),
+        );
+        push(
+            "Corrector (only if the review finds issues)".to_string(),
+            "corrector".to_string(),
+            build_corrector_prompt(spec, 1, MAX_REVIEW_ITERATIONS),
+        );
+    }
+
+    if config.commit {
+        push(
+            "Commit".to_string(),
+            "commit".to_string(),
+            build_commit_prompt(spec),
+        );
+
+        if config.pull_request {
+            if let Some(template) = pr_template {
+                push(
+                    "PR template".to_string(),
+                    "pr-template".to_string(),
+                    build_template_prompt(
+                        spec,
+                        &[],
+                        template,
+                        &format_pr_title(spec),
+                        None,
+                        config.pull_request_draft,
+                    ),
+                );
+            }
+        }
+    }
+
+    prompts
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::spec::{StoryStatus, UserStory, SPEC_SCHEMA_VERSION};
+
+    fn make_story(id: &str, priority: u32) -> UserStory {
+        UserStory {
+            id: id.to_string(),
+            title: format!("Title of {}", id),
+            description: format!("Description for {}", id),
+            acceptance_criteria: vec!["Works".to_string()],
+            priority,
+            status: StoryStatus::default(),
+            notes: String::new(),
+            depends_on: Vec::new(),
+            verify: None,
+        }
+    }
+
+    fn make_spec() -> Spec {
+        let mut spec = Spec {
+            schema_version: SPEC_SCHEMA_VERSION,
+            project: "demo".to_string(),
+            branch_name: "feature/demo".to_string(),
+            description: "Demo feature".to_string(),
+            user_stories: vec![
+                make_story("US-001", 1),
+                make_story("US-002", 3),
+                make_story("US-003", 2),
+            ],
+            verify: Vec::new(),
+        };
+        spec.mark_story_complete("US-001");
+        spec
+    }
+
+    fn file_names(prompts: &[RenderedPrompt]) -> Vec<&str> {
+        prompts.iter().map(|p| p.file_name.as_str()).collect()
+    }
+
+    #[test]
+    fn test_prompts_follow_run_order_and_config() {
+        let spec = make_spec();
+        let config = Config::default();
+        let prompts = run_prompts(
+            &spec,
+            Path::new("spec.json"),
+            &config,
+            &ProjectKnowledge::default(),
+            Some("## Summary"),
+        );
+
+        // US-001 already passes; US-003 has the higher priority
+        assert_eq!(
+            file_names(&prompts),
+            vec![
+                "01-story-US-003.md",
+                "02-story-US-002.md",
+                "03-reviewer.md",
+                "04-corrector.md",
+                "05-commit.md",
+                "06-pr-template.md",
+            ]
+        );
+        assert!(prompts[0].text.contains("Description for US-003"));
+        assert!(prompts[5].text.contains("## Summary"));
+    }
+
+    #[test]
+    fn test_disabled_phases_are_left_out() {
+        let spec = make_spec();
+        let config = Config {
+            review: false,
+            pull_request: false,
+            ..Default::default()
+        };
+        let prompts = run_prompts(
+            &spec,
+            Path::new("spec.json"),
+            &config,
+            &ProjectKnowledge::default(),
+            Some("## Summary"),
+        );
+        assert_eq!(
+            file_names(&prompts),
+            vec!["01-story-US-003.md", "02-story-US-002.md", "03-commit.md"]
+        );
+
+        // Without a template the PR body is generated without Claude
+        let config = Config::default();
+        let prompts = run_prompts(
+            &spec,
+            Path::new("spec.json"),
+            &config,
+            &ProjectKnowledge::default(),
+            None,
+        );
+        assert_eq!(prompts.last().unwrap().file_name, "05-commit.md");
+    }
+
+    #[test]
+    fn test_estimated_tokens() {
+        let prompt = RenderedPrompt::new(1, "x".into(), "x", "abcdefghi".into());
+        assert_eq!(prompt.estimated_tokens(), 3);
+    }
+}
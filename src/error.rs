@@ -9,15 +9,34 @@ pub enum Autom8Error {
     #[error("Invalid spec format: {0}\n\nThe spec file exists but cannot be parsed.\n\nTo fix this:\n  1. Ensure the file is valid JSON or Markdown\n  2. Check for syntax errors (missing commas, brackets, etc.)\n  3. See CLAUDE.md for spec format requirements")]
     InvalidSpec(String),
 
-    #[error("No incomplete stories found in spec\n\nAll user stories in the spec have passes: true.\n\nTo continue:\n  1. Add new user stories to the spec, or\n  2. Set passes: false on stories you want to re-implement")]
+    #[error("Spec file {path} uses schema version {version}, but this autom8 only supports up to version {supported}\n\nThe spec was written by a newer version of autom8.\n\nTo fix this:\n  1. Upgrade autom8: cargo install autom8-cli\n  2. Or restore the spec from a backup written by this version")]
+    UnsupportedSpecVersion {
+        path: PathBuf,
+        version: u32,
+        supported: u32,
+    },
+
+    #[error("No incomplete stories found in spec\n\nAll user stories in the spec are done.\n\nTo continue:\n  1. Add new user stories: autom8 spec add-story --title <title>, or\n  2. Reset stories you want to re-implement: autom8 spec reset <ID>")]
     NoIncompleteStories,
 
+    #[error("Story {story_id} not found in {path}\n\nThe spec has no story with that ID.\n\nTo fix this:\n  1. List the spec's stories: autom8 spec show\n  2. Check you are editing the right spec (use --spec <file>)")]
+    StoryNotFound { story_id: String, path: PathBuf },
+
+    #[error("Several specs found: {0}\n\nautom8 can't tell which spec to use.\n\nTo fix this:\n  1. Pick one with --spec <file>, e.g. autom8 spec show --spec spec-auth.json\n  2. Or start a run for the spec you want: commands then use the active run's spec")]
+    AmbiguousSpec(String),
+
+    #[error("Story {0} is being implemented by a running session\n\nRemoving it now would leave the run working on a story that no longer exists.\n\nTo fix this:\n  1. Wait for the story to finish, or\n  2. Stop the run (Ctrl+C), then remove the story, or\n  3. Remove it anyway with --force")]
+    StoryInProgress(String),
+
     #[error("Claude process failed: {0}")]
     ClaudeError(String),
 
     #[error("Claude process timed out after {0} seconds")]
     ClaudeTimeout(u64),
 
+    #[error("Claude produced no output for {0} seconds")]
+    ClaudeStalled(u64),
+
     #[error("State file error: {0}")]
     StateError(String),
 
@@ -54,7 +73,13 @@ pub enum Autom8Error {
     #[error("Review failed after 3 iterations. Please manually review autom8_review.md for remaining issues.")]
     MaxReviewIterationsReached,
 
-    #[error("No incomplete specs found in spec/\n\nNo spec files with incomplete user stories were found.\n\nTo start a new run:\n  1. Run 'autom8' to create a new spec interactively, or\n  2. Add a spec file to ~/.config/autom8/<project>/spec/, or\n  3. Set passes: false on stories you want to re-implement")]
+    #[error("Story {story_id} still doesn't pass after {iterations} iterations\n\nautom8 stops retrying a story once it reaches max_iterations_per_story.\n\nTo fix this:\n  1. Check the story's acceptance criteria and verification commands\n  2. Raise the limit: autom8 config set max_iterations_per_story <n>\n  3. Or continue with other stories instead: autom8 config set on_story_failure skip")]
+    StoryIterationLimit { story_id: String, iterations: u32 },
+
+    #[error("Run stopped after {0} iterations\n\nThe run reached max_total_iterations before all stories passed.\n\nTo fix this:\n  1. Check the run summary for stories that kept failing\n  2. Raise the limit: autom8 config set max_total_iterations <n>\n  3. Resume the run: autom8 resume")]
+    MaxIterationsReached(u32),
+
+    #[error("No incomplete specs found in spec/\n\nNo spec files with incomplete user stories were found.\n\nTo start a new run:\n  1. Run 'autom8' to create a new spec interactively, or\n  2. Add a spec file to ~/.config/autom8/<project>/spec/, or\n  3. Reset stories you want to re-implement: autom8 spec reset <ID>")]
     NoSpecsToResume,
 
     #[error("Shell completion error: {0}")]
@@ -76,6 +101,18 @@ pub enum Autom8Error {
     #[error("Interrupted by user")]
     Interrupted,
 
+    #[error("Can't roll back: {0}\n\nTo fix this:\n  1. Check the story ID with: autom8 spec show\n  2. To keep a story and undo what came after it (--to), run with commit_strategy = \"per_story\" so each story has its own commit\n  3. To only mark a story as not done, without touching git: autom8 spec reset <story-id>")]
+    Rollback(String),
+
+    #[error("{0}\n\nA failing pre_story, pre_review or pre_commit hook stops the run before that step.\n\nTo fix this:\n  1. Run the hook command yourself to see why it fails\n  2. Fix it, or change it under [hooks] in the config\n  3. Resume the run: autom8 resume")]
+    HookFailed(String),
+
+    #[error("Session fixture error: {0}\n\nClaude sessions are being recorded or replayed (AUTOM8_RECORD_DIR, AUTOM8_REPLAY_DIR or the [fixtures] config table).\n\nTo fix this:\n  1. Check the fixture directory exists and holds files written by autom8\n  2. If the run no longer matches the recording, record it again with AUTOM8_RECORD_DIR=<dir>\n  3. To run Claude for real, unset AUTOM8_REPLAY_DIR and remove replay from [fixtures]")]
+    Fixture(String),
+
+    #[error("Sandbox error: {0}\n\nWith sandbox = \"bwrap\", agents run inside bubblewrap, which must be installed (Linux only).\n\nTo fix this:\n  1. Install bubblewrap, e.g. apt install bubblewrap or dnf install bubblewrap\n  2. Check that it runs: bwrap --version\n  3. Or run agents unconfined: autom8 config set sandbox none")]
+    Sandbox(String),
+
     #[error("GUI error: {0}")]
     GuiError(String),
 
@@ -179,6 +216,59 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_unsupported_spec_version_error_includes_fix() {
+        let err = Autom8Error::UnsupportedSpecVersion {
+            path: PathBuf::from("/specs/spec-auth.json"),
+            version: 9,
+            supported: 2,
+        };
+        let msg = err.to_string();
+
+        assert!(msg.contains("/specs/spec-auth.json"));
+        assert!(msg.contains("version 9"));
+        assert!(msg.contains("up to version 2"));
+        assert!(msg.contains("To fix"), "Error should include fix steps");
+        assert!(
+            msg.contains("Upgrade autom8"),
+            "Error should suggest upgrading"
+        );
+    }
+
+    #[test]
+    fn test_story_iteration_limit_error_includes_fix() {
+        let err = Autom8Error::StoryIterationLimit {
+            story_id: "US-003".to_string(),
+            iterations: 10,
+        };
+        let msg = err.to_string();
+
+        assert!(msg.contains("US-003"));
+        assert!(msg.contains("after 10 iterations"));
+        assert!(msg.contains("To fix"), "Error should include fix steps");
+        assert!(msg.contains("max_iterations_per_story"));
+        assert!(msg.contains("on_story_failure skip"));
+    }
+
+    #[test]
+    fn test_spec_editing_errors_include_fix() {
+        let err = Autom8Error::StoryNotFound {
+            story_id: "US-009".to_string(),
+            path: PathBuf::from("spec-auth.json"),
+        };
+        let msg = err.to_string();
+        assert!(msg.contains("US-009") && msg.contains("spec-auth.json"));
+        assert!(msg.contains("autom8 spec show"));
+
+        let msg = Autom8Error::AmbiguousSpec("spec-a.json, spec-b.json".to_string()).to_string();
+        assert!(msg.contains("spec-a.json, spec-b.json"));
+        assert!(msg.contains("--spec"));
+
+        let msg = Autom8Error::StoryInProgress("US-002".to_string()).to_string();
+        assert!(msg.contains("US-002"));
+        assert!(msg.contains("--force"));
+    }
+
     #[test]
     fn test_us012_no_active_run_error_includes_fix() {
         let err = Autom8Error::NoActiveRun;
@@ -228,7 +318,7 @@ mod tests {
             "Error should include fix steps"
         );
         assert!(
-            msg.contains("passes: false"),
+            msg.contains("autom8 spec reset"),
             "Error should suggest how to re-run stories"
         );
     }
@@ -87,7 +87,7 @@ pub fn find_spec_for_branch(branch: &str) -> Result<Option<(Spec, PathBuf)>> {
 
     // First, try to find a spec that matches the branch name
     for spec_path in &specs {
-        if let Ok(spec) = Spec::load(spec_path) {
+        if let Ok(spec) = Spec::read(spec_path) {
             if spec.branch_name == branch {
                 return Ok(Some((spec, spec_path.clone())));
             }
@@ -108,7 +108,7 @@ pub fn find_spec_for_branch(branch: &str) -> Result<Option<(Spec, PathBuf)>> {
         if filename.ends_with(branch_suffix)
             || filename.replace("spec-", "").contains(branch_suffix)
         {
-            if let Ok(spec) = Spec::load(spec_path) {
+            if let Ok(spec) = Spec::read(spec_path) {
                 return Ok(Some((spec, spec_path.clone())));
             }
         }
@@ -1,6 +1,7 @@
 //! PR title and description formatting.
 
 use crate::spec::Spec;
+use crate::state::FailedStory;
 
 /// Maximum length for PR titles (GitHub standard)
 const PR_TITLE_MAX_LENGTH: usize = 72;
@@ -55,15 +56,23 @@ fn truncate_with_ellipsis(text: &str, max_len: usize) -> String {
 }
 
 /// Format a Spec into a well-structured GitHub PR description in Markdown format
-pub fn format_pr_description(spec: &Spec) -> String {
+///
+/// Stories in `failed_stories` are listed under their own heading with the
+/// reason the run gave up on them.
+pub fn format_pr_description(spec: &Spec, failed_stories: &[FailedStory]) -> String {
     let mut output = String::new();
 
     output.push_str("## Summary\n\n");
     output.push_str(&spec.description);
     output.push_str("\n\n");
 
-    let completed: Vec<_> = spec.user_stories.iter().filter(|s| s.passes).collect();
-    let incomplete: Vec<_> = spec.user_stories.iter().filter(|s| !s.passes).collect();
+    let completed: Vec<_> = spec.user_stories.iter().filter(|s| s.passes()).collect();
+    let is_failed = |id: &str| failed_stories.iter().any(|f| f.story_id == id);
+    let incomplete: Vec<_> = spec
+        .user_stories
+        .iter()
+        .filter(|s| !s.passes() && !is_failed(&s.id))
+        .collect();
 
     if completed.is_empty() {
         output.push_str("## Changes\n\n");
@@ -84,6 +93,23 @@ pub fn format_pr_description(spec: &Spec) -> String {
         }
     }
 
+    if !failed_stories.is_empty() {
+        output.push_str("## Skipped\n\n");
+        output.push_str("These stories were not implemented:\n\n");
+        for failed in failed_stories {
+            let title = spec
+                .user_stories
+                .iter()
+                .find(|s| s.id == failed.story_id)
+                .map(|s| s.title.as_str())
+                .unwrap_or_default();
+            output.push_str(&format!(
+                "- **{}: {}** - {}\n",
+                failed.story_id, title, failed.reason
+            ));
+        }
+    }
+
     output.trim_end().to_string()
 }
 
@@ -95,7 +121,7 @@ fn format_story(output: &mut String, story: &crate::spec::UserStory) {
 
     if !story.acceptance_criteria.is_empty() {
         output.push_str("**Acceptance Criteria:**\n\n");
-        let checkbox = if story.passes { "[x]" } else { "[ ]" };
+        let checkbox = if story.passes() { "[x]" } else { "[ ]" };
         for criterion in &story.acceptance_criteria {
             output.push_str(&format!("- {} {}\n", checkbox, criterion));
         }
@@ -112,14 +138,17 @@ fn format_story(output: &mut String, story: &crate::spec::UserStory) {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::spec::StoryStatus;
 
     #[test]
     fn test_format_pr_title_simple() {
         let spec = Spec {
+            schema_version: crate::spec::SPEC_SCHEMA_VERSION,
             project: "TestApp".into(),
             description: "Add user authentication.".into(),
             branch_name: "feature/auth".into(),
             user_stories: vec![],
+            verify: Vec::new(),
         };
         let title = format_pr_title(&spec);
         assert_eq!(title, "[TestApp] Add user authentication.");
@@ -128,13 +157,54 @@ mod tests {
     #[test]
     fn test_format_pr_title_truncation() {
         let spec = Spec {
+            schema_version: crate::spec::SPEC_SCHEMA_VERSION,
             project: "TestApp".into(),
             description: "This is a very long description that exceeds the maximum GitHub title length and should be truncated.".into(),
             branch_name: "feature/test".into(),
             user_stories: vec![],
+            verify: Vec::new(),
         };
         let title = format_pr_title(&spec);
         assert!(title.len() <= 72);
         assert!(title.ends_with("..."));
     }
+
+    #[test]
+    fn test_format_pr_description_lists_failed_stories() {
+        let story = |id: &str, passes: bool| crate::spec::UserStory {
+            id: id.into(),
+            title: format!("Story {}", id),
+            description: String::new(),
+            acceptance_criteria: vec![],
+            priority: 1,
+            status: if passes {
+                StoryStatus::done()
+            } else {
+                StoryStatus::default()
+            },
+            notes: String::new(),
+            depends_on: vec![],
+            verify: None,
+        };
+        let spec = Spec {
+            schema_version: crate::spec::SPEC_SCHEMA_VERSION,
+            project: "TestApp".into(),
+            description: "Add user authentication.".into(),
+            branch_name: "feature/auth".into(),
+            user_stories: vec![story("US-001", true), story("US-002", false)],
+            verify: Vec::new(),
+        };
+        let failed = vec![FailedStory {
+            story_id: "US-002".into(),
+            reason: "still not passing after 10 iterations".into(),
+        }];
+        let body = format_pr_description(&spec, &failed);
+
+        assert!(body.contains("## Completed"));
+        assert!(!body.contains("## Remaining"));
+        assert!(body.contains(
+            "## Skipped\n\nThese stories were not implemented:\n\n\
+             - **US-002: Story US-002** - still not passing after 10 iterations"
+        ));
+    }
 }
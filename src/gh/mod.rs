@@ -36,7 +36,7 @@ pub use pr::{
     update_pr_description,
 };
 pub use template::{
-    build_gh_command, detect_pr_template, extract_pr_url, format_spec_for_template,
-    run_template_agent, TemplateAgentResult,
+    build_gh_command, build_template_prompt, detect_pr_template, extract_pr_url,
+    format_spec_for_template, run_template_agent, TemplateAgentResult,
 };
 pub use types::{PRDetectionResult, PRResult, PullRequestInfo};
@@ -2,12 +2,14 @@
 
 use std::process::Command;
 
+use crate::claude::{ClaudeErrorInfo, ClaudeRunner};
 use crate::error::Result;
 use crate::git::{self, PushResult};
 use crate::output::{
     print_push_already_up_to_date, print_push_success, print_pushing_branch, print_warning,
 };
 use crate::spec::Spec;
+use crate::state::FailedStory;
 
 use super::detection::{get_existing_pr_number, get_existing_pr_url, pr_exists_for_branch};
 use super::format::{format_pr_description, format_pr_title};
@@ -33,20 +35,31 @@ pub fn is_gh_authenticated() -> bool {
 }
 
 /// Update the description of an existing pull request
-pub fn update_pr_description(spec: &Spec, pr_number: u32) -> Result<PRResult> {
+///
+/// `runner` runs the template agent, if the repository has a PR template,
+/// and `retry` decides whether it runs again after a failure.
+pub fn update_pr_description(
+    spec: &Spec,
+    failed_stories: &[FailedStory],
+    pr_number: u32,
+    runner: &ClaudeRunner,
+    retry: &mut dyn FnMut(&ClaudeErrorInfo) -> bool,
+) -> Result<PRResult> {
     // Check for PR template in the repository
     let repo_root = std::env::current_dir().unwrap_or_default();
     if let Some(template_content) = detect_pr_template(&repo_root) {
         // Template found - use agent path
         let title = format_pr_title(spec);
         // Draft flag is not applicable when updating existing PRs
-        match run_template_agent(
+        match run_template_agent_with_retries(
             spec,
+            failed_stories,
             &template_content,
             &title,
             Some(pr_number),
             false,
-            |_| {},
+            runner,
+            retry,
         ) {
             Ok(TemplateAgentResult::Success(url)) => {
                 return Ok(PRResult::Updated(url));
@@ -69,12 +82,16 @@ pub fn update_pr_description(spec: &Spec, pr_number: u32) -> Result<PRResult> {
     }
 
     // No template or agent failed - use current generated description path
-    update_pr_description_direct(spec, pr_number)
+    update_pr_description_direct(spec, failed_stories, pr_number)
 }
 
 /// Update PR description directly using generated format (internal fallback)
-fn update_pr_description_direct(spec: &Spec, pr_number: u32) -> Result<PRResult> {
-    let body = format_pr_description(spec);
+fn update_pr_description_direct(
+    spec: &Spec,
+    failed_stories: &[FailedStory],
+    pr_number: u32,
+) -> Result<PRResult> {
+    let body = format_pr_description(spec, failed_stories);
 
     let output = Command::new("gh")
         .args(["pr", "edit", &pr_number.to_string(), "--body", &body])
@@ -105,7 +122,19 @@ fn update_pr_description_direct(spec: &Spec, pr_number: u32) -> Result<PRResult>
 }
 
 /// Create a pull request for the current branch using the GitHub CLI
-pub fn create_pull_request(spec: &Spec, commits_were_made: bool, draft: bool) -> Result<PRResult> {
+///
+/// `failed_stories` are the stories the run gave up on; they're listed
+/// separately in the PR description. `runner` runs the template agent, with
+/// its time limit; if it fails, the generated description is used instead. `retry`
+/// decides whether the agent runs again after a failure, e.g. an overloaded API.
+pub fn create_pull_request(
+    spec: &Spec,
+    failed_stories: &[FailedStory],
+    commits_were_made: bool,
+    draft: bool,
+    runner: &ClaudeRunner,
+    retry: &mut dyn FnMut(&ClaudeErrorInfo) -> bool,
+) -> Result<PRResult> {
     if !commits_were_made {
         return Ok(PRResult::Skipped(
             "No commits were made in this session".to_string(),
@@ -149,7 +178,7 @@ pub fn create_pull_request(spec: &Spec, commits_were_made: bool, draft: bool) ->
     if pr_exists_for_branch(&branch)? {
         // PR exists - update description instead
         if let Some(pr_number) = get_existing_pr_number(&branch)? {
-            return update_pr_description(spec, pr_number);
+            return update_pr_description(spec, failed_stories, pr_number, runner, retry);
         } else if let Some(url) = get_existing_pr_url(&branch)? {
             return Ok(PRResult::AlreadyExists(url));
         }
@@ -167,7 +196,16 @@ pub fn create_pull_request(spec: &Spec, commits_were_made: bool, draft: bool) ->
     if let Some(template_content) = detect_pr_template(&repo_root) {
         // Template found - use agent path
         let title = format_pr_title(spec);
-        match run_template_agent(spec, &template_content, &title, None, draft, |_| {}) {
+        match run_template_agent_with_retries(
+            spec,
+            failed_stories,
+            &template_content,
+            &title,
+            None,
+            draft,
+            runner,
+            retry,
+        ) {
             Ok(TemplateAgentResult::Success(url)) => {
                 return Ok(PRResult::Success(url));
             }
@@ -189,7 +227,40 @@ pub fn create_pull_request(spec: &Spec, commits_were_made: bool, draft: bool) ->
     }
 
     // No template or agent failed - use current generated description path
-    create_pull_request_direct(spec, draft)
+    create_pull_request_direct(spec, failed_stories, draft)
+}
+
+/// Run the template agent, running it again for as long as `retry` asks to
+/// after a failure.
+#[allow(clippy::too_many_arguments)]
+fn run_template_agent_with_retries(
+    spec: &Spec,
+    failed_stories: &[FailedStory],
+    template_content: &str,
+    title: &str,
+    pr_number: Option<u32>,
+    draft: bool,
+    runner: &ClaudeRunner,
+    retry: &mut dyn FnMut(&ClaudeErrorInfo) -> bool,
+) -> Result<TemplateAgentResult> {
+    loop {
+        let result = run_template_agent(
+            spec,
+            failed_stories,
+            template_content,
+            title,
+            pr_number,
+            draft,
+            runner,
+            |_| {},
+        );
+        if let Ok(TemplateAgentResult::Error(error_info)) = &result {
+            if retry(error_info) {
+                continue;
+            }
+        }
+        return result;
+    }
 }
 
 #[cfg(test)]
@@ -202,9 +273,13 @@ fn build_pr_create_args<'a>(title: &'a str, body: &'a str, draft: bool) -> Vec<&
 }
 
 /// Create PR directly using generated format (internal fallback)
-fn create_pull_request_direct(spec: &Spec, draft: bool) -> Result<PRResult> {
+fn create_pull_request_direct(
+    spec: &Spec,
+    failed_stories: &[FailedStory],
+    draft: bool,
+) -> Result<PRResult> {
     let title = format_pr_title(spec);
-    let body = format_pr_description(spec);
+    let body = format_pr_description(spec, failed_stories);
 
     let mut args = vec!["pr", "create", "--title", &title, "--body", &body];
     if draft {
@@ -244,10 +319,11 @@ pub fn ensure_branch_pushed(branch: &str) -> Result<PushResult> {
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::spec::UserStory;
+    use crate::spec::{StoryStatus, UserStory};
 
     fn make_test_spec() -> Spec {
         Spec {
+            schema_version: crate::spec::SPEC_SCHEMA_VERSION,
             project: "TestProject".to_string(),
             branch_name: "feature/test".to_string(),
             description: "Test feature description.".to_string(),
@@ -257,9 +333,12 @@ mod tests {
                 description: "Test story description".to_string(),
                 acceptance_criteria: vec!["Criterion 1".to_string()],
                 priority: 1,
-                passes: true,
+                status: StoryStatus::done(),
                 notes: String::new(),
+                depends_on: Vec::new(),
+                verify: None,
             }],
+            verify: Vec::new(),
         }
     }
 
@@ -304,7 +383,10 @@ mod tests {
     #[test]
     fn test_create_pr_skips_when_no_commits() {
         let spec = make_test_spec();
-        let result = create_pull_request(&spec, false, false);
+        let result =
+            create_pull_request(&spec, &[], false, false, &ClaudeRunner::new(), &mut |_| {
+                false
+            });
         assert!(result.is_ok());
         match result.unwrap() {
             PRResult::Skipped(msg) => {
@@ -358,7 +440,7 @@ mod tests {
         // the function compiles and the format functions work correctly
         let spec = make_test_spec();
         let title = format_pr_title(&spec);
-        let body = format_pr_description(&spec);
+        let body = format_pr_description(&spec, &[]);
 
         assert!(title.contains("TestProject"));
         assert!(body.contains("Summary"));
@@ -369,7 +451,7 @@ mod tests {
     fn test_update_pr_description_direct_builds_correct_command_args() {
         // Verify the format functions work correctly for updates
         let spec = make_test_spec();
-        let body = format_pr_description(&spec);
+        let body = format_pr_description(&spec, &[]);
 
         assert!(body.contains("Summary"));
         assert!(body.contains("US-001"));
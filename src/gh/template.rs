@@ -5,15 +5,13 @@
 //! - Run a Claude agent to populate templates and execute PR commands
 
 use std::fs;
-use std::io::{BufRead, BufReader, Write};
 use std::path::Path;
-use std::process::{Command, Stdio};
 
-use crate::claude::extract_text_from_stream_line;
-use crate::claude::ClaudeErrorInfo;
-use crate::error::{Autom8Error, Result};
+use crate::claude::{AgentEvent, ClaudeErrorInfo, ClaudePhase, ClaudeRunner, Session};
+use crate::error::Result;
 use crate::prompts::PR_TEMPLATE_PROMPT;
 use crate::spec::Spec;
+use crate::state::FailedStory;
 
 /// Standard locations for GitHub PR templates, in order of precedence.
 const PR_TEMPLATE_PATHS: &[&str] = &[
@@ -74,7 +72,8 @@ pub enum TemplateAgentResult {
 /// - Project name
 /// - Feature description
 /// - User stories with their completion status
-pub fn format_spec_for_template(spec: &Spec) -> String {
+/// - Stories the run gave up on, with the reason
+pub fn format_spec_for_template(spec: &Spec, failed_stories: &[FailedStory]) -> String {
     let mut output = String::new();
 
     output.push_str(&format!("**Project:** {}\n\n", spec.project));
@@ -82,20 +81,27 @@ pub fn format_spec_for_template(spec: &Spec) -> String {
     output.push_str("**User Stories:**\n\n");
 
     for story in &spec.user_stories {
-        let status = if story.passes { "[x]" } else { "[ ]" };
+        let status = if story.passes() { "[x]" } else { "[ ]" };
         output.push_str(&format!("- {} **{}**: {}\n", status, story.id, story.title));
         output.push_str(&format!("  {}\n", story.description));
 
         if !story.acceptance_criteria.is_empty() {
             output.push_str("  - Acceptance Criteria:\n");
             for criterion in &story.acceptance_criteria {
-                let criterion_status = if story.passes { "[x]" } else { "[ ]" };
+                let criterion_status = if story.passes() { "[x]" } else { "[ ]" };
                 output.push_str(&format!("    - {} {}\n", criterion_status, criterion));
             }
         }
         output.push('\n');
     }
 
+    if !failed_stories.is_empty() {
+        output.push_str("**Skipped Stories (not implemented):**\n\n");
+        for failed in failed_stories {
+            output.push_str(&format!("- **{}**: {}\n", failed.story_id, failed.reason));
+        }
+    }
+
     output.trim_end().to_string()
 }
 
@@ -119,6 +125,25 @@ pub fn build_gh_command(title: &str, pr_number: Option<u32>, draft: bool) -> Str
     }
 }
 
+/// The PR template agent's prompt: the spec data, the raw template and the
+/// `gh` command it should run.
+pub fn build_template_prompt(
+    spec: &Spec,
+    failed_stories: &[FailedStory],
+    template_content: &str,
+    title: &str,
+    pr_number: Option<u32>,
+    draft: bool,
+) -> String {
+    let spec_data = format_spec_for_template(spec, failed_stories);
+    let gh_command = build_gh_command(title, pr_number, draft);
+
+    PR_TEMPLATE_PROMPT
+        .replace("{spec_data}", &spec_data)
+        .replace("{template_content}", template_content)
+        .replace("{gh_command}", &gh_command)
+}
+
 /// Extracts a PR URL from the agent's output.
 ///
 /// Looks for GitHub PR URLs in the format:
@@ -160,92 +185,49 @@ pub fn extract_pr_url(output: &str) -> Option<String> {
 /// * `title` - The PR title
 /// * `pr_number` - If Some, updates existing PR; if None, creates new PR
 /// * `draft` - If true and creating a new PR, includes the `--draft` flag
+/// * `runner` - Runs the agent, with its time limit
 /// * `on_output` - Callback for streaming output
 ///
 /// # Returns
 ///
 /// `TemplateAgentResult::Success(url)` if the agent successfully created/updated the PR,
 /// `TemplateAgentResult::Error(info)` if the agent failed.
+#[allow(clippy::too_many_arguments)]
 pub fn run_template_agent<F>(
     spec: &Spec,
+    failed_stories: &[FailedStory],
     template_content: &str,
     title: &str,
     pr_number: Option<u32>,
     draft: bool,
+    runner: &ClaudeRunner,
     mut on_output: F,
 ) -> Result<TemplateAgentResult>
 where
     F: FnMut(&str),
 {
-    let spec_data = format_spec_for_template(spec);
-    let gh_command = build_gh_command(title, pr_number, draft);
-
-    let prompt = PR_TEMPLATE_PROMPT
-        .replace("{spec_data}", &spec_data)
-        .replace("{template_content}", template_content)
-        .replace("{gh_command}", &gh_command);
-
-    let mut child = Command::new("claude")
-        .args([
-            "--dangerously-skip-permissions",
-            "--print",
-            "--output-format",
-            "stream-json",
-            "--verbose",
-        ])
-        .stdin(Stdio::piped())
-        .stdout(Stdio::piped())
-        .stderr(Stdio::piped())
-        .spawn()
-        .map_err(|e| Autom8Error::ClaudeError(format!("Failed to spawn claude: {}", e)))?;
-
-    // Write prompt to stdin
-    if let Some(mut stdin) = child.stdin.take() {
-        stdin
-            .write_all(prompt.as_bytes())
-            .map_err(|e| Autom8Error::ClaudeError(format!("Failed to write to stdin: {}", e)))?;
-    }
-
-    // Take stderr handle before consuming stdout
-    let stderr = child.stderr.take();
+    let prompt = build_template_prompt(
+        spec,
+        failed_stories,
+        template_content,
+        title,
+        pr_number,
+        draft,
+    );
 
     // Stream stdout
-    let stdout = child
-        .stdout
-        .take()
-        .ok_or_else(|| Autom8Error::ClaudeError("Failed to capture stdout".into()))?;
-
-    let reader = BufReader::new(stdout);
     let mut accumulated_text = String::new();
 
-    for line in reader.lines() {
-        let line = line.map_err(|e| Autom8Error::ClaudeError(format!("Read error: {}", e)))?;
-
-        // Parse stream-json output and extract text content
-        if let Some(text) = extract_text_from_stream_line(&line) {
-            on_output(&text);
-            accumulated_text.push_str(&text);
+    let session = Session::new(ClaudePhase::PrTemplate, &prompt);
+    let exit = runner.run_session(session, |event| {
+        if let AgentEvent::Text(text) = event {
+            on_output(text);
+            accumulated_text.push_str(text);
         }
-    }
+    })?;
 
-    // Wait for process to complete
-    let status = child
-        .wait()
-        .map_err(|e| Autom8Error::ClaudeError(format!("Wait error: {}", e)))?;
-
-    if !status.success() {
-        let stderr_content = stderr
-            .map(|s| std::io::read_to_string(s).unwrap_or_default())
-            .unwrap_or_default();
-        let error_info = ClaudeErrorInfo::from_process_failure(
-            status,
-            if stderr_content.is_empty() {
-                None
-            } else {
-                Some(stderr_content)
-            },
-        );
-        return Ok(TemplateAgentResult::Error(error_info));
+    if let Some(exit) = exit.filter(|e| !e.success) {
+        return Ok(TemplateAgentResult::Error(exit.error_info()));
     }
 
     // Extract PR URL from output
@@ -263,7 +245,7 @@ where
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::spec::UserStory;
+    use crate::spec::{StoryStatus, UserStory};
     use std::fs::{self, File};
     use std::io::Write;
     use tempfile::TempDir;
@@ -451,13 +433,20 @@ mod tests {
             description: format!("Description for {}", id),
             acceptance_criteria: vec!["Criterion 1".to_string(), "Criterion 2".to_string()],
             priority: 1,
-            passes,
+            status: if passes {
+                StoryStatus::done()
+            } else {
+                StoryStatus::default()
+            },
             notes: String::new(),
+            depends_on: Vec::new(),
+            verify: None,
         }
     }
 
     fn make_test_spec() -> Spec {
         Spec {
+            schema_version: crate::spec::SPEC_SCHEMA_VERSION,
             project: "TestProject".to_string(),
             branch_name: "feature/test".to_string(),
             description: "This is a test feature description.".to_string(),
@@ -465,20 +454,21 @@ mod tests {
                 make_test_story("US-001", "First Story", true),
                 make_test_story("US-002", "Second Story", false),
             ],
+            verify: Vec::new(),
         }
     }
 
     #[test]
     fn test_format_spec_includes_project_name() {
         let spec = make_test_spec();
-        let formatted = format_spec_for_template(&spec);
+        let formatted = format_spec_for_template(&spec, &[]);
         assert!(formatted.contains("**Project:** TestProject"));
     }
 
     #[test]
     fn test_format_spec_includes_description() {
         let spec = make_test_spec();
-        let formatted = format_spec_for_template(&spec);
+        let formatted = format_spec_for_template(&spec, &[]);
         assert!(formatted.contains("**Description:**"));
         assert!(formatted.contains("This is a test feature description."));
     }
@@ -486,14 +476,14 @@ mod tests {
     #[test]
     fn test_format_spec_includes_user_stories_header() {
         let spec = make_test_spec();
-        let formatted = format_spec_for_template(&spec);
+        let formatted = format_spec_for_template(&spec, &[]);
         assert!(formatted.contains("**User Stories:**"));
     }
 
     #[test]
     fn test_format_spec_includes_story_ids_and_titles() {
         let spec = make_test_spec();
-        let formatted = format_spec_for_template(&spec);
+        let formatted = format_spec_for_template(&spec, &[]);
         assert!(formatted.contains("**US-001**: First Story"));
         assert!(formatted.contains("**US-002**: Second Story"));
     }
@@ -501,21 +491,21 @@ mod tests {
     #[test]
     fn test_format_spec_shows_completed_story_with_checkbox() {
         let spec = make_test_spec();
-        let formatted = format_spec_for_template(&spec);
+        let formatted = format_spec_for_template(&spec, &[]);
         assert!(formatted.contains("[x] **US-001**: First Story"));
     }
 
     #[test]
     fn test_format_spec_shows_incomplete_story_without_checkbox() {
         let spec = make_test_spec();
-        let formatted = format_spec_for_template(&spec);
+        let formatted = format_spec_for_template(&spec, &[]);
         assert!(formatted.contains("[ ] **US-002**: Second Story"));
     }
 
     #[test]
     fn test_format_spec_includes_acceptance_criteria() {
         let spec = make_test_spec();
-        let formatted = format_spec_for_template(&spec);
+        let formatted = format_spec_for_template(&spec, &[]);
         assert!(formatted.contains("Acceptance Criteria:"));
         assert!(formatted.contains("Criterion 1"));
         assert!(formatted.contains("Criterion 2"));
@@ -524,7 +514,7 @@ mod tests {
     #[test]
     fn test_format_spec_includes_story_descriptions() {
         let spec = make_test_spec();
-        let formatted = format_spec_for_template(&spec);
+        let formatted = format_spec_for_template(&spec, &[]);
         assert!(formatted.contains("Description for US-001"));
         assert!(formatted.contains("Description for US-002"));
     }
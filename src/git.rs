@@ -1,5 +1,7 @@
 use crate::error::{Autom8Error, Result};
-use std::process::Command;
+use std::io::Write;
+use std::path::Path;
+use std::process::{Command, Stdio};
 
 /// Check if current directory is a git repository
 pub fn is_git_repo() -> bool {
@@ -800,6 +802,307 @@ pub fn get_merge_base_auto() -> Result<String> {
     get_merge_base(&base_branch)
 }
 
+// ============================================================================
+// Parallel story branches
+// ============================================================================
+
+/// Result type for merge operations
+#[derive(Debug, Clone, PartialEq)]
+pub enum MergeResult {
+    /// Merge succeeded with the short hash of the resulting HEAD
+    Merged(String),
+    /// Merge hit conflicts in these files and was aborted
+    Conflict(Vec<std::path::PathBuf>),
+    /// Merge failed for another reason
+    Error(String),
+}
+
+/// Stage and commit all changes in the repository at `dir`.
+///
+/// Used for temporary story worktrees, which are not the current directory.
+///
+/// # Returns
+/// * `CommitResult::Success(hash)` - Commit created with short hash
+/// * `CommitResult::NothingToCommit` - No changes to commit
+/// * `CommitResult::Error(msg)` - Staging or commit failed
+pub fn commit_all_in(dir: &std::path::Path, message: &str) -> Result<CommitResult> {
+    let add = Command::new("git")
+        .arg("-C")
+        .arg(dir)
+        .args(["add", "-A"])
+        .output()?;
+    if !add.status.success() {
+        return Ok(CommitResult::Error(
+            String::from_utf8_lossy(&add.stderr).trim().to_string(),
+        ));
+    }
+
+    let output = Command::new("git")
+        .arg("-C")
+        .arg(dir)
+        .args(["commit", "-m", message])
+        .output()?;
+
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    let stdout = String::from_utf8_lossy(&output.stdout);
+
+    if output.status.success() {
+        let hash = Command::new("git")
+            .arg("-C")
+            .arg(dir)
+            .args(["rev-parse", "--short", "HEAD"])
+            .output()
+            .ok()
+            .filter(|o| o.status.success())
+            .map(|o| String::from_utf8_lossy(&o.stdout).trim().to_string())
+            .unwrap_or_else(|| "unknown".to_string());
+        return Ok(CommitResult::Success(hash));
+    }
+
+    let combined = format!("{} {}", stdout, stderr).to_lowercase();
+    if combined.contains("nothing to commit") || combined.contains("no changes added") {
+        return Ok(CommitResult::NothingToCommit);
+    }
+
+    Ok(CommitResult::Error(stderr.trim().to_string()))
+}
+
+/// Merge a branch into the current branch with a merge commit.
+///
+/// If the merge conflicts, it is aborted so the working tree is left as it
+/// was before the merge, and the conflicting files are returned.
+pub fn merge_branch(branch: &str, message: &str) -> Result<MergeResult> {
+    let output = Command::new("git")
+        .args(["merge", "--no-ff", "-m", message, branch])
+        .output()?;
+
+    if output.status.success() {
+        let hash = latest_commit_short().unwrap_or_else(|_| "unknown".to_string());
+        return Ok(MergeResult::Merged(hash));
+    }
+
+    let conflicts = Command::new("git")
+        .args(["diff", "--name-only", "--diff-filter=U"])
+        .output()?;
+    let conflicted = parse_conflicted_files(&String::from_utf8_lossy(&conflicts.stdout));
+
+    if conflicted.is_empty() {
+        return Ok(MergeResult::Error(
+            String::from_utf8_lossy(&output.stderr).trim().to_string(),
+        ));
+    }
+
+    let abort = Command::new("git").args(["merge", "--abort"]).output()?;
+    if !abort.status.success() {
+        return Err(Autom8Error::GitError(format!(
+            "Failed to abort conflicted merge of '{}': {}",
+            branch,
+            String::from_utf8_lossy(&abort.stderr).trim()
+        )));
+    }
+
+    Ok(MergeResult::Conflict(conflicted))
+}
+
+/// Parse the output of `git diff --name-only --diff-filter=U`.
+fn parse_conflicted_files(output: &str) -> Vec<std::path::PathBuf> {
+    output
+        .lines()
+        .map(str::trim)
+        .filter(|line| !line.is_empty())
+        .map(std::path::PathBuf::from)
+        .collect()
+}
+
+// ============================================================================
+// Rollback
+// ============================================================================
+
+/// Check whether `ancestor` is `descendant` or one of its ancestors.
+///
+/// Unknown commits count as not being an ancestor.
+pub fn is_ancestor(ancestor: &str, descendant: &str) -> bool {
+    Command::new("git")
+        .args(["merge-base", "--is-ancestor", ancestor, descendant])
+        .output()
+        .is_ok_and(|o| o.status.success())
+}
+
+/// Reset the working tree to `commit`, discarding uncommitted changes and
+/// untracked files (ignored files are kept).
+pub fn reset_hard(commit: &str) -> Result<()> {
+    let reset = Command::new("git")
+        .args(["reset", "--hard", commit])
+        .output()?;
+    if !reset.status.success() {
+        return Err(Autom8Error::GitError(format!(
+            "Failed to reset to {}: {}",
+            commit,
+            String::from_utf8_lossy(&reset.stderr).trim()
+        )));
+    }
+
+    let clean = Command::new("git").args(["clean", "-fd"]).output()?;
+    if !clean.status.success() {
+        return Err(Autom8Error::GitError(format!(
+            "Failed to remove untracked files: {}",
+            String::from_utf8_lossy(&clean.stderr).trim()
+        )));
+    }
+
+    Ok(())
+}
+
+/// Force-delete a local branch.
+pub fn delete_branch(branch: &str) -> Result<()> {
+    let output = Command::new("git")
+        .args(["branch", "-D", branch])
+        .output()?;
+
+    if !output.status.success() {
+        return Err(Autom8Error::GitError(format!(
+            "Failed to delete branch '{}': {}",
+            branch,
+            String::from_utf8_lossy(&output.stderr).trim()
+        )));
+    }
+
+    Ok(())
+}
+
+// ============================================================================
+// Session fixtures
+// ============================================================================
+
+/// Write the working tree of the repository at `dir`, including untracked
+/// (but not ignored) files, as a tree object and return its hash.
+///
+/// Uses a scratch copy of the index, so the repository's index is untouched.
+pub fn snapshot_tree_in(dir: &Path) -> Result<String> {
+    let index = std::env::temp_dir().join(format!("autom8-index-{}", uuid::Uuid::new_v4()));
+    // Starting from the real index saves hashing files that haven't changed
+    if let Some(real_index) = git_path_in(dir, "index") {
+        let _ = std::fs::copy(real_index, &index);
+    }
+
+    let result = (|| {
+        let add = Command::new("git")
+            .arg("-C")
+            .arg(dir)
+            .args(["add", "-A"])
+            .env("GIT_INDEX_FILE", &index)
+            .output()?;
+        if !add.status.success() {
+            return Err(Autom8Error::GitError(format!(
+                "Failed to snapshot the working tree: {}",
+                String::from_utf8_lossy(&add.stderr).trim()
+            )));
+        }
+
+        let tree = Command::new("git")
+            .arg("-C")
+            .arg(dir)
+            .arg("write-tree")
+            .env("GIT_INDEX_FILE", &index)
+            .output()?;
+        if !tree.status.success() {
+            return Err(Autom8Error::GitError(format!(
+                "Failed to snapshot the working tree: {}",
+                String::from_utf8_lossy(&tree.stderr).trim()
+            )));
+        }
+        Ok(String::from_utf8_lossy(&tree.stdout).trim().to_string())
+    })();
+
+    let _ = std::fs::remove_file(&index);
+    result
+}
+
+/// The binary patch that turns tree `from` into tree `to`.
+pub fn diff_trees_in(dir: &Path, from: &str, to: &str) -> Result<String> {
+    let output = Command::new("git")
+        .arg("-C")
+        .arg(dir)
+        .args(["diff", "--binary", from, to])
+        .output()?;
+    if !output.status.success() {
+        return Err(Autom8Error::GitError(format!(
+            "Failed to diff {} and {}: {}",
+            from,
+            to,
+            String::from_utf8_lossy(&output.stderr).trim()
+        )));
+    }
+    Ok(String::from_utf8_lossy(&output.stdout).into_owned())
+}
+
+/// The paths whose content differs between trees `from` and `to`, e.g. two
+/// [`snapshot_tree_in`] snapshots.
+pub fn changed_paths_in(dir: &Path, from: &str, to: &str) -> Result<Vec<std::path::PathBuf>> {
+    let output = Command::new("git")
+        .arg("-C")
+        .arg(dir)
+        .args(["diff", "--name-only", "--no-renames", "-z", from, to])
+        .output()?;
+    if !output.status.success() {
+        return Err(Autom8Error::GitError(format!(
+            "Failed to diff {} and {}: {}",
+            from,
+            to,
+            String::from_utf8_lossy(&output.stderr).trim()
+        )));
+    }
+    Ok(String::from_utf8_lossy(&output.stdout)
+        .split('\0')
+        .filter(|path| !path.is_empty())
+        .map(std::path::PathBuf::from)
+        .collect())
+}
+
+/// Apply a patch from [`diff_trees_in`] to the working tree at `dir`.
+pub fn apply_patch_in(dir: &Path, patch: &str) -> Result<()> {
+    if patch.trim().is_empty() {
+        return Ok(());
+    }
+
+    let mut child = Command::new("git")
+        .arg("-C")
+        .arg(dir)
+        .args(["apply", "--binary", "--whitespace=nowarn", "-"])
+        .stdin(Stdio::piped())
+        .stdout(Stdio::null())
+        .stderr(Stdio::piped())
+        .spawn()?;
+    if let Some(mut stdin) = child.stdin.take() {
+        stdin.write_all(patch.as_bytes())?;
+    }
+    let output = child.wait_with_output()?;
+    if !output.status.success() {
+        return Err(Autom8Error::GitError(format!(
+            "Failed to apply patch: {}",
+            String::from_utf8_lossy(&output.stderr).trim()
+        )));
+    }
+    Ok(())
+}
+
+/// Resolve a path inside the `.git` directory of the repository at `dir`.
+fn git_path_in(dir: &Path, name: &str) -> Option<std::path::PathBuf> {
+    let output = Command::new("git")
+        .arg("-C")
+        .arg(dir)
+        .args(["rev-parse", "--git-path", name])
+        .output()
+        .ok()
+        .filter(|o| o.status.success())?;
+    let path = std::path::PathBuf::from(String::from_utf8_lossy(&output.stdout).trim());
+    Some(if path.is_absolute() {
+        path
+    } else {
+        dir.join(path)
+    })
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -872,6 +1175,19 @@ mod tests {
         assert!(DiffEntry::parse_name_status_line("").is_none());
     }
 
+    #[test]
+    fn test_parse_conflicted_files() {
+        let files = parse_conflicted_files("src/lib.rs\nsrc/main.rs\n\n");
+        assert_eq!(
+            files,
+            vec![
+                std::path::PathBuf::from("src/lib.rs"),
+                std::path::PathBuf::from("src/main.rs")
+            ]
+        );
+        assert!(parse_conflicted_files("").is_empty());
+    }
+
     // ========================================================================
     // Logic tests - test actual behavior without side effects
     // ========================================================================
@@ -0,0 +1,246 @@
+//! Lifecycle hooks.
+//!
+//! Hooks are shell commands from the `[hooks]` config table that autom8 runs
+//! at points in a run, e.g. starting a dev database before each story or
+//! posting to chat once a PR is open. They get the run's context through
+//! `AUTOM8_*` environment variables.
+//!
+//! A failing `pre_*` hook blocks the step it guards; other hooks only warn.
+
+use std::fmt;
+use std::path::PathBuf;
+use std::time::Duration;
+
+use crate::config::HooksConfig;
+use crate::verify::run_shell;
+
+/// How long a hook may run before it is killed, so a hook that never exits
+/// can't stall the run. Hooks that start services should background them.
+const HOOK_TIMEOUT: Duration = Duration::from_secs(10 * 60);
+
+/// A point in a run where a hook can run.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Hook {
+    /// Once the run's state has been created.
+    OnRunStart,
+    /// Before Claude starts working on a story.
+    PreStory,
+    /// After Claude finishes a story iteration, whether or not it passed.
+    PostStory,
+    /// Before the review phase.
+    PreReview,
+    /// Before changes are committed.
+    PreCommit,
+    /// After a pull request is created or updated.
+    PostPr,
+    /// When the run fails.
+    OnFailure,
+    /// When the run completes.
+    OnComplete,
+}
+
+impl Hook {
+    /// The hook's key in the `[hooks]` table.
+    pub fn name(self) -> &'static str {
+        match self {
+            Hook::OnRunStart => "on_run_start",
+            Hook::PreStory => "pre_story",
+            Hook::PostStory => "post_story",
+            Hook::PreReview => "pre_review",
+            Hook::PreCommit => "pre_commit",
+            Hook::PostPr => "post_pr",
+            Hook::OnFailure => "on_failure",
+            Hook::OnComplete => "on_complete",
+        }
+    }
+
+    /// Whether a failure stops the step the hook runs before.
+    pub fn is_blocking(self) -> bool {
+        matches!(self, Hook::PreStory | Hook::PreReview | Hook::PreCommit)
+    }
+
+    /// The configured command for this hook, if any.
+    pub fn command(self, hooks: &HooksConfig) -> Option<&str> {
+        let command = match self {
+            Hook::OnRunStart => &hooks.on_run_start,
+            Hook::PreStory => &hooks.pre_story,
+            Hook::PostStory => &hooks.post_story,
+            Hook::PreReview => &hooks.pre_review,
+            Hook::PreCommit => &hooks.pre_commit,
+            Hook::PostPr => &hooks.post_pr,
+            Hook::OnFailure => &hooks.on_failure,
+            Hook::OnComplete => &hooks.on_complete,
+        };
+        command.as_deref().filter(|c| !c.trim().is_empty())
+    }
+}
+
+impl fmt::Display for Hook {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.name())
+    }
+}
+
+/// What a hook is told about the run.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct HookContext {
+    pub run_id: String,
+    pub branch: String,
+    pub story_id: Option<String>,
+    pub worktree_path: Option<PathBuf>,
+    pub pr_url: Option<String>,
+}
+
+impl HookContext {
+    /// The environment variables a hook runs with. Unknown values are left out.
+    pub fn env(&self, hook: Hook) -> Vec<(&'static str, String)> {
+        let mut env = vec![
+            ("AUTOM8_HOOK", hook.name().to_string()),
+            ("AUTOM8_RUN_ID", self.run_id.clone()),
+            ("AUTOM8_BRANCH", self.branch.clone()),
+        ];
+        if let Some(story_id) = &self.story_id {
+            env.push(("AUTOM8_STORY_ID", story_id.clone()));
+        }
+        if let Some(path) = &self.worktree_path {
+            env.push(("AUTOM8_WORKTREE_PATH", path.display().to_string()));
+        }
+        if let Some(url) = &self.pr_url {
+            env.push(("AUTOM8_PR_URL", url.clone()));
+        }
+        env
+    }
+}
+
+/// A hook that didn't exit 0.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct HookFailure {
+    pub hook: Hook,
+    pub command: String,
+    /// Exit code, or `None` if the command was killed by a signal or couldn't start
+    pub exit_code: Option<i32>,
+    /// Combined stdout and stderr (tail only)
+    pub output: String,
+}
+
+impl fmt::Display for HookFailure {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.exit_code {
+            Some(code) => write!(
+                f,
+                "The {} hook `{}` exited with code {}",
+                self.hook, self.command, code
+            ),
+            None => write!(
+                f,
+                "The {} hook `{}` did not complete",
+                self.hook, self.command
+            ),
+        }
+    }
+}
+
+/// Run `hook` if it is configured, through `sh -c` in the context's worktree
+/// path (or the current directory if it has none).
+///
+/// Returns `Ok(false)` when no command is configured and `Ok(true)` when it
+/// ran and exited 0.
+pub fn run_hook(
+    hooks: &HooksConfig,
+    hook: Hook,
+    context: &HookContext,
+) -> Result<bool, HookFailure> {
+    let Some(command) = hook.command(hooks) else {
+        return Ok(false);
+    };
+
+    run_shell(
+        command,
+        context.worktree_path.as_deref(),
+        &context.env(hook),
+        Some(HOOK_TIMEOUT),
+    )
+    .map(|()| true)
+    .map_err(|failure| HookFailure {
+        hook,
+        command: command.to_string(),
+        exit_code: failure.exit_code,
+        output: failure.output,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn context() -> HookContext {
+        HookContext {
+            run_id: "run-1".into(),
+            branch: "feature/hooks".into(),
+            story_id: Some("US-002".into()),
+            worktree_path: None,
+            pr_url: None,
+        }
+    }
+
+    #[test]
+    fn test_unconfigured_hook_does_nothing() {
+        let hooks = HooksConfig {
+            pre_story: Some("  ".into()),
+            ..Default::default()
+        };
+        assert_eq!(run_hook(&hooks, Hook::PreStory, &context()), Ok(false));
+        assert_eq!(run_hook(&hooks, Hook::PostPr, &context()), Ok(false));
+    }
+
+    #[test]
+    fn test_hook_gets_context_in_environment() {
+        let dir = tempfile::TempDir::new().unwrap();
+        let out = dir.path().join("env.txt");
+        let hooks = HooksConfig {
+            post_story: Some(format!(
+                "echo \"$AUTOM8_HOOK $AUTOM8_RUN_ID $AUTOM8_BRANCH $AUTOM8_STORY_ID ${{AUTOM8_PR_URL:-none}}\" > {}",
+                out.display()
+            )),
+            ..Default::default()
+        };
+
+        assert_eq!(run_hook(&hooks, Hook::PostStory, &context()), Ok(true));
+        assert_eq!(
+            std::fs::read_to_string(&out).unwrap().trim(),
+            "post_story run-1 feature/hooks US-002 none"
+        );
+    }
+
+    #[test]
+    fn test_hook_runs_in_worktree_path() {
+        let dir = tempfile::TempDir::new().unwrap();
+        let hooks = HooksConfig {
+            pre_commit: Some("touch ran-here".into()),
+            ..Default::default()
+        };
+        let context = HookContext {
+            worktree_path: Some(dir.path().to_path_buf()),
+            ..context()
+        };
+
+        assert_eq!(run_hook(&hooks, Hook::PreCommit, &context), Ok(true));
+        assert!(dir.path().join("ran-here").exists());
+    }
+
+    #[test]
+    fn test_failing_hook_reports_exit_code_and_output() {
+        let hooks = HooksConfig {
+            pre_commit: Some("echo formatting; echo 'fmt failed' >&2; exit 2".into()),
+            ..Default::default()
+        };
+        let failure = run_hook(&hooks, Hook::PreCommit, &context()).unwrap_err();
+
+        assert_eq!(failure.exit_code, Some(2));
+        assert!(failure.output.contains("formatting"));
+        assert!(failure.output.contains("fmt failed"));
+        assert!(failure.hook.is_blocking());
+        assert!(failure.to_string().starts_with("The pre_commit hook `echo"));
+        assert!(!Hook::PostPr.is_blocking());
+    }
+}
@@ -4,11 +4,17 @@
 //! accomplish during implementation runs. The knowledge is accumulated across
 //! multiple story implementations and can be injected into subsequent agent
 //! prompts to provide richer context.
+//!
+//! When a run completes, what it learned is merged into the project's
+//! [`KnowledgeStore`], and later runs start with it as
+//! [`ProjectKnowledge::inherited`].
 
+use crate::error::Result;
 use crate::git::DiffEntry;
 use serde::{Deserialize, Serialize};
-use std::collections::{HashMap, HashSet};
-use std::path::PathBuf;
+use std::collections::{BTreeMap, HashMap, HashSet};
+use std::fs;
+use std::path::{Path, PathBuf};
 
 /// Cumulative project knowledge tracked across agent runs.
 ///
@@ -32,6 +38,11 @@ pub struct ProjectKnowledge {
 
     /// The baseline commit hash when the run started (for git diff calculations)
     pub baseline_commit: Option<String>,
+
+    /// Knowledge from earlier runs on the project, seeded from the
+    /// [`KnowledgeStore`] when the run started
+    #[serde(default, skip_serializing_if = "InheritedKnowledge::is_empty")]
+    pub inherited: InheritedKnowledge,
 }
 
 impl ProjectKnowledge {
@@ -91,6 +102,24 @@ impl ProjectKnowledge {
             .cloned()
             .collect()
     }
+
+    /// Drop everything recorded for the given stories.
+    ///
+    /// Used when stories are removed or rewritten in the spec mid-run, so
+    /// later prompts don't carry decisions made for work that no longer
+    /// applies. Files touched only by those stories are dropped too.
+    pub fn forget_stories(&mut self, story_ids: &[String]) {
+        let forgotten = |id: &String| story_ids.contains(id);
+
+        self.decisions.retain(|d| !forgotten(&d.story_id));
+        self.patterns.retain(|p| !forgotten(&p.story_id));
+        self.story_changes.retain(|c| !forgotten(&c.story_id));
+        self.files.retain(|_, info| {
+            let touched_before = !info.touched_by.is_empty();
+            info.touched_by.retain(|id| !forgotten(id));
+            !touched_before || !info.touched_by.is_empty()
+        });
+    }
 }
 
 /// Metadata about a known file in the project.
@@ -159,6 +188,233 @@ pub struct StoryChanges {
 
     /// The commit hash for these changes (if committed)
     pub commit_hash: Option<String>,
+
+    /// HEAD when the story iteration started, used to roll the story back
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub base_commit: Option<String>,
+}
+
+/// Knowledge carried over from earlier runs on the project.
+#[derive(Debug, Clone, Serialize, Deserialize, Default)]
+#[serde(rename_all = "camelCase")]
+pub struct InheritedKnowledge {
+    /// Files earlier runs worked on, keyed by path
+    pub files: HashMap<PathBuf, FileInfo>,
+
+    /// Decisions earlier runs made, most recently learned first
+    pub decisions: Vec<Decision>,
+
+    /// Patterns earlier runs followed, most recently learned first
+    pub patterns: Vec<Pattern>,
+}
+
+impl InheritedKnowledge {
+    pub fn is_empty(&self) -> bool {
+        self.files.is_empty() && self.decisions.is_empty() && self.patterns.is_empty()
+    }
+}
+
+/// File the store is kept in, inside the project's `knowledge/` directory.
+const KNOWLEDGE_STORE_FILE: &str = "knowledge.json";
+
+/// What runs have learned about a project, kept in
+/// `~/.config/autom8/<project>/knowledge/`.
+///
+/// Each completed run's files, decisions and patterns are merged in, keyed
+/// by file path, decision topic and pattern description, so learning the
+/// same thing again replaces the older entry. Entries that no run has
+/// learned again for `knowledge_decay_runs` runs are dropped.
+#[derive(Debug, Clone, Serialize, Deserialize, Default)]
+#[serde(rename_all = "camelCase")]
+pub struct KnowledgeStore {
+    /// Number of completed runs merged into the store
+    pub runs_merged: u32,
+
+    /// Known files, keyed by path
+    pub files: BTreeMap<PathBuf, StoredEntry<FileInfo>>,
+
+    /// Decisions, keyed by normalized topic
+    pub decisions: BTreeMap<String, StoredEntry<Decision>>,
+
+    /// Patterns, keyed by normalized description
+    pub patterns: BTreeMap<String, StoredEntry<Pattern>>,
+}
+
+/// An entry in the [`KnowledgeStore`] and the run that last learned it.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct StoredEntry<T> {
+    /// [`KnowledgeStore::runs_merged`] after the run that last learned it
+    pub last_run: u32,
+
+    pub entry: T,
+}
+
+impl KnowledgeStore {
+    /// Load the store from `dir`, or an empty store if there is none yet.
+    pub fn load(dir: &Path) -> Result<Self> {
+        let path = dir.join(KNOWLEDGE_STORE_FILE);
+        if !path.exists() {
+            return Ok(Self::default());
+        }
+        let content = fs::read_to_string(path)?;
+        Ok(serde_json::from_str(&content)?)
+    }
+
+    /// Save the store to `dir`, creating it if needed.
+    pub fn save(&self, dir: &Path) -> Result<()> {
+        fs::create_dir_all(dir)?;
+        let content = serde_json::to_string_pretty(self)?;
+        fs::write(dir.join(KNOWLEDGE_STORE_FILE), content)?;
+        Ok(())
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.files.is_empty() && self.decisions.is_empty() && self.patterns.is_empty()
+    }
+
+    /// Merge what a completed run learned, then drop entries older than
+    /// `decay_runs` runs (`0` keeps them forever).
+    ///
+    /// Only the run's own knowledge is merged: inherited entries it didn't
+    /// learn again keep their age. Files the run deleted are forgotten.
+    pub fn merge_run(&mut self, knowledge: &ProjectKnowledge, decay_runs: u32) {
+        self.runs_merged += 1;
+        let run = self.runs_merged;
+
+        for (path, info) in &knowledge.files {
+            let mut info = info.clone();
+            if let Some(known) = self.files.get(path) {
+                if info.purpose.trim().is_empty() {
+                    info.purpose = known.entry.purpose.clone();
+                }
+                if info.key_symbols.is_empty() {
+                    info.key_symbols = known.entry.key_symbols.clone();
+                }
+            }
+            self.files.insert(
+                path.clone(),
+                StoredEntry {
+                    last_run: run,
+                    entry: info,
+                },
+            );
+        }
+        let created: HashSet<&PathBuf> = knowledge
+            .story_changes
+            .iter()
+            .flat_map(|story| story.files_created.iter().map(|change| &change.path))
+            .collect();
+        for story in &knowledge.story_changes {
+            for path in &story.files_deleted {
+                if !created.contains(path) {
+                    self.files.remove(path);
+                }
+            }
+        }
+
+        for decision in &knowledge.decisions {
+            self.decisions.insert(
+                entry_key(&decision.topic),
+                StoredEntry {
+                    last_run: run,
+                    entry: decision.clone(),
+                },
+            );
+        }
+        for pattern in &knowledge.patterns {
+            self.patterns.insert(
+                entry_key(&pattern.description),
+                StoredEntry {
+                    last_run: run,
+                    entry: pattern.clone(),
+                },
+            );
+        }
+
+        if decay_runs > 0 {
+            let fresh = |stored_run: u32| run - stored_run < decay_runs;
+            self.files.retain(|_, stored| fresh(stored.last_run));
+            self.decisions.retain(|_, stored| fresh(stored.last_run));
+            self.patterns.retain(|_, stored| fresh(stored.last_run));
+        }
+    }
+
+    /// Drop what a run's `knowledge` learned from the given stories, for
+    /// when they are rolled back after the run was merged in.
+    ///
+    /// Entries only go if the store still holds them as those stories
+    /// learned them, so anything a later run learned again is kept.
+    pub fn forget_stories(&mut self, knowledge: &ProjectKnowledge, story_ids: &[String]) {
+        let forgotten = |id: &String| story_ids.contains(id);
+        let only_forgotten =
+            |touched_by: &[String]| !touched_by.is_empty() && touched_by.iter().all(forgotten);
+
+        for decision in knowledge
+            .decisions
+            .iter()
+            .filter(|d| forgotten(&d.story_id))
+        {
+            let key = entry_key(&decision.topic);
+            if self.decisions.get(&key).is_some_and(|stored| {
+                stored.entry.story_id == decision.story_id && stored.entry.choice == decision.choice
+            }) {
+                self.decisions.remove(&key);
+            }
+        }
+        for pattern in knowledge.patterns.iter().filter(|p| forgotten(&p.story_id)) {
+            let key = entry_key(&pattern.description);
+            if self
+                .patterns
+                .get(&key)
+                .is_some_and(|stored| stored.entry.story_id == pattern.story_id)
+            {
+                self.patterns.remove(&key);
+            }
+        }
+        for (path, info) in &knowledge.files {
+            if only_forgotten(&info.touched_by)
+                && self
+                    .files
+                    .get(path)
+                    .is_some_and(|stored| only_forgotten(&stored.entry.touched_by))
+            {
+                self.files.remove(path);
+            }
+        }
+    }
+
+    /// The store's contents, to seed a new run with.
+    pub fn inherited(&self) -> InheritedKnowledge {
+        InheritedKnowledge {
+            files: self
+                .files
+                .iter()
+                .map(|(path, stored)| (path.clone(), stored.entry.clone()))
+                .collect(),
+            decisions: most_recent_first(&self.decisions),
+            patterns: most_recent_first(&self.patterns),
+        }
+    }
+}
+
+/// A topic or description with case and whitespace normalized, so the same
+/// entry learned twice is stored once.
+fn entry_key(text: &str) -> String {
+    text.split_whitespace()
+        .collect::<Vec<_>>()
+        .join(" ")
+        .to_lowercase()
+}
+
+/// The entries in `stored`, most recently learned first.
+fn most_recent_first<T: Clone>(stored: &BTreeMap<String, StoredEntry<T>>) -> Vec<T> {
+    let mut entries: Vec<&StoredEntry<T>> = stored.values().collect();
+    entries.sort_by_key(|stored| std::cmp::Reverse(stored.last_run));
+    entries
+        .into_iter()
+        .map(|stored| stored.entry.clone())
+        .collect()
 }
 
 /// Information about a file change (creation or modification).
@@ -208,16 +464,20 @@ mod tests {
 
     #[test]
     fn test_project_knowledge_clone() {
-        let mut knowledge = ProjectKnowledge::default();
-        knowledge.baseline_commit = Some("abc123".to_string());
+        let knowledge = ProjectKnowledge {
+            baseline_commit: Some("abc123".to_string()),
+            ..Default::default()
+        };
         let cloned = knowledge.clone();
         assert_eq!(cloned.baseline_commit, Some("abc123".to_string()));
     }
 
     #[test]
     fn test_project_knowledge_serialization_roundtrip() {
-        let mut knowledge = ProjectKnowledge::default();
-        knowledge.baseline_commit = Some("abc123".to_string());
+        let mut knowledge = ProjectKnowledge {
+            baseline_commit: Some("abc123".to_string()),
+            ..Default::default()
+        };
         knowledge.decisions.push(Decision {
             story_id: "US-001".to_string(),
             topic: "Architecture".to_string(),
@@ -243,6 +503,7 @@ mod tests {
                 files_modified: vec![],
                 files_deleted: vec![],
                 commit_hash: None,
+                base_commit: None,
             }],
             ..Default::default()
         };
@@ -397,6 +658,7 @@ mod tests {
             }],
             files_deleted: vec![PathBuf::from("src/old.rs")],
             commit_hash: Some("def456".to_string()),
+            base_commit: None,
         };
 
         assert_eq!(changes.story_id, "US-001");
@@ -414,6 +676,7 @@ mod tests {
             files_modified: vec![],
             files_deleted: vec![],
             commit_hash: None,
+            base_commit: None,
         };
 
         assert!(changes.commit_hash.is_none());
@@ -427,6 +690,7 @@ mod tests {
             files_modified: vec![],
             files_deleted: vec![],
             commit_hash: Some("abc".to_string()),
+            base_commit: None,
         };
 
         let json = serde_json::to_string(&changes).unwrap();
@@ -515,8 +779,10 @@ mod tests {
 
     #[test]
     fn test_full_knowledge_serialization_roundtrip() {
-        let mut knowledge = ProjectKnowledge::default();
-        knowledge.baseline_commit = Some("baseline123".to_string());
+        let mut knowledge = ProjectKnowledge {
+            baseline_commit: Some("baseline123".to_string()),
+            ..Default::default()
+        };
 
         knowledge.files.insert(
             PathBuf::from("src/lib.rs"),
@@ -559,6 +825,7 @@ mod tests {
             }],
             files_deleted: vec![],
             commit_hash: Some("commit123".to_string()),
+            base_commit: None,
         });
 
         // Serialize
@@ -615,6 +882,7 @@ mod tests {
             files_modified: vec![],
             files_deleted: vec![],
             commit_hash: None,
+            base_commit: None,
         });
 
         let files = knowledge.our_files();
@@ -637,6 +905,7 @@ mod tests {
             }],
             files_deleted: vec![],
             commit_hash: None,
+            base_commit: None,
         });
 
         let files = knowledge.our_files();
@@ -653,6 +922,7 @@ mod tests {
             files_modified: vec![],
             files_deleted: vec![PathBuf::from("src/old.rs")],
             commit_hash: None,
+            base_commit: None,
         });
 
         let files = knowledge.our_files();
@@ -683,6 +953,7 @@ mod tests {
             }],
             files_deleted: vec![],
             commit_hash: None,
+            base_commit: None,
         });
 
         // Second story
@@ -704,6 +975,7 @@ mod tests {
             }],
             files_deleted: vec![PathBuf::from("src/old.rs")],
             commit_hash: None,
+            base_commit: None,
         });
 
         let files = knowledge.our_files();
@@ -733,6 +1005,7 @@ mod tests {
             }],
             files_deleted: vec![],
             commit_hash: None,
+            base_commit: None,
         });
 
         knowledge.story_changes.push(StoryChanges {
@@ -747,6 +1020,7 @@ mod tests {
             }],
             files_deleted: vec![],
             commit_hash: None,
+            base_commit: None,
         });
 
         let files = knowledge.our_files();
@@ -799,6 +1073,7 @@ mod tests {
             files_modified: vec![],
             files_deleted: vec![],
             commit_hash: None,
+            base_commit: None,
         });
 
         let all_changes = vec![
@@ -840,6 +1115,7 @@ mod tests {
             files_modified: vec![],
             files_deleted: vec![],
             commit_hash: None,
+            base_commit: None,
         });
 
         let all_changes = vec![
@@ -882,6 +1158,7 @@ mod tests {
             files_modified: vec![],
             files_deleted: vec![],
             commit_hash: None,
+            base_commit: None,
         });
 
         let all_changes = vec![
@@ -930,6 +1207,7 @@ mod tests {
             }],
             files_deleted: vec![],
             commit_hash: None,
+            base_commit: None,
         });
 
         let all_changes = vec![
@@ -1006,6 +1284,7 @@ mod tests {
             }],
             files_deleted: vec![],
             commit_hash: None,
+            base_commit: None,
         });
 
         let all_changes = vec![DiffEntry {
@@ -1029,4 +1308,186 @@ mod tests {
         let filtered = knowledge.filter_our_changes(&[]);
         assert!(filtered.is_empty());
     }
+
+    // ===========================================
+    // forget_stories tests
+    // ===========================================
+
+    #[test]
+    fn test_forget_stories_drops_story_records_and_orphaned_files() {
+        let file = |touched_by: &[&str]| FileInfo {
+            purpose: String::new(),
+            key_symbols: vec![],
+            touched_by: touched_by.iter().map(|s| s.to_string()).collect(),
+            line_count: 10,
+        };
+        let mut knowledge = ProjectKnowledge::default();
+        knowledge
+            .files
+            .insert(PathBuf::from("src/only_two.rs"), file(&["US-002"]));
+        knowledge
+            .files
+            .insert(PathBuf::from("src/shared.rs"), file(&["US-001", "US-002"]));
+        for id in ["US-001", "US-002"] {
+            knowledge.decisions.push(Decision {
+                story_id: id.to_string(),
+                topic: "Storage".to_string(),
+                choice: "SQLite".to_string(),
+                rationale: "Simple".to_string(),
+            });
+            knowledge.story_changes.push(StoryChanges {
+                story_id: id.to_string(),
+                files_created: vec![],
+                files_modified: vec![],
+                files_deleted: vec![],
+                commit_hash: None,
+                base_commit: None,
+            });
+        }
+
+        knowledge.forget_stories(&["US-002".to_string()]);
+
+        assert_eq!(knowledge.decisions.len(), 1);
+        assert_eq!(knowledge.decisions[0].story_id, "US-001");
+        assert_eq!(knowledge.story_changes.len(), 1);
+        assert!(!knowledge
+            .files
+            .contains_key(&PathBuf::from("src/only_two.rs")));
+        assert_eq!(
+            knowledge.files[&PathBuf::from("src/shared.rs")].touched_by,
+            vec!["US-001"]
+        );
+    }
+
+    // ===========================================
+    // KnowledgeStore tests
+    // ===========================================
+
+    fn run_knowledge(files: &[(&str, &str)], topics: &[&str]) -> ProjectKnowledge {
+        let mut knowledge = ProjectKnowledge::default();
+        for (path, purpose) in files {
+            knowledge.files.insert(
+                PathBuf::from(path),
+                FileInfo {
+                    purpose: purpose.to_string(),
+                    key_symbols: vec![],
+                    touched_by: vec!["US-001".to_string()],
+                    line_count: 10,
+                },
+            );
+        }
+        for topic in topics {
+            knowledge.decisions.push(Decision {
+                story_id: "US-001".to_string(),
+                topic: topic.to_string(),
+                choice: format!("choice for {}", topic),
+                rationale: "Simple".to_string(),
+            });
+        }
+        knowledge
+    }
+
+    #[test]
+    fn test_store_merge_deduplicates_by_path_and_topic() {
+        let mut store = KnowledgeStore::default();
+        store.merge_run(
+            &run_knowledge(&[("src/db.rs", "Database access")], &["Storage"]),
+            5,
+        );
+        store.merge_run(&run_knowledge(&[("src/db.rs", "")], &["  storage "]), 5);
+
+        assert_eq!(store.runs_merged, 2);
+        assert_eq!(store.files.len(), 1);
+        let db = &store.files[&PathBuf::from("src/db.rs")];
+        assert_eq!(db.last_run, 2);
+        assert_eq!(db.entry.purpose, "Database access");
+        assert_eq!(store.decisions.len(), 1);
+        assert_eq!(store.decisions["storage"].entry.topic, "  storage ");
+    }
+
+    #[test]
+    fn test_store_decays_entries_not_learned_again() {
+        let mut store = KnowledgeStore::default();
+        store.merge_run(&run_knowledge(&[("src/old.rs", "Old")], &["Old"]), 2);
+        store.merge_run(&run_knowledge(&[("src/new.rs", "New")], &["New"]), 2);
+        assert_eq!(store.files.len(), 2);
+
+        store.merge_run(&run_knowledge(&[("src/new.rs", "New")], &[]), 2);
+        assert!(!store.files.contains_key(&PathBuf::from("src/old.rs")));
+        assert!(store.files.contains_key(&PathBuf::from("src/new.rs")));
+        assert_eq!(store.decisions.keys().collect::<Vec<_>>(), vec!["new"]);
+
+        let mut forever = KnowledgeStore::default();
+        for _ in 0..10 {
+            forever.merge_run(&ProjectKnowledge::default(), 0);
+        }
+        forever.merge_run(&run_knowledge(&[], &["Kept"]), 0);
+        forever.merge_run(&ProjectKnowledge::default(), 0);
+        assert_eq!(forever.decisions.len(), 1);
+    }
+
+    #[test]
+    fn test_store_forgets_deleted_files() {
+        let mut store = KnowledgeStore::default();
+        store.merge_run(&run_knowledge(&[("src/gone.rs", "Legacy")], &[]), 5);
+
+        let mut knowledge = ProjectKnowledge::default();
+        knowledge.story_changes.push(StoryChanges {
+            story_id: "US-001".to_string(),
+            files_created: vec![],
+            files_modified: vec![],
+            files_deleted: vec![PathBuf::from("src/gone.rs")],
+            commit_hash: None,
+            base_commit: None,
+        });
+        store.merge_run(&knowledge, 5);
+
+        assert!(store.files.is_empty());
+    }
+
+    #[test]
+    fn test_store_forgets_rolled_back_stories() {
+        let mut store = KnowledgeStore::default();
+        let mut rolled_back = run_knowledge(&[("src/db.rs", "Database access")], &["Storage"]);
+        rolled_back.decisions.push(Decision {
+            story_id: "US-002".to_string(),
+            topic: "Caching".to_string(),
+            choice: "None".to_string(),
+            rationale: "Simple".to_string(),
+        });
+        store.merge_run(&rolled_back, 5);
+        let mut later = run_knowledge(&[], &[]);
+        later.decisions.push(Decision {
+            story_id: "US-007".to_string(),
+            topic: "Storage".to_string(),
+            choice: "Postgres".to_string(),
+            rationale: "Scale".to_string(),
+        });
+        store.merge_run(&later, 5);
+
+        store.forget_stories(&rolled_back, &["US-001".to_string()]);
+
+        assert!(store.files.is_empty());
+        assert_eq!(store.decisions["storage"].entry.choice, "Postgres");
+        assert!(store.decisions.contains_key("caching"));
+    }
+
+    #[test]
+    fn test_store_save_load_and_inherited_order() {
+        let dir = tempfile::TempDir::new().unwrap();
+        let knowledge_dir = dir.path().join("knowledge");
+        assert!(KnowledgeStore::load(&knowledge_dir).unwrap().is_empty());
+
+        let mut store = KnowledgeStore::default();
+        store.merge_run(&run_knowledge(&[("src/a.rs", "A")], &["Alpha"]), 5);
+        store.merge_run(&run_knowledge(&[], &["Beta"]), 5);
+        store.save(&knowledge_dir).unwrap();
+
+        let loaded = KnowledgeStore::load(&knowledge_dir).unwrap();
+        assert_eq!(loaded.runs_merged, 2);
+        let inherited = loaded.inherited();
+        assert_eq!(inherited.files[&PathBuf::from("src/a.rs")].purpose, "A");
+        let topics: Vec<_> = inherited.decisions.iter().map(|d| &d.topic).collect();
+        assert_eq!(topics, vec!["Beta", "Alpha"]);
+    }
 }
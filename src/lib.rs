@@ -14,35 +14,51 @@
 //!
 //! - [`commands`] - CLI command handlers
 //! - [`runner`] - Main orchestration loop
+//! - [`parallel`] - Parallel execution of independent stories
 //! - [`claude`] - Claude CLI integration
 //! - [`gh`] - GitHub CLI integration
 //! - [`output`] - Terminal output formatting
 //! - [`state`] - State machine and persistence
 //! - [`config`] - Configuration management
 //! - [`spec`] - Spec/user story structures
+//! - [`spec_parser`] - Native Markdown spec parsing
+//! - [`spec_lint`] - Spec structural and quality checks
+//! - [`spec_diff`] - Spec fingerprints for detecting edits on resume
+//! - [`verify`] - Story verification commands
+//! - [`hooks`] - Lifecycle hook commands
+//! - [`rollback`] - Planning story rollbacks
+//! - [`dry_run`] - Rendering a run's prompts without running it
 
 pub mod claude;
 pub mod commands;
 pub mod completion;
 pub mod config;
 pub mod display;
+pub mod dry_run;
 pub mod error;
 pub mod gh;
 pub mod git;
+pub mod hooks;
 pub mod knowledge;
 pub mod output;
+pub mod parallel;
 pub mod progress;
 pub mod prompt;
 pub mod prompts;
+pub mod rollback;
 pub mod runner;
 pub mod self_test;
 pub mod signal;
 pub mod snapshot;
 pub mod spec;
+pub mod spec_diff;
+pub mod spec_lint;
+pub mod spec_parser;
 pub mod state;
 #[cfg(test)]
 pub mod test_utils;
 pub mod ui;
+pub mod verify;
 pub mod worktree;
 
 pub use display::{BannerColor, StoryResult};
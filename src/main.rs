@@ -4,10 +4,12 @@
 
 use autom8::commands::{
     all_sessions_status_command, clean_command, config_display_command, config_reset_command,
-    config_set_command, default_command, describe_command, global_status_command, gui_command,
-    improve_command, init_command, list_command, monitor_command, pr_review_command,
-    projects_command, resume_command, run_command, run_with_file, status_command, CleanOptions,
-    ConfigScope, ConfigSubcommand,
+    config_set_command, default_command, describe_command, dry_run_command, global_status_command,
+    gui_command, improve_command, init_command, list_command, monitor_command, pr_review_command,
+    projects_command, prompt_show_command, resume_command, rollback_command, run_command,
+    run_with_file, spec_add_story_command, spec_lint_command, spec_remove_story_command,
+    spec_reset_command, spec_set_priority_command, spec_show_command, status_command, CleanOptions,
+    ConfigScope, ConfigSubcommand, PromptSubcommand, SpecSubcommand,
 };
 use autom8::completion::{print_completion_script, ShellType, SUPPORTED_SHELLS};
 use autom8::output::{print_error, print_header};
@@ -59,6 +61,8 @@ enum Commands {
     autom8 run --spec feature.json           # Run on current branch
     autom8 run --worktree                    # Create dedicated worktree for parallel execution
     autom8 run --worktree --spec feature.json # Run in worktree with specific spec
+    autom8 run --dry-run --spec feature.json  # Print the prompts a run would send
+    autom8 run --dry-run --prompts-dir prompts/  # Write them to files instead
 
 WORKTREE MODE:
     When --worktree is enabled, autom8 creates a separate worktree directory
@@ -87,6 +91,16 @@ WORKTREE MODE:
         /// Bypasses the normal spec file requirement and cleans up all artifacts after completion.
         #[arg(long, conflicts_with = "spec")]
         self_test: bool,
+
+        /// Render the prompts the run would send to Claude, with estimated sizes,
+        /// without running Claude or changing anything in git.
+        #[arg(long, conflicts_with = "self_test")]
+        dry_run: bool,
+
+        /// With --dry-run, write each prompt to a numbered file in this directory
+        /// instead of printing it.
+        #[arg(long, value_name = "DIR", requires = "dry_run")]
+        prompts_dir: Option<PathBuf>,
     },
 
     /// Check the current run status
@@ -139,6 +153,32 @@ BEHAVIOR:
         list: bool,
     },
 
+    /// Undo a story's changes so it can run again
+    #[command(after_help = "EXAMPLES:
+    autom8 rollback US-003            # Undo US-003 and every story after it
+    autom8 rollback --to US-003       # Keep US-003, undo the stories after it
+    autom8 rollback US-003 -y         # Skip the confirmation prompt
+
+BEHAVIOR:
+    Resets the worktree to the commit before the story (or right after it
+    with --to), discarding uncommitted changes and untracked files.
+    The undone stories are marked not started in the spec and dropped from
+    the run's knowledge. Continue with 'autom8 resume'.
+    --to needs the story's own commit (commit_strategy = \"per_story\").")]
+    Rollback {
+        /// Story to undo, along with the stories after it
+        #[arg(required_unless_present = "to", conflicts_with = "to")]
+        story_id: Option<String>,
+
+        /// Keep this story and undo only the stories after it
+        #[arg(long, value_name = "STORY_ID")]
+        to: Option<String>,
+
+        /// Skip confirmation prompt
+        #[arg(short, long)]
+        yes: bool,
+    },
+
     /// Clean up sessions and worktrees from the project
     #[command(after_help = "EXAMPLES:
     autom8 clean                      # Remove completed/failed session state
@@ -205,10 +245,30 @@ CONFIG FILES:
 VALID KEYS:
     review              - Enable code review step (true/false)
     commit              - Enable auto-commit (true/false)
+    commit_strategy     - When to commit (end/per_story)
     pull_request        - Enable auto-PR creation (true/false)
     worktree            - Enable worktree mode (true/false)
     worktree_path_pattern - Pattern for worktree names (string)
     worktree_cleanup    - Auto-cleanup worktrees (true/false)
+    max_parallel_stories - Independent stories to run at once (number)
+    spec_migration_backup - Back up older spec files when upgrading (true/false)
+    max_iterations_per_story - Iterations allowed per story (number)
+    max_total_iterations - Story iterations allowed per run (number)
+    on_story_failure    - What to do when a story fails (fail/skip/pause)
+    story_timeout_minutes - Time limit per story iteration (minutes, default 0 = none)
+    review_timeout_minutes - Time limit per review (minutes, default 0 = none)
+    correct_timeout_minutes - Time limit per correction (minutes, 0 = none)
+    commit_timeout_minutes - Time limit for the commit agent (minutes, 0 = none)
+    pr_timeout_minutes  - Time limit for the PR template agent (minutes, 0 = none)
+    stall_timeout_minutes - Minutes without output before Claude counts as stalled (0 = never)
+    kill_on_stall       - Kill and retry stalled Claude processes (true/false)
+    claude_retry_attempts - Retries after a transient Claude failure (number, 0 = none)
+    max_run_tokens      - Tokens a run may use before pausing (number, 0 = no limit)
+    max_story_tokens    - Tokens one story may use before pausing (number, 0 = no limit)
+    max_run_cost_usd    - Estimated USD a run may spend before pausing (0 = no limit)
+    sandbox             - What agent processes are confined to (none/bwrap)
+    knowledge_decay_runs - Runs a stored knowledge entry survives unless relearned (number, 0 = forever)
+    knowledge_context_tokens - Tokens of project knowledge per story prompt (number, 0 = no limit)
 
 SUBCOMMANDS:
     set    Set a configuration value
@@ -270,6 +330,32 @@ BEHAVIOR:
     spec and session knowledge are included when a matching session exists.")]
     Improve,
 
+    /// Work with spec files
+    #[command(after_help = "EXAMPLES:
+    autom8 spec lint spec-auth.json           # Check a spec for problems
+    autom8 spec lint --json specs/*.json      # JSON output for hooks and CI
+    autom8 spec show                          # List the current spec's stories
+    autom8 spec add-story --title \"Add logout\" -c \"Session ends\"
+    autom8 spec set-priority US-004 2         # Run US-004 earlier
+    autom8 spec reset US-003                  # Re-implement a finished story
+    autom8 spec remove-story US-005
+
+Run 'autom8 spec <subcommand> --help' for more details on each subcommand.")]
+    Spec {
+        #[command(subcommand)]
+        subcommand: SpecSubcommand,
+    },
+
+    /// Show the prompts autom8 sends to Claude
+    #[command(after_help = "EXAMPLES:
+    autom8 prompt show --story US-003         # The prompt for US-003, with current knowledge
+
+See also 'autom8 run --dry-run' for every prompt of a run.")]
+    Prompt {
+        #[command(subcommand)]
+        subcommand: PromptSubcommand,
+    },
+
     /// Output shell completion script to stdout (hidden utility command)
     #[command(hide = true)]
     Completions {
@@ -313,6 +399,39 @@ fn main() {
             }
         }
 
+        // Spec utilities work on spec files directly and don't need a Runner
+        (None, Some(Commands::Spec { subcommand })) => match subcommand {
+            SpecSubcommand::Lint { files, json } => match spec_lint_command(files, *json) {
+                Ok(true) => Ok(()),
+                Ok(false) => std::process::exit(1),
+                Err(e) => Err(e),
+            },
+            SpecSubcommand::Show { spec } => spec_show_command(spec.as_deref()),
+            SpecSubcommand::AddStory(args) => spec_add_story_command(args),
+            SpecSubcommand::RemoveStory { id, force, spec } => {
+                spec_remove_story_command(id, *force, spec.as_deref())
+            }
+            SpecSubcommand::SetPriority { id, priority, spec } => {
+                spec_set_priority_command(id, *priority, spec.as_deref())
+            }
+            SpecSubcommand::Reset { id, spec } => spec_reset_command(id, spec.as_deref()),
+        },
+
+        // Prompt rendering reads specs and state without running anything
+        (
+            None,
+            Some(Commands::Run {
+                spec,
+                skip_review,
+                dry_run: true,
+                prompts_dir,
+                ..
+            }),
+        ) => dry_run_command(spec, *skip_review, prompts_dir.as_deref()),
+        (None, Some(Commands::Prompt { subcommand })) => match subcommand {
+            PromptSubcommand::Show { story, spec } => prompt_show_command(story, spec.as_deref()),
+        },
+
         // Completions command doesn't need a git repo
         (None, Some(Commands::Completions { shell })) => match ShellType::from_name(shell) {
             Ok(shell_type) => {
@@ -352,6 +471,7 @@ fn main() {
                         worktree,
                         no_worktree,
                         self_test,
+                        ..
                     }),
                 ) => run_command(
                     cli.verbose,
@@ -384,6 +504,12 @@ fn main() {
                     resume_command(session.as_deref(), *list)
                 }
 
+                (None, Some(Commands::Rollback { story_id, to, yes })) => match (story_id, to) {
+                    (_, Some(to)) => rollback_command(to, true, *yes),
+                    (Some(story_id), None) => rollback_command(story_id, false, *yes),
+                    (None, None) => unreachable!("clap requires a story ID or --to"),
+                },
+
                 (
                     None,
                     Some(Commands::Clean {
@@ -427,7 +553,9 @@ fn main() {
 
                 (None, Some(Commands::Improve)) => improve_command(cli.verbose),
 
-                // Completions already handled above
+                // Spec and completions already handled above
+                (None, Some(Commands::Spec { .. })) => unreachable!(),
+                (None, Some(Commands::Prompt { .. })) => unreachable!(),
                 (None, Some(Commands::Completions { .. })) => unreachable!(),
 
                 // No file and no command - check for existing state first, then start spec creation
@@ -491,6 +619,117 @@ mod tests {
         assert!(Cli::try_parse_from(["autom8", "completions", "bash"]).is_ok());
     }
 
+    #[test]
+    fn test_dry_run_and_prompt_show_parsing() {
+        let cli = Cli::try_parse_from(["autom8", "run", "--dry-run", "--prompts-dir", "prompts"])
+            .unwrap();
+        match cli.command {
+            Some(Commands::Run {
+                dry_run,
+                prompts_dir,
+                ..
+            }) => {
+                assert!(dry_run);
+                assert_eq!(prompts_dir, Some(PathBuf::from("prompts")));
+            }
+            _ => panic!("Expected run command"),
+        }
+
+        // --prompts-dir only makes sense for a dry run
+        assert!(Cli::try_parse_from(["autom8", "run", "--prompts-dir", "prompts"]).is_err());
+        assert!(Cli::try_parse_from(["autom8", "run", "--dry-run", "--self-test"]).is_err());
+
+        let cli = Cli::try_parse_from(["autom8", "prompt", "show", "--story", "US-003"]).unwrap();
+        match cli.command {
+            Some(Commands::Prompt {
+                subcommand: PromptSubcommand::Show { story, spec },
+            }) => {
+                assert_eq!(story, "US-003");
+                assert!(spec.is_none());
+            }
+            _ => panic!("Expected prompt show command"),
+        }
+    }
+
+    #[test]
+    fn test_spec_lint_parsing() {
+        let cli =
+            Cli::try_parse_from(["autom8", "spec", "lint", "--json", "a.json", "b.md"]).unwrap();
+        match cli.command {
+            Some(Commands::Spec {
+                subcommand: SpecSubcommand::Lint { files, json },
+            }) => {
+                assert_eq!(files, vec![PathBuf::from("a.json"), PathBuf::from("b.md")]);
+                assert!(json);
+            }
+            _ => panic!("Expected spec lint command"),
+        }
+
+        // At least one file is required
+        assert!(Cli::try_parse_from(["autom8", "spec", "lint"]).is_err());
+    }
+
+    #[test]
+    fn test_spec_edit_subcommands_parsing() {
+        let cli = Cli::try_parse_from([
+            "autom8",
+            "spec",
+            "add-story",
+            "--title",
+            "Logout",
+            "-c",
+            "Button shown",
+            "-c",
+            "Session ends",
+            "--depends-on",
+            "US-001",
+        ])
+        .unwrap();
+        match cli.command {
+            Some(Commands::Spec {
+                subcommand: SpecSubcommand::AddStory(args),
+            }) => {
+                assert_eq!(args.title, "Logout");
+                assert_eq!(args.criteria, vec!["Button shown", "Session ends"]);
+                assert_eq!(args.depends_on, vec!["US-001"]);
+                assert!(args.id.is_none() && args.priority.is_none() && args.spec.is_none());
+            }
+            _ => panic!("Expected spec add-story command"),
+        }
+
+        let cli = Cli::try_parse_from(["autom8", "spec", "set-priority", "US-002", "5"]).unwrap();
+        assert!(matches!(
+            cli.command,
+            Some(Commands::Spec {
+                subcommand: SpecSubcommand::SetPriority { priority: 5, .. }
+            })
+        ));
+
+        let cli =
+            Cli::try_parse_from(["autom8", "spec", "remove-story", "US-002", "--force"]).unwrap();
+        assert!(matches!(
+            cli.command,
+            Some(Commands::Spec {
+                subcommand: SpecSubcommand::RemoveStory { force: true, .. }
+            })
+        ));
+
+        for args in [
+            vec!["autom8", "spec", "show", "--spec", "spec-auth.json"],
+            vec!["autom8", "spec", "reset", "US-003"],
+        ] {
+            assert!(
+                Cli::try_parse_from(&args).is_ok(),
+                "{:?} should parse",
+                args
+            );
+        }
+
+        // Title and story IDs are required
+        assert!(Cli::try_parse_from(["autom8", "spec", "add-story"]).is_err());
+        assert!(Cli::try_parse_from(["autom8", "spec", "reset"]).is_err());
+    }
+
     #[test]
     fn test_version_flag() {
         for flag in ["--version", "-V"] {
@@ -604,12 +843,14 @@ mod tests {
         // Set subcommand with global flag
         let cli =
             Cli::try_parse_from(["autom8", "config", "set", "-g", "review", "false"]).unwrap();
-        if let Some(Commands::Config { subcommand, .. }) = cli.command {
-            if let Some(ConfigSubcommand::Set { global, key, value }) = subcommand {
-                assert!(global);
-                assert_eq!(key, "review");
-                assert_eq!(value, "false");
-            }
+        if let Some(Commands::Config {
+            subcommand: Some(ConfigSubcommand::Set { global, key, value }),
+            ..
+        }) = cli.command
+        {
+            assert!(global);
+            assert_eq!(key, "review");
+            assert_eq!(value, "false");
         }
 
         // Set requires key and value
@@ -125,7 +125,7 @@ mod tests {
 
     #[test]
     fn test_banner_width_clamping() {
-        assert!(MIN_BANNER_WIDTH < MAX_BANNER_WIDTH);
+        const { assert!(MIN_BANNER_WIDTH < MAX_BANNER_WIDTH) };
         assert_eq!(MIN_BANNER_WIDTH, 20);
         assert_eq!(MAX_BANNER_WIDTH, 80);
     }
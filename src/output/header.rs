@@ -3,6 +3,7 @@
 //! Provides session headers and iteration progress display.
 
 use crate::spec::Spec;
+use crate::spec_parser::SpecDiagnostic;
 use crate::state::MachineState;
 
 use super::colors::*;
@@ -102,6 +103,26 @@ pub fn print_generating_spec() {
     println!("{GRAY}{}{RESET}", "-".repeat(57));
 }
 
+/// Maximum number of parse diagnostics shown before summarizing the rest.
+const MAX_SPEC_DIAGNOSTICS_SHOWN: usize = 10;
+
+/// Print why a Markdown spec couldn't be parsed natively and is being converted with Claude.
+pub fn print_spec_parse_fallback(diagnostics: &[SpecDiagnostic]) {
+    println!("{YELLOW}Spec doesn't follow the standard format, converting with Claude:{RESET}");
+    for diagnostic in diagnostics.iter().take(MAX_SPEC_DIAGNOSTICS_SHOWN) {
+        println!(
+            "  {GRAY}line {}:{RESET} {}",
+            diagnostic.line, diagnostic.message
+        );
+    }
+    if diagnostics.len() > MAX_SPEC_DIAGNOSTICS_SHOWN {
+        println!(
+            "  {GRAY}... and {} more{RESET}",
+            diagnostics.len() - MAX_SPEC_DIAGNOSTICS_SHOWN
+        );
+    }
+}
+
 /// Print spec generated success message.
 pub fn print_spec_generated(spec: &Spec, output_path: &std::path::Path) {
     println!("{GRAY}{}{RESET}", "-".repeat(57));
@@ -234,7 +234,7 @@ mod tests {
     use crate::commands::GitContext;
     use crate::git::{DiffEntry, DiffStatus};
     use crate::knowledge::{Decision, Pattern, ProjectKnowledge};
-    use crate::spec::{Spec, UserStory};
+    use crate::spec::{Spec, StoryStatus, UserStory};
     use std::path::PathBuf;
 
     fn make_commit(hash: &str, message: &str) -> CommitInfo {
@@ -276,6 +276,7 @@ mod tests {
 
     fn make_spec() -> Spec {
         Spec {
+            schema_version: crate::spec::SPEC_SCHEMA_VERSION,
             project: "TestProject".to_string(),
             branch_name: "feature/test".to_string(),
             description: "Test description".to_string(),
@@ -286,8 +287,10 @@ mod tests {
                     description: "Test".to_string(),
                     acceptance_criteria: vec![],
                     priority: 1,
-                    passes: true,
+                    status: StoryStatus::done(),
                     notes: String::new(),
+                    depends_on: Vec::new(),
+                    verify: None,
                 },
                 UserStory {
                     id: "US-002".to_string(),
@@ -295,10 +298,13 @@ mod tests {
                     description: "Test 2".to_string(),
                     acceptance_criteria: vec![],
                     priority: 2,
-                    passes: false,
+                    status: StoryStatus::default(),
                     notes: String::new(),
+                    depends_on: Vec::new(),
+                    verify: None,
                 },
             ],
+            verify: Vec::new(),
         }
     }
 
@@ -542,7 +548,7 @@ mod tests {
         // Spec with all stories complete
         let mut spec = make_spec();
         for story in &mut spec.user_stories {
-            story.passes = true;
+            story.status = StoryStatus::done();
         }
 
         let context = FollowUpContext {
@@ -63,6 +63,28 @@ pub fn print_resuming_interrupted(machine_state: &str) {
     println!();
 }
 
+/// Print how a spec changed since the interrupted run started.
+pub fn print_spec_diff(diff: &crate::spec_diff::SpecDiff) {
+    println!("{YELLOW}The spec changed since this run started:{RESET}");
+    println!();
+    for (id, title) in &diff.added {
+        println!("  {GREEN}+ {}{RESET}: {} {GRAY}(added){RESET}", id, title);
+    }
+    for (id, title) in &diff.removed {
+        println!("  {RED}- {}{RESET}: {} {GRAY}(removed){RESET}", id, title);
+    }
+    for (id, title) in &diff.changed {
+        println!(
+            "  {YELLOW}~ {}{RESET}: {} {GRAY}(changed){RESET}",
+            id, title
+        );
+    }
+    if diff.spec_changed {
+        println!("  {YELLOW}~{RESET} Project, branch, description or verify commands");
+    }
+    println!();
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -89,4 +111,16 @@ mod tests {
         print_resuming_interrupted("Failed");
         // All should succeed without panic
     }
+
+    #[test]
+    fn test_print_spec_diff_smoke() {
+        let diff = crate::spec_diff::SpecDiff {
+            added: vec![("US-004".to_string(), "Export".to_string())],
+            removed: vec![("US-002".to_string(), "Import".to_string())],
+            changed: vec![("US-003".to_string(), "Search".to_string())],
+            spec_changed: true,
+        };
+        print_spec_diff(&diff);
+        print_spec_diff(&crate::spec_diff::SpecDiff::default());
+    }
 }
@@ -44,7 +44,7 @@ pub use error::{print_error_panel, ErrorDetails};
 pub use header::{
     print_claude_output, print_generating_spec, print_header, print_iteration_complete,
     print_iteration_start, print_proceeding_to_implementation, print_project_info,
-    print_spec_generated, print_spec_loaded, print_state_transition,
+    print_spec_generated, print_spec_loaded, print_spec_parse_fallback, print_state_transition,
 };
 pub use improve::{
     print_branch_info, print_context_banner, print_context_summary, print_files_changed_summary,
@@ -53,7 +53,8 @@ pub use improve::{
 };
 pub use messages::{
     print_error, print_info, print_interrupted, print_paused, print_resuming_interrupted,
-    print_warning, print_worktree_context, print_worktree_created, print_worktree_reused,
+    print_spec_diff, print_warning, print_worktree_context, print_worktree_created,
+    print_worktree_reused,
 };
 pub use pr::{
     format_pr_for_selection, print_branch_switched, print_no_open_prs, print_pr_already_exists,
@@ -71,10 +72,11 @@ pub use pr_review::{
     print_pr_review_streaming, print_pr_review_streaming_done, print_pr_review_summary,
 };
 pub use progress::{
-    make_progress_bar, print_all_complete, print_breadcrumb_trail, print_full_progress,
-    print_issues_found, print_max_review_iterations, print_review_passed, print_review_progress,
-    print_reviewing, print_run_completed, print_run_summary, print_skip_review,
-    print_story_complete, print_tasks_progress, StoryResult,
+    make_progress_bar, print_all_complete, print_breadcrumb_trail, print_claude_retry,
+    print_finished_with_skipped, print_full_progress, print_issues_found,
+    print_max_review_iterations, print_review_passed, print_review_progress, print_reviewing,
+    print_run_completed, print_run_summary, print_skip_review, print_story_complete,
+    print_story_skipped, print_tasks_progress, StoryResult,
 };
 pub use status::{
     print_branch_context_summary, print_commit_list, print_global_status, print_history_entry,
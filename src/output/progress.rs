@@ -2,7 +2,8 @@
 //!
 //! Provides progress bars, story completion tracking, and run summaries.
 
-use crate::progress::{format_tokens, Breadcrumb};
+use crate::progress::{format_cost_usd, format_tokens, Breadcrumb};
+use crate::state::FailedStory;
 
 use super::colors::*;
 
@@ -50,16 +51,57 @@ pub fn print_all_complete() {
     println!();
 }
 
-/// Print the final run completion message with duration and optional token count.
+/// Print the message shown when the run gives up on a story and moves on.
+pub fn print_story_skipped(story_id: &str, reason: &str) {
+    println!();
+    println!(
+        "{YELLOW}{BOLD}Skipping {}{RESET}{YELLOW}: {}{RESET}",
+        story_id, reason
+    );
+    println!("{GRAY}Stories that depend on it won't run.{RESET}");
+    println!();
+}
+
+/// Print the message shown when a failed Claude attempt will be retried.
+pub fn print_claude_retry(reason: &str, error: &str, retry_at: chrono::DateTime<chrono::Utc>) {
+    let wait_secs = retry_at
+        .signed_duration_since(chrono::Utc::now())
+        .num_seconds()
+        .max(0) as u64;
+    println!();
+    println!("{YELLOW}{BOLD}{}{RESET}{YELLOW}: {}{RESET}", reason, error);
+    println!(
+        "{GRAY}Retrying at {} (in {}). Press Ctrl+C to stop.{RESET}",
+        retry_at.with_timezone(&chrono::Local).format("%H:%M"),
+        crate::ui::shared::format_duration_secs(wait_secs)
+    );
+    println!();
+}
+
+/// Print the message shown when no more stories can run because some were skipped.
+pub fn print_finished_with_skipped(skipped: usize) {
+    println!();
+    println!(
+        "{YELLOW}{BOLD}No more stories can run ({} skipped){RESET}",
+        skipped
+    );
+    println!();
+}
+
+/// Print the final run completion message with duration, optional token count
+/// and optional estimated cost.
 ///
-/// Format: `✓ Run completed in 1h 2m 26s - 1,234,567 total tokens`
+/// Format: `✓ Run completed in 1h 2m 26s - 1,234,567 total tokens (~$4.12)`
 ///
-/// If total_tokens is None, the token portion is omitted:
+/// If total_tokens is None, the token and cost portion is omitted:
 /// Format: `✓ Run completed in 1h 2m 26s`
-pub fn print_run_completed(duration_secs: u64, total_tokens: Option<u64>) {
+pub fn print_run_completed(duration_secs: u64, total_tokens: Option<u64>, cost_usd: Option<f64>) {
     let duration = format_run_duration(duration_secs);
+    let cost_suffix = cost_usd
+        .map(|usd| format!(" (~{})", format_cost_usd(usd)))
+        .unwrap_or_default();
     let token_suffix = total_tokens
-        .map(|t| format!(" - {} total tokens", format_tokens(t)))
+        .map(|t| format!(" - {} total tokens{}", format_tokens(t), cost_suffix))
         .unwrap_or_default();
 
     println!();
@@ -173,6 +215,7 @@ pub fn print_run_summary(
     total_iterations: u32,
     total_duration_secs: u64,
     story_results: &[StoryResult],
+    failed_stories: &[FailedStory],
 ) {
     let hours = total_duration_secs / 3600;
     let mins = (total_duration_secs % 3600) / 60;
@@ -209,6 +252,14 @@ pub fn print_run_summary(
         }
         println!();
     }
+
+    if !failed_stories.is_empty() {
+        println!("{BOLD}Skipped stories:{RESET}");
+        for failed in failed_stories {
+            println!("  {YELLOW}{}{RESET}: {}", failed.story_id, failed.reason);
+        }
+        println!();
+    }
     println!("{GRAY}{}{RESET}", "-".repeat(57));
 }
 
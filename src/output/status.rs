@@ -2,7 +2,10 @@
 //!
 //! Output functions for displaying run status, project trees, and descriptions.
 
+use crate::progress::{format_cost_usd, format_tokens};
+use crate::spec::StoryState;
 use crate::state::{MachineState, RunState, RunStatus, SessionStatus};
+use crate::ui::shared::format_attention_counts;
 use chrono::Utc;
 
 use super::colors::*;
@@ -24,6 +27,20 @@ pub fn print_status(state: &RunState) {
         state.started_at.format("%Y-%m-%d %H:%M:%S")
     );
     println!("{BLUE}Tasks run:{RESET}  {}", state.iterations.len());
+    if let Some(usage) = &state.total_usage {
+        let config = state.effective_config();
+        let budget = if config.max_run_cost_usd > 0.0 {
+            format!(" of {}", format_cost_usd(config.max_run_cost_usd))
+        } else {
+            String::new()
+        };
+        println!(
+            "{BLUE}Usage:{RESET}     {} tokens, ~{}{}",
+            format_tokens(usage.total_tokens()),
+            format_cost_usd(state.estimated_cost_usd()),
+            budget
+        );
+    }
 }
 
 /// Print global status across all projects.
@@ -262,9 +279,15 @@ fn print_spec_summary(spec: &crate::config::SpecSummary) {
         };
         let first_line = desc_preview.lines().next().unwrap_or(&desc_preview);
         println!("{GRAY}{}{RESET}", first_line);
+        let attention = format_attention_counts(
+            spec.count_in_state(StoryState::Failed),
+            spec.count_in_state(StoryState::Blocked),
+        )
+        .map(|a| format!(", {}", a))
+        .unwrap_or_default();
         println!(
-            "{GRAY}({}/{} stories complete){RESET}",
-            spec.completed_count, spec.total_count
+            "{GRAY}({}/{} stories complete{}){RESET}",
+            spec.completed_count, spec.total_count, attention
         );
         println!();
         return;
@@ -299,16 +322,30 @@ fn print_spec_summary(spec: &crate::config::SpecSummary) {
 
     println!("{BOLD}User Stories:{RESET}");
     for story in &spec.stories {
-        let status_icon = if story.passes {
-            format!("{GREEN}✓{RESET}")
+        let state = story.status.state;
+        let icon_color = match state {
+            StoryState::Done => GREEN,
+            StoryState::InProgress => CYAN,
+            StoryState::Blocked => YELLOW,
+            StoryState::Failed => RED,
+            StoryState::NotStarted => GRAY,
+        };
+        let title_color = if state == StoryState::Done {
+            GREEN
         } else {
-            format!("{GRAY}○{RESET}")
+            RESET
         };
-        let title_color = if story.passes { GREEN } else { RESET };
         println!(
-            "  {} {BOLD}{}{RESET}: {}{}{}",
-            status_icon, story.id, title_color, story.title, RESET
+            "  {icon_color}{}{RESET} {BOLD}{}{RESET}: {}{}{}",
+            state.icon(),
+            story.id,
+            title_color,
+            story.title,
+            RESET
         );
+        if story.status.reason.is_some() {
+            println!("      {icon_color}{}{RESET}", story.status.describe());
+        }
     }
     println!();
 }
@@ -825,7 +862,7 @@ mod tests {
     #[test]
     fn test_us006_summary_counts() {
         // Test that summary correctly counts running and stale sessions
-        let sessions = vec![
+        let sessions = [
             make_session_status(
                 "main",
                 "main",
@@ -0,0 +1,222 @@
+//! Parallel story execution.
+//!
+//! Stories whose dependencies are complete (see [`Spec::ready_stories`]) can be
+//! implemented at the same time. Each one runs in a temporary git worktree on
+//! its own branch, with its own [`ClaudeRunner`] and a private copy of the spec
+//! kept in a directory of its own, which a sandboxed session can write to.
+//! When a story finishes, its work is committed in the worktree and the branch
+//! is merged back into the run branch by the runner.
+
+use std::collections::HashMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::mpsc;
+use std::thread;
+use std::time::Duration;
+
+use crate::claude::{ClaudeRunner, ClaudeStoryResult};
+use crate::error::Result;
+use crate::git::{self, CommitResult};
+use crate::knowledge::ProjectKnowledge;
+use crate::spec::{Spec, UserStory};
+use crate::state::IterationRecord;
+use crate::worktree::{create_worktree, remove_worktree, slugify_branch_name};
+
+/// How often the coordinator checks whether the batch should be stopped.
+const STOP_POLL_INTERVAL_MS: u64 = 200;
+
+/// A temporary worktree in which a single story is implemented.
+#[derive(Debug, Clone)]
+pub struct StoryWorkspace {
+    /// The story being implemented in this workspace
+    pub story_id: String,
+    /// Temporary branch the story is committed to
+    pub branch: String,
+    /// Path of the temporary worktree
+    pub worktree_path: PathBuf,
+    /// Private copy of the spec; Claude marks the story as passing here
+    pub spec_path: PathBuf,
+}
+
+impl StoryWorkspace {
+    /// Create a worktree and spec copy for a story, branching from the current HEAD.
+    /// The spec copy goes in its own directory under `workspaces_dir`.
+    ///
+    /// Leftovers from a previous crashed run (same run and story) are removed first.
+    pub fn create(
+        workspaces_dir: &Path,
+        run_id: &str,
+        run_branch: &str,
+        spec: &Spec,
+        story_id: &str,
+    ) -> Result<Self> {
+        let name = workspace_name(run_id, story_id);
+        let workspace = Self {
+            story_id: story_id.to_string(),
+            branch: story_branch_name(run_branch, story_id),
+            worktree_path: std::env::temp_dir().join(&name),
+            spec_path: workspaces_dir.join(&name).join("spec.json"),
+        };
+
+        workspace.cleanup();
+        create_worktree(&workspace.worktree_path, &workspace.branch)?;
+        if let Some(dir) = workspace.spec_path.parent() {
+            fs::create_dir_all(dir)?;
+        }
+        spec.save(&workspace.spec_path)?;
+
+        Ok(workspace)
+    }
+
+    /// Whether Claude marked the story as passing in the workspace spec copy.
+    pub fn story_passes(&self) -> bool {
+        Spec::read(&self.spec_path)
+            .ok()
+            .and_then(|spec| {
+                spec.user_stories
+                    .iter()
+                    .find(|s| s.id == self.story_id)
+                    .map(|s| s.passes())
+            })
+            .unwrap_or(false)
+    }
+
+    /// Commit everything the story changed in the worktree to its branch.
+    pub fn commit(&self, story: &UserStory) -> Result<CommitResult> {
+        git::commit_all_in(&self.worktree_path, &story.commit_message())
+    }
+
+    /// Remove the worktree, branch and spec copy with its directory.
+    ///
+    /// Best-effort: leftovers are also cleaned up the next time the same
+    /// workspace is created.
+    pub fn cleanup(&self) {
+        if self.worktree_path.exists() {
+            let _ = remove_worktree(&self.worktree_path, true);
+            let _ = fs::remove_dir_all(&self.worktree_path);
+        }
+        if git::branch_exists(&self.branch).unwrap_or(false) {
+            let _ = git::delete_branch(&self.branch);
+        }
+        if let Some(dir) = self.spec_path.parent() {
+            let _ = fs::remove_dir_all(dir);
+        }
+    }
+}
+
+/// Name of the temporary branch for a story in a run.
+///
+/// Uses a `--` separator rather than `/` so it can coexist with the run
+/// branch (git can't have both `a/b` and `a/b/c` refs).
+pub fn story_branch_name(run_branch: &str, story_id: &str) -> String {
+    format!("{}--{}", run_branch, story_slug(story_id))
+}
+
+/// Directory/file name for a story workspace, unique per run and story.
+fn workspace_name(run_id: &str, story_id: &str) -> String {
+    let short_run: String = run_id.chars().take(8).collect();
+    format!("autom8-{}-{}", short_run, story_slug(story_id))
+}
+
+fn story_slug(story_id: &str) -> String {
+    slugify_branch_name(story_id).to_lowercase()
+}
+
+/// Events sent from story worker threads to the coordinating thread.
+enum StoryEvent {
+    Output(String, String),
+    Finished(String, Result<ClaudeStoryResult>),
+}
+
+/// Run Claude for each story in its workspace concurrently.
+///
+/// Output from every story is forwarded to `on_output`, prefixed with the
+/// story ID. `should_stop` is polled while waiting; once it returns true,
+/// every running Claude process is killed. Each story gets its own runner
+/// from `new_runner`, which carries the run's time and stall limits.
+///
+/// Returns each story's result, keyed by story ID.
+pub fn run_stories_parallel(
+    spec: &Spec,
+    jobs: &[(&StoryWorkspace, &UserStory)],
+    previous_iterations: &[IterationRecord],
+    knowledge: &ProjectKnowledge,
+    new_runner: &dyn Fn() -> ClaudeRunner,
+    on_output: &mut dyn FnMut(&str),
+    should_stop: &dyn Fn() -> bool,
+) -> HashMap<String, Result<ClaudeStoryResult>> {
+    let runners: Vec<ClaudeRunner> = jobs
+        .iter()
+        .map(|(workspace, _)| new_runner().with_working_dir(&workspace.worktree_path))
+        .collect();
+    let mut results = HashMap::new();
+
+    thread::scope(|scope| {
+        let (tx, rx) = mpsc::channel();
+
+        for ((workspace, story), runner) in jobs.iter().zip(&runners) {
+            let tx = tx.clone();
+            scope.spawn(move || {
+                let result = runner.run(
+                    spec,
+                    story,
+                    &workspace.spec_path,
+                    previous_iterations,
+                    knowledge,
+                    |line| {
+                        let _ = tx.send(StoryEvent::Output(story.id.clone(), line.to_string()));
+                    },
+                );
+                let _ = tx.send(StoryEvent::Finished(story.id.clone(), result));
+            });
+        }
+        drop(tx);
+
+        let mut stopped = false;
+        while results.len() < jobs.len() {
+            match rx.recv_timeout(Duration::from_millis(STOP_POLL_INTERVAL_MS)) {
+                Ok(StoryEvent::Output(story_id, line)) => {
+                    on_output(&format!("[{}] {}", story_id, line));
+                }
+                Ok(StoryEvent::Finished(story_id, result)) => {
+                    results.insert(story_id, result);
+                }
+                Err(mpsc::RecvTimeoutError::Timeout) => {
+                    if !stopped && should_stop() {
+                        for runner in &runners {
+                            let _ = runner.kill();
+                        }
+                        stopped = true;
+                    }
+                }
+                Err(mpsc::RecvTimeoutError::Disconnected) => break,
+            }
+        }
+    });
+
+    results
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_story_branch_name_does_not_nest_under_run_branch() {
+        assert_eq!(
+            story_branch_name("autom8/feature", "US-001"),
+            "autom8/feature--us-001"
+        );
+    }
+
+    #[test]
+    fn test_workspace_name_is_unique_per_run_and_story() {
+        let a = workspace_name("0123456789abcdef", "US-001");
+        let b = workspace_name("0123456789abcdef", "US-002");
+        let c = workspace_name("fedcba9876543210", "US-001");
+
+        assert_eq!(a, "autom8-01234567-us-001");
+        assert_ne!(a, b);
+        assert_ne!(a, c);
+    }
+}
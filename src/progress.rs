@@ -481,6 +481,15 @@ pub fn format_tokens(tokens: u64) -> String {
     result
 }
 
+/// Format an amount in USD with cents (e.g., $12.34), or "<$0.01" for less.
+pub fn format_cost_usd(usd: f64) -> String {
+    if usd > 0.0 && usd < 0.01 {
+        "<$0.01".to_string()
+    } else {
+        format!("${:.2}", usd)
+    }
+}
+
 /// Format the display prefix based on story_id and iteration info
 /// Returns format like "[US-001 2/5]", "[Review 1/3]", "[Commit]", or just the story_id
 fn format_display_prefix(story_id: &str, iteration_info: &Option<IterationInfo>) -> String {
@@ -1264,6 +1273,13 @@ mod tests {
         assert_eq!(format_tokens(100000), "100,000");
     }
 
+    #[test]
+    fn test_format_cost_usd() {
+        assert_eq!(format_cost_usd(0.0), "$0.00");
+        assert_eq!(format_cost_usd(0.004), "<$0.01");
+        assert_eq!(format_cost_usd(12.345), "$12.35");
+    }
+
     #[test]
     fn test_outcome_with_tokens() {
         let outcome = Outcome::success("Done").with_tokens(45678);
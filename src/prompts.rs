@@ -55,6 +55,7 @@ Once you have enough information, generate a spec-<feature>.md file in this form
 
 ### US-002: [Story Title]
 **Priority:** 2
+**Depends On:** US-001
 
 ...
 ```
@@ -66,6 +67,8 @@ Once you have enough information, generate a spec-<feature>.md file in this form
 - Lower priority number = higher priority (1 is highest)
 - Include 3-7 user stories for most features
 - Stories should be ordered by dependency (prerequisites first)
+- Use `**Depends On:**` to list stories that must be completed first (omit it when there are none)
+- Optionally add a `## Verify` section (or a per-story `**Verify:**` list) of shell commands such as `- \`cargo test\`` that must pass before a story counts as done
 
 ## REQUIRED Save Location
 
@@ -211,7 +214,8 @@ Produce a JSON object with this exact structure:
       ],
       "priority": 1,
       "passes": false,
-      "notes": "Implementation hints or empty string"
+      "notes": "Implementation hints or empty string",
+      "dependsOn": []
     }
   ]
 }
@@ -226,7 +230,9 @@ Produce a JSON object with this exact structure:
 5. Priority should be a number (1 = highest priority)
 6. All stories should have `passes: false` initially
 7. Convert markdown checkbox items to plain text acceptance criteria
-8. Use camelCase for JSON keys
+8. Convert "**Depends On:**" lines to a `dependsOn` array of story IDs (empty array if absent)
+9. Convert a "## Verify" section to a top-level `verify` array of commands, and a story's "**Verify:**" list to that story's `verify` array (omit both when absent; "**Verify:** none" becomes an empty array)
+10. Use camelCase for JSON keys
 
 ## Output
 
@@ -278,7 +284,7 @@ Fix the JSON above OR regenerate it from the original spec if the JSON is too co
    - `project` (string)
    - `branchName` (string)
    - `description` (string)
-   - `userStories` (array of objects with: id, title, description, acceptanceCriteria, priority, passes, notes)
+   - `userStories` (array of objects with: id, title, description, acceptanceCriteria, priority, passes, notes, dependsOn)
 
 ## Expected Output Format
 
@@ -299,7 +305,8 @@ The output must be a valid JSON object with this exact structure:
       ],
       "priority": 1,
       "passes": false,
-      "notes": "Implementation hints or empty string"
+      "notes": "Implementation hints or empty string",
+      "dependsOn": []
     }
   ]
 }
@@ -0,0 +1,185 @@
+//! Story rollback planning.
+//!
+//! `autom8 rollback` resets the working tree to the commit a story started
+//! from, or with `--to` to the story's own commit. This module works out
+//! that commit and which stories it undoes, from the [`StoryChanges`]
+//! records of a session's runs.
+//!
+//! A story survives the reset only if its work is part of the target
+//! commit: its own commit is an ancestor of the target, or, for work that
+//! was never committed per story, something was committed after the story
+//! started. Everything else is left in the working tree, so it is undone.
+
+use std::collections::HashMap;
+
+use crate::error::{Autom8Error, Result};
+use crate::knowledge::{ProjectKnowledge, StoryChanges};
+
+/// What a rollback does.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RollbackPlan {
+    /// Commit the working tree is reset to.
+    pub target_commit: String,
+    /// Stories that go back to not started, in the order they ran.
+    pub story_ids: Vec<String>,
+}
+
+/// Plan a rollback from the knowledge of a session's runs, oldest first.
+///
+/// With `keep_story` false, `story_id` and everything after it is undone
+/// (`autom8 rollback <id>`). With it true, the tree goes back to right after
+/// `story_id` (`autom8 rollback --to <id>`), which needs the story's own
+/// commit. `is_ancestor(a, b)` reports whether commit `a` is `b` or one of
+/// its ancestors.
+pub fn plan_rollback(
+    runs: &[&ProjectKnowledge],
+    story_id: &str,
+    keep_story: bool,
+    is_ancestor: impl Fn(&str, &str) -> bool,
+) -> Result<RollbackPlan> {
+    // Each story's records from the latest run that worked on it, keyed with
+    // that run's index and the record's position so they can be ordered
+    let mut latest: HashMap<&str, ((usize, usize), Vec<&StoryChanges>)> = HashMap::new();
+    for (run, knowledge) in runs.iter().enumerate() {
+        let mut in_run: HashMap<&str, ((usize, usize), Vec<&StoryChanges>)> = HashMap::new();
+        for (pos, changes) in knowledge.story_changes.iter().enumerate() {
+            in_run
+                .entry(changes.story_id.as_str())
+                .or_insert_with(|| ((run, pos), Vec::new()))
+                .1
+                .push(changes);
+        }
+        latest.extend(in_run);
+    }
+
+    let Some((_, records)) = latest.get(story_id) else {
+        return Err(Autom8Error::Rollback(format!(
+            "no changes were recorded for {} in this session",
+            story_id
+        )));
+    };
+    let target_commit = if keep_story {
+        last_commit(records).ok_or_else(|| {
+            Autom8Error::Rollback(format!(
+                "{} wasn't committed on its own, so the tree right after it can't be restored",
+                story_id
+            ))
+        })?
+    } else {
+        records[0].base_commit.clone().ok_or_else(|| {
+            Autom8Error::Rollback(format!("no starting commit was recorded for {}", story_id))
+        })?
+    };
+
+    let survives = |records: &[&StoryChanges]| match last_commit(records) {
+        Some(hash) => is_ancestor(&hash, &target_commit),
+        None => match records[0].base_commit.as_deref() {
+            Some(base) => is_ancestor(base, &target_commit) && !is_ancestor(&target_commit, base),
+            // Recorded before starting commits were: leave it alone
+            None => true,
+        },
+    };
+
+    let mut undone: Vec<(&(usize, usize), &str)> = latest
+        .iter()
+        .filter(|(id, (_, records))| (**id == story_id && !keep_story) || !survives(records))
+        .map(|(id, (order, _))| (order, *id))
+        .collect();
+    undone.sort();
+
+    Ok(RollbackPlan {
+        target_commit,
+        story_ids: undone.into_iter().map(|(_, id)| id.to_string()).collect(),
+    })
+}
+
+/// The most recent commit made for a story, if any.
+fn last_commit(records: &[&StoryChanges]) -> Option<String> {
+    records.iter().rev().find_map(|c| c.commit_hash.clone())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Linear history c0 <- c1 <- c2 <- ...
+    fn is_ancestor(a: &str, b: &str) -> bool {
+        a[1..].parse::<u32>().unwrap() <= b[1..].parse::<u32>().unwrap()
+    }
+
+    fn changes(story_id: &str, base: &str, commit: Option<&str>) -> StoryChanges {
+        StoryChanges {
+            story_id: story_id.to_string(),
+            files_created: Vec::new(),
+            files_modified: Vec::new(),
+            files_deleted: Vec::new(),
+            commit_hash: commit.map(str::to_string),
+            base_commit: Some(base.to_string()),
+        }
+    }
+
+    fn run(story_changes: Vec<StoryChanges>) -> ProjectKnowledge {
+        ProjectKnowledge {
+            story_changes,
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_per_story_commits_roll_back_story_and_later_ones() {
+        let knowledge = run(vec![
+            changes("US-001", "c0", Some("c1")),
+            // A failed first attempt, then the passing one
+            changes("US-002", "c1", None),
+            changes("US-002", "c1", Some("c2")),
+            changes("US-003", "c2", Some("c3")),
+        ]);
+
+        let plan = plan_rollback(&[&knowledge], "US-002", false, is_ancestor).unwrap();
+        assert_eq!(plan.target_commit, "c1");
+        assert_eq!(plan.story_ids, vec!["US-002", "US-003"]);
+
+        let plan = plan_rollback(&[&knowledge], "US-002", true, is_ancestor).unwrap();
+        assert_eq!(plan.target_commit, "c2");
+        assert_eq!(plan.story_ids, vec!["US-003"]);
+    }
+
+    #[test]
+    fn test_uncommitted_stories_are_all_undone() {
+        let knowledge = run(vec![
+            changes("US-001", "c0", None),
+            changes("US-002", "c0", None),
+            changes("US-003", "c0", None),
+        ]);
+
+        let plan = plan_rollback(&[&knowledge], "US-002", false, is_ancestor).unwrap();
+        assert_eq!(plan.target_commit, "c0");
+        assert_eq!(plan.story_ids, vec!["US-001", "US-002", "US-003"]);
+
+        let err = plan_rollback(&[&knowledge], "US-002", true, is_ancestor).unwrap_err();
+        assert!(err.to_string().contains("wasn't committed on its own"));
+    }
+
+    #[test]
+    fn test_earlier_runs_and_unknown_stories() {
+        // The first run's work was committed at the end (c1) before a resume
+        let first = run(vec![
+            changes("US-001", "c0", None),
+            changes("US-002", "c0", None),
+        ]);
+        // The resumed run redid US-002 and went on to US-003
+        let second = run(vec![
+            changes("US-002", "c1", Some("c2")),
+            changes("US-003", "c2", None),
+        ]);
+
+        let plan = plan_rollback(&[&first, &second], "US-002", false, is_ancestor).unwrap();
+        assert_eq!(plan.target_commit, "c1");
+        assert_eq!(plan.story_ids, vec!["US-002", "US-003"]);
+
+        let err = plan_rollback(&[&first, &second], "US-009", false, is_ancestor).unwrap_err();
+        assert!(err
+            .to_string()
+            .contains("no changes were recorded for US-009"));
+    }
+}
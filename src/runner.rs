@@ -1,38 +1,56 @@
 use crate::claude::{
-    run_corrector, run_for_commit, run_for_spec_generation, run_reviewer, ClaudeOutcome,
-    ClaudeRunner, ClaudeStoryResult, CommitOutcome, CorrectorOutcome, ReviewOutcome,
+    build_story_knowledge_context, is_known_model, run_corrector, run_for_commit,
+    run_for_spec_generation, run_reviewer, AgentSessions, ClaudeActivity, ClaudeErrorInfo,
+    ClaudeFailureKind, ClaudeOutcome, ClaudePhase, ClaudeRunner, ClaudeStoryResult, CommitOutcome,
+    CommitResult as AgentCommitResult, CorrectorOutcome, CorrectorResult, ReviewOutcome,
+    ReviewResult, SpecGenerationResult,
+};
+use crate::config::{
+    get_effective_config, timeout_from_minutes, token_limit, CommitStrategy, Config,
+    StoryFailurePolicy,
 };
-use crate::config::get_effective_config;
 use crate::display::{BannerColor, StoryResult};
 use crate::error::{Autom8Error, Result};
 use crate::gh::{create_pull_request, PRResult};
-use crate::git;
+use crate::git::{self, CommitResult, MergeResult};
+use crate::hooks::{run_hook, Hook, HookContext};
+use crate::knowledge::KnowledgeStore;
 use crate::output::{
-    print_all_complete, print_breadcrumb_trail, print_claude_output, print_error_panel,
-    print_full_progress, print_generating_spec, print_header, print_info, print_interrupted,
-    print_issues_found, print_iteration_complete, print_iteration_start,
-    print_max_review_iterations, print_paused, print_phase_banner, print_phase_footer,
-    print_pr_already_exists, print_pr_skipped, print_pr_success, print_pr_updated,
-    print_proceeding_to_implementation, print_project_info, print_resuming_interrupted,
-    print_review_passed, print_reviewing, print_run_completed, print_run_summary,
-    print_skip_review, print_spec_generated, print_spec_loaded, print_state_transition,
-    print_story_complete, print_tasks_progress, print_worktree_context, print_worktree_created,
-    print_worktree_reused, BOLD, CYAN, GRAY, RESET, YELLOW,
+    print_all_complete, print_breadcrumb_trail, print_claude_output, print_claude_retry,
+    print_error_panel, print_finished_with_skipped, print_full_progress, print_generating_spec,
+    print_header, print_info, print_interrupted, print_issues_found, print_iteration_complete,
+    print_iteration_start, print_max_review_iterations, print_paused, print_phase_banner,
+    print_phase_footer, print_pr_already_exists, print_pr_skipped, print_pr_success,
+    print_pr_updated, print_proceeding_to_implementation, print_project_info,
+    print_resuming_interrupted, print_review_passed, print_reviewing, print_run_completed,
+    print_run_summary, print_skip_review, print_spec_generated, print_spec_loaded,
+    print_spec_parse_fallback, print_state_transition, print_story_complete, print_story_skipped,
+    print_tasks_progress, print_warning, print_worktree_context, print_worktree_created,
+    print_worktree_reused, ErrorDetails, BOLD, CYAN, GRAY, RESET, YELLOW,
 };
+use crate::parallel::{run_stories_parallel, StoryWorkspace};
 use crate::progress::{
     AgentDisplay, Breadcrumb, BreadcrumbState, ClaudeSpinner, Outcome, VerboseTimer,
 };
 use crate::signal::SignalHandler;
-use crate::spec::{Spec, UserStory};
+use crate::spec::{Spec, StoryState, StoryStatus, UserStory};
+use crate::spec_diff::SpecFingerprint;
+use crate::spec_parser::parse_markdown_spec;
 use crate::state::{
-    IterationStatus, LiveState, MachineState, RunMode, RunState, RunStatus, StateManager,
+    ClaudeRetry, IterationStatus, LiveState, MachineState, RunMode, RunState, RunStatus,
+    StateManager,
 };
+use crate::verify::{run_verify_commands, VerifyFailure};
 use crate::worktree::{
     ensure_worktree, format_worktree_error, generate_session_id, generate_worktree_path,
     is_in_worktree, remove_worktree, WorktreeResult,
 };
+use chrono::Utc;
+use std::collections::BTreeSet;
 use std::fs;
 use std::path::{Path, PathBuf};
+use std::sync::mpsc::{self, RecvTimeoutError};
+use std::sync::Mutex;
 use std::time::{Duration, Instant};
 
 // ============================================================================
@@ -41,7 +59,7 @@ use std::time::{Duration, Instant};
 
 /// Maximum number of review/correct iterations before giving up.
 /// This prevents infinite loops when the corrector cannot resolve review issues.
-const MAX_REVIEW_ITERATIONS: u32 = 3;
+pub(crate) const MAX_REVIEW_ITERATIONS: u32 = 3;
 
 // ============================================================================
 // Progress Display Helper (US-006)
@@ -56,25 +74,132 @@ const LIVE_FLUSH_LINE_COUNT: usize = 10;
 /// The heartbeat is updated every 2-3 seconds.
 const HEARTBEAT_INTERVAL_MS: u64 = 2500;
 
+/// Backoff for transient Claude failures: 30s, 60s, 120s, ... up to 10 minutes.
+const RETRY_BASE_DELAY_SECS: u64 = 30;
+const RETRY_MAX_DELAY_SECS: u64 = 600;
+
+/// Extra wait after a usage limit's reset time, in case the clocks disagree.
+const USAGE_LIMIT_RESET_GRACE_SECS: u64 = 60;
+
+/// How often a retry wait checks for Ctrl+C.
+const RETRY_WAIT_POLL_MS: u64 = 500;
+
+/// What live.json should report about the Claude process behind an operation,
+/// so the monitor can tell a stalled session from a busy one.
+struct LiveWatch {
+    /// Activity of the Claude process being run. `None` when several run at
+    /// once, in which case output written to live.json stands in for it.
+    activity: Option<ClaudeActivity>,
+    /// Seconds without output before the session counts as stalled.
+    stall_after_secs: Option<u64>,
+}
+
+impl LiveWatch {
+    fn new(config: &Config, runner: Option<&ClaudeRunner>) -> Self {
+        Self {
+            activity: runner.map(|r| r.activity().clone()),
+            stall_after_secs: timeout_from_minutes(config.stall_timeout_minutes)
+                .map(|limit| limit.as_secs()),
+        }
+    }
+}
+
+/// Build a Claude runner for one of the run's sessions, with a phase's time
+/// limit and, when `kill_on_stall` is set, the stall limit.
+fn claude_runner_for(agent: &AgentSessions, config: &Config, timeout_minutes: u32) -> ClaudeRunner {
+    let stall_limit = if config.kill_on_stall {
+        timeout_from_minutes(config.stall_timeout_minutes)
+    } else {
+        None
+    };
+    agent
+        .runner()
+        .with_timeout(timeout_from_minutes(timeout_minutes))
+        .with_stall_limit(stall_limit)
+        .with_knowledge_budget(token_limit(config.knowledge_context_tokens))
+}
+
+/// Models already warned about by [`warn_unpriced_models`].
+static WARNED_UNPRICED_MODELS: Mutex<BTreeSet<String>> = Mutex::new(BTreeSet::new());
+
+/// Warn, once per model, about models the run used that have no known
+/// pricing, since their cost is only a conservative guess.
+fn warn_unpriced_models(state: &RunState) {
+    let mut warned = WARNED_UNPRICED_MODELS
+        .lock()
+        .unwrap_or_else(|e| e.into_inner());
+    for model in state
+        .phase_usage
+        .values()
+        .filter_map(|u| u.model.as_deref())
+    {
+        if !is_known_model(model) && warned.insert(model.to_string()) {
+            print_warning(&format!(
+                "No pricing known for model '{}'; estimating its cost at the most \
+                 expensive known rates",
+                model
+            ));
+        }
+    }
+}
+
+/// How long to wait before retrying a failed Claude run, or `None` if it
+/// shouldn't be retried.
+///
+/// Transient failures back off exponentially and stop after `max_retries`
+/// attempts; usage limits wait until the reset time however often they recur.
+fn retry_delay(
+    kind: &ClaudeFailureKind,
+    retries: u32,
+    max_retries: u32,
+    now: chrono::DateTime<Utc>,
+) -> Option<Duration> {
+    match kind {
+        ClaudeFailureKind::Transient if retries < max_retries => {
+            let delay = RETRY_BASE_DELAY_SECS.saturating_mul(1 << retries.min(16));
+            Some(Duration::from_secs(delay.min(RETRY_MAX_DELAY_SECS)))
+        }
+        ClaudeFailureKind::UsageLimit { resets_at } => {
+            let until_reset = resets_at.signed_duration_since(now).num_seconds().max(0) as u64;
+            Some(Duration::from_secs(
+                until_reset + USAGE_LIMIT_RESET_GRACE_SECS,
+            ))
+        }
+        _ => None,
+    }
+}
+
+/// Headline for a retry, e.g. "Claude failed (retry 2 of 3)".
+fn retry_reason(kind: &ClaudeFailureKind, retries: u32, max_retries: u32) -> String {
+    match kind {
+        ClaudeFailureKind::UsageLimit { .. } => "Usage limit reached".to_string(),
+        _ => format!("Claude failed (retry {} of {})", retries + 1, max_retries),
+    }
+}
+
 /// Helper struct that wraps a callback and periodically flushes output to live.json.
 /// Flushes every ~200ms or every ~10 lines, whichever comes first.
-/// Also updates heartbeat every ~2.5 seconds to indicate the run is still active.
+/// The heartbeat is kept fresh separately by `with_progress_display_and_live`.
 struct LiveOutputFlusher<'a> {
     state_manager: &'a StateManager,
     live_state: LiveState,
+    watch: LiveWatch,
+    started_at: chrono::DateTime<Utc>,
     line_count_since_flush: usize,
     last_flush: Instant,
-    last_heartbeat: Instant,
 }
 
 impl<'a> LiveOutputFlusher<'a> {
-    fn new(state_manager: &'a StateManager, machine_state: MachineState) -> Self {
+    fn new(state_manager: &'a StateManager, machine_state: MachineState, watch: LiveWatch) -> Self {
+        let mut live_state = LiveState::new(machine_state);
+        live_state.stall_after_secs = watch.stall_after_secs;
         let mut flusher = Self {
             state_manager,
-            live_state: LiveState::new(machine_state),
+            live_state,
+            watch,
+            started_at: Utc::now(),
             line_count_since_flush: 0,
             last_flush: Instant::now(),
-            last_heartbeat: Instant::now(),
         };
         // Immediately flush to ensure live.json exists with current state
         flusher.flush();
@@ -84,6 +209,9 @@ impl<'a> LiveOutputFlusher<'a> {
     /// Append a line to the buffer and flush if thresholds are met.
     fn append(&mut self, line: &str) {
         self.live_state.append_line(line.to_string());
+        if self.watch.activity.is_none() {
+            self.live_state.last_output_at = Some(self.live_state.updated_at);
+        }
         self.line_count_since_flush += 1;
 
         // Check if we should flush output
@@ -94,18 +222,20 @@ impl<'a> LiveOutputFlusher<'a> {
         if time_elapsed || lines_threshold {
             self.flush();
         }
-
-        // Check if we should update heartbeat (even if no flush needed)
-        self.maybe_update_heartbeat();
     }
 
-    /// Update heartbeat if the interval has elapsed.
-    /// This ensures the heartbeat is updated even during periods of low output.
-    fn maybe_update_heartbeat(&mut self) {
-        if self.last_heartbeat.elapsed() >= Duration::from_millis(HEARTBEAT_INTERVAL_MS) {
-            self.live_state.update_heartbeat();
-            self.flush();
-            self.last_heartbeat = Instant::now();
+    /// Copy the Claude process's latest activity into the live state.
+    ///
+    /// Activity from before this operation started belongs to an earlier
+    /// process and is ignored.
+    fn sync_activity(&mut self) {
+        let Some(activity) = &self.watch.activity else {
+            return;
+        };
+        let snapshot = activity.snapshot();
+        if snapshot.last_line_at >= self.started_at {
+            self.live_state.last_output_at = Some(snapshot.last_line_at);
+            self.live_state.last_activity = snapshot.last_tool;
         }
     }
 
@@ -113,11 +243,11 @@ impl<'a> LiveOutputFlusher<'a> {
     fn flush(&mut self) {
         // Update heartbeat on every flush to keep it fresh
         self.live_state.update_heartbeat();
+        self.sync_activity();
         // Ignore errors - live output is best-effort for monitoring
         let _ = self.state_manager.save_live(&self.live_state);
         self.line_count_since_flush = 0;
         self.last_flush = Instant::now();
-        self.last_heartbeat = Instant::now();
     }
 
     /// Final flush to ensure all remaining output is written.
@@ -184,12 +314,15 @@ where
 /// Runs an operation with progress display and live output streaming to live.json.
 ///
 /// Similar to `with_progress_display`, but also writes streaming output to live.json
-/// for the monitor command to read. Flushes every ~200ms or ~10 lines.
+/// for the monitor command to read. Flushes every ~200ms or ~10 lines, and
+/// refreshes the heartbeat every ~2.5 seconds even while Claude is silent,
+/// so the monitor can show a stalled session instead of a dead one.
 ///
 /// # Arguments
 /// * `verbose` - Whether to use verbose mode (timer) or spinner mode
 /// * `state_manager` - StateManager for writing live output
 /// * `machine_state` - Current machine state to include in live.json
+/// * `watch` - Claude activity and stall threshold to report in live.json
 /// * `create_timer` - Factory function to create a VerboseTimer
 /// * `create_spinner` - Factory function to create a ClaudeSpinner
 /// * `run_operation` - The operation to run, receiving a callback for progress updates
@@ -197,10 +330,12 @@ where
 ///
 /// # Returns
 /// The result of the operation, after the display has been finished with the appropriate outcome.
+#[allow(clippy::too_many_arguments)]
 fn with_progress_display_and_live<T, F, M>(
     verbose: bool,
     state_manager: &StateManager,
     machine_state: MachineState,
+    watch: LiveWatch,
     create_timer: impl FnOnce() -> VerboseTimer,
     create_spinner: impl FnOnce() -> ClaudeSpinner,
     run_operation: F,
@@ -210,34 +345,184 @@ where
     F: FnOnce(&mut dyn FnMut(&str)) -> Result<T>,
     M: FnOnce(&Result<T>) -> Outcome,
 {
-    let mut live_flusher = LiveOutputFlusher::new(state_manager, machine_state);
-
-    let result = if verbose {
-        let mut timer = create_timer();
-        let result = run_operation(&mut |line| {
-            print_claude_output(line);
-            live_flusher.append(line);
-        });
-        let outcome = map_outcome(&result);
-        timer.finish_with_outcome(outcome);
-        result
-    } else {
-        let mut spinner = create_spinner();
-        let result = run_operation(&mut |line| {
-            spinner.update(line);
-            live_flusher.append(line);
+    let live_flusher = Mutex::new(LiveOutputFlusher::new(state_manager, machine_state, watch));
+    let (stop_heartbeat, heartbeat_stopped) = mpsc::channel::<()>();
+
+    let result = std::thread::scope(|scope| {
+        // Owned by this closure so a panic below still drops it and ends the thread
+        let stop_heartbeat = stop_heartbeat;
+        let flusher = &live_flusher;
+        scope.spawn(move || {
+            let interval = Duration::from_millis(HEARTBEAT_INTERVAL_MS);
+            while let Err(RecvTimeoutError::Timeout) = heartbeat_stopped.recv_timeout(interval) {
+                if let Ok(mut flusher) = flusher.lock() {
+                    flusher.flush();
+                }
+            }
         });
-        let outcome = map_outcome(&result);
-        spinner.finish_with_outcome(outcome);
+
+        let append = |line: &str| {
+            if let Ok(mut flusher) = flusher.lock() {
+                flusher.append(line);
+            }
+        };
+        let result = if verbose {
+            let mut timer = create_timer();
+            let result = run_operation(&mut |line| {
+                print_claude_output(line);
+                append(line);
+            });
+            let outcome = map_outcome(&result);
+            timer.finish_with_outcome(outcome);
+            result
+        } else {
+            let mut spinner = create_spinner();
+            let result = run_operation(&mut |line| {
+                spinner.update(line);
+                append(line);
+            });
+            let outcome = map_outcome(&result);
+            spinner.finish_with_outcome(outcome);
+            result
+        };
+        drop(stop_heartbeat);
         result
-    };
+    });
 
     // Ensure any remaining output is flushed
-    live_flusher.final_flush();
+    if let Ok(mut flusher) = live_flusher.into_inner() {
+        flusher.final_flush();
+    }
 
     result
 }
 
+/// Error details from a failed parallel story, kept for the error panel.
+struct ClaudeErrorDetails {
+    message: String,
+    exit_code: Option<i32>,
+    stderr: Option<String>,
+}
+
+/// Mark a parallel story's iteration as finished.
+fn finish_story_iteration(
+    state: &mut RunState,
+    story_id: &str,
+    status: IterationStatus,
+    output_snippet: &str,
+) {
+    if let Some(iter) = state.iteration_for_story_mut(story_id) {
+        iter.finished_at = Some(Utc::now());
+        iter.status = status;
+        iter.output_snippet = output_snippet.to_string();
+    }
+    state.machine_state = MachineState::PickingStory;
+}
+
+/// Start an iteration for a story and mark it in progress in the spec.
+///
+/// Retries note which attempt this is, so a story that keeps coming back
+/// is visible in `status` and the GUI. An iteration left waiting for a
+/// Claude retry is continued rather than counted as a new attempt. `model`
+/// is the one the run's backend asks story sessions to use.
+fn start_story(
+    state: &mut RunState,
+    spec_json_path: &Path,
+    story_id: &str,
+    model: Option<String>,
+) -> Result<()> {
+    if state.resume_retried_iteration(story_id) {
+        return Ok(());
+    }
+
+    let attempts = state.story_iteration_count(story_id);
+    let reason = (attempts > 0).then(|| {
+        format!(
+            "attempt {} of {}",
+            attempts + 1,
+            state.effective_config().max_iterations_per_story
+        )
+    });
+    state.start_iteration(story_id);
+    if let Some(iter) = state.iteration_for_story_mut(story_id) {
+        iter.model = model;
+    }
+
+    let mut spec = Spec::load(spec_json_path)?;
+    spec.set_story_status(story_id, StoryStatus::new(StoryState::InProgress, reason));
+    spec.save(spec_json_path)
+}
+
+/// Mark a story the run gave up on as failed in the spec.
+///
+/// When the run carries on without it, stories that depend on it are marked
+/// blocked.
+fn record_story_failure(
+    spec_json_path: &Path,
+    story_id: &str,
+    reason: &str,
+    block_dependents: bool,
+) -> Result<()> {
+    let mut spec = Spec::load(spec_json_path)?;
+    spec.set_story_status(
+        story_id,
+        StoryStatus::new(StoryState::Failed, Some(reason.to_string())),
+    );
+    if block_dependents {
+        spec.block_dependents(story_id);
+    }
+    spec.save(spec_json_path)
+}
+
+/// Mark the stories a resumed run stopped on as not started, so it retries
+/// them.
+///
+/// With `on_story_failure` set to `fail` or `pause`, the story the run
+/// stopped on is marked failed in the spec without being skipped. Failed
+/// stories the run never worked on, or skipped, stay failed until reset.
+fn retry_stopped_stories(state: &RunState, spec_json_path: &Path) -> Result<()> {
+    let mut spec = Spec::load(spec_json_path)?;
+    let stopped: Vec<String> = spec
+        .user_stories
+        .iter()
+        .filter(|s| s.status.state == StoryState::Failed)
+        .filter(|s| !state.is_story_failed(&s.id) && state.story_iteration_count(&s.id) > 0)
+        .map(|s| s.id.clone())
+        .collect();
+    if stopped.is_empty() {
+        return Ok(());
+    }
+    for id in &stopped {
+        spec.set_story_status(id, StoryStatus::new(StoryState::NotStarted, None));
+    }
+    spec.save(spec_json_path)
+}
+
+/// Run a story's verification commands, printing the outcome.
+///
+/// Returns the failure, if any command didn't exit 0.
+fn verify_story(story_id: &str, commands: &[String], dir: Option<&Path>) -> Option<VerifyFailure> {
+    print_info(&format!(
+        "Verifying {} ({} command{})",
+        story_id,
+        commands.len(),
+        if commands.len() == 1 { "" } else { "s" }
+    ));
+    match run_verify_commands(commands, dir) {
+        Ok(()) => {
+            print_info(&format!("{} passed verification", story_id));
+            None
+        }
+        Err(failure) => {
+            print_warning(&format!(
+                "{} failed verification: {}; it stays incomplete",
+                story_id, failure
+            ));
+            Some(failure)
+        }
+    }
+}
+
 /// Control flow action returned from extracted helper methods
 /// to communicate back to the main implementation loop.
 enum LoopAction {
@@ -329,6 +614,8 @@ pub struct Runner {
     /// Override for the pull_request config setting.
     /// None = use config value, Some(true/false) = override config.
     pull_request_override: Option<bool>,
+    /// How the run's agent sessions are started, from its config snapshot.
+    agent: AgentSessions,
 }
 
 impl Runner {
@@ -340,6 +627,7 @@ impl Runner {
             worktree_override: None,
             commit_override: None,
             pull_request_override: None,
+            agent: AgentSessions::default(),
         })
     }
 
@@ -391,6 +679,16 @@ impl Runner {
         Ok(config.worktree)
     }
 
+    /// The agent sessions a run with `config` starts, saying so if they are
+    /// recorded or replayed.
+    fn agent_sessions(&self, config: &Config) -> Result<AgentSessions> {
+        let agent = AgentSessions::from_config(config)?;
+        if let Some(description) = agent.describe() {
+            print_info(&description);
+        }
+        Ok(agent)
+    }
+
     /// Load the effective config, applying any CLI overrides.
     fn load_config_with_override(&self) -> Result<crate::config::Config> {
         let mut config = get_effective_config()?;
@@ -532,6 +830,9 @@ impl Runner {
             print_error_panel(error_panel_title, error_panel_msg, exit_code, stderr);
         }
 
+        // on_failure never blocks, so this can't fail
+        let _ = self.run_hook(state, Hook::OnFailure, state.current_story.as_deref(), None);
+
         // Print summary if provided
         if let Some(summary_fn) = print_summary {
             if let Err(e) = summary_fn() {
@@ -542,6 +843,191 @@ impl Runner {
         error
     }
 
+    /// Run a lifecycle hook with the run's context, if it is configured.
+    ///
+    /// A failing `pre_*` hook is shown in an error panel and returned as
+    /// `HookFailed`; any other failing hook is only a warning.
+    fn run_hook(
+        &self,
+        state: &RunState,
+        hook: Hook,
+        story_id: Option<&str>,
+        pr_url: Option<&str>,
+    ) -> Result<()> {
+        self.run_hook_in(state, hook, story_id, pr_url, None)
+    }
+
+    /// Run a lifecycle hook in `dir`, such as a parallel story's worktree,
+    /// or the current directory if `None`.
+    fn run_hook_in(
+        &self,
+        state: &RunState,
+        hook: Hook,
+        story_id: Option<&str>,
+        pr_url: Option<&str>,
+        dir: Option<&Path>,
+    ) -> Result<()> {
+        let context = HookContext {
+            run_id: state.run_id.clone(),
+            branch: state.branch.clone(),
+            story_id: story_id.map(str::to_string),
+            worktree_path: dir
+                .map(Path::to_path_buf)
+                .or_else(|| std::env::current_dir().ok()),
+            pr_url: pr_url.map(str::to_string),
+        };
+        match run_hook(&state.effective_config().hooks, hook, &context) {
+            Ok(ran) => {
+                if ran {
+                    print_info(&format!("Ran {} hook", hook));
+                }
+                Ok(())
+            }
+            Err(failure) if hook.is_blocking() => {
+                let mut details = ErrorDetails::new("Hook Failed", failure.to_string())
+                    .with_stderr(failure.output.clone())
+                    .with_source(hook.name());
+                if let Some(code) = failure.exit_code {
+                    details = details.with_exit_code(code);
+                }
+                details.print_panel();
+                Err(Autom8Error::HookFailed(failure.to_string()))
+            }
+            Err(failure) => {
+                print_warning(&failure.to_string());
+                Ok(())
+            }
+        }
+    }
+
+    /// Run a `pre_*` hook, failing the run if the hook fails so that the
+    /// step it guards doesn't start.
+    fn run_blocking_hook(
+        &self,
+        state: &mut RunState,
+        hook: Hook,
+        story_id: Option<&str>,
+    ) -> Result<()> {
+        self.run_blocking_hook_in(state, hook, story_id, None)
+    }
+
+    /// Run a `pre_*` hook in `dir`, like [`Self::run_blocking_hook`].
+    fn run_blocking_hook_in(
+        &self,
+        state: &mut RunState,
+        hook: Hook,
+        story_id: Option<&str>,
+        dir: Option<&Path>,
+    ) -> Result<()> {
+        self.run_hook_in(state, hook, story_id, None, dir)
+            .map_err(|e| {
+                self.handle_fatal_error(state, "", "", None, None, None::<fn() -> Result<()>>, e)
+            })
+    }
+
+    /// Fail the run when a review, correction or commit times out or is
+    /// killed for stalling, so the state is saved as `Failed` and `resume`
+    /// retries the phase. Other results pass through unchanged.
+    fn fail_on_timeout<T>(
+        &self,
+        state: &mut RunState,
+        phase: &str,
+        result: Result<T>,
+    ) -> Result<T> {
+        match result {
+            Err(e @ Autom8Error::ClaudeTimeout(_)) => Err(self.handle_fatal_error(
+                state,
+                &format!("{} Timed Out", phase),
+                &e.to_string(),
+                None,
+                None,
+                None::<fn() -> Result<()>>,
+                e,
+            )),
+            Err(e @ Autom8Error::ClaudeStalled(_)) => Err(self.handle_fatal_error(
+                state,
+                &format!("{} Stalled", phase),
+                &e.to_string(),
+                None,
+                None,
+                None::<fn() -> Result<()>>,
+                e,
+            )),
+            other => other,
+        }
+    }
+
+    /// Decide whether to retry a review, correction, commit or PR agent that
+    /// failed, waiting out the backoff or usage limit first, as story
+    /// iterations do. `retries` counts this attempt's transient retries so far.
+    ///
+    /// Returns `false` if the failure isn't worth retrying, the retries are
+    /// used up or Ctrl+C was pressed while waiting.
+    fn retry_phase(
+        &self,
+        phase: &str,
+        error: &ClaudeErrorInfo,
+        retries: &mut u32,
+        config: &Config,
+        signal_handler: &SignalHandler,
+    ) -> bool {
+        let Some(wait) = retry_delay(
+            &error.kind,
+            *retries,
+            config.claude_retry_attempts,
+            Utc::now(),
+        ) else {
+            return false;
+        };
+        let reason = format!(
+            "{}: {}",
+            phase,
+            retry_reason(&error.kind, *retries, config.claude_retry_attempts)
+        );
+        if error.kind == ClaudeFailureKind::Transient {
+            *retries += 1;
+        }
+        self.wait_for_retry(&reason, &error.message, wait, signal_handler)
+    }
+
+    /// Wait before retrying a failed Claude run, keeping live.json's
+    /// countdown and heartbeat fresh so the monitor shows the run waiting.
+    ///
+    /// Returns `false` if Ctrl+C was pressed while waiting.
+    fn wait_for_retry(
+        &self,
+        reason: &str,
+        error: &str,
+        wait: Duration,
+        signal_handler: &SignalHandler,
+    ) -> bool {
+        let retry_at = Utc::now() + chrono::Duration::from_std(wait).unwrap_or_default();
+        print_claude_retry(reason, error, retry_at);
+
+        let mut live_state = LiveState::new(MachineState::RunningClaude);
+        live_state.waiting_until = Some(retry_at);
+        live_state.waiting_reason = Some(reason.to_string());
+        let _ = self.state_manager.save_live(&live_state);
+
+        let deadline = Instant::now() + wait;
+        let mut last_heartbeat = Instant::now();
+        while Instant::now() < deadline {
+            if signal_handler.is_shutdown_requested() {
+                return false;
+            }
+            let slice = deadline
+                .saturating_duration_since(Instant::now())
+                .min(Duration::from_millis(RETRY_WAIT_POLL_MS));
+            std::thread::sleep(slice);
+            if last_heartbeat.elapsed() >= Duration::from_millis(HEARTBEAT_INTERVAL_MS) {
+                live_state.update_heartbeat();
+                let _ = self.state_manager.save_live(&live_state);
+                last_heartbeat = Instant::now();
+            }
+        }
+        !signal_handler.is_shutdown_requested()
+    }
+
     /// Handle graceful shutdown on SIGINT.
     ///
     /// This method:
@@ -639,6 +1125,27 @@ impl Runner {
         }
     }
 
+    /// Pause the run if it has used up a token or cost budget, before
+    /// running any of `story_ids` or, with none, before a review, correction,
+    /// commit or PR phase.
+    ///
+    /// Returns `Some(Autom8Error::Interrupted)` if paused, `None` otherwise.
+    fn check_budget(&self, state: &mut RunState, story_ids: &[&str]) -> Option<Autom8Error> {
+        warn_unpriced_models(state);
+        let config = state.effective_config();
+        let exceeded = state.exceeded_budget(&config, None).or_else(|| {
+            story_ids
+                .iter()
+                .find_map(|id| state.exceeded_budget(&config, Some(id)))
+        })?;
+        print_warning(&format!(
+            "{}. Pausing the run; raise the limit with `autom8 config set` and run \
+             `autom8 resume` to continue.",
+            exceeded
+        ));
+        Some(self.handle_pause(state))
+    }
+
     /// Check if Step mode requires a pause after story completion.
     ///
     /// In Step mode, the runner automatically pauses after each story completes.
@@ -661,10 +1168,20 @@ impl Runner {
         breadcrumb: &mut Breadcrumb,
         story_results: &[StoryResult],
         print_summary_fn: &impl Fn(u32, &[StoryResult]) -> Result<()>,
+        signal_handler: &SignalHandler,
     ) -> Result<()> {
+        self.run_blocking_hook(state, Hook::PreReview, None)?;
+
         state.review_iteration = 1;
+        let config = state.effective_config();
+        let reviewer = claude_runner_for(&self.agent, &config, config.review_timeout_minutes);
+        let corrector = claude_runner_for(&self.agent, &config, config.correct_timeout_minutes);
 
         loop {
+            if let Some(err) = self.check_budget(state, &[]) {
+                return Err(err);
+            }
+
             // Check if we've exceeded max review iterations
             if state.review_iteration > MAX_REVIEW_ITERATIONS {
                 print_max_review_iterations();
@@ -695,33 +1212,69 @@ impl Runner {
 
             // Run reviewer with progress display and live output (for heartbeat updates)
             let review_iter = state.review_iteration;
-            let review_result = with_progress_display_and_live(
-                self.verbose,
-                &self.state_manager,
-                MachineState::Reviewing,
-                || VerboseTimer::new_for_review(review_iter, MAX_REVIEW_ITERATIONS),
-                || ClaudeSpinner::new_for_review(review_iter, MAX_REVIEW_ITERATIONS),
-                |callback| run_reviewer(spec, review_iter, MAX_REVIEW_ITERATIONS, callback),
-                |res| match res {
-                    Ok(r) => {
-                        let tokens = r.usage.as_ref().map(|u| u.total_tokens());
-                        match &r.outcome {
-                            ReviewOutcome::Pass => {
-                                Outcome::success("No issues found").with_optional_tokens(tokens)
-                            }
-                            ReviewOutcome::IssuesFound => {
-                                Outcome::success("Issues found").with_optional_tokens(tokens)
+            let mut retries = 0;
+            let review_result = loop {
+                let result =
+                    with_progress_display_and_live(
+                        self.verbose,
+                        &self.state_manager,
+                        MachineState::Reviewing,
+                        LiveWatch::new(&config, Some(&reviewer)),
+                        || VerboseTimer::new_for_review(review_iter, MAX_REVIEW_ITERATIONS),
+                        || ClaudeSpinner::new_for_review(review_iter, MAX_REVIEW_ITERATIONS),
+                        |callback| {
+                            run_reviewer(
+                                &reviewer,
+                                spec,
+                                review_iter,
+                                MAX_REVIEW_ITERATIONS,
+                                callback,
+                            )
+                        },
+                        |res| match res {
+                            Ok(r) => {
+                                let tokens = r.usage.as_ref().map(|u| u.total_tokens());
+                                match &r.outcome {
+                                    ReviewOutcome::Pass => Outcome::success("No issues found")
+                                        .with_optional_tokens(tokens),
+                                    ReviewOutcome::IssuesFound => Outcome::success("Issues found")
+                                        .with_optional_tokens(tokens),
+                                    ReviewOutcome::Error(e) => Outcome::failure(e.to_string()),
+                                }
                             }
-                            ReviewOutcome::Error(e) => Outcome::failure(e.to_string()),
-                        }
+                            Err(e) => Outcome::failure(e.to_string()),
+                        },
+                    );
+                if let Ok(ReviewResult {
+                    outcome: ReviewOutcome::Error(e),
+                    usage,
+                    ..
+                }) = &result
+                {
+                    if self.retry_phase("Review", e, &mut retries, &config, signal_handler) {
+                        state.capture_usage("Final Review", usage.clone());
+                        continue;
                     }
-                    Err(e) => Outcome::failure(e.to_string()),
-                },
-            )?;
+                }
+                break result;
+            };
+            let review_result = self.fail_on_timeout(state, "Review", review_result)?;
 
             // Capture usage from review into "Final Review" phase (US-005)
             state.capture_usage("Final Review", review_result.usage.clone());
 
+            // The reviewer should only read; flag it if it changed the tree
+            if !review_result.changed_files.is_empty() {
+                let files: Vec<String> = review_result
+                    .changed_files
+                    .iter()
+                    .map(|path| path.display().to_string())
+                    .collect();
+                let reason = format!("reviewer changed {}", files.join(", "));
+                print_warning(&format!("Review {} flagged: {}", review_iter, reason));
+                state.flag_phase(&format!("Review {}", review_iter), &reason);
+            }
+
             // Print bottom border to close the output frame
             print_phase_footer(BannerColor::Cyan);
 
@@ -750,6 +1303,10 @@ impl Runner {
                     return Ok(()); // Exit review loop, proceed to commit
                 }
                 ReviewOutcome::IssuesFound => {
+                    if let Some(err) = self.check_budget(state, &[]) {
+                        return Err(err);
+                    }
+
                     // Transition to Correcting state
                     print_state_transition(MachineState::Reviewing, MachineState::Correcting);
                     state.transition_to(MachineState::Correcting);
@@ -763,27 +1320,52 @@ impl Runner {
                     print_issues_found(state.review_iteration, MAX_REVIEW_ITERATIONS);
 
                     // Run corrector with progress display and live output (for heartbeat updates)
-                    let corrector_result = with_progress_display_and_live(
-                        self.verbose,
-                        &self.state_manager,
-                        MachineState::Correcting,
-                        || VerboseTimer::new_for_correct(review_iter, MAX_REVIEW_ITERATIONS),
-                        || ClaudeSpinner::new_for_correct(review_iter, MAX_REVIEW_ITERATIONS),
-                        |callback| run_corrector(spec, review_iter, callback),
-                        |res| match res {
-                            Ok(r) => {
-                                let tokens = r.usage.as_ref().map(|u| u.total_tokens());
-                                match &r.outcome {
-                                    CorrectorOutcome::Complete => {
-                                        Outcome::success("Issues addressed")
-                                            .with_optional_tokens(tokens)
+                    let mut retries = 0;
+                    let corrector_result = loop {
+                        let result = with_progress_display_and_live(
+                            self.verbose,
+                            &self.state_manager,
+                            MachineState::Correcting,
+                            LiveWatch::new(&config, Some(&corrector)),
+                            || VerboseTimer::new_for_correct(review_iter, MAX_REVIEW_ITERATIONS),
+                            || ClaudeSpinner::new_for_correct(review_iter, MAX_REVIEW_ITERATIONS),
+                            |callback| run_corrector(&corrector, spec, review_iter, callback),
+                            |res| match res {
+                                Ok(r) => {
+                                    let tokens = r.usage.as_ref().map(|u| u.total_tokens());
+                                    match &r.outcome {
+                                        CorrectorOutcome::Complete => {
+                                            Outcome::success("Issues addressed")
+                                                .with_optional_tokens(tokens)
+                                        }
+                                        CorrectorOutcome::Error(e) => {
+                                            Outcome::failure(e.to_string())
+                                        }
                                     }
-                                    CorrectorOutcome::Error(e) => Outcome::failure(e.to_string()),
                                 }
+                                Err(e) => Outcome::failure(e.to_string()),
+                            },
+                        );
+                        if let Ok(CorrectorResult {
+                            outcome: CorrectorOutcome::Error(e),
+                            usage,
+                        }) = &result
+                        {
+                            if self.retry_phase(
+                                "Correction",
+                                e,
+                                &mut retries,
+                                &config,
+                                signal_handler,
+                            ) {
+                                state.capture_usage("Final Review", usage.clone());
+                                continue;
                             }
-                            Err(e) => Outcome::failure(e.to_string()),
-                        },
-                    )?;
+                        }
+                        break result;
+                    };
+                    let corrector_result =
+                        self.fail_on_timeout(state, "Correction", corrector_result)?;
 
                     // Capture usage from correction into "Final Review" phase (US-005)
                     // This accumulates with the review usage since both are part of the review loop
@@ -852,6 +1434,7 @@ impl Runner {
         state: &mut RunState,
         spec: &Spec,
         breadcrumb: &mut Breadcrumb,
+        signal_handler: &SignalHandler,
     ) -> Result<()> {
         // Get the effective config for this run (US-005)
         let config = state.effective_config();
@@ -868,6 +1451,12 @@ impl Runner {
             return Ok(());
         }
 
+        if let Some(err) = self.check_budget(state, &[]) {
+            return Err(err);
+        }
+
+        self.run_blocking_hook(state, Hook::PreCommit, None)?;
+
         print_state_transition(state.machine_state, MachineState::Committing);
         state.transition_to(MachineState::Committing);
         self.state_manager.save(state)?;
@@ -879,29 +1468,46 @@ impl Runner {
         print_phase_banner("COMMITTING", BannerColor::Cyan);
 
         // Run commit with progress display and live output (for heartbeat updates)
-        let commit_result = with_progress_display_and_live(
-            self.verbose,
-            &self.state_manager,
-            MachineState::Committing,
-            VerboseTimer::new_for_commit,
-            ClaudeSpinner::new_for_commit,
-            |callback| run_for_commit(spec, callback),
-            |res| match res {
-                Ok(r) => {
-                    let tokens = r.usage.as_ref().map(|u| u.total_tokens());
-                    match &r.outcome {
-                        CommitOutcome::Success(hash) => {
-                            Outcome::success(hash.clone()).with_optional_tokens(tokens)
-                        }
-                        CommitOutcome::NothingToCommit => {
-                            Outcome::success("Nothing to commit").with_optional_tokens(tokens)
+        let committer = claude_runner_for(&self.agent, &config, config.commit_timeout_minutes);
+        let mut retries = 0;
+        let commit_result = loop {
+            let result = with_progress_display_and_live(
+                self.verbose,
+                &self.state_manager,
+                MachineState::Committing,
+                LiveWatch::new(&config, Some(&committer)),
+                VerboseTimer::new_for_commit,
+                ClaudeSpinner::new_for_commit,
+                |callback| run_for_commit(&committer, spec, callback),
+                |res| match res {
+                    Ok(r) => {
+                        let tokens = r.usage.as_ref().map(|u| u.total_tokens());
+                        match &r.outcome {
+                            CommitOutcome::Success(hash) => {
+                                Outcome::success(hash.clone()).with_optional_tokens(tokens)
+                            }
+                            CommitOutcome::NothingToCommit => {
+                                Outcome::success("Nothing to commit").with_optional_tokens(tokens)
+                            }
+                            CommitOutcome::Error(e) => Outcome::failure(e.to_string()),
                         }
-                        CommitOutcome::Error(e) => Outcome::failure(e.to_string()),
                     }
+                    Err(e) => Outcome::failure(e.to_string()),
+                },
+            );
+            if let Ok(AgentCommitResult {
+                outcome: CommitOutcome::Error(e),
+                usage,
+            }) = &result
+            {
+                if self.retry_phase("Commit", e, &mut retries, &config, signal_handler) {
+                    state.capture_usage("PR & Commit", usage.clone());
+                    continue;
                 }
-                Err(e) => Outcome::failure(e.to_string()),
-            },
-        )?;
+            }
+            break result;
+        };
+        let commit_result = self.fail_on_timeout(state, "Commit", commit_result)?;
 
         // Capture usage from commit into "PR & Commit" phase (US-005)
         state.capture_usage("PR & Commit", commit_result.usage.clone());
@@ -913,8 +1519,14 @@ impl Runner {
         // Print breadcrumb trail after commit phase completion
         print_breadcrumb_trail(breadcrumb);
 
-        // Track whether commits were made for PR creation
-        let commits_were_made = matches!(&commit_result.outcome, CommitOutcome::Success(_));
+        // Track whether commits were made for PR creation, including any
+        // made per story along the way
+        let commits_were_made = matches!(&commit_result.outcome, CommitOutcome::Success(_))
+            || state
+                .knowledge
+                .story_changes
+                .iter()
+                .any(|c| c.commit_hash.is_some());
 
         match &commit_result.outcome {
             CommitOutcome::Success(hash) => {
@@ -939,7 +1551,13 @@ impl Runner {
         }
 
         // PR Creation step
-        self.handle_pr_creation(state, spec, commits_were_made, config.pull_request_draft)
+        self.handle_pr_creation(
+            state,
+            spec,
+            commits_were_made,
+            config.pull_request_draft,
+            signal_handler,
+        )
     }
 
     /// Handle PR creation after committing.
@@ -949,15 +1567,30 @@ impl Runner {
         spec: &Spec,
         commits_were_made: bool,
         draft: bool,
+        signal_handler: &SignalHandler,
     ) -> Result<()> {
+        if let Some(err) = self.check_budget(state, &[]) {
+            return Err(err);
+        }
+
         print_state_transition(MachineState::Committing, MachineState::CreatingPR);
         state.transition_to(MachineState::CreatingPR);
         self.state_manager.save(state)?;
         self.flush_live(MachineState::CreatingPR);
 
-        match create_pull_request(spec, commits_were_made, draft) {
+        let config = state.effective_config();
+        let mut retries = 0;
+        match create_pull_request(
+            spec,
+            &state.failed_stories,
+            commits_were_made,
+            draft,
+            &claude_runner_for(&self.agent, &config, config.pr_timeout_minutes),
+            &mut |e| self.retry_phase("PR description", e, &mut retries, &config, signal_handler),
+        ) {
             Ok(PRResult::Success(url)) => {
                 print_pr_success(&url);
+                self.run_hook(state, Hook::PostPr, None, Some(&url))?;
                 print_state_transition(MachineState::CreatingPR, MachineState::Completed);
                 Ok(())
             }
@@ -968,11 +1601,13 @@ impl Runner {
             }
             Ok(PRResult::AlreadyExists(url)) => {
                 print_pr_already_exists(&url);
+                self.run_hook(state, Hook::PostPr, None, Some(&url))?;
                 print_state_transition(MachineState::CreatingPR, MachineState::Completed);
                 Ok(())
             }
             Ok(PRResult::Updated(url)) => {
                 print_pr_updated(&url);
+                self.run_hook(state, Hook::PostPr, None, Some(&url))?;
                 print_state_transition(MachineState::CreatingPR, MachineState::Completed);
                 Ok(())
             }
@@ -1012,8 +1647,13 @@ impl Runner {
         breadcrumb: &mut Breadcrumb,
         story_results: &[StoryResult],
         print_summary_fn: &impl Fn(u32, &[StoryResult]) -> Result<()>,
+        signal_handler: &SignalHandler,
     ) -> Result<LoopAction> {
-        print_all_complete();
+        if state.failed_stories.is_empty() {
+            print_all_complete();
+        } else {
+            print_finished_with_skipped(state.failed_stories.len());
+        }
 
         // Get the effective config for this run (US-005)
         let config = state.effective_config();
@@ -1023,29 +1663,39 @@ impl Runner {
             print_skip_review();
         } else {
             // Run review/correct loop
-            self.run_review_correct_loop(state, spec, breadcrumb, story_results, print_summary_fn)?;
+            self.run_review_correct_loop(
+                state,
+                spec,
+                breadcrumb,
+                story_results,
+                print_summary_fn,
+                signal_handler,
+            )?;
         }
 
         // Commit changes and create PR (respects commit and pull_request config)
-        self.handle_commit_and_pr(state, spec, breadcrumb)?;
+        self.handle_commit_and_pr(state, spec, breadcrumb, signal_handler)?;
 
         state.transition_to(MachineState::Completed);
         // Flush live state to ensure GUI sees the Completed state before cleanup
         self.flush_live(MachineState::Completed);
         self.state_manager.save(state)?;
+        self.run_hook(state, Hook::OnComplete, None, None)?;
         print_summary_fn(state.iteration, story_results)?;
 
-        // Print final run completion message with total tokens (US-007)
+        // Print final run completion message with total tokens and cost (US-007)
         let duration_secs = state.run_duration_secs();
         let total_tokens = state.total_usage.as_ref().map(|u| u.total_tokens());
-        print_run_completed(duration_secs, total_tokens);
+        let cost = total_tokens.map(|_| state.estimated_cost_usd());
+        print_run_completed(duration_secs, total_tokens, cost);
 
         self.archive_and_cleanup(state)?;
         Ok(LoopAction::Break)
     }
 
-    /// Handle an error from Claude story execution.
-    /// Transitions to Failed state and returns the appropriate error.
+    /// Handle an error from Claude story execution, including a timeout.
+    /// Records the failed iteration, then applies the `on_story_failure` policy,
+    /// which returns `error` if the run fails.
     #[allow(clippy::too_many_arguments)]
     fn handle_story_error(
         &self,
@@ -1054,6 +1704,7 @@ impl Runner {
         story_results: &mut Vec<StoryResult>,
         story_start: Instant,
         error_msg: &str,
+        error: Autom8Error,
         error_panel_title: &str,
         error_panel_msg: &str,
         exit_code: Option<i32>,
@@ -1061,7 +1712,6 @@ impl Runner {
         print_summary_fn: &impl Fn(u32, &[StoryResult]) -> Result<()>,
     ) -> Result<LoopAction> {
         state.finish_iteration(IterationStatus::Failed, error_msg.to_string());
-        state.transition_to(MachineState::Failed);
         // Clear live output when iteration finishes (US-003)
         let _ = self.state_manager.clear_live();
         self.state_manager.save(state)?;
@@ -1074,23 +1724,146 @@ impl Runner {
         });
 
         print_error_panel(error_panel_title, error_panel_msg, exit_code, stderr);
-        print_summary_fn(state.iteration, story_results)?;
-        Err(Autom8Error::ClaudeError(error_msg.to_string()))
+        self.handle_story_failure(
+            state,
+            story,
+            error_msg,
+            error,
+            story_results,
+            print_summary_fn,
+        )?;
+        Ok(LoopAction::Continue)
     }
 
-    /// Handle a single story iteration, processing the Claude result.
-    /// Returns LoopAction::Continue to continue the loop, LoopAction::Break to finish.
-    #[allow(clippy::too_many_arguments)]
-    fn handle_story_iteration(
+    /// Handle a story iteration whose Claude process was killed for going
+    /// quiet (`kill_on_stall`).
+    ///
+    /// The iteration is recorded as failed and the story is picked again,
+    /// so it is retried until it passes or hits `max_iterations_per_story`.
+    fn handle_story_stall(
         &self,
         state: &mut RunState,
-        spec: &Spec,
-        spec_json_path: &Path,
         story: &UserStory,
-        breadcrumb: &mut Breadcrumb,
-        story_results: &mut Vec<StoryResult>,
-        story_start: Instant,
+        error_msg: &str,
         claude_runner: &ClaudeRunner,
+    ) -> Result<LoopAction> {
+        state.finish_iteration(IterationStatus::Failed, error_msg.to_string());
+        // Clear live output when iteration finishes (US-003)
+        let _ = self.state_manager.clear_live();
+        state.transition_to(MachineState::PickingStory);
+        self.state_manager.save(state)?;
+
+        let last_activity = claude_runner
+            .activity()
+            .snapshot()
+            .last_tool
+            .map(|tool| format!(" (last activity: {})", tool))
+            .unwrap_or_default();
+        print_phase_footer(BannerColor::Cyan);
+        print_warning(&format!(
+            "{} stalled: {}{}. Retrying.",
+            story.id, error_msg, last_activity
+        ));
+        print_state_transition(MachineState::RunningClaude, MachineState::PickingStory);
+        Ok(LoopAction::Continue)
+    }
+
+    /// Apply the `on_story_failure` policy to a story that errored or hit
+    /// its iteration limit.
+    ///
+    /// Returns `Ok(())` when the run carries on without the story (`skip`).
+    /// Otherwise returns `error` after failing the run (`fail`), or
+    /// `Autom8Error::Interrupted` after pausing it (`pause`).
+    fn handle_story_failure(
+        &self,
+        state: &mut RunState,
+        story: &UserStory,
+        reason: &str,
+        error: Autom8Error,
+        story_results: &[StoryResult],
+        print_summary_fn: &impl Fn(u32, &[StoryResult]) -> Result<()>,
+    ) -> Result<()> {
+        let policy = state.effective_config().on_story_failure;
+        record_story_failure(
+            &state.spec_json_path,
+            &story.id,
+            reason,
+            policy == StoryFailurePolicy::Skip,
+        )?;
+        match policy {
+            StoryFailurePolicy::Skip => {
+                state.mark_story_failed(&story.id, reason);
+                state.transition_to(MachineState::PickingStory);
+                self.state_manager.save(state)?;
+                print_story_skipped(&story.id, reason);
+                Ok(())
+            }
+            StoryFailurePolicy::Pause => {
+                print_warning(&format!("{} failed: {}", story.id, reason));
+                state.transition_to(MachineState::PickingStory);
+                Err(self.handle_pause(state))
+            }
+            StoryFailurePolicy::Fail => {
+                let iteration = state.iteration;
+                Err(self.handle_fatal_error(
+                    state,
+                    "",
+                    "",
+                    None,
+                    None,
+                    Some(|| print_summary_fn(iteration, story_results)),
+                    error,
+                ))
+            }
+        }
+    }
+
+    /// Stop the run once it has used `max_total_iterations`.
+    ///
+    /// Pauses instead of failing when `on_story_failure = "pause"`, so the run
+    /// can be resumed with a fresh budget.
+    fn handle_run_iteration_limit(
+        &self,
+        state: &mut RunState,
+        story_results: &[StoryResult],
+        print_summary_fn: &impl Fn(u32, &[StoryResult]) -> Result<()>,
+    ) -> Autom8Error {
+        let config = state.effective_config();
+        let limit = config.max_total_iterations;
+        if config.on_story_failure == StoryFailurePolicy::Pause {
+            print_warning(&format!(
+                "Reached the limit of {} iterations for this run",
+                limit
+            ));
+            return self.handle_pause(state);
+        }
+
+        let iteration = state.iteration;
+        self.handle_fatal_error(
+            state,
+            "",
+            "",
+            None,
+            None,
+            Some(|| print_summary_fn(iteration, story_results)),
+            Autom8Error::MaxIterationsReached(limit),
+        )
+    }
+
+    /// Handle a single story iteration, processing the Claude result.
+    /// Returns LoopAction::Continue to continue the loop, LoopAction::Break to finish.
+    #[allow(clippy::too_many_arguments)]
+    fn handle_story_iteration(
+        &self,
+        state: &mut RunState,
+        spec: &Spec,
+        spec_json_path: &Path,
+        story: &UserStory,
+        breadcrumb: &mut Breadcrumb,
+        story_results: &mut Vec<StoryResult>,
+        story_start: Instant,
+        claude_runner: &ClaudeRunner,
+        signal_handler: &SignalHandler,
         print_summary_fn: &impl Fn(u32, &[StoryResult]) -> Result<()>,
     ) -> Result<LoopAction> {
         // Calculate story progress for display: [US-001 2/5]
@@ -1104,33 +1877,81 @@ impl Runner {
         let story_id = story.id.clone();
         let iterations = state.iterations.clone();
         let knowledge = state.knowledge.clone();
+        let config = state.effective_config();
 
         // Run Claude with progress display and live output streaming (US-003)
-        // Use the provided ClaudeRunner so it can be killed on interrupt
-        let result = with_progress_display_and_live(
-            self.verbose,
-            &self.state_manager,
-            MachineState::RunningClaude,
-            || VerboseTimer::new_with_story_progress(&story_id, story_index, total_stories),
-            || ClaudeSpinner::new_with_story_progress(&story_id, story_index, total_stories),
-            |callback| {
-                claude_runner.run(
-                    spec,
-                    story,
-                    spec_json_path,
-                    &iterations,
-                    &knowledge,
-                    callback,
-                )
-            },
-            |res| match res {
-                Ok(result) => {
-                    let tokens = result.usage.as_ref().map(|u| u.total_tokens());
-                    Outcome::success("Implementation done").with_optional_tokens(tokens)
-                }
-                Err(e) => Outcome::failure(e.to_string()),
-            },
-        );
+        // Use the provided ClaudeRunner so it can be killed on interrupt.
+        // Transient failures and usage limits are retried within the iteration.
+        let result = loop {
+            let result = with_progress_display_and_live(
+                self.verbose,
+                &self.state_manager,
+                MachineState::RunningClaude,
+                LiveWatch::new(&config, Some(claude_runner)),
+                || VerboseTimer::new_with_story_progress(&story_id, story_index, total_stories),
+                || ClaudeSpinner::new_with_story_progress(&story_id, story_index, total_stories),
+                |callback| {
+                    claude_runner.run(
+                        spec,
+                        story,
+                        spec_json_path,
+                        &iterations,
+                        &knowledge,
+                        callback,
+                    )
+                },
+                |res| match res {
+                    Ok(ClaudeStoryResult {
+                        outcome: ClaudeOutcome::Error(e),
+                        ..
+                    }) => Outcome::failure(e.to_string()),
+                    Ok(result) => {
+                        let tokens = result.usage.as_ref().map(|u| u.total_tokens());
+                        Outcome::success("Implementation done").with_optional_tokens(tokens)
+                    }
+                    Err(e) => Outcome::failure(e.to_string()),
+                },
+            );
+
+            let Ok(ClaudeStoryResult {
+                outcome: ClaudeOutcome::Error(error_info),
+                usage,
+                ..
+            }) = &result
+            else {
+                break result;
+            };
+            let retries = state.transient_retry_count(&story.id);
+            let Some(wait) = retry_delay(
+                &error_info.kind,
+                retries,
+                config.claude_retry_attempts,
+                Utc::now(),
+            ) else {
+                break result;
+            };
+
+            state.capture_usage(&story.id, usage.clone());
+            state.record_retry(
+                &story.id,
+                ClaudeRetry {
+                    failed_at: Utc::now(),
+                    error: error_info.message.clone(),
+                    failure: error_info.kind,
+                    wait_secs: wait.as_secs(),
+                },
+            );
+            self.state_manager.save(state)?;
+
+            let reason = retry_reason(&error_info.kind, retries, config.claude_retry_attempts);
+            if !self.wait_for_retry(&reason, &error_info.message, wait, signal_handler) {
+                // Ctrl+C while waiting: the main loop handles the interruption
+                state.finish_iteration(IterationStatus::Failed, error_info.message.clone());
+                self.state_manager.save(state)?;
+                return Ok(LoopAction::Continue);
+            }
+        };
+        self.run_hook(state, Hook::PostStory, Some(&story.id), None)?;
 
         match result {
             Ok(ClaudeStoryResult {
@@ -1142,6 +1963,7 @@ impl Runner {
                 // Capture usage from story implementation (US-005)
                 state.capture_usage(&story.id, usage.clone());
                 state.set_iteration_usage(usage);
+                self.verify_newly_passing_stories(state, spec, spec_json_path)?;
                 self.handle_all_stories_complete_from_story(
                     state,
                     spec,
@@ -1152,6 +1974,7 @@ impl Runner {
                     work_summary,
                     &full_output,
                     print_summary_fn,
+                    signal_handler,
                 )
             }
             Ok(ClaudeStoryResult {
@@ -1163,6 +1986,7 @@ impl Runner {
                 // Capture usage from story implementation (US-005)
                 state.capture_usage(&story.id, usage.clone());
                 state.set_iteration_usage(usage);
+                self.verify_newly_passing_stories(state, spec, spec_json_path)?;
                 self.handle_iteration_complete(
                     state,
                     spec_json_path,
@@ -1187,6 +2011,7 @@ impl Runner {
                     story_results,
                     story_start,
                     &error_info.message,
+                    Autom8Error::ClaudeError(error_info.message.clone()),
                     "Claude Process Failed",
                     &error_info.message,
                     error_info.exit_code,
@@ -1194,18 +2019,30 @@ impl Runner {
                     print_summary_fn,
                 )
             }
-            Err(e) => self.handle_story_error(
-                state,
-                story,
-                story_results,
-                story_start,
-                &e.to_string(),
-                "Claude Error",
-                &e.to_string(),
-                None,
-                None,
-                print_summary_fn,
-            ),
+            Err(e @ Autom8Error::ClaudeStalled(_)) => {
+                self.handle_story_stall(state, story, &e.to_string(), claude_runner)
+            }
+            Err(e) => {
+                let message = e.to_string();
+                let title = if matches!(e, Autom8Error::ClaudeTimeout(_)) {
+                    "Claude Timed Out"
+                } else {
+                    "Claude Error"
+                };
+                self.handle_story_error(
+                    state,
+                    story,
+                    story_results,
+                    story_start,
+                    &message,
+                    e,
+                    title,
+                    &message,
+                    None,
+                    None,
+                    print_summary_fn,
+                )
+            }
         }
     }
 
@@ -1222,6 +2059,7 @@ impl Runner {
         work_summary: Option<String>,
         full_output: &str,
         print_summary_fn: &impl Fn(u32, &[StoryResult]) -> Result<()>,
+        signal_handler: &SignalHandler,
     ) -> Result<LoopAction> {
         state.finish_iteration(IterationStatus::Success, full_output.to_string());
         state.set_work_summary(work_summary.clone());
@@ -1229,16 +2067,25 @@ impl Runner {
         let _ = self.state_manager.clear_live();
 
         // Capture story knowledge from git diff and agent output (US-006)
-        state.capture_story_knowledge(&story.id, full_output, None);
+        let commit_hash = self.commit_passing_story(state, spec_json_path, story)?;
+        state.capture_story_knowledge(&story.id, full_output, commit_hash);
         self.state_manager.save(state)?;
 
         let duration = state.current_iteration_duration();
-        story_results.push(StoryResult {
-            id: story.id.clone(),
-            title: story.title.clone(),
-            passed: true,
-            duration_secs: duration,
-        });
+        let updated_spec = Spec::load(spec_json_path)?;
+        let story_passed = updated_spec
+            .user_stories
+            .iter()
+            .find(|s| s.id == story.id)
+            .is_some_and(|s| s.passes());
+        if story_passed {
+            story_results.push(StoryResult {
+                id: story.id.clone(),
+                title: story.title.clone(),
+                passed: true,
+                duration_secs: duration,
+            });
+        }
 
         // Print bottom border to close the output frame
         print_phase_footer(BannerColor::Cyan);
@@ -1247,11 +2094,10 @@ impl Runner {
         print_breadcrumb_trail(breadcrumb);
 
         // Show progress bar after story task completion
-        let updated_spec = Spec::load(spec_json_path)?;
         print_tasks_progress(updated_spec.completed_count(), updated_spec.total_count());
         println!();
 
-        if self.verbose {
+        if self.verbose && story_passed {
             print_story_complete(&story.id, duration);
         }
 
@@ -1277,27 +2123,131 @@ impl Runner {
                 breadcrumb,
                 story_results,
                 print_summary_fn,
+                signal_handler,
             )?;
         }
 
         // Commit changes and create PR (respects commit and pull_request config)
-        self.handle_commit_and_pr(state, spec, breadcrumb)?;
+        self.handle_commit_and_pr(state, spec, breadcrumb, signal_handler)?;
 
         state.transition_to(MachineState::Completed);
         // Flush live state to ensure GUI sees the Completed state before cleanup
         self.flush_live(MachineState::Completed);
         self.state_manager.save(state)?;
+        self.run_hook(state, Hook::OnComplete, None, None)?;
         print_summary_fn(state.iteration, story_results)?;
 
-        // Print final run completion message with total tokens (US-007)
+        // Print final run completion message with total tokens and cost (US-007)
         let run_duration = state.run_duration_secs();
         let total_tokens = state.total_usage.as_ref().map(|u| u.total_tokens());
-        print_run_completed(run_duration, total_tokens);
+        let cost = total_tokens.map(|_| state.estimated_cost_usd());
+        print_run_completed(run_duration, total_tokens, cost);
 
         self.archive_and_cleanup(state)?;
         Ok(LoopAction::Break)
     }
 
+    /// Run verification commands for stories Claude marked as passing during
+    /// the last iteration.
+    ///
+    /// Stories that pass are marked done. Stories whose commands fail go back
+    /// to in progress, and the failure is recorded on the story's latest
+    /// iteration so the next prompt includes the command output.
+    fn verify_newly_passing_stories(
+        &self,
+        state: &mut RunState,
+        spec_before: &Spec,
+        spec_json_path: &Path,
+    ) -> Result<()> {
+        let mut spec = Spec::load(spec_json_path)?;
+        let newly_passing: Vec<(String, Vec<String>)> = spec
+            .user_stories
+            .iter()
+            .filter(|s| s.passes())
+            .filter(|s| {
+                !spec_before
+                    .user_stories
+                    .iter()
+                    .any(|before| before.id == s.id && before.passes())
+            })
+            .map(|s| (s.id.clone(), spec.verify_commands(s).to_vec()))
+            .collect();
+
+        if newly_passing.is_empty() {
+            return Ok(());
+        }
+
+        let mut reverted = false;
+        for (story_id, commands) in newly_passing {
+            if commands.is_empty() {
+                spec.mark_story_complete(&story_id);
+                continue;
+            }
+            match verify_story(&story_id, &commands, None) {
+                Some(failure) => {
+                    let reason = format!("verification failed: `{}`", failure.command);
+                    spec.set_story_status(
+                        &story_id,
+                        StoryStatus::new(StoryState::InProgress, Some(reason)),
+                    );
+                    reverted = true;
+                    if let Some(iter) = state.iteration_for_story_mut(&story_id) {
+                        iter.verify_failure = Some(failure.to_prompt_text());
+                    }
+                }
+                None => spec.mark_story_complete(&story_id),
+            }
+        }
+
+        spec.save(spec_json_path)?;
+        if reverted {
+            self.state_manager.save(state)?;
+        }
+        Ok(())
+    }
+
+    /// Commit a story's changes once it passes, when `commit_strategy = "per_story"`.
+    ///
+    /// Returns the short hash of the commit, or `None` if per-story commits
+    /// are off, the story hasn't passed or there was nothing to commit. A
+    /// failed commit only warns: the changes are picked up by the final commit.
+    fn commit_passing_story(
+        &self,
+        state: &mut RunState,
+        spec_json_path: &Path,
+        story: &UserStory,
+    ) -> Result<Option<String>> {
+        let config = state.effective_config();
+        if !config.commit
+            || config.commit_strategy != CommitStrategy::PerStory
+            || !git::is_git_repo()
+        {
+            return Ok(None);
+        }
+        let passed = Spec::load(spec_json_path)?
+            .story(&story.id)
+            .is_some_and(|s| s.passes());
+        if !passed {
+            return Ok(None);
+        }
+
+        self.run_blocking_hook(state, Hook::PreCommit, Some(&story.id))?;
+        match git::commit_all_in(Path::new("."), &story.commit_message())? {
+            CommitResult::Success(hash) => {
+                print_info(&format!("Committed {} ({})", story.id, hash));
+                Ok(Some(hash))
+            }
+            CommitResult::NothingToCommit => Ok(None),
+            CommitResult::Error(msg) => {
+                print_warning(&format!(
+                    "Could not commit {}: {} (its changes go into the final commit)",
+                    story.id, msg
+                ));
+                Ok(None)
+            }
+        }
+    }
+
     /// Handle a normal iteration completion (story done, more to go).
     #[allow(clippy::too_many_arguments)]
     fn handle_iteration_complete(
@@ -1316,46 +2266,595 @@ impl Runner {
         let _ = self.state_manager.clear_live();
 
         // Capture story knowledge from git diff and agent output (US-006)
-        state.capture_story_knowledge(&story.id, full_output, None);
+        let commit_hash = self.commit_passing_story(state, spec_json_path, story)?;
+        state.capture_story_knowledge(&story.id, full_output, commit_hash);
         self.state_manager.save(state)?;
 
         let duration = state.current_iteration_duration();
 
-        // Print bottom border to close the output frame
-        print_phase_footer(BannerColor::Cyan);
+        // Print bottom border to close the output frame
+        print_phase_footer(BannerColor::Cyan);
+
+        // Print breadcrumb trail after story phase completion
+        print_breadcrumb_trail(breadcrumb);
+
+        print_state_transition(MachineState::RunningClaude, MachineState::PickingStory);
+        print_iteration_complete(state.iteration);
+
+        // Reload spec and check if current story passed
+        let updated_spec = Spec::load(spec_json_path)?;
+        let story_passed = match updated_spec.story(&story.id) {
+            Some(s) => s.passes(),
+            None => {
+                // `autom8 spec remove-story --force` ran while it was implemented
+                print_info(&format!(
+                    "{} was removed from the spec, moving on",
+                    story.id
+                ));
+                false
+            }
+        };
+
+        if story_passed {
+            story_results.push(StoryResult {
+                id: story.id.clone(),
+                title: story.title.clone(),
+                passed: true,
+                duration_secs: duration,
+            });
+            if self.verbose {
+                print_story_complete(&story.id, duration);
+            }
+        }
+
+        // Show progress bar after story task completion
+        print_tasks_progress(updated_spec.completed_count(), updated_spec.total_count());
+        println!();
+
+        // Continue to next iteration
+        Ok(LoopAction::Continue)
+    }
+
+    /// Pick the stories to run as a parallel batch, if parallel execution applies.
+    ///
+    /// Returns an empty list (run sequentially) unless `max_parallel_stories > 1`,
+    /// commits are enabled, the git checkout has no uncommitted changes from
+    /// outside this run, and at least two ready stories haven't been re-queued
+    /// for sequential execution.
+    fn parallel_batch(&self, state: &RunState, spec: &Spec) -> Vec<UserStory> {
+        let config = state.effective_config();
+        let max = config.max_parallel_stories as usize;
+        if max <= 1 || !config.commit || !git::is_git_repo() {
+            return Vec::new();
+        }
+        if !state.started_clean && !git::is_clean().unwrap_or(false) {
+            return Vec::new();
+        }
+
+        // Stay within the run's iteration budget, and leave stories at their
+        // own limit to the sequential path, which applies the failure policy
+        let remaining = config.max_total_iterations.saturating_sub(state.iteration) as usize;
+        let batch: Vec<UserStory> = spec
+            .ready_stories()
+            .into_iter()
+            .filter(|s| !state.sequential_stories.contains(&s.id))
+            .filter(|s| !state.is_story_failed(&s.id))
+            .filter(|s| {
+                state.story_iteration_count(&s.id) < config.max_iterations_per_story
+                    || state.is_awaiting_retry(&s.id)
+            })
+            .take(max.min(remaining))
+            .cloned()
+            .collect();
+
+        if batch.len() < 2 {
+            Vec::new()
+        } else {
+            batch
+        }
+    }
+
+    /// Run a batch of independent stories at the same time, each in its own
+    /// temporary worktree, then merge the story branches into the run branch.
+    ///
+    /// The run's uncommitted work (e.g. with `commit_strategy = "end"`) is
+    /// committed first, since the worktrees are created from `HEAD`. Stories
+    /// whose branch can't be merged cleanly are re-queued to run
+    /// sequentially. Claude failures are handled by the `on_story_failure`
+    /// policy, after the other stories in the batch have been merged.
+    #[allow(clippy::too_many_arguments)]
+    fn run_parallel_batch(
+        &self,
+        state: &mut RunState,
+        spec: &Spec,
+        spec_json_path: &Path,
+        batch: &[UserStory],
+        story_results: &mut Vec<StoryResult>,
+        signal_handler: &SignalHandler,
+        print_summary_fn: &impl Fn(u32, &[StoryResult]) -> Result<()>,
+    ) -> Result<()> {
+        if !self.commit_before_batch(state, batch)? {
+            self.state_manager.save(state)?;
+            return Ok(());
+        }
+
+        // Capture the baseline commit before any story branches are created
+        state.capture_pre_story_state();
+
+        let workspaces_dir = self.state_manager.workspaces_dir();
+        let mut workspaces: Vec<(StoryWorkspace, &UserStory)> = Vec::new();
+        for story in batch {
+            match StoryWorkspace::create(
+                &workspaces_dir,
+                &state.run_id,
+                &state.branch,
+                spec,
+                &story.id,
+            ) {
+                Ok(workspace) => workspaces.push((workspace, story)),
+                Err(e) => {
+                    print_info(&format!(
+                        "Could not create a worktree for {} ({}), it will run sequentially",
+                        story.id, e
+                    ));
+                    state.sequential_stories.push(story.id.clone());
+                }
+            }
+        }
+
+        if workspaces.is_empty() {
+            self.state_manager.save(state)?;
+            return Ok(());
+        }
+
+        for (workspace, story) in &workspaces {
+            let hook = self.run_blocking_hook_in(
+                state,
+                Hook::PreStory,
+                Some(&story.id),
+                Some(&workspace.worktree_path),
+            );
+            if let Err(e) = hook {
+                for (workspace, _) in &workspaces {
+                    workspace.cleanup();
+                }
+                return Err(e);
+            }
+        }
+
+        let ids: Vec<&str> = workspaces.iter().map(|(_, s)| s.id.as_str()).collect();
+        let label = ids.join(", ");
+
+        print_state_transition(MachineState::PickingStory, MachineState::RunningClaude);
+        for (_, story) in &workspaces {
+            start_story(
+                state,
+                spec_json_path,
+                &story.id,
+                self.agent.model(ClaudePhase::Story),
+            )?;
+        }
+        self.state_manager.save(state)?;
+        self.flush_live(MachineState::RunningClaude);
+
+        print_phase_banner("RUNNING", BannerColor::Cyan);
+        print_info(&format!(
+            "Running {} independent stories in parallel: {}",
+            workspaces.len(),
+            label
+        ));
+
+        let iterations = state.iterations.clone();
+        let knowledge = state.knowledge.clone();
+        let config = state.effective_config();
+        let jobs: Vec<(&StoryWorkspace, &UserStory)> =
+            workspaces.iter().map(|(w, s)| (w, *s)).collect();
+
+        let mut results = with_progress_display_and_live(
+            self.verbose,
+            &self.state_manager,
+            MachineState::RunningClaude,
+            LiveWatch::new(&config, None),
+            || VerboseTimer::new(&label),
+            || ClaudeSpinner::new(&label),
+            |callback| {
+                Ok(run_stories_parallel(
+                    spec,
+                    &jobs,
+                    &iterations,
+                    &knowledge,
+                    &|| claude_runner_for(&self.agent, &config, config.story_timeout_minutes),
+                    callback,
+                    &|| signal_handler.is_shutdown_requested(),
+                ))
+            },
+            |res| match res {
+                Ok(results) => {
+                    let finished = results.values().filter(|r| r.is_ok()).count();
+                    Outcome::success(format!("{}/{} stories finished", finished, results.len()))
+                }
+                Err(e) => Outcome::failure(e.to_string()),
+            },
+        )?;
+
+        print_phase_footer(BannerColor::Cyan);
+
+        // Leave the interruption itself to the main loop's checkpoint
+        if signal_handler.is_shutdown_requested() {
+            for (workspace, _) in &workspaces {
+                workspace.cleanup();
+            }
+            return Ok(());
+        }
+
+        let mut first_error: Option<(String, ClaudeErrorDetails)> = None;
+        let mut errored: Vec<(String, String)> = Vec::new();
+        let mut retry_wait: Option<(Duration, String, String)> = None;
+        for (workspace, story) in &workspaces {
+            self.run_hook_in(
+                state,
+                Hook::PostStory,
+                Some(&story.id),
+                None,
+                Some(&workspace.worktree_path),
+            )?;
+            let result = results
+                .remove(&story.id)
+                .unwrap_or_else(|| Err(Autom8Error::ClaudeError("No result reported".into())));
+
+            match result {
+                Ok(ClaudeStoryResult {
+                    outcome: ClaudeOutcome::Error(error_info),
+                    usage,
+                    ..
+                }) => {
+                    state.capture_usage(&story.id, usage.clone());
+                    let retries = state.transient_retry_count(&story.id);
+                    let delay = retry_delay(
+                        &error_info.kind,
+                        retries,
+                        config.claude_retry_attempts,
+                        Utc::now(),
+                    );
+                    if let Some(iter) = state.iteration_for_story_mut(&story.id) {
+                        iter.usage = usage;
+                    }
+                    if let Some(wait) = delay {
+                        // The iteration is left open and the story pending, so
+                        // it is picked again after the wait and retried within
+                        // the same iteration
+                        state.record_retry(
+                            &story.id,
+                            ClaudeRetry {
+                                failed_at: Utc::now(),
+                                error: error_info.message.clone(),
+                                failure: error_info.kind,
+                                wait_secs: wait.as_secs(),
+                            },
+                        );
+                        state.machine_state = MachineState::PickingStory;
+                        let reason =
+                            retry_reason(&error_info.kind, retries, config.claude_retry_attempts);
+                        print_warning(&format!("{}: {}", story.id, error_info.message));
+                        if retry_wait
+                            .as_ref()
+                            .is_none_or(|(longest, ..)| wait > *longest)
+                        {
+                            retry_wait = Some((wait, reason, error_info.message));
+                        }
+                        workspace.cleanup();
+                        continue;
+                    }
+                    finish_story_iteration(
+                        state,
+                        &story.id,
+                        IterationStatus::Failed,
+                        &error_info.message,
+                    );
+                    errored.push((story.id.clone(), error_info.message.clone()));
+                    first_error.get_or_insert((
+                        "Claude Process Failed".to_string(),
+                        ClaudeErrorDetails {
+                            message: error_info.message,
+                            exit_code: error_info.exit_code,
+                            stderr: error_info.stderr,
+                        },
+                    ));
+                }
+                Err(e @ Autom8Error::ClaudeStalled(_)) => {
+                    // Left pending, so the story is picked again
+                    finish_story_iteration(
+                        state,
+                        &story.id,
+                        IterationStatus::Failed,
+                        &e.to_string(),
+                    );
+                    print_warning(&format!("{} stalled: {}. Retrying.", story.id, e));
+                }
+                Err(e) => {
+                    finish_story_iteration(
+                        state,
+                        &story.id,
+                        IterationStatus::Failed,
+                        &e.to_string(),
+                    );
+                    errored.push((story.id.clone(), e.to_string()));
+                    first_error.get_or_insert((
+                        "Claude Error".to_string(),
+                        ClaudeErrorDetails {
+                            message: e.to_string(),
+                            exit_code: None,
+                            stderr: None,
+                        },
+                    ));
+                }
+                Ok(story_result) => {
+                    let merged = self.merge_parallel_story(
+                        state,
+                        spec,
+                        spec_json_path,
+                        workspace,
+                        story,
+                        story_result,
+                        story_results,
+                    );
+                    if merged.is_err() {
+                        for (workspace, _) in &workspaces {
+                            workspace.cleanup();
+                        }
+                    }
+                    merged?;
+                }
+            }
+
+            workspace.cleanup();
+        }
+
+        let _ = self.state_manager.clear_live();
+
+        if let Some((title, details)) = first_error {
+            print_error_panel(
+                &title,
+                &details.message,
+                details.exit_code,
+                details.stderr.as_deref(),
+            );
+            let policy = state.effective_config().on_story_failure;
+            for (story_id, reason) in &errored {
+                record_story_failure(
+                    spec_json_path,
+                    story_id,
+                    reason,
+                    policy == StoryFailurePolicy::Skip,
+                )?;
+            }
+            match policy {
+                StoryFailurePolicy::Skip => {
+                    for (story_id, reason) in &errored {
+                        state.mark_story_failed(story_id, reason);
+                        print_story_skipped(story_id, reason);
+                    }
+                }
+                StoryFailurePolicy::Pause => {
+                    state.transition_to(MachineState::PickingStory);
+                    return Err(self.handle_pause(state));
+                }
+                StoryFailurePolicy::Fail => {
+                    state.transition_to(MachineState::Failed);
+                    self.state_manager.save(state)?;
+                    self.run_hook(state, Hook::OnFailure, None, None)?;
+                    print_summary_fn(state.iteration, story_results)?;
+                    return Err(Autom8Error::ClaudeError(details.message));
+                }
+            }
+        }
+
+        self.state_manager.save(state)?;
+
+        let updated_spec = Spec::load(spec_json_path)?;
+        print_tasks_progress(updated_spec.completed_count(), updated_spec.total_count());
+        println!();
+
+        // Ctrl+C while waiting is handled at the main loop's checkpoint
+        if let Some((wait, reason, error)) = retry_wait {
+            self.wait_for_retry(&reason, &error, wait, signal_handler);
+        }
+
+        Ok(())
+    }
+
+    /// Commit the run's outstanding changes so a parallel batch can build on them.
+    ///
+    /// Returns `false` if they couldn't be committed, after re-queueing the
+    /// batch to run sequentially.
+    fn commit_before_batch(&self, state: &mut RunState, batch: &[UserStory]) -> Result<bool> {
+        if git::is_clean().unwrap_or(false) {
+            return Ok(true);
+        }
+
+        let ids: Vec<&str> = batch.iter().map(|s| s.id.as_str()).collect();
+        self.run_blocking_hook(state, Hook::PreCommit, None)?;
+        let message = format!("Checkpoint before parallel stories {}", ids.join(", "));
+        match git::commit_all_in(Path::new("."), &message)? {
+            CommitResult::Success(hash) => {
+                print_info(&format!("Committed the run's changes so far ({})", hash));
+                Ok(true)
+            }
+            CommitResult::NothingToCommit => Ok(true),
+            CommitResult::Error(msg) => {
+                print_info(&format!(
+                    "Could not commit the run's changes ({}), {} will run sequentially",
+                    msg,
+                    ids.join(", ")
+                ));
+                state
+                    .sequential_stories
+                    .extend(ids.iter().map(|id| id.to_string()));
+                Ok(false)
+            }
+        }
+    }
+
+    /// Commit a finished parallel story in its worktree and merge it into the run branch.
+    ///
+    /// Verification commands run in the worktree first: a story that fails
+    /// them isn't merged, its iteration is recorded as failed and it is
+    /// re-queued to run sequentially. Otherwise the story's knowledge is
+    /// captured and its `passes` flag is copied into the run's spec. If the
+    /// branch can't be merged, the story is re-queued to run sequentially.
+    #[allow(clippy::too_many_arguments)]
+    fn merge_parallel_story(
+        &self,
+        state: &mut RunState,
+        spec: &Spec,
+        spec_json_path: &Path,
+        workspace: &StoryWorkspace,
+        story: &UserStory,
+        story_result: ClaudeStoryResult,
+        story_results: &mut Vec<StoryResult>,
+    ) -> Result<()> {
+        state.capture_usage(&story.id, story_result.usage.clone());
+        if let Some(iter) = state.iteration_for_story_mut(&story.id) {
+            iter.usage = story_result.usage.clone();
+            iter.work_summary = story_result.work_summary.clone();
+        }
+
+        let passed = workspace.story_passes();
+        let commands = spec.verify_commands(story);
+        if passed && !commands.is_empty() {
+            if let Some(failure) = verify_story(&story.id, commands, Some(&workspace.worktree_path))
+            {
+                // The branch is dropped with the worktree; the next attempt
+                // runs sequentially, with the failure in its prompt
+                let reason = format!("verification failed: `{}`", failure.command);
+                print_info(&format!(
+                    "{} failed verification, re-queued to run sequentially",
+                    story.id
+                ));
+                if let Some(iter) = state.iteration_for_story_mut(&story.id) {
+                    iter.verify_failure = Some(failure.to_prompt_text());
+                }
+                finish_story_iteration(state, &story.id, IterationStatus::Failed, &reason);
+                state.sequential_stories.push(story.id.clone());
+
+                let mut spec = Spec::load(spec_json_path)?;
+                spec.set_story_status(
+                    &story.id,
+                    StoryStatus::new(StoryState::InProgress, Some(reason)),
+                );
+                return spec.save(spec_json_path);
+            }
+        }
+        self.run_blocking_hook_in(
+            state,
+            Hook::PreCommit,
+            Some(&story.id),
+            Some(&workspace.worktree_path),
+        )?;
+        let merge_error = match workspace.commit(story)? {
+            CommitResult::Success(commit_hash) => {
+                let pre_merge = git::get_head_commit().ok();
+                let merge_message = format!("Merge {}: {}", story.id, story.title);
+                match git::merge_branch(&workspace.branch, &merge_message)? {
+                    MergeResult::Merged(_) => {
+                        state.pre_story_commit = pre_merge;
+                        state.capture_story_knowledge(
+                            &story.id,
+                            &story_result.full_output,
+                            Some(commit_hash),
+                        );
+                        None
+                    }
+                    MergeResult::Conflict(files) => Some(format!(
+                        "merge conflict in {}",
+                        files
+                            .iter()
+                            .map(|f| f.display().to_string())
+                            .collect::<Vec<_>>()
+                            .join(", ")
+                    )),
+                    MergeResult::Error(msg) => Some(format!("merge failed: {}", msg)),
+                }
+            }
+            CommitResult::NothingToCommit => {
+                state.pre_story_commit = None;
+                state.capture_story_knowledge(&story.id, &story_result.full_output, None);
+                None
+            }
+            CommitResult::Error(msg) => Some(format!("commit failed: {}", msg)),
+        };
 
-        // Print breadcrumb trail after story phase completion
-        print_breadcrumb_trail(breadcrumb);
+        if let Some(reason) = merge_error {
+            print_info(&format!(
+                "{} could not be merged ({}), re-queued to run sequentially",
+                story.id, reason
+            ));
+            finish_story_iteration(state, &story.id, IterationStatus::Failed, &reason);
+            state.sequential_stories.push(story.id.clone());
+            return Ok(());
+        }
 
-        print_state_transition(MachineState::RunningClaude, MachineState::PickingStory);
-        print_iteration_complete(state.iteration);
+        finish_story_iteration(
+            state,
+            &story.id,
+            IterationStatus::Success,
+            &story_result.full_output,
+        );
 
-        // Reload spec and check if current story passed
-        let updated_spec = Spec::load(spec_json_path)?;
-        let story_passed = updated_spec
-            .user_stories
-            .iter()
-            .find(|s| s.id == story.id)
-            .is_some_and(|s| s.passes);
+        if passed {
+            let mut spec = Spec::load(spec_json_path)?;
+            spec.mark_story_complete(&story.id);
+            spec.save(spec_json_path)?;
 
-        if story_passed {
+            let duration_secs = state
+                .iteration_for_story_mut(&story.id)
+                .and_then(|iter| {
+                    iter.finished_at
+                        .map(|end| (end - iter.started_at).num_seconds().max(0) as u64)
+                })
+                .unwrap_or(0);
             story_results.push(StoryResult {
                 id: story.id.clone(),
                 title: story.title.clone(),
                 passed: true,
-                duration_secs: duration,
+                duration_secs,
             });
             if self.verbose {
-                print_story_complete(&story.id, duration);
+                print_story_complete(&story.id, duration_secs);
             }
         }
 
-        // Show progress bar after story task completion
-        print_tasks_progress(updated_spec.completed_count(), updated_spec.total_count());
-        println!();
+        Ok(())
+    }
 
-        // Continue to next iteration
-        Ok(LoopAction::Continue)
+    /// Convert a Markdown spec to JSON with Claude, with progress display.
+    fn generate_spec_with_claude(
+        &self,
+        agent: &AgentSessions,
+        spec_content: &str,
+        spec_json_path: &Path,
+    ) -> Result<SpecGenerationResult> {
+        match with_progress_display(
+            self.verbose,
+            VerboseTimer::new_for_spec,
+            ClaudeSpinner::new_for_spec,
+            |callback| {
+                run_for_spec_generation(&agent.runner(), spec_content, spec_json_path, callback)
+            },
+            |res| match res {
+                Ok(r) => {
+                    let tokens = r.usage.as_ref().map(|u| u.total_tokens());
+                    Outcome::success("Spec generated").with_optional_tokens(tokens)
+                }
+                Err(e) => Outcome::failure(e.to_string()),
+            },
+        ) {
+            Ok(result) => Ok(result),
+            Err(e) => {
+                print_error_panel("Spec Generation Failed", &e.to_string(), None, None);
+                Err(e)
+            }
+        }
     }
 
     /// Run from a spec-<feature>.md markdown file - converts to JSON first, then implements
@@ -1374,6 +2873,7 @@ impl Runner {
 
         // Load effective config at startup, applying CLI flag override (US-002, US-005)
         let config = self.load_config_with_override()?;
+        let agent = self.agent_sessions(&config)?;
 
         // Canonicalize spec path
         let spec_path = spec_path
@@ -1419,24 +2919,16 @@ impl Runner {
 
         print_generating_spec();
 
-        // Run Claude to generate spec JSON with progress display
-        let spec_result = match with_progress_display(
-            self.verbose,
-            VerboseTimer::new_for_spec,
-            ClaudeSpinner::new_for_spec,
-            |callback| run_for_spec_generation(&spec_content, &spec_json_path, callback),
-            |res| match res {
-                Ok(r) => {
-                    let tokens = r.usage.as_ref().map(|u| u.total_tokens());
-                    Outcome::success("Spec generated").with_optional_tokens(tokens)
-                }
-                Err(e) => Outcome::failure(e.to_string()),
-            },
-        ) {
-            Ok(result) => result,
-            Err(e) => {
-                print_error_panel("Spec Generation Failed", &e.to_string(), None, None);
-                return Err(e);
+        // Parse well-formed specs natively; only fall back to Claude when that fails
+        let spec_result = match parse_markdown_spec(&spec_content) {
+            Ok(spec) => {
+                spec.save(&spec_json_path)?;
+                print_info("Spec follows the standard format, parsed without Claude");
+                SpecGenerationResult { spec, usage: None }
+            }
+            Err(diagnostics) => {
+                print_spec_parse_fallback(&diagnostics);
+                self.generate_spec_with_claude(&agent, &spec_content, &spec_json_path)?
             }
         };
         let spec = spec_result.spec;
@@ -1493,6 +2985,7 @@ impl Runner {
         if let Some((ref session_id, _)) = &worktree_context {
             state.session_id = Some(session_id.clone());
         }
+        state.spec_fingerprint = Some(SpecFingerprint::of(&spec));
         state.transition_to(MachineState::Initializing);
         effective_state_manager.save(&state)?;
         // Flush live.json immediately so GUI sees the state transition
@@ -1510,6 +3003,7 @@ impl Runner {
             worktree_override: self.worktree_override,
             commit_override: self.commit_override,
             pull_request_override: self.pull_request_override,
+            agent,
         };
 
         // Mark metadata as saved since the state was just saved above
@@ -1519,20 +3013,36 @@ impl Runner {
     }
 
     pub fn run(&self, spec_json_path: &Path) -> Result<()> {
+        self.start_run(spec_json_path, None)
+    }
+
+    /// Start a run of the spec at `spec_json_path`, or continue `resumed`,
+    /// a run that stopped before completing.
+    ///
+    /// A resumed run keeps its config snapshot, usage, iteration history,
+    /// knowledge and failed stories, so limits and budgets apply to the
+    /// feature as a whole rather than to each resume.
+    fn start_run(&self, spec_json_path: &Path, resumed: Option<RunState>) -> Result<()> {
         // IMPORTANT: State must NOT be persisted until after worktree context is determined.
         // Saving state before we know the correct session ID would create phantom sessions
         // in the main repo when running in worktree mode. State is first persisted in
         // run_implementation_loop() after the effective StateManager is known.
 
         // Check for existing active run
-        if self.state_manager.has_active_run()? {
+        if resumed.is_none() && self.state_manager.has_active_run()? {
             if let Some(state) = self.state_manager.load_current()? {
                 return Err(Autom8Error::RunInProgress(state.run_id));
             }
         }
 
-        // Load effective config at startup, applying CLI flag override (US-002, US-005)
+        // Load effective config at startup, applying CLI flag override (US-002, US-005).
+        // A resumed run keeps the config it started with.
         let config = self.load_config_with_override()?;
+        let config = match &resumed {
+            Some(state) => state.config.clone().unwrap_or(config),
+            None => config,
+        };
+        let agent = self.agent_sessions(&config)?;
 
         // Canonicalize path so resume works from any directory
         let spec_json_path = spec_json_path
@@ -1585,19 +3095,34 @@ impl Runner {
         }
 
         // Initialize state with config snapshot for resume support
-        let state = if let Some((ref session_id, _)) = worktree_context {
-            RunState::new_with_config_and_session(
-                spec_json_path.to_path_buf(),
-                spec.branch_name.clone(),
-                config,
-                session_id.clone(),
-            )
+        let state = if let Some(mut state) = resumed {
+            // Budgets follow the current config, so raising a limit is how
+            // a run paused on its budget is allowed to go on
+            state.resume(&get_effective_config()?);
+            retry_stopped_stories(&state, &spec_json_path)?;
+            if let Some((ref session_id, _)) = worktree_context {
+                state.session_id = Some(session_id.clone());
+            }
+            state
         } else {
-            RunState::new_with_config(
-                spec_json_path.to_path_buf(),
-                spec.branch_name.clone(),
-                config,
-            )
+            let mut state = if let Some((ref session_id, _)) = worktree_context {
+                RunState::new_with_config_and_session(
+                    spec_json_path.to_path_buf(),
+                    spec.branch_name.clone(),
+                    config,
+                    session_id.clone(),
+                )
+            } else {
+                RunState::new_with_config(
+                    spec_json_path.to_path_buf(),
+                    spec.branch_name.clone(),
+                    config,
+                )
+            };
+            // Fingerprint the spec so resume can tell if it was edited mid-run
+            state.spec_fingerprint = Some(SpecFingerprint::of(&spec));
+            state.started_clean = git::is_git_repo() && git::is_clean().unwrap_or(false);
+            state
         };
 
         print_state_transition(MachineState::Idle, MachineState::Initializing);
@@ -1612,6 +3137,7 @@ impl Runner {
             worktree_override: self.worktree_override,
             commit_override: self.commit_override,
             pull_request_override: self.pull_request_override,
+            agent,
         };
 
         worktree_runner.run_implementation_loop(state, &spec_json_path, worktree_setup_ctx)
@@ -1627,7 +3153,15 @@ impl Runner {
         let signal_handler = SignalHandler::new()?;
 
         // Create ClaudeRunner that can be killed on interrupt (US-004)
-        let claude_runner = ClaudeRunner::new();
+        // or when a story iteration runs past its time or stall limit
+        let config = state.effective_config();
+        let claude_runner = claude_runner_for(&self.agent, &config, config.story_timeout_minutes);
+
+        // Seed the run with what earlier runs of this project learned
+        match KnowledgeStore::load(&self.state_manager.knowledge_dir()) {
+            Ok(store) => state.knowledge.inherited = store.inherited(),
+            Err(e) => print_warning(&format!("Could not load the knowledge store: {}", e)),
+        }
 
         // Transition to PickingStory
         print_state_transition(state.machine_state, MachineState::PickingStory);
@@ -1639,6 +3173,8 @@ impl Runner {
         // This ensures cleanup_on_interruption won't remove the worktree
         worktree_setup_ctx.metadata_saved = true;
 
+        self.run_hook(&state, Hook::OnRunStart, None, None)?;
+
         // Track story results for summary
         let mut story_results: Vec<StoryResult> = Vec::new();
         let run_start = Instant::now();
@@ -1646,15 +3182,21 @@ impl Runner {
         // Breadcrumb trail for tracking workflow journey
         let mut breadcrumb = Breadcrumb::new();
 
-        // Helper to print run summary (loads spec and prints)
+        // Helper to print run summary (loads spec and saved state, then prints)
         let print_summary_fn = |iteration: u32, results: &[StoryResult]| -> Result<()> {
             let spec = Spec::load(spec_json_path)?;
+            let failed_stories = self
+                .state_manager
+                .load_current()?
+                .map(|s| s.failed_stories)
+                .unwrap_or_default();
             print_run_summary(
                 spec.total_count(),
                 spec.completed_count(),
                 iteration,
                 run_start.elapsed().as_secs(),
                 results,
+                &failed_stories,
             );
             Ok(())
         };
@@ -1675,8 +3217,10 @@ impl Runner {
                 return Err(err);
             }
 
-            // Reload spec to get latest passes state
+            // Reload spec to get latest passes state, and pick up stories
+            // that were reset since they were skipped
             let spec = Spec::load(spec_json_path)?;
+            state.forget_reset_stories(&spec);
 
             // Check if all stories complete at loop start
             if spec.all_complete() {
@@ -1686,17 +3230,95 @@ impl Runner {
                     &mut breadcrumb,
                     &story_results,
                     &print_summary_fn,
+                    &signal_handler,
+                )? {
+                    LoopAction::Break => return Ok(()),
+                    LoopAction::Continue => continue,
+                }
+            }
+
+            // Runaway-loop protection for the run as a whole
+            let config = state.effective_config();
+            if state.iteration >= config.max_total_iterations {
+                return Err(self.handle_run_iteration_limit(
+                    &mut state,
+                    &story_results,
+                    &print_summary_fn,
+                ));
+            }
+
+            // Run independent stories in parallel when enabled
+            let batch = self.parallel_batch(&state, &spec);
+            if !batch.is_empty() {
+                let ids: Vec<&str> = batch.iter().map(|s| s.id.as_str()).collect();
+                if let Some(err) = self.check_budget(&mut state, &ids) {
+                    return Err(err);
+                }
+                breadcrumb.reset();
+                breadcrumb.enter_state(BreadcrumbState::Story);
+                self.run_parallel_batch(
+                    &mut state,
+                    &spec,
+                    spec_json_path,
+                    &batch,
+                    &mut story_results,
+                    &signal_handler,
+                    &print_summary_fn,
+                )?;
+                print_breadcrumb_trail(&breadcrumb);
+
+                // In Step mode, request pause after each batch completes (US-002)
+                self.check_step_mode_pause();
+                continue;
+            }
+
+            // Pick next story, leaving out stories the run gave up on
+            let Some(story) = spec
+                .ready_stories()
+                .into_iter()
+                .find(|s| !state.is_story_failed(&s.id))
+                .cloned()
+            else {
+                if state.failed_stories.is_empty() {
+                    return Err(Autom8Error::NoIncompleteStories);
+                }
+                // Everything left was skipped or depends on a skipped story
+                match self.handle_all_stories_complete(
+                    &mut state,
+                    &spec,
+                    &mut breadcrumb,
+                    &story_results,
+                    &print_summary_fn,
+                    &signal_handler,
                 )? {
                     LoopAction::Break => return Ok(()),
                     LoopAction::Continue => continue,
                 }
+            };
+
+            // Runaway-loop protection for a story that never passes
+            let story_iterations = state.story_iteration_count(&story.id);
+            if story_iterations >= config.max_iterations_per_story
+                && !state.is_awaiting_retry(&story.id)
+            {
+                self.handle_story_failure(
+                    &mut state,
+                    &story,
+                    &format!("still not passing after {} iterations", story_iterations),
+                    Autom8Error::StoryIterationLimit {
+                        story_id: story.id.clone(),
+                        iterations: story_iterations,
+                    },
+                    &story_results,
+                    &print_summary_fn,
+                )?;
+                continue;
             }
 
-            // Pick next story
-            let story = spec
-                .next_incomplete_story()
-                .ok_or(Autom8Error::NoIncompleteStories)?
-                .clone();
+            if let Some(err) = self.check_budget(&mut state, &[story.id.as_str()]) {
+                return Err(err);
+            }
+            self.run_blocking_hook(&mut state, Hook::PreStory, Some(&story.id))?;
 
             // Reset breadcrumb trail at start of each new story
             breadcrumb.reset();
@@ -1706,7 +3328,12 @@ impl Runner {
 
             // Start iteration
             print_state_transition(MachineState::PickingStory, MachineState::RunningClaude);
-            state.start_iteration(&story.id);
+            start_story(
+                &mut state,
+                spec_json_path,
+                &story.id,
+                self.agent.model(ClaudePhase::Story),
+            )?;
             self.state_manager.save(&state)?;
             self.flush_live(MachineState::RunningClaude);
 
@@ -1715,6 +3342,18 @@ impl Runner {
 
             print_phase_banner("RUNNING", BannerColor::Cyan);
             print_iteration_start(state.iteration, &story.id, &story.title);
+            if let Some(context) = build_story_knowledge_context(
+                &state.knowledge,
+                Some(&story),
+                token_limit(config.knowledge_context_tokens),
+            )
+            .filter(|context| context.is_trimmed())
+            {
+                print_info(&format!(
+                    "Project knowledge trimmed to fit knowledge_context_tokens: {}",
+                    context.trim_summary()
+                ));
+            }
 
             // Process the story iteration
             let story_start = Instant::now();
@@ -1727,6 +3366,7 @@ impl Runner {
                 &mut story_results,
                 story_start,
                 &claude_runner,
+                &signal_handler,
                 &print_summary_fn,
             )? {
                 LoopAction::Break => return Ok(()),
@@ -1766,15 +3406,9 @@ impl Runner {
                     print_resuming_interrupted(&format!("{:?}", state.machine_state));
                 }
 
+                // Continue the same run, with its history and usage
                 let spec_json_path = state.spec_json_path.clone();
-
-                // Archive the interrupted/failed run before starting fresh
-                // Preserve metadata so run_mode preference survives resume
-                self.state_manager.archive(&state)?;
-                self.state_manager.clear_current(true)?;
-
-                // Start a new run with the same parameters
-                return self.run(&spec_json_path);
+                return self.start_run(&spec_json_path, Some(state));
             }
         }
 
@@ -1866,7 +3500,23 @@ impl Runner {
         self.run(spec_path)
     }
 
+    /// Merge what a completed run learned into the project's knowledge store.
+    fn merge_knowledge(&self, state: &RunState, config: &Config) -> Result<()> {
+        let dir = self.state_manager.knowledge_dir();
+        let mut store = KnowledgeStore::load(&dir)?;
+        store.merge_run(&state.knowledge, config.knowledge_decay_runs);
+        store.save(&dir)
+    }
+
     fn archive_and_cleanup(&self, state: &RunState) -> Result<()> {
+        let config = state.effective_config();
+        if state.status == RunStatus::Completed {
+            if let Err(e) = self.merge_knowledge(state, &config) {
+                // Non-fatal - the run itself succeeded
+                print_warning(&format!("Could not update the knowledge store: {}", e));
+            }
+        }
+
         self.state_manager.archive(state)?;
 
         // Check if we should clean up the worktree after successful completion
@@ -1874,7 +3524,6 @@ impl Runner {
         // 1. Run completed successfully (not failed)
         // 2. worktree_cleanup is enabled in config
         // 3. We're currently in a worktree (not the main repo)
-        let config = state.effective_config();
         if state.status == crate::state::RunStatus::Completed && config.worktree_cleanup {
             // Check if we're in a worktree
             if let Ok(true) = is_in_worktree() {
@@ -1927,7 +3576,7 @@ impl Runner {
 mod tests {
     use super::*;
     use crate::config::Config;
-    use crate::spec::{Spec, UserStory};
+    use crate::spec::{Spec, StoryState, StoryStatus, UserStory};
     use crate::state::RunStatus;
     use tempfile::TempDir;
 
@@ -1937,6 +3586,7 @@ mod tests {
 
     fn create_test_spec(passes: bool) -> Spec {
         Spec {
+            schema_version: crate::spec::SPEC_SCHEMA_VERSION,
             project: "TestProject".into(),
             branch_name: "test-branch".into(),
             description: "A test project".into(),
@@ -1946,9 +3596,16 @@ mod tests {
                 description: "A test story".into(),
                 acceptance_criteria: vec!["Test criterion".into()],
                 priority: 1,
-                passes,
+                status: if passes {
+                    StoryStatus::done()
+                } else {
+                    StoryStatus::default()
+                },
                 notes: String::new(),
+                depends_on: Vec::new(),
+                verify: None,
             }],
+            verify: Vec::new(),
         }
     }
 
@@ -1960,15 +3617,23 @@ mod tests {
                 description: format!("Description for story {}", i + 1),
                 acceptance_criteria: vec!["Criterion".into()],
                 priority: (i + 1) as u32,
-                passes: i < completed_count,
+                status: if i < completed_count {
+                    StoryStatus::done()
+                } else {
+                    StoryStatus::default()
+                },
                 notes: String::new(),
+                depends_on: Vec::new(),
+                verify: None,
             })
             .collect();
         Spec {
+            schema_version: crate::spec::SPEC_SCHEMA_VERSION,
             project: "TestProject".into(),
             branch_name: "test-branch".into(),
             description: "Multi-story test".into(),
             user_stories: stories,
+            verify: Vec::new(),
         }
     }
 
@@ -2016,7 +3681,7 @@ mod tests {
 
     #[test]
     fn test_story_index_is_one_indexed() {
-        let story_ids = vec!["US-001", "US-002", "US-003"];
+        let story_ids = ["US-001", "US-002", "US-003"];
 
         // First story should be 1, not 0
         let idx = story_ids
@@ -2172,7 +3837,7 @@ mod tests {
         assert_eq!(spec.next_incomplete_story().unwrap().id, "US-001");
 
         let mut spec = create_multi_story_spec(0, 3);
-        spec.user_stories[0].passes = true;
+        spec.mark_story_complete("US-001");
         assert_eq!(spec.next_incomplete_story().unwrap().id, "US-002");
 
         let spec = create_multi_story_spec(3, 3);
@@ -2320,8 +3985,10 @@ mod tests {
         let sm = StateManager::with_dir(temp_dir.path().to_path_buf());
         sm.ensure_dirs().unwrap();
 
-        let mut flusher = LiveOutputFlusher::new(&sm, MachineState::RunningClaude);
+        let watch = LiveWatch::new(&Config::default(), None);
+        let mut flusher = LiveOutputFlusher::new(&sm, MachineState::RunningClaude, watch);
         assert!(flusher.live_state.output_lines.is_empty());
+        assert_eq!(flusher.live_state.stall_after_secs, Some(600));
 
         // Append lines
         flusher.append("Line 1");
@@ -2340,6 +4007,36 @@ mod tests {
         assert!(sm.load_live().is_some());
     }
 
+    #[test]
+    fn test_live_output_flusher_reports_claude_activity() {
+        let temp_dir = TempDir::new().unwrap();
+        let sm = StateManager::with_dir(temp_dir.path().to_path_buf());
+        sm.ensure_dirs().unwrap();
+
+        let config = Config {
+            kill_on_stall: true,
+            stall_timeout_minutes: 5,
+            ..Default::default()
+        };
+        let runner = claude_runner_for(
+            &AgentSessions::default(),
+            &config,
+            config.story_timeout_minutes,
+        );
+        let watch = LiveWatch::new(&config, Some(&runner));
+        let mut flusher = LiveOutputFlusher::new(&sm, MachineState::RunningClaude, watch);
+
+        runner.activity().record_line();
+        runner.activity().record_tool("Bash: cargo test");
+        flusher.flush();
+
+        let live = sm.load_live().unwrap();
+        assert_eq!(live.last_activity.as_deref(), Some("Bash: cargo test"));
+        assert!(live.last_output_at.is_some());
+        assert_eq!(live.stall_after_secs, Some(300));
+        assert_eq!(live.stalled_for(), None);
+    }
+
     #[test]
     fn test_live_flush_constants() {
         assert_eq!(LIVE_FLUSH_INTERVAL_MS, 200);
@@ -2364,6 +4061,7 @@ mod tests {
             worktree_override: None,
             commit_override: None,
             pull_request_override: None,
+            agent: AgentSessions::default(),
         };
 
         let mut state = RunState::new(PathBuf::from("test.json"), "test".to_string());
@@ -2512,6 +4210,7 @@ mod tests {
             worktree_override: None,
             commit_override: None,
             pull_request_override: None,
+            agent: AgentSessions::default(),
         }
     }
 
@@ -2551,6 +4250,76 @@ mod tests {
         assert!(matches!(result.unwrap(), Autom8Error::Interrupted));
     }
 
+    #[test]
+    fn test_check_budget_pauses_when_story_budget_used_up() {
+        let temp_dir = TempDir::new().unwrap();
+        let config = crate::config::Config {
+            max_story_tokens: 1000,
+            ..Default::default()
+        };
+        let mut state = RunState::new_with_config(
+            PathBuf::from("test.json"),
+            "test-branch".to_string(),
+            config,
+        );
+        state.capture_usage(
+            "US-001",
+            Some(crate::claude::ClaudeUsage {
+                input_tokens: 900,
+                output_tokens: 100,
+                ..Default::default()
+            }),
+        );
+        let runner = create_test_runner_at(temp_dir.path());
+        runner.state_manager.save(&state).unwrap();
+
+        assert!(runner.check_budget(&mut state, &[]).is_none());
+        assert!(runner.check_budget(&mut state, &["US-002"]).is_none());
+        assert_eq!(state.status, RunStatus::Running);
+
+        let result = runner.check_budget(&mut state, &["US-002", "US-001"]);
+        assert!(matches!(result, Some(Autom8Error::Interrupted)));
+        assert_eq!(state.status, RunStatus::Interrupted);
+    }
+
+    #[test]
+    fn test_review_pauses_when_run_budget_used_up() {
+        let temp_dir = TempDir::new().unwrap();
+        let config = crate::config::Config {
+            max_run_cost_usd: 1.0,
+            ..Default::default()
+        };
+        let mut state = RunState::new_with_config(
+            PathBuf::from("test.json"),
+            "test-branch".to_string(),
+            config,
+        );
+        state.capture_usage(
+            "US-001",
+            Some(crate::claude::ClaudeUsage {
+                output_tokens: 100_000,
+                model: Some("claude-sonnet-4-20250514".to_string()),
+                ..Default::default()
+            }),
+        );
+        let runner = create_test_runner_at(temp_dir.path());
+        runner.state_manager.save(&state).unwrap();
+
+        // All stories are done, but the review must not start over budget
+        let result = runner.run_review_correct_loop(
+            &mut state,
+            &create_test_spec(true),
+            &mut Breadcrumb::new(),
+            &[],
+            &|_, _| Ok(()),
+            &SignalHandler::unregistered(),
+        );
+        assert!(matches!(result, Err(Autom8Error::Interrupted)));
+        assert_eq!(state.status, RunStatus::Interrupted);
+        assert_ne!(state.machine_state, MachineState::Reviewing);
+        assert!(!state.phase_usage.contains_key("Final Review"));
+    }
+
     #[test]
     fn test_us002_handle_pause_clears_pause_request() {
         let temp_dir = TempDir::new().unwrap();
@@ -2700,4 +4469,212 @@ mod tests {
 
         // The resume logic accepts Interrupted status (verified in commands/resume.rs tests)
     }
+
+    // ========================================================================
+    // Story failure policy tests
+    // ========================================================================
+
+    /// A running state whose spec has a chain US-001 <- US-002 <- US-003.
+    fn state_with_policy(policy: StoryFailurePolicy, dir: &Path) -> RunState {
+        let mut spec = create_multi_story_spec(0, 3);
+        spec.user_stories[1].depends_on = vec!["US-001".into()];
+        spec.user_stories[2].depends_on = vec!["US-002".into()];
+        let spec_path = dir.join("spec-test.json");
+        spec.save(&spec_path).unwrap();
+
+        let config = crate::config::Config {
+            on_story_failure: policy,
+            ..Default::default()
+        };
+        let mut state = RunState::new_with_config(spec_path, "test-branch".to_string(), config);
+        state.transition_to(MachineState::RunningClaude);
+        state
+    }
+
+    fn no_summary(_: u32, _: &[StoryResult]) -> Result<()> {
+        Ok(())
+    }
+
+    #[test]
+    fn test_story_failure_skip_records_story_and_continues() {
+        let temp_dir = TempDir::new().unwrap();
+        let runner = create_test_runner_at(temp_dir.path());
+        let mut state = state_with_policy(StoryFailurePolicy::Skip, temp_dir.path());
+        let story = create_test_spec(false).user_stories[0].clone();
+
+        let result = runner.handle_story_failure(
+            &mut state,
+            &story,
+            "still not passing after 10 iterations",
+            Autom8Error::ClaudeError("unused".into()),
+            &[],
+            &no_summary,
+        );
+
+        assert!(result.is_ok());
+        assert!(state.is_story_failed(&story.id));
+        assert_eq!(state.machine_state, MachineState::PickingStory);
+        assert_eq!(state.status, RunStatus::Running);
+
+        let saved = runner.state_manager.load_current().unwrap().unwrap();
+        assert_eq!(saved.failed_stories, state.failed_stories);
+
+        let spec = Spec::load(&state.spec_json_path).unwrap();
+        let status = &spec.user_stories[0].status;
+        assert_eq!(status.state, StoryState::Failed);
+        assert_eq!(
+            status.reason.as_deref(),
+            Some("still not passing after 10 iterations")
+        );
+        assert_eq!(spec.user_stories[1].status.state, StoryState::Blocked);
+        assert_eq!(
+            spec.user_stories[2].status.reason.as_deref(),
+            Some("waiting on US-002, which is blocked by US-001")
+        );
+    }
+
+    #[test]
+    fn test_story_failure_fail_stops_run_with_error() {
+        let temp_dir = TempDir::new().unwrap();
+        let runner = create_test_runner_at(temp_dir.path());
+        let mut state = state_with_policy(StoryFailurePolicy::Fail, temp_dir.path());
+        let story = create_test_spec(false).user_stories[0].clone();
+
+        let result = runner.handle_story_failure(
+            &mut state,
+            &story,
+            "still not passing after 10 iterations",
+            Autom8Error::StoryIterationLimit {
+                story_id: story.id.clone(),
+                iterations: 10,
+            },
+            &[],
+            &no_summary,
+        );
+
+        assert!(matches!(
+            result,
+            Err(Autom8Error::StoryIterationLimit { iterations: 10, .. })
+        ));
+        assert!(state.failed_stories.is_empty());
+        assert_eq!(state.status, RunStatus::Failed);
+
+        // The run stops, so dependents aren't marked blocked
+        let spec = Spec::load(&state.spec_json_path).unwrap();
+        assert_eq!(spec.user_stories[0].status.state, StoryState::Failed);
+        assert_eq!(spec.user_stories[1].status.state, StoryState::NotStarted);
+    }
+
+    #[test]
+    fn test_story_failure_pause_interrupts_run() {
+        let temp_dir = TempDir::new().unwrap();
+        let runner = create_test_runner_at(temp_dir.path());
+        let mut state = state_with_policy(StoryFailurePolicy::Pause, temp_dir.path());
+        let story = create_test_spec(false).user_stories[0].clone();
+
+        let result = runner.handle_story_failure(
+            &mut state,
+            &story,
+            "Claude exited with code 1",
+            Autom8Error::ClaudeError("Claude exited with code 1".into()),
+            &[],
+            &no_summary,
+        );
+
+        assert!(matches!(result, Err(Autom8Error::Interrupted)));
+        assert!(state.failed_stories.is_empty());
+        assert_eq!(state.status, RunStatus::Interrupted);
+        assert_eq!(state.machine_state, MachineState::PickingStory);
+    }
+
+    #[test]
+    fn test_resume_retries_stopped_story_but_not_skipped_ones() {
+        let temp_dir = TempDir::new().unwrap();
+        let mut state = state_with_policy(StoryFailurePolicy::Pause, temp_dir.path());
+        let mut spec = Spec::load(&state.spec_json_path).unwrap();
+        for id in ["US-001", "US-002", "US-003"] {
+            spec.set_story_status(id, StoryStatus::new(StoryState::Failed, None));
+        }
+        spec.save(&state.spec_json_path).unwrap();
+        state.start_iteration("US-001");
+        state.start_iteration("US-002");
+        state.mark_story_failed("US-002", "still not passing after 1 iterations");
+
+        retry_stopped_stories(&state, &state.spec_json_path).unwrap();
+
+        let spec = Spec::load(&state.spec_json_path).unwrap();
+        let states: Vec<_> = spec.user_stories.iter().map(|s| s.status.state).collect();
+        assert_eq!(
+            states,
+            vec![
+                StoryState::NotStarted,
+                StoryState::Failed,
+                StoryState::Failed
+            ]
+        );
+    }
+
+    #[test]
+    fn test_run_iteration_limit_fails_or_pauses() {
+        let temp_dir = TempDir::new().unwrap();
+        let runner = create_test_runner_at(temp_dir.path());
+
+        let mut state = state_with_policy(StoryFailurePolicy::Skip, temp_dir.path());
+        let err = runner.handle_run_iteration_limit(&mut state, &[], &no_summary);
+        assert!(matches!(err, Autom8Error::MaxIterationsReached(100)));
+        assert_eq!(state.status, RunStatus::Failed);
+
+        let mut state = state_with_policy(StoryFailurePolicy::Pause, temp_dir.path());
+        let err = runner.handle_run_iteration_limit(&mut state, &[], &no_summary);
+        assert!(matches!(err, Autom8Error::Interrupted));
+        assert_eq!(state.status, RunStatus::Interrupted);
+    }
+
+    // ========================================================================
+    // Claude retry tests
+    // ========================================================================
+
+    #[test]
+    fn test_retry_delay_backs_off_transient_failures() {
+        let now = Utc::now();
+        let kind = ClaudeFailureKind::Transient;
+        let delays: Vec<Option<u64>> = (0..4)
+            .map(|retries| retry_delay(&kind, retries, 3, now).map(|d| d.as_secs()))
+            .collect();
+        assert_eq!(delays, vec![Some(30), Some(60), Some(120), None]);
+
+        // Capped, and disabled entirely by claude_retry_attempts = 0
+        assert_eq!(
+            retry_delay(&kind, 9, 20, now),
+            Some(Duration::from_secs(RETRY_MAX_DELAY_SECS))
+        );
+        assert_eq!(retry_delay(&kind, 0, 0, now), None);
+        assert_eq!(retry_delay(&ClaudeFailureKind::Permanent, 0, 3, now), None);
+    }
+
+    #[test]
+    fn test_retry_delay_waits_out_usage_limits() {
+        let now = Utc::now();
+        let kind = ClaudeFailureKind::UsageLimit {
+            resets_at: now + chrono::Duration::minutes(45),
+        };
+        // Not limited by the retry count
+        assert_eq!(
+            retry_delay(&kind, 5, 0, now),
+            Some(Duration::from_secs(45 * 60 + USAGE_LIMIT_RESET_GRACE_SECS))
+        );
+
+        let past = ClaudeFailureKind::UsageLimit {
+            resets_at: now - chrono::Duration::minutes(5),
+        };
+        assert_eq!(
+            retry_delay(&past, 0, 3, now),
+            Some(Duration::from_secs(USAGE_LIMIT_RESET_GRACE_SECS))
+        );
+        assert_eq!(retry_reason(&kind, 0, 3), "Usage limit reached");
+        assert_eq!(
+            retry_reason(&ClaudeFailureKind::Transient, 1, 3),
+            "Claude failed (retry 2 of 3)"
+        );
+    }
 }
@@ -9,7 +9,7 @@ use std::process::Command;
 
 use crate::config::spec_dir;
 use crate::error::Result;
-use crate::spec::{Spec, UserStory};
+use crate::spec::{Spec, StoryStatus, UserStory};
 use crate::state::StateManager;
 use crate::worktree::get_main_repo_root;
 
@@ -33,6 +33,7 @@ pub const SELF_TEST_SPEC_FILENAME: &str = "test_spec.json";
 /// without touching any real code.
 pub fn create_self_test_spec() -> Spec {
     Spec {
+        schema_version: crate::spec::SPEC_SCHEMA_VERSION,
         project: "autom8-self-test".to_string(),
         branch_name: SELF_TEST_BRANCH.to_string(),
         description: "Self-test spec for validating autom8 functionality. Creates and modifies a dummy test_output.txt file.".to_string(),
@@ -46,8 +47,10 @@ pub fn create_self_test_spec() -> Spec {
                     "File contains the text 'Hello from autom8 self-test!'".to_string(),
                 ],
                 priority: 1,
-                passes: false,
+                status: StoryStatus::default(),
                 notes: "This is the first step - just create the file with a simple greeting.".to_string(),
+                depends_on: Vec::new(),
+                verify: None,
             },
             UserStory {
                 id: "ST-002".to_string(),
@@ -58,8 +61,10 @@ pub fn create_self_test_spec() -> Spec {
                     "File contains a status line with 'Status: Running'".to_string(),
                 ],
                 priority: 2,
-                passes: false,
+                status: StoryStatus::default(),
                 notes: "Appends content to the existing file.".to_string(),
+                depends_on: Vec::new(),
+                verify: None,
             },
             UserStory {
                 id: "ST-003".to_string(),
@@ -70,10 +75,13 @@ pub fn create_self_test_spec() -> Spec {
                     "Message includes 'Self-test complete!'".to_string(),
                 ],
                 priority: 3,
-                passes: false,
+                status: StoryStatus::default(),
                 notes: "Final step - adds the completion marker.".to_string(),
+                depends_on: Vec::new(),
+                verify: None,
             },
         ],
+        verify: Vec::new(),
     }
 }
 
@@ -386,6 +394,9 @@ pub fn print_failure_details(run_error: &crate::error::Autom8Error) {
         crate::error::Autom8Error::ClaudeTimeout(secs) => {
             print_warning(&format!("Claude timed out after {} seconds", secs));
         }
+        crate::error::Autom8Error::ClaudeStalled(secs) => {
+            print_warning(&format!("Claude produced no output for {} seconds", secs));
+        }
         crate::error::Autom8Error::MaxReviewIterationsReached => {
             print_warning("Review failed after maximum iterations");
         }
@@ -457,7 +468,7 @@ mod tests {
 
         for story in &spec.user_stories {
             assert!(
-                !story.passes,
+                !story.passes(),
                 "Story {} should not be passing initially",
                 story.id
             );
@@ -553,20 +564,24 @@ mod tests {
 
     #[test]
     fn test_cleanup_result_is_complete_when_no_errors() {
-        let mut result = CleanupResult::default();
-        result.test_file_deleted = true;
-        result.spec_file_deleted = true;
-        result.session_cleared = true;
-        result.branch_deleted = true;
-        result.worktree_deleted = true;
+        let result = CleanupResult {
+            test_file_deleted: true,
+            spec_file_deleted: true,
+            session_cleared: true,
+            branch_deleted: true,
+            worktree_deleted: true,
+            ..Default::default()
+        };
 
         assert!(result.is_complete());
     }
 
     #[test]
     fn test_cleanup_result_is_not_complete_with_errors() {
-        let mut result = CleanupResult::default();
-        result.test_file_deleted = true;
+        let mut result = CleanupResult {
+            test_file_deleted: true,
+            ..Default::default()
+        };
         result.errors.push("Failed to delete something".to_string());
 
         assert!(!result.is_complete());
@@ -586,9 +601,7 @@ mod tests {
     fn test_branch_exists_local_returns_bool() {
         // This test just verifies the function doesn't panic and returns a bool.
         // In the test environment, the branch may or may not exist.
-        let exists = branch_exists_local("main");
-        // Result is either true or false - just verify it's a valid bool
-        assert!(exists || !exists);
+        let _exists: bool = branch_exists_local("main");
     }
 
     #[test]
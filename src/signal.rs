@@ -78,6 +78,15 @@ impl SignalHandler {
     pub fn reset(&self) {
         self.shutdown_flag.store(false, Ordering::SeqCst);
     }
+
+    /// A handler that isn't registered for SIGINT, since tests can only
+    /// register one per process.
+    #[cfg(test)]
+    pub fn unregistered() -> Self {
+        Self {
+            shutdown_flag: Arc::new(AtomicBool::new(false)),
+        }
+    }
 }
 
 #[cfg(test)]
@@ -1,56 +1,325 @@
 use crate::error::{Autom8Error, Result};
+use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::collections::{HashMap, HashSet};
 use std::fs;
-use std::path::Path;
+use std::path::{Path, PathBuf};
+
+/// Current version of the spec JSON format, stored as `schemaVersion`.
+///
+/// Bump this and append a step to [`SPEC_MIGRATIONS`] whenever a change to
+/// the format would make an existing spec file load differently.
+pub const SPEC_SCHEMA_VERSION: u32 = 2;
+
+/// Version assumed for spec files written before `schemaVersion` existed.
+const LEGACY_SCHEMA_VERSION: u32 = 1;
+
+/// Migration steps, in order. Entry `i` upgrades a spec from version `i + 1`
+/// to version `i + 2`.
+const SPEC_MIGRATIONS: &[fn(&mut Value)] = &[migrate_v1_to_v2];
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct Spec {
+    /// Version of the spec format this spec was written in.
+    #[serde(default = "current_schema_version")]
+    pub schema_version: u32,
     pub project: String,
     #[serde(default = "default_branch_name")]
     pub branch_name: String,
     pub description: String,
     pub user_stories: Vec<UserStory>,
+    /// Shell commands that must succeed before any story counts as passing,
+    /// unless the story sets its own `verify` list.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub verify: Vec<String>,
 }
 
 fn default_branch_name() -> String {
     "autom8/feature".to_string()
 }
 
+/// Specs deserialized directly (e.g. freshly generated ones) are current.
+fn current_schema_version() -> u32 {
+    SPEC_SCHEMA_VERSION
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
-#[serde(rename_all = "camelCase")]
+#[serde(from = "UserStoryRecord", into = "UserStoryRecord")]
 pub struct UserStory {
     pub id: String,
     pub title: String,
     pub description: String,
     pub acceptance_criteria: Vec<String>,
     pub priority: u32,
-    pub passes: bool,
-    #[serde(default)]
+    /// Where the story is in its lifecycle.
+    pub status: StoryStatus,
     pub notes: String,
+    /// IDs of stories that must pass before this story can be picked.
+    pub depends_on: Vec<String>,
+    /// Shell commands that must succeed before this story counts as passing.
+    /// Overrides the spec-level `verify` list; an empty list disables it.
+    pub verify: Option<Vec<String>>,
+}
+
+impl UserStory {
+    /// Returns true if the story is done.
+    pub fn passes(&self) -> bool {
+        self.status.is_done()
+    }
+
+    /// Commit message for the story's changes: `<id>: <title>`, with the
+    /// description and acceptance criteria as the body.
+    pub fn commit_message(&self) -> String {
+        let mut message = format!("{}: {}", self.id, self.title);
+        if !self.description.trim().is_empty() {
+            message.push_str("\n\n");
+            message.push_str(self.description.trim());
+        }
+        if !self.acceptance_criteria.is_empty() {
+            message.push_str("\n\nAcceptance criteria:");
+            for criterion in &self.acceptance_criteria {
+                message.push_str("\n- ");
+                message.push_str(criterion);
+            }
+        }
+        message
+    }
+}
+
+/// On-disk form of a [`UserStory`].
+///
+/// The `passes` flag is still written (true when the story is done) and read
+/// back, since Claude marks stories complete by setting it and older specs
+/// have no `status`. When the two disagree, `passes` wins.
+#[derive(Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct UserStoryRecord {
+    id: String,
+    title: String,
+    description: String,
+    acceptance_criteria: Vec<String>,
+    priority: u32,
+    passes: bool,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    status: Option<StoryStatus>,
+    #[serde(default)]
+    notes: String,
+    #[serde(default)]
+    depends_on: Vec<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    verify: Option<Vec<String>>,
+}
+
+impl From<UserStoryRecord> for UserStory {
+    fn from(record: UserStoryRecord) -> Self {
+        let status = match record.status {
+            Some(status) if status.is_done() == record.passes => status,
+            // Marked passing (by Claude or by hand) since the status was written
+            _ if record.passes => StoryStatus {
+                state: StoryState::Done,
+                reason: None,
+                updated_at: None,
+            },
+            // Reopened by hand, or a spec written before statuses existed
+            _ => StoryStatus::default(),
+        };
+        Self {
+            id: record.id,
+            title: record.title,
+            description: record.description,
+            acceptance_criteria: record.acceptance_criteria,
+            priority: record.priority,
+            status,
+            notes: record.notes,
+            depends_on: record.depends_on,
+            verify: record.verify,
+        }
+    }
+}
+
+impl From<UserStory> for UserStoryRecord {
+    fn from(story: UserStory) -> Self {
+        Self {
+            id: story.id,
+            title: story.title,
+            description: story.description,
+            acceptance_criteria: story.acceptance_criteria,
+            priority: story.priority,
+            passes: story.status.is_done(),
+            status: Some(story.status),
+            notes: story.notes,
+            depends_on: story.depends_on,
+            verify: story.verify,
+        }
+    }
+}
+
+/// Lifecycle state of a story.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum StoryState {
+    /// Not picked by a run yet.
+    #[default]
+    NotStarted,
+    /// Being implemented, or picked again after an attempt that didn't pass.
+    InProgress,
+    /// Can't run because a story it depends on failed.
+    Blocked,
+    /// The run gave up on it (errors or too many attempts).
+    Failed,
+    /// Implemented and verified.
+    Done,
+}
+
+impl StoryState {
+    /// Short lowercase label, e.g. "in progress".
+    pub fn label(&self) -> &'static str {
+        match self {
+            StoryState::NotStarted => "not started",
+            StoryState::InProgress => "in progress",
+            StoryState::Blocked => "blocked",
+            StoryState::Failed => "failed",
+            StoryState::Done => "done",
+        }
+    }
+
+    /// Single-character icon for listings.
+    pub fn icon(&self) -> &'static str {
+        match self {
+            StoryState::NotStarted => "○",
+            StoryState::InProgress => "◐",
+            StoryState::Blocked => "⊘",
+            StoryState::Failed => "✗",
+            StoryState::Done => "✓",
+        }
+    }
+}
+
+/// A story's state, with why and when it last changed.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct StoryStatus {
+    pub state: StoryState,
+    /// Why the story is in this state (e.g., what it's blocked on).
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub reason: Option<String>,
+    /// When the state last changed, if autom8 changed it.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub updated_at: Option<DateTime<Utc>>,
+}
+
+impl StoryStatus {
+    /// A status that changes now.
+    pub fn new(state: StoryState, reason: Option<String>) -> Self {
+        Self {
+            state,
+            reason,
+            updated_at: Some(Utc::now()),
+        }
+    }
+
+    /// A done status, changed now.
+    pub fn done() -> Self {
+        Self::new(StoryState::Done, None)
+    }
+
+    pub fn is_done(&self) -> bool {
+        self.state == StoryState::Done
+    }
+
+    /// The state label with its reason, e.g. "blocked: waiting on US-002".
+    pub fn describe(&self) -> String {
+        match &self.reason {
+            Some(reason) => format!("{}: {}", self.state.label(), reason),
+            None => self.state.label().to_string(),
+        }
+    }
 }
 
 impl Spec {
+    /// Load a spec, upgrading it in place if it was written in an older format.
+    ///
+    /// Whether a backup of the original file is kept during an upgrade is
+    /// controlled by the `spec_migration_backup` config option.
     pub fn load(path: &Path) -> Result<Self> {
+        Self::load_inner(path, &|| {
+            crate::config::get_effective_config()
+                .map(|c| c.spec_migration_backup)
+                .unwrap_or(true)
+        })
+    }
+
+    /// Load a spec, upgrading it in place if it was written in an older format.
+    ///
+    /// When `backup` is true, the original file is copied to
+    /// `<file>.v<old version>.bak` before being rewritten.
+    pub fn load_with_backup(path: &Path, backup: bool) -> Result<Self> {
+        Self::load_inner(path, &|| backup)
+    }
+
+    /// Load a spec without writing anything: a spec in an older format is
+    /// upgraded in memory only.
+    pub fn read(path: &Path) -> Result<Self> {
         if !path.exists() {
             return Err(Autom8Error::SpecNotFound(path.to_path_buf()));
         }
 
         let content = fs::read_to_string(path)?;
-        let spec: Spec =
-            serde_json::from_str(&content).map_err(|e| Autom8Error::InvalidSpec(e.to_string()))?;
+        let (spec, _) = Self::parse_migrated(&content, path)?;
+        spec.validate()?;
+        Ok(spec)
+    }
+
+    fn load_inner(path: &Path, backup: &dyn Fn() -> bool) -> Result<Self> {
+        if !path.exists() {
+            return Err(Autom8Error::SpecNotFound(path.to_path_buf()));
+        }
 
+        let content = fs::read_to_string(path)?;
+        let (spec, version) = Self::parse_migrated(&content, path)?;
         spec.validate()?;
+
+        if version < SPEC_SCHEMA_VERSION {
+            if backup() {
+                fs::write(migration_backup_path(path, version), &content)?;
+            }
+            spec.save(path)?;
+        }
         Ok(spec)
     }
 
+    /// Parse spec JSON, upgrading it in memory to the current format.
+    ///
+    /// Returns the spec along with the schema version it was written in.
+    /// Nothing is written to disk and the spec is not validated.
+    pub(crate) fn parse_migrated(content: &str, path: &Path) -> Result<(Self, u32)> {
+        let mut value: Value =
+            serde_json::from_str(content).map_err(|e| Autom8Error::InvalidSpec(e.to_string()))?;
+
+        let version = schema_version(&value)?;
+        if version > SPEC_SCHEMA_VERSION {
+            return Err(Autom8Error::UnsupportedSpecVersion {
+                path: path.to_path_buf(),
+                version,
+                supported: SPEC_SCHEMA_VERSION,
+            });
+        }
+        migrate_spec_value(&mut value, version);
+
+        let spec: Spec =
+            serde_json::from_value(value).map_err(|e| Autom8Error::InvalidSpec(e.to_string()))?;
+        Ok((spec, version))
+    }
+
     pub fn save(&self, path: &Path) -> Result<()> {
         let content = serde_json::to_string_pretty(self)?;
         fs::write(path, content)?;
         Ok(())
     }
 
-    fn validate(&self) -> Result<()> {
+    pub(crate) fn validate(&self) -> Result<()> {
         if self.project.is_empty() {
             return Err(Autom8Error::InvalidSpec("project name is required".into()));
         }
@@ -64,18 +333,128 @@ impl Spec {
                 return Err(Autom8Error::InvalidSpec("story id is required".into()));
             }
         }
+        self.validate_dependencies()
+    }
+
+    /// Check that every `dependsOn` entry names a known story and that the
+    /// dependency graph has no cycles.
+    fn validate_dependencies(&self) -> Result<()> {
+        let ids: HashSet<&str> = self.user_stories.iter().map(|s| s.id.as_str()).collect();
+        for story in &self.user_stories {
+            for dep in &story.depends_on {
+                if !ids.contains(dep.as_str()) {
+                    return Err(Autom8Error::InvalidSpec(format!(
+                        "story {} depends on unknown story {}",
+                        story.id, dep
+                    )));
+                }
+            }
+        }
+
+        if let Some(cycle) = self.find_dependency_cycle() {
+            return Err(Autom8Error::InvalidSpec(format!(
+                "dependency cycle detected: {}",
+                cycle.join(" -> ")
+            )));
+        }
         Ok(())
     }
 
-    pub fn next_incomplete_story(&self) -> Option<&UserStory> {
+    /// Find a cycle in the story dependency graph, if any.
+    ///
+    /// Returns the story IDs along the cycle, with the first ID repeated at
+    /// the end (e.g. `["US-001", "US-002", "US-001"]`).
+    fn find_dependency_cycle(&self) -> Option<Vec<String>> {
+        #[derive(Clone, Copy, PartialEq)]
+        enum Mark {
+            Visiting,
+            Done,
+        }
+
+        fn visit<'a>(
+            id: &'a str,
+            deps: &HashMap<&'a str, &'a [String]>,
+            marks: &mut HashMap<&'a str, Mark>,
+            path: &mut Vec<&'a str>,
+        ) -> Option<Vec<String>> {
+            match marks.get(id) {
+                Some(Mark::Done) => return None,
+                Some(Mark::Visiting) => {
+                    let start = path.iter().position(|p| *p == id).unwrap_or(0);
+                    let mut cycle: Vec<String> =
+                        path[start..].iter().map(|s| s.to_string()).collect();
+                    cycle.push(id.to_string());
+                    return Some(cycle);
+                }
+                None => {}
+            }
+
+            marks.insert(id, Mark::Visiting);
+            path.push(id);
+            for dep in deps.get(id).copied().unwrap_or_default() {
+                if let Some(cycle) = visit(dep, deps, marks, path) {
+                    return Some(cycle);
+                }
+            }
+            path.pop();
+            marks.insert(id, Mark::Done);
+            None
+        }
+
+        let deps: HashMap<&str, &[String]> = self
+            .user_stories
+            .iter()
+            .map(|s| (s.id.as_str(), s.depends_on.as_slice()))
+            .collect();
+        let mut marks = HashMap::new();
+        let mut path = Vec::new();
+
         self.user_stories
             .iter()
-            .filter(|s| !s.passes)
-            .min_by_key(|s| s.priority)
+            .find_map(|s| visit(&s.id, &deps, &mut marks, &mut path))
+    }
+
+    /// Returns true if every story this story depends on already passes.
+    pub fn dependencies_met(&self, story: &UserStory) -> bool {
+        story.depends_on.iter().all(|dep| {
+            self.user_stories
+                .iter()
+                .find(|s| &s.id == dep)
+                .is_some_and(|s| s.passes())
+        })
+    }
+
+    /// The verification commands that gate `passes` for a story.
+    pub fn verify_commands<'a>(&'a self, story: &'a UserStory) -> &'a [String] {
+        story.verify.as_deref().unwrap_or(&self.verify)
+    }
+
+    /// Pick the next story to implement.
+    ///
+    /// Only incomplete stories whose dependencies all pass are considered;
+    /// among those, the lowest `priority` number wins.
+    pub fn next_incomplete_story(&self) -> Option<&UserStory> {
+        self.ready_stories().into_iter().next()
+    }
+
+    /// All incomplete stories whose dependencies pass, ordered by priority.
+    ///
+    /// Failed stories are left out until they are reset. None of the
+    /// returned stories depend on each other, so they can be implemented
+    /// independently.
+    pub fn ready_stories(&self) -> Vec<&UserStory> {
+        let mut ready: Vec<&UserStory> = self
+            .user_stories
+            .iter()
+            .filter(|s| s.status.state != StoryState::Failed)
+            .filter(|s| !s.passes() && self.dependencies_met(s))
+            .collect();
+        ready.sort_by_key(|s| s.priority);
+        ready
     }
 
     pub fn completed_count(&self) -> usize {
-        self.user_stories.iter().filter(|s| s.passes).count()
+        self.user_stories.iter().filter(|s| s.passes()).count()
     }
 
     pub fn total_count(&self) -> usize {
@@ -83,7 +462,7 @@ impl Spec {
     }
 
     pub fn all_complete(&self) -> bool {
-        self.user_stories.iter().all(|s| s.passes)
+        self.user_stories.iter().all(|s| s.passes())
     }
 
     /// Returns true if spec has incomplete stories
@@ -96,9 +475,178 @@ impl Spec {
         (self.completed_count(), self.total_count())
     }
 
+    /// Number of stories in the given state.
+    pub fn count_in_state(&self, state: StoryState) -> usize {
+        self.user_stories
+            .iter()
+            .filter(|s| s.status.state == state)
+            .count()
+    }
+
     pub fn mark_story_complete(&mut self, story_id: &str) {
+        self.set_story_status(story_id, StoryStatus::done());
+    }
+
+    pub fn set_story_status(&mut self, story_id: &str, status: StoryStatus) {
         if let Some(story) = self.user_stories.iter_mut().find(|s| s.id == story_id) {
-            story.passes = true;
+            story.status = status;
+        }
+    }
+
+    /// Mark every unfinished story that depends on `story_id`, directly or
+    /// through other stories, as blocked. Returns the IDs that were blocked.
+    pub fn block_dependents(&mut self, story_id: &str) -> Vec<String> {
+        let mut blocked = Vec::new();
+        let mut queue = vec![story_id.to_string()];
+        while let Some(current) = queue.pop() {
+            for story in self.user_stories.iter_mut() {
+                if story.passes()
+                    || story.status.state == StoryState::Blocked
+                    || !story.depends_on.contains(&current)
+                {
+                    continue;
+                }
+                let reason = if current == story_id {
+                    format!("waiting on {}, which failed", story_id)
+                } else {
+                    format!("waiting on {}, which is blocked by {}", current, story_id)
+                };
+                story.status = StoryStatus::new(StoryState::Blocked, Some(reason));
+                blocked.push(story.id.clone());
+                queue.push(story.id.clone());
+            }
+        }
+        blocked
+    }
+
+    pub fn story(&self, story_id: &str) -> Option<&UserStory> {
+        self.user_stories.iter().find(|s| s.id == story_id)
+    }
+
+    /// The next free story ID, continuing the numbering of existing IDs.
+    ///
+    /// Uses the prefix of the highest-numbered ID (e.g. `US-004` -> `US-005`),
+    /// or `US-001` when no ID ends in a number.
+    pub fn next_story_id(&self) -> String {
+        let numbered = self.user_stories.iter().filter_map(|s| {
+            let digits = s.id.len() - s.id.trim_end_matches(|c: char| c.is_ascii_digit()).len();
+            let (prefix, number) = s.id.split_at(s.id.len() - digits);
+            number
+                .parse::<u32>()
+                .ok()
+                .map(|n| (n, prefix.to_string(), digits))
+        });
+        match numbered.max_by_key(|(n, _, _)| *n) {
+            Some((n, prefix, width)) => format!("{}{:0width$}", prefix, n + 1, width = width),
+            None => "US-001".to_string(),
+        }
+    }
+
+    /// Add a story, rejecting duplicate IDs and unknown dependencies.
+    pub fn add_story(&mut self, story: UserStory) -> Result<()> {
+        if self.story(&story.id).is_some() {
+            return Err(Autom8Error::InvalidSpec(format!(
+                "story {} already exists",
+                story.id
+            )));
+        }
+        self.user_stories.push(story);
+        if let Err(e) = self.validate_dependencies() {
+            self.user_stories.pop();
+            return Err(e);
+        }
+        Ok(())
+    }
+
+    /// Remove a story and drop it from other stories' `dependsOn`.
+    ///
+    /// Returns the removed story and the IDs of stories that depended on it,
+    /// or `None` if there is no such story.
+    pub fn remove_story(&mut self, story_id: &str) -> Option<(UserStory, Vec<String>)> {
+        let index = self.user_stories.iter().position(|s| s.id == story_id)?;
+        let removed = self.user_stories.remove(index);
+        let mut dependents = Vec::new();
+        for story in self.user_stories.iter_mut() {
+            if story.depends_on.iter().any(|d| d == story_id) {
+                story.depends_on.retain(|d| d != story_id);
+                dependents.push(story.id.clone());
+            }
+        }
+        Some((removed, dependents))
+    }
+
+    /// Mark a story as not started so the next run implements it again.
+    ///
+    /// Stories that were blocked on it, directly or through other blocked
+    /// stories, are reset too. Returns false if there is no such story.
+    pub fn reset_story(&mut self, story_id: &str) -> bool {
+        if self.story(story_id).is_none() {
+            return false;
+        }
+        self.set_story_status(story_id, StoryStatus::new(StoryState::NotStarted, None));
+
+        let mut queue = vec![story_id.to_string()];
+        while let Some(current) = queue.pop() {
+            for story in self.user_stories.iter_mut() {
+                if story.status.state == StoryState::Blocked && story.depends_on.contains(&current)
+                {
+                    story.status = StoryStatus::new(StoryState::NotStarted, None);
+                    queue.push(story.id.clone());
+                }
+            }
+        }
+        true
+    }
+}
+
+/// Read the `schemaVersion` of a raw spec, treating a missing one as legacy.
+fn schema_version(value: &Value) -> Result<u32> {
+    match value.get("schemaVersion") {
+        None => Ok(LEGACY_SCHEMA_VERSION),
+        Some(v) => v
+            .as_u64()
+            .and_then(|v| u32::try_from(v).ok())
+            .filter(|v| *v >= LEGACY_SCHEMA_VERSION)
+            .ok_or_else(|| Autom8Error::InvalidSpec(format!("invalid schemaVersion: {}", v))),
+    }
+}
+
+/// Upgrade a raw spec from `version` to [`SPEC_SCHEMA_VERSION`].
+fn migrate_spec_value(value: &mut Value, version: u32) {
+    for step in (version..SPEC_SCHEMA_VERSION).map(|v| SPEC_MIGRATIONS[(v - 1) as usize]) {
+        step(value);
+    }
+    if let Some(obj) = value.as_object_mut() {
+        obj.insert("schemaVersion".into(), SPEC_SCHEMA_VERSION.into());
+    }
+}
+
+/// Where the original file is kept when a spec is upgraded from `version`.
+fn migration_backup_path(path: &Path, version: u32) -> PathBuf {
+    let file_name = path
+        .file_name()
+        .map(|n| n.to_string_lossy().into_owned())
+        .unwrap_or_default();
+    path.with_file_name(format!("{}.v{}.bak", file_name, version))
+}
+
+/// v1 specs predate `dependsOn` and left `branchName` and `notes` to their
+/// defaults. Write those values out so the spec keeps its meaning even if
+/// the defaults change later.
+fn migrate_v1_to_v2(value: &mut Value) {
+    let Some(spec) = value.as_object_mut() else {
+        return;
+    };
+    spec.entry("branchName")
+        .or_insert_with(|| default_branch_name().into());
+
+    let stories = spec.get_mut("userStories").and_then(Value::as_array_mut);
+    for story in stories.into_iter().flatten() {
+        if let Some(story) = story.as_object_mut() {
+            story.entry("notes").or_insert_with(|| "".into());
+            story
+                .entry("dependsOn")
+                .or_insert_with(|| Value::Array(Vec::new()));
         }
     }
 }
@@ -116,17 +664,32 @@ mod tests {
             description: format!("Description for {}", id),
             acceptance_criteria: vec!["Criteria 1".into()],
             priority,
-            passes,
+            status: if passes {
+                StoryStatus::done()
+            } else {
+                StoryStatus::default()
+            },
             notes: String::new(),
+            depends_on: Vec::new(),
+            verify: None,
+        }
+    }
+
+    fn make_story_with_deps(id: &str, priority: u32, passes: bool, deps: &[&str]) -> UserStory {
+        UserStory {
+            depends_on: deps.iter().map(|d| d.to_string()).collect(),
+            ..make_story(id, priority, passes)
         }
     }
 
     fn make_spec(stories: Vec<UserStory>) -> Spec {
         Spec {
+            schema_version: SPEC_SCHEMA_VERSION,
             project: "TestProject".into(),
             branch_name: "test-branch".into(),
             description: "Test description".into(),
             user_stories: stories,
+            verify: Vec::new(),
         }
     }
 
@@ -137,10 +700,12 @@ mod tests {
     #[test]
     fn test_validate_empty_project_name_fails() {
         let spec = Spec {
+            schema_version: SPEC_SCHEMA_VERSION,
             project: "".into(),
             branch_name: "test".into(),
             description: "Test".into(),
             user_stories: vec![make_story("US-001", 1, false)],
+            verify: Vec::new(),
         };
         let result = spec.validate();
         assert!(result.is_err());
@@ -153,10 +718,12 @@ mod tests {
     #[test]
     fn test_validate_empty_stories_fails() {
         let spec = Spec {
+            schema_version: SPEC_SCHEMA_VERSION,
             project: "Test".into(),
             branch_name: "test".into(),
             description: "Test".into(),
             user_stories: vec![],
+            verify: Vec::new(),
         };
         let result = spec.validate();
         assert!(result.is_err());
@@ -169,6 +736,7 @@ mod tests {
     #[test]
     fn test_validate_story_with_empty_id_fails() {
         let spec = Spec {
+            schema_version: SPEC_SCHEMA_VERSION,
             project: "Test".into(),
             branch_name: "test".into(),
             description: "Test".into(),
@@ -178,9 +746,12 @@ mod tests {
                 description: "Desc".into(),
                 acceptance_criteria: vec![],
                 priority: 1,
-                passes: false,
+                status: StoryStatus::default(),
                 notes: String::new(),
+                depends_on: Vec::new(),
+                verify: None,
             }],
+            verify: Vec::new(),
         };
         let result = spec.validate();
         assert!(result.is_err());
@@ -239,9 +810,9 @@ mod tests {
         assert_eq!(loaded.description, spec.description);
         assert_eq!(loaded.user_stories.len(), 2);
         assert_eq!(loaded.user_stories[0].id, "US-001");
-        assert!(loaded.user_stories[0].passes);
+        assert!(loaded.user_stories[0].passes());
         assert_eq!(loaded.user_stories[1].id, "US-002");
-        assert!(!loaded.user_stories[1].passes);
+        assert!(!loaded.user_stories[1].passes());
     }
 
     #[test]
@@ -313,6 +884,119 @@ mod tests {
         assert_eq!(next.id, "US-001");
     }
 
+    #[test]
+    fn test_next_incomplete_story_waits_for_dependencies() {
+        // Schema story has a worse priority but the API stories depend on it
+        let spec = make_spec(vec![
+            make_story_with_deps("US-001", 1, false, &["US-003"]),
+            make_story_with_deps("US-002", 2, false, &["US-003"]),
+            make_story("US-003", 5, false),
+        ]);
+        let next = spec.next_incomplete_story().unwrap();
+        assert_eq!(next.id, "US-003");
+    }
+
+    #[test]
+    fn test_next_incomplete_story_picks_by_priority_once_dependencies_pass() {
+        let spec = make_spec(vec![
+            make_story_with_deps("US-001", 1, false, &["US-003"]),
+            make_story_with_deps("US-002", 2, false, &["US-001"]),
+            make_story("US-003", 5, true),
+        ]);
+        let next = spec.next_incomplete_story().unwrap();
+        assert_eq!(next.id, "US-001");
+    }
+
+    #[test]
+    fn test_ready_stories_excludes_blocked_and_completed() {
+        let spec = make_spec(vec![
+            make_story("US-001", 3, false),
+            make_story("US-002", 1, true),
+            make_story_with_deps("US-003", 2, false, &["US-001"]),
+            make_story_with_deps("US-004", 4, false, &["US-002"]),
+        ]);
+        let ids: Vec<&str> = spec.ready_stories().iter().map(|s| s.id.as_str()).collect();
+        assert_eq!(ids, vec!["US-001", "US-004"]);
+    }
+
+    #[test]
+    fn test_ready_stories_excludes_failed_until_reset() {
+        let mut spec = make_spec(vec![
+            make_story("US-001", 1, false),
+            make_story("US-002", 2, false),
+        ]);
+        spec.set_story_status("US-001", StoryStatus::new(StoryState::Failed, None));
+        let ids: Vec<&str> = spec.ready_stories().iter().map(|s| s.id.as_str()).collect();
+        assert_eq!(ids, vec!["US-002"]);
+
+        spec.reset_story("US-001");
+        assert_eq!(spec.next_incomplete_story().unwrap().id, "US-001");
+    }
+
+    #[test]
+    fn test_dependencies_met() {
+        let spec = make_spec(vec![
+            make_story("US-001", 1, true),
+            make_story("US-002", 2, false),
+            make_story_with_deps("US-003", 3, false, &["US-001", "US-002"]),
+        ]);
+        assert!(spec.dependencies_met(&spec.user_stories[0]));
+        assert!(!spec.dependencies_met(&spec.user_stories[2]));
+    }
+
+    // ===========================================
+    // Dependency validation tests
+    // ===========================================
+
+    #[test]
+    fn test_validate_unknown_dependency_fails() {
+        let spec = make_spec(vec![make_story_with_deps("US-001", 1, false, &["US-009"])]);
+        let err = spec.validate().unwrap_err().to_string();
+        assert!(err.contains("story US-001 depends on unknown story US-009"));
+    }
+
+    #[test]
+    fn test_validate_dependency_cycle_fails() {
+        let spec = make_spec(vec![
+            make_story_with_deps("US-001", 1, false, &["US-002"]),
+            make_story_with_deps("US-002", 2, false, &["US-003"]),
+            make_story_with_deps("US-003", 3, false, &["US-001"]),
+        ]);
+        let err = spec.validate().unwrap_err().to_string();
+        assert!(err.contains("dependency cycle detected: US-001 -> US-002 -> US-003 -> US-001"));
+    }
+
+    #[test]
+    fn test_validate_self_dependency_fails() {
+        let spec = make_spec(vec![make_story_with_deps("US-001", 1, false, &["US-001"])]);
+        let err = spec.validate().unwrap_err().to_string();
+        assert!(err.contains("dependency cycle detected: US-001 -> US-001"));
+    }
+
+    #[test]
+    fn test_validate_diamond_dependencies_succeeds() {
+        let spec = make_spec(vec![
+            make_story("US-001", 1, false),
+            make_story_with_deps("US-002", 2, false, &["US-001"]),
+            make_story_with_deps("US-003", 3, false, &["US-001"]),
+            make_story_with_deps("US-004", 4, false, &["US-002", "US-003"]),
+        ]);
+        assert!(spec.validate().is_ok());
+    }
+
+    #[test]
+    fn test_depends_on_defaults_to_empty_and_round_trips() {
+        let mut file = NamedTempFile::new().unwrap();
+        writeln!(
+            file,
+            r#"{{"project": "Test", "description": "Test", "userStories": [{{"id": "US-001", "title": "T", "description": "D", "acceptanceCriteria": [], "priority": 1, "passes": false}}, {{"id": "US-002", "title": "T", "description": "D", "acceptanceCriteria": [], "priority": 2, "passes": false, "dependsOn": ["US-001"]}}]}}"#
+        )
+        .unwrap();
+        let loaded = Spec::load_with_backup(file.path(), false).unwrap();
+        assert!(loaded.user_stories[0].depends_on.is_empty());
+        assert_eq!(loaded.user_stories[1].depends_on, vec!["US-001"]);
+    }
+
     // ===========================================
     // Completion calculation tests
     // ===========================================
@@ -397,22 +1081,112 @@ mod tests {
             make_story("US-002", 2, false),
         ]);
         spec.mark_story_complete("US-001");
-        assert!(spec.user_stories[0].passes);
-        assert!(!spec.user_stories[1].passes);
+        assert!(spec.user_stories[0].passes());
+        assert!(!spec.user_stories[1].passes());
     }
 
     #[test]
     fn test_mark_story_complete_nonexistent_id_is_noop() {
         let mut spec = make_spec(vec![make_story("US-001", 1, false)]);
         spec.mark_story_complete("US-999"); // doesn't exist
-        assert!(!spec.user_stories[0].passes); // unchanged
+        assert!(!spec.user_stories[0].passes()); // unchanged
     }
 
     #[test]
     fn test_mark_story_complete_already_complete_is_idempotent() {
         let mut spec = make_spec(vec![make_story("US-001", 1, true)]);
         spec.mark_story_complete("US-001");
-        assert!(spec.user_stories[0].passes); // still true
+        assert!(spec.user_stories[0].passes()); // still true
+    }
+
+    // ===========================================
+    // Story status tests
+    // ===========================================
+
+    fn story_json(passes: bool, status: &str) -> String {
+        format!(
+            r#"{{"id": "US-001", "title": "T", "description": "D", "acceptanceCriteria": [], "priority": 1, "passes": {}{}}}"#,
+            passes, status
+        )
+    }
+
+    #[test]
+    fn test_story_status_reads_legacy_passes_flag() {
+        let done: UserStory = serde_json::from_str(&story_json(true, "")).unwrap();
+        assert_eq!(done.status.state, StoryState::Done);
+        assert!(done.status.updated_at.is_none());
+
+        let pending: UserStory = serde_json::from_str(&story_json(false, "")).unwrap();
+        assert_eq!(pending.status, StoryStatus::default());
+    }
+
+    #[test]
+    fn test_story_status_round_trips_and_keeps_passes() {
+        let mut story = make_story("US-001", 1, false);
+        story.status = StoryStatus::new(StoryState::Blocked, Some("waiting on US-000".into()));
+
+        let json = serde_json::to_value(&story).unwrap();
+        assert_eq!(json["passes"], false);
+        assert_eq!(json["status"]["state"], "blocked");
+        assert_eq!(json["status"]["reason"], "waiting on US-000");
+
+        let loaded: UserStory = serde_json::from_value(json).unwrap();
+        assert_eq!(loaded.status, story.status);
+
+        let json = serde_json::to_value(make_story("US-002", 1, true)).unwrap();
+        assert_eq!(json["passes"], true);
+        assert_eq!(json["status"]["state"], "done");
+    }
+
+    #[test]
+    fn test_story_status_passes_flag_wins_on_disagreement() {
+        // Claude set passes while the story was in progress
+        let json = story_json(true, r#", "status": {"state": "inProgress"}"#);
+        let story: UserStory = serde_json::from_str(&json).unwrap();
+        assert_eq!(story.status.state, StoryState::Done);
+
+        // Reopened by hand
+        let json = story_json(false, r#", "status": {"state": "done"}"#);
+        let story: UserStory = serde_json::from_str(&json).unwrap();
+        assert_eq!(story.status.state, StoryState::NotStarted);
+    }
+
+    #[test]
+    fn test_next_story_id_continues_numbering() {
+        let spec = make_spec(vec![
+            make_story("US-002", 1, false),
+            make_story("US-010", 2, false),
+            make_story("setup", 3, false),
+        ]);
+        assert_eq!(spec.next_story_id(), "US-011");
+
+        let spec = make_spec(vec![make_story("T9", 1, false)]);
+        assert_eq!(spec.next_story_id(), "T10");
+
+        let spec = make_spec(vec![make_story("setup", 1, false)]);
+        assert_eq!(spec.next_story_id(), "US-001");
+    }
+
+    #[test]
+    fn test_block_dependents_is_transitive_and_skips_done_stories() {
+        let mut spec = make_spec(vec![
+            make_story("US-001", 1, false),
+            make_story_with_deps("US-002", 2, false, &["US-001"]),
+            make_story_with_deps("US-003", 3, false, &["US-002"]),
+            make_story_with_deps("US-004", 4, true, &["US-001"]),
+            make_story("US-005", 5, false),
+        ]);
+
+        let blocked = spec.block_dependents("US-001");
+
+        assert_eq!(blocked, vec!["US-002", "US-003"]);
+        assert_eq!(spec.count_in_state(StoryState::Blocked), 2);
+        assert_eq!(
+            spec.user_stories[1].status.describe(),
+            "blocked: waiting on US-001, which failed"
+        );
+        assert!(spec.user_stories[3].passes());
+        assert_eq!(spec.user_stories[4].status.state, StoryState::NotStarted);
     }
 
     // ===========================================
@@ -428,7 +1202,162 @@ mod tests {
             r#"{{"project": "Test", "description": "Test", "userStories": [{{"id": "US-001", "title": "T", "description": "D", "acceptanceCriteria": [], "priority": 1, "passes": false}}]}}"#
         )
         .unwrap();
-        let loaded = Spec::load(file.path()).unwrap();
+        let loaded = Spec::load_with_backup(file.path(), false).unwrap();
         assert_eq!(loaded.branch_name, "autom8/feature");
     }
+
+    // ===========================================
+    // Schema migration tests
+    // ===========================================
+
+    const LEGACY_SPEC_JSON: &str = r#"{"project": "Test", "description": "Test", "userStories": [{"id": "US-001", "title": "T", "description": "D", "acceptanceCriteria": [], "priority": 1, "passes": true}]}"#;
+
+    #[test]
+    fn test_saved_spec_includes_schema_version() {
+        let file = NamedTempFile::new().unwrap();
+        make_spec(vec![make_story("US-001", 1, false)])
+            .save(file.path())
+            .unwrap();
+
+        let content = fs::read_to_string(file.path()).unwrap();
+        assert!(content.contains(&format!("\"schemaVersion\": {}", SPEC_SCHEMA_VERSION)));
+    }
+
+    #[test]
+    fn test_load_upgrades_legacy_spec_in_place() {
+        let dir = tempfile::TempDir::new().unwrap();
+        let path = dir.path().join("spec-test.json");
+        fs::write(&path, LEGACY_SPEC_JSON).unwrap();
+
+        let loaded = Spec::load_with_backup(&path, false).unwrap();
+        assert_eq!(loaded.schema_version, SPEC_SCHEMA_VERSION);
+        assert_eq!(loaded.branch_name, "autom8/feature");
+        assert!(loaded.user_stories[0].passes());
+
+        let upgraded: Value = serde_json::from_str(&fs::read_to_string(&path).unwrap()).unwrap();
+        assert_eq!(upgraded["schemaVersion"], SPEC_SCHEMA_VERSION);
+        assert_eq!(upgraded["branchName"], "autom8/feature");
+        assert_eq!(
+            upgraded["userStories"][0]["dependsOn"],
+            serde_json::json!([])
+        );
+        assert!(!dir.path().join("spec-test.json.v1.bak").exists());
+    }
+
+    #[test]
+    fn test_load_upgrade_keeps_backup_of_original() {
+        let dir = tempfile::TempDir::new().unwrap();
+        let path = dir.path().join("spec-test.json");
+        fs::write(&path, LEGACY_SPEC_JSON).unwrap();
+
+        Spec::load_with_backup(&path, true).unwrap();
+
+        let backup = fs::read_to_string(dir.path().join("spec-test.json.v1.bak")).unwrap();
+        assert_eq!(backup, LEGACY_SPEC_JSON);
+    }
+
+    #[test]
+    fn test_read_upgrades_legacy_spec_in_memory_only() {
+        let dir = tempfile::TempDir::new().unwrap();
+        let path = dir.path().join("spec-test.json");
+        fs::write(&path, LEGACY_SPEC_JSON).unwrap();
+
+        let read = Spec::read(&path).unwrap();
+        assert_eq!(read.schema_version, SPEC_SCHEMA_VERSION);
+        assert_eq!(read.branch_name, "autom8/feature");
+
+        assert_eq!(fs::read_to_string(&path).unwrap(), LEGACY_SPEC_JSON);
+        assert_eq!(fs::read_dir(dir.path()).unwrap().count(), 1);
+    }
+
+    #[test]
+    fn test_load_current_spec_does_not_rewrite_or_back_up() {
+        let dir = tempfile::TempDir::new().unwrap();
+        let path = dir.path().join("spec-test.json");
+        let content = format!(
+            r#"{{"schemaVersion": {}, "project": "Test", "branchName": "b", "description": "Test", "userStories": [{{"id": "US-001", "title": "T", "description": "D", "acceptanceCriteria": [], "priority": 1, "passes": false}}]}}"#,
+            SPEC_SCHEMA_VERSION
+        );
+        fs::write(&path, &content).unwrap();
+
+        Spec::load_with_backup(&path, true).unwrap();
+
+        assert_eq!(fs::read_to_string(&path).unwrap(), content);
+        assert_eq!(fs::read_dir(dir.path()).unwrap().count(), 1);
+    }
+
+    #[test]
+    fn test_load_rejects_spec_from_newer_version() {
+        let mut file = NamedTempFile::new().unwrap();
+        let content = format!(
+            r#"{{"schemaVersion": {}, "project": "Test", "description": "Test", "userStories": []}}"#,
+            SPEC_SCHEMA_VERSION + 1
+        );
+        write!(file, "{}", content).unwrap();
+
+        match Spec::load_with_backup(file.path(), true).unwrap_err() {
+            Autom8Error::UnsupportedSpecVersion {
+                version, supported, ..
+            } => {
+                assert_eq!(version, SPEC_SCHEMA_VERSION + 1);
+                assert_eq!(supported, SPEC_SCHEMA_VERSION);
+            }
+            e => panic!("Expected UnsupportedSpecVersion, got {:?}", e),
+        }
+        assert_eq!(fs::read_to_string(file.path()).unwrap(), content);
+    }
+
+    #[test]
+    fn test_load_rejects_invalid_schema_version() {
+        let mut file = NamedTempFile::new().unwrap();
+        write!(
+            file,
+            r#"{{"schemaVersion": "two", "project": "Test", "description": "Test", "userStories": []}}"#
+        )
+        .unwrap();
+
+        let err = Spec::load_with_backup(file.path(), false).unwrap_err();
+        assert!(err.to_string().contains("invalid schemaVersion"));
+    }
+
+    #[test]
+    fn test_migrate_v1_to_v2_keeps_explicit_values() {
+        let mut value = serde_json::json!({
+            "branchName": "feature/x",
+            "userStories": [{"id": "US-002", "notes": "keep", "dependsOn": ["US-001"]}]
+        });
+        migrate_spec_value(&mut value, 1);
+
+        assert_eq!(value["schemaVersion"], SPEC_SCHEMA_VERSION);
+        assert_eq!(value["branchName"], "feature/x");
+        assert_eq!(value["userStories"][0]["notes"], "keep");
+        assert_eq!(
+            value["userStories"][0]["dependsOn"],
+            serde_json::json!(["US-001"])
+        );
+    }
+
+    #[test]
+    fn test_every_schema_version_has_a_migration() {
+        assert_eq!(
+            SPEC_MIGRATIONS.len() as u32,
+            SPEC_SCHEMA_VERSION - LEGACY_SCHEMA_VERSION
+        );
+    }
+
+    #[test]
+    fn test_story_commit_message() {
+        let story = make_story("US-003", 1, true);
+        assert_eq!(
+            story.commit_message(),
+            "US-003: Story US-003\n\nDescription for US-003\n\nAcceptance criteria:\n- Criteria 1"
+        );
+
+        let bare = UserStory {
+            description: " ".into(),
+            acceptance_criteria: Vec::new(),
+            ..story
+        };
+        assert_eq!(bare.commit_message(), "US-003: Story US-003");
+    }
 }
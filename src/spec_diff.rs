@@ -0,0 +1,249 @@
+//! Spec fingerprints and diffs.
+//!
+//! A run records a [`SpecFingerprint`] of its spec when it starts, so that
+//! `autom8 resume` can tell when the spec was edited in the meantime and show
+//! which stories were added, removed or changed.
+//!
+//! Only what a story asks for is fingerprinted: its title, description,
+//! acceptance criteria, dependencies and verification commands. Status,
+//! priority and notes change during normal use and don't count as edits.
+
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+
+use crate::spec::{Spec, UserStory};
+
+/// Content hashes of a spec and each of its stories.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SpecFingerprint {
+    /// Hash of the spec-level fields and every story hash, in spec order.
+    pub hash: String,
+    /// One entry per story, in spec order.
+    pub stories: Vec<StoryFingerprint>,
+}
+
+/// The ID, title and content hash of one story.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct StoryFingerprint {
+    pub id: String,
+    pub title: String,
+    pub hash: String,
+}
+
+/// How a spec differs from a fingerprint taken earlier.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct SpecDiff {
+    /// Stories that are new, as (ID, title).
+    pub added: Vec<(String, String)>,
+    /// Stories that no longer exist, as (ID, title at the time of the fingerprint).
+    pub removed: Vec<(String, String)>,
+    /// Stories whose content changed, as (ID, current title).
+    pub changed: Vec<(String, String)>,
+    /// Whether the project, branch, description or spec-level verify changed.
+    pub spec_changed: bool,
+}
+
+impl SpecDiff {
+    pub fn is_empty(&self) -> bool {
+        self.added.is_empty()
+            && self.removed.is_empty()
+            && self.changed.is_empty()
+            && !self.spec_changed
+    }
+
+    /// IDs of removed stories.
+    pub fn removed_ids(&self) -> Vec<String> {
+        self.removed.iter().map(|(id, _)| id.clone()).collect()
+    }
+
+    /// IDs of changed stories.
+    pub fn changed_ids(&self) -> Vec<String> {
+        self.changed.iter().map(|(id, _)| id.clone()).collect()
+    }
+}
+
+impl SpecFingerprint {
+    /// Fingerprint the current content of a spec.
+    pub fn of(spec: &Spec) -> Self {
+        let stories: Vec<StoryFingerprint> = spec
+            .user_stories
+            .iter()
+            .map(|story| StoryFingerprint {
+                id: story.id.clone(),
+                title: story.title.clone(),
+                hash: story_hash(story),
+            })
+            .collect();
+
+        let mut hasher = Sha256::new();
+        hasher.update(spec_fields_hash(spec));
+        for story in &stories {
+            hasher.update(&story.id);
+            hasher.update(&story.hash);
+        }
+
+        Self {
+            hash: hex::encode(hasher.finalize()),
+            stories,
+        }
+    }
+
+    /// Compare this fingerprint with a spec's current content.
+    pub fn diff(&self, spec: &Spec) -> SpecDiff {
+        let current = SpecFingerprint::of(spec);
+        if current.hash == self.hash {
+            return SpecDiff::default();
+        }
+
+        let find =
+            |stories: &[StoryFingerprint], id: &str| stories.iter().find(|s| s.id == id).cloned();
+
+        let mut diff = SpecDiff {
+            spec_changed: self.spec_fields_hash_differs(spec),
+            ..Default::default()
+        };
+        for story in &current.stories {
+            match find(&self.stories, &story.id) {
+                None => diff.added.push((story.id.clone(), story.title.clone())),
+                Some(before) if before.hash != story.hash => {
+                    diff.changed.push((story.id.clone(), story.title.clone()))
+                }
+                Some(_) => {}
+            }
+        }
+        for story in &self.stories {
+            if find(&current.stories, &story.id).is_none() {
+                diff.removed.push((story.id.clone(), story.title.clone()));
+            }
+        }
+        diff
+    }
+
+    /// The story hashes don't cover spec-level fields, so recompute the
+    /// overall hash from the recorded stories and the spec's current fields.
+    fn spec_fields_hash_differs(&self, spec: &Spec) -> bool {
+        let mut hasher = Sha256::new();
+        hasher.update(spec_fields_hash(spec));
+        for story in &self.stories {
+            hasher.update(&story.id);
+            hasher.update(&story.hash);
+        }
+        hex::encode(hasher.finalize()) != self.hash
+    }
+}
+
+fn spec_fields_hash(spec: &Spec) -> String {
+    hash_fields(&[
+        &spec.project,
+        &spec.branch_name,
+        &spec.description,
+        &spec.verify.join("\n"),
+    ])
+}
+
+fn story_hash(story: &UserStory) -> String {
+    let verify = story
+        .verify
+        .as_ref()
+        .map(|commands| format!("some:{}", commands.join("\n")))
+        .unwrap_or_default();
+    hash_fields(&[
+        &story.title,
+        &story.description,
+        &story.acceptance_criteria.join("\n"),
+        &story.depends_on.join("\n"),
+        &verify,
+    ])
+}
+
+/// Hash fields with their lengths, so moving text between fields changes it.
+fn hash_fields(fields: &[&str]) -> String {
+    let mut hasher = Sha256::new();
+    for field in fields {
+        hasher.update((field.len() as u64).to_le_bytes());
+        hasher.update(field.as_bytes());
+    }
+    hex::encode(hasher.finalize())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::spec::{StoryStatus, SPEC_SCHEMA_VERSION};
+
+    fn make_story(id: &str, title: &str) -> UserStory {
+        UserStory {
+            id: id.to_string(),
+            title: title.to_string(),
+            description: format!("Description for {}", id),
+            acceptance_criteria: vec!["Works".to_string()],
+            priority: 1,
+            status: StoryStatus::default(),
+            notes: String::new(),
+            depends_on: Vec::new(),
+            verify: None,
+        }
+    }
+
+    fn make_spec(stories: Vec<UserStory>) -> Spec {
+        Spec {
+            schema_version: SPEC_SCHEMA_VERSION,
+            project: "test".to_string(),
+            branch_name: "feature/test".to_string(),
+            description: "Test".to_string(),
+            user_stories: stories,
+            verify: Vec::new(),
+        }
+    }
+
+    #[test]
+    fn test_diff_ignores_status_priority_and_notes() {
+        let mut spec = make_spec(vec![make_story("US-001", "One")]);
+        let fingerprint = SpecFingerprint::of(&spec);
+
+        spec.mark_story_complete("US-001");
+        spec.user_stories[0].priority = 7;
+        spec.user_stories[0].notes = "Try the cache first".to_string();
+
+        assert_eq!(SpecFingerprint::of(&spec), fingerprint);
+        assert!(fingerprint.diff(&spec).is_empty());
+    }
+
+    #[test]
+    fn test_diff_reports_added_removed_and_changed_stories() {
+        let before = make_spec(vec![
+            make_story("US-001", "One"),
+            make_story("US-002", "Two"),
+            make_story("US-003", "Three"),
+        ]);
+        let fingerprint = SpecFingerprint::of(&before);
+
+        let mut after = before.clone();
+        after.remove_story("US-002");
+        after.user_stories[1]
+            .acceptance_criteria
+            .push("Handles errors".to_string());
+        after.user_stories.push(make_story("US-004", "Four"));
+
+        let diff = fingerprint.diff(&after);
+        assert_eq!(diff.added, vec![("US-004".to_string(), "Four".to_string())]);
+        assert_eq!(
+            diff.removed,
+            vec![("US-002".to_string(), "Two".to_string())]
+        );
+        assert_eq!(diff.changed_ids(), vec!["US-003"]);
+        assert!(!diff.spec_changed);
+    }
+
+    #[test]
+    fn test_diff_reports_spec_level_changes() {
+        let mut spec = make_spec(vec![make_story("US-001", "One")]);
+        let fingerprint = SpecFingerprint::of(&spec);
+
+        spec.verify = vec!["cargo test".to_string()];
+
+        let diff = fingerprint.diff(&spec);
+        assert!(diff.spec_changed);
+        assert!(diff.added.is_empty() && diff.removed.is_empty() && diff.changed.is_empty());
+    }
+}
@@ -0,0 +1,560 @@
+//! Spec linting.
+//!
+//! Goes beyond [`Spec`] validation (which only rejects specs autom8 can't run
+//! at all) to catch problems that make a run go wrong: duplicate IDs, stories
+//! Claude can't verify, and branch names that git or the workflow would reject.
+//!
+//! Linting never modifies the spec file; older specs are upgraded in memory.
+
+use std::collections::HashMap;
+use std::fs;
+use std::path::Path;
+
+use serde::Serialize;
+
+use crate::spec::{Spec, SPEC_SCHEMA_VERSION};
+use crate::spec_parser::parse_markdown_spec;
+
+/// Phrases that describe a feeling rather than something that can be checked.
+const VAGUE_PHRASES: &[&str] = &[
+    "works well",
+    "works correctly",
+    "works properly",
+    "works as expected",
+    "works fine",
+    "is fast",
+    "is intuitive",
+    "user-friendly",
+    "user friendly",
+    "looks good",
+    "looks nice",
+    "is clean",
+    "clean code",
+    "is robust",
+    "seamless",
+    "good performance",
+    "good user experience",
+    "as appropriate",
+    "etc.",
+];
+
+/// How serious a lint issue is.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum LintSeverity {
+    /// The spec will fail or misbehave when run.
+    Error,
+    /// The spec will run, but probably not the way it was meant to.
+    Warning,
+}
+
+/// A single problem found in a spec.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct LintIssue {
+    pub severity: LintSeverity,
+    /// Short kebab-case identifier of the check that failed
+    pub rule: &'static str,
+    /// The story the issue is about, if any
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub story_id: Option<String>,
+    pub message: String,
+}
+
+impl LintIssue {
+    fn error(rule: &'static str, story_id: Option<&str>, message: impl Into<String>) -> Self {
+        Self {
+            severity: LintSeverity::Error,
+            rule,
+            story_id: story_id.map(String::from),
+            message: message.into(),
+        }
+    }
+
+    fn warning(rule: &'static str, story_id: Option<&str>, message: impl Into<String>) -> Self {
+        Self {
+            severity: LintSeverity::Warning,
+            ..Self::error(rule, story_id, message)
+        }
+    }
+}
+
+/// Lint results for one spec file.
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct LintReport {
+    pub path: String,
+    pub errors: usize,
+    pub warnings: usize,
+    pub issues: Vec<LintIssue>,
+}
+
+impl LintReport {
+    fn new(path: &Path, issues: Vec<LintIssue>) -> Self {
+        let errors = issues
+            .iter()
+            .filter(|i| i.severity == LintSeverity::Error)
+            .count();
+        Self {
+            path: path.display().to_string(),
+            errors,
+            warnings: issues.len() - errors,
+            issues,
+        }
+    }
+}
+
+/// Repository facts some checks compare the spec against.
+///
+/// Checks whose context is missing (e.g. outside a git repo) are skipped.
+#[derive(Debug, Clone, Default)]
+pub struct LintContext {
+    /// The repository's base branch (e.g. "main")
+    pub base_branch: Option<String>,
+    /// The autom8 project name for the current directory
+    pub project_name: Option<String>,
+}
+
+impl LintContext {
+    /// Detect the context from the current directory.
+    pub fn detect() -> Self {
+        let in_repo = crate::git::is_git_repo();
+        Self {
+            base_branch: in_repo
+                .then(crate::git::detect_base_branch)
+                .and_then(|r| r.ok()),
+            project_name: in_repo
+                .then(crate::config::current_project_name)
+                .and_then(|r| r.ok()),
+        }
+    }
+}
+
+/// Lint a spec file (`.json` or `.md`) without modifying it.
+pub fn lint_file(path: &Path, ctx: &LintContext) -> LintReport {
+    let content = match fs::read_to_string(path) {
+        Ok(content) => content,
+        Err(e) => {
+            let issue = LintIssue::error("unreadable", None, format!("cannot read file: {}", e));
+            return LintReport::new(path, vec![issue]);
+        }
+    };
+
+    let issues = if path.extension().is_some_and(|e| e == "md") {
+        match parse_markdown_spec(&content) {
+            Ok(spec) => lint_spec(&spec, ctx),
+            Err(diagnostics) => diagnostics
+                .iter()
+                .map(|d| LintIssue::error("markdown-format", None, d.to_string()))
+                .collect(),
+        }
+    } else {
+        match Spec::parse_migrated(&content, path) {
+            Ok((spec, version)) => {
+                let mut issues = lint_spec(&spec, ctx);
+                if version < SPEC_SCHEMA_VERSION {
+                    issues.insert(
+                        0,
+                        LintIssue::warning(
+                            "schema-version",
+                            None,
+                            format!(
+                                "spec uses schema version {} (current is {}); it will be upgraded the next time autom8 loads it",
+                                version, SPEC_SCHEMA_VERSION
+                            ),
+                        ),
+                    );
+                }
+                issues
+            }
+            Err(e) => vec![LintIssue::error("parse", None, first_line(&e.to_string()))],
+        }
+    };
+
+    LintReport::new(path, issues)
+}
+
+/// Run every check against a parsed spec.
+pub fn lint_spec(spec: &Spec, ctx: &LintContext) -> Vec<LintIssue> {
+    let mut issues = Vec::new();
+
+    if let Err(e) = spec.validate() {
+        issues.push(LintIssue::error(
+            "invalid",
+            None,
+            first_line(&e.to_string()),
+        ));
+    }
+    check_duplicates(spec, &mut issues);
+    check_acceptance_criteria(spec, &mut issues);
+    check_branch_name(spec, ctx, &mut issues);
+    check_project_name(spec, ctx, &mut issues);
+
+    issues
+}
+
+fn check_duplicates(spec: &Spec, issues: &mut Vec<LintIssue>) {
+    let mut by_id: HashMap<&str, usize> = HashMap::new();
+    let mut by_priority: HashMap<u32, Vec<&str>> = HashMap::new();
+    for story in &spec.user_stories {
+        *by_id.entry(story.id.as_str()).or_default() += 1;
+        let ids = by_priority.entry(story.priority).or_default();
+        if !ids.contains(&story.id.as_str()) {
+            ids.push(story.id.as_str());
+        }
+    }
+
+    let mut seen = Vec::new();
+    for story in &spec.user_stories {
+        let count = by_id[story.id.as_str()];
+        if count > 1 && !seen.contains(&story.id.as_str()) {
+            seen.push(story.id.as_str());
+            issues.push(LintIssue::error(
+                "duplicate-id",
+                Some(&story.id),
+                format!("story ID {} is used by {} stories", story.id, count),
+            ));
+        }
+    }
+
+    let mut priorities: Vec<_> = by_priority
+        .into_iter()
+        .filter(|(_, ids)| ids.len() > 1)
+        .collect();
+    priorities.sort_by_key(|(priority, _)| *priority);
+    for (priority, ids) in priorities {
+        issues.push(LintIssue::warning(
+            "duplicate-priority",
+            None,
+            format!(
+                "stories {} share priority {}; their order is ambiguous",
+                ids.join(", "),
+                priority
+            ),
+        ));
+    }
+}
+
+fn check_acceptance_criteria(spec: &Spec, issues: &mut Vec<LintIssue>) {
+    for story in &spec.user_stories {
+        if story
+            .acceptance_criteria
+            .iter()
+            .all(|c| c.trim().is_empty())
+        {
+            issues.push(LintIssue::error(
+                "missing-criteria",
+                Some(&story.id),
+                "story has no acceptance criteria",
+            ));
+            continue;
+        }
+        for criterion in &story.acceptance_criteria {
+            if let Some(phrase) = vague_phrase(criterion) {
+                issues.push(LintIssue::warning(
+                    "vague-criterion",
+                    Some(&story.id),
+                    format!(
+                        "\"{}\" is too vague to verify (\"{}\"); describe an observable result",
+                        criterion.trim(),
+                        phrase
+                    ),
+                ));
+            }
+        }
+    }
+}
+
+fn check_branch_name(spec: &Spec, ctx: &LintContext, issues: &mut Vec<LintIssue>) {
+    if let Some(reason) = invalid_branch_name_reason(&spec.branch_name) {
+        issues.push(LintIssue::error(
+            "invalid-branch",
+            None,
+            format!(
+                "branchName \"{}\" is not a valid git branch name: {}",
+                spec.branch_name, reason
+            ),
+        ));
+    }
+    if ctx.base_branch.as_deref() == Some(spec.branch_name.as_str()) {
+        issues.push(LintIssue::error(
+            "base-branch",
+            None,
+            format!(
+                "branchName \"{}\" is the base branch; autom8 would commit to it directly",
+                spec.branch_name
+            ),
+        ));
+    }
+}
+
+fn check_project_name(spec: &Spec, ctx: &LintContext, issues: &mut Vec<LintIssue>) {
+    let Some(project_name) = &ctx.project_name else {
+        return;
+    };
+    if normalize_name(&spec.project) != normalize_name(project_name) {
+        issues.push(LintIssue::warning(
+            "project-mismatch",
+            None,
+            format!(
+                "project \"{}\" doesn't match the current project \"{}\"",
+                spec.project, project_name
+            ),
+        ));
+    }
+}
+
+/// The vague phrase a criterion relies on, if any.
+fn vague_phrase(criterion: &str) -> Option<&'static str> {
+    let lower = criterion.to_lowercase();
+    VAGUE_PHRASES
+        .iter()
+        .copied()
+        .find(|phrase| lower.contains(phrase))
+}
+
+/// Why git would reject `name` as a branch name, following `git check-ref-format`.
+pub fn invalid_branch_name_reason(name: &str) -> Option<&'static str> {
+    if name.is_empty() {
+        return Some("it is empty");
+    }
+    if name == "@" {
+        return Some("it is \"@\"");
+    }
+    if name.starts_with('-') {
+        return Some("it starts with '-'");
+    }
+    if name.starts_with('/') || name.ends_with('/') || name.contains("//") {
+        return Some("it has an empty path component");
+    }
+    if name.ends_with('.') {
+        return Some("it ends with '.'");
+    }
+    if name.contains("..") {
+        return Some("it contains \"..\"");
+    }
+    if name.contains("@{") {
+        return Some("it contains \"@{\"");
+    }
+    if name
+        .chars()
+        .any(|c| c.is_ascii_control() || " ~^:?*[\\".contains(c))
+    {
+        return Some("it contains a space, control character or one of ~^:?*[\\");
+    }
+    if name
+        .split('/')
+        .any(|part| part.starts_with('.') || part.ends_with(".lock"))
+    {
+        return Some("a component starts with '.' or ends with \".lock\"");
+    }
+    None
+}
+
+/// Compare project names loosely: case and separators don't matter.
+fn normalize_name(name: &str) -> String {
+    name.chars()
+        .filter(|c| c.is_alphanumeric())
+        .flat_map(char::to_lowercase)
+        .collect()
+}
+
+/// Errors carry multi-line fix-it text; lint output only needs the summary.
+fn first_line(message: &str) -> String {
+    message.lines().next().unwrap_or_default().to_string()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::spec::UserStory;
+    use crate::test_utils::{test_spec, test_story};
+
+    fn make_story(id: &str, priority: u32, criteria: &[&str]) -> UserStory {
+        UserStory {
+            acceptance_criteria: criteria.iter().map(|c| c.to_string()).collect(),
+            priority,
+            ..test_story(id)
+        }
+    }
+
+    fn rules(issues: &[LintIssue]) -> Vec<&'static str> {
+        issues.iter().map(|i| i.rule).collect()
+    }
+
+    // ===========================================
+    // Spec checks
+    // ===========================================
+
+    #[test]
+    fn test_clean_spec_has_no_issues() {
+        let spec = test_spec(vec![
+            make_story("US-001", 1, &["Returns 401 for a wrong password"]),
+            make_story("US-002", 2, &["Session cookie is set on login"]),
+        ]);
+        let ctx = LintContext {
+            base_branch: Some("main".into()),
+            project_name: Some("Test Project".into()),
+        };
+        assert!(lint_spec(&spec, &ctx).is_empty());
+    }
+
+    #[test]
+    fn test_duplicate_ids_and_priorities() {
+        let spec = test_spec(vec![
+            make_story("US-001", 1, &["A"]),
+            make_story("US-001", 2, &["B"]),
+            make_story("US-003", 2, &["C"]),
+        ]);
+        let issues = lint_spec(&spec, &LintContext::default());
+
+        assert_eq!(rules(&issues), vec!["duplicate-id", "duplicate-priority"]);
+        assert_eq!(issues[0].severity, LintSeverity::Error);
+        assert!(issues[1].message.contains("US-001, US-003"));
+        assert_eq!(issues[1].severity, LintSeverity::Warning);
+    }
+
+    #[test]
+    fn test_missing_and_vague_criteria() {
+        let spec = test_spec(vec![
+            make_story("US-001", 1, &[]),
+            make_story(
+                "US-002",
+                2,
+                &["Login works well", "Button is disabled while loading"],
+            ),
+        ]);
+        let issues = lint_spec(&spec, &LintContext::default());
+
+        assert_eq!(rules(&issues), vec!["missing-criteria", "vague-criterion"]);
+        assert_eq!(issues[0].story_id.as_deref(), Some("US-001"));
+        assert_eq!(issues[1].story_id.as_deref(), Some("US-002"));
+        assert!(issues[1].message.contains("works well"));
+    }
+
+    #[test]
+    fn test_branch_equal_to_base_branch() {
+        let mut spec = test_spec(vec![make_story("US-001", 1, &["A"])]);
+        spec.branch_name = "main".into();
+        let ctx = LintContext {
+            base_branch: Some("main".into()),
+            project_name: None,
+        };
+        assert_eq!(rules(&lint_spec(&spec, &ctx)), vec!["base-branch"]);
+    }
+
+    #[test]
+    fn test_project_mismatch_is_a_warning() {
+        let spec = test_spec(vec![make_story("US-001", 1, &["A"])]);
+        let ctx = LintContext {
+            base_branch: None,
+            project_name: Some("other-app".into()),
+        };
+        let issues = lint_spec(&spec, &ctx);
+        assert_eq!(rules(&issues), vec!["project-mismatch"]);
+        assert_eq!(issues[0].severity, LintSeverity::Warning);
+    }
+
+    #[test]
+    fn test_validation_errors_are_reported() {
+        let mut story = make_story("US-001", 1, &["A"]);
+        story.depends_on = vec!["US-404".into()];
+        let issues = lint_spec(&test_spec(vec![story]), &LintContext::default());
+
+        assert_eq!(rules(&issues), vec!["invalid"]);
+        assert!(issues[0].message.contains("unknown story US-404"));
+    }
+
+    // ===========================================
+    // Branch name tests
+    // ===========================================
+
+    #[test]
+    fn test_invalid_branch_names() {
+        for name in [
+            "",
+            "@",
+            "-feature",
+            "/feature",
+            "feature/",
+            "a//b",
+            "feature.",
+            "a..b",
+            "a@{b",
+            "has space",
+            "a~b",
+            "a:b",
+            "a*b",
+            "feature/.hidden",
+            "feature.lock",
+        ] {
+            assert!(
+                invalid_branch_name_reason(name).is_some(),
+                "{:?} should be invalid",
+                name
+            );
+        }
+    }
+
+    #[test]
+    fn test_valid_branch_names() {
+        for name in ["main", "feature/login", "autom8/us-001_fix", "release-1.2"] {
+            assert_eq!(invalid_branch_name_reason(name), None, "{:?}", name);
+        }
+    }
+
+    // ===========================================
+    // File tests
+    // ===========================================
+
+    #[test]
+    fn test_lint_file_reports_legacy_schema_without_upgrading() {
+        let dir = tempfile::TempDir::new().unwrap();
+        let path = dir.path().join("spec-test.json");
+        let content = r#"{"project": "my-app", "branchName": "feature/x", "description": "D", "userStories": [{"id": "US-001", "title": "T", "description": "D", "acceptanceCriteria": ["A"], "priority": 1, "passes": false}]}"#;
+        fs::write(&path, content).unwrap();
+
+        let report = lint_file(&path, &LintContext::default());
+
+        assert_eq!(rules(&report.issues), vec!["schema-version"]);
+        assert_eq!((report.errors, report.warnings), (0, 1));
+        assert_eq!(fs::read_to_string(&path).unwrap(), content);
+    }
+
+    #[test]
+    fn test_lint_file_reports_parse_errors() {
+        let dir = tempfile::TempDir::new().unwrap();
+        let json = dir.path().join("spec-bad.json");
+        fs::write(&json, "not json").unwrap();
+        let md = dir.path().join("spec-bad.md");
+        fs::write(&md, "## Unknown\n").unwrap();
+
+        let json_report = lint_file(&json, &LintContext::default());
+        assert_eq!(rules(&json_report.issues), vec!["parse"]);
+
+        let md_report = lint_file(&md, &LintContext::default());
+        assert!(md_report.errors > 0);
+        assert!(md_report.issues.iter().all(|i| i.rule == "markdown-format"));
+
+        let missing = lint_file(&dir.path().join("missing.json"), &LintContext::default());
+        assert_eq!(rules(&missing.issues), vec!["unreadable"]);
+    }
+
+    #[test]
+    fn test_report_serializes_to_json() {
+        let report = LintReport::new(
+            Path::new("spec.json"),
+            vec![LintIssue::warning(
+                "vague-criterion",
+                Some("US-001"),
+                "vague",
+            )],
+        );
+        let json = serde_json::to_value(&report).unwrap();
+
+        assert_eq!(json["path"], "spec.json");
+        assert_eq!(json["warnings"], 1);
+        assert_eq!(json["issues"][0]["severity"], "warning");
+        assert_eq!(json["issues"][0]["storyId"], "US-001");
+    }
+}
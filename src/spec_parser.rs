@@ -0,0 +1,828 @@
+//! Deterministic Markdown spec parser.
+//!
+//! Parses `spec-<feature>.md` files written in the format described by
+//! `SPEC_SKILL_PROMPT` directly into a [`Spec`], without a Claude call:
+//!
+//! ```markdown
+//! # Feature Name
+//!
+//! ## Project
+//! my-app
+//!
+//! ## Branch
+//! feature/user-auth
+//!
+//! ## Description
+//! What the feature is and why.
+//!
+//! ## Verify
+//! - `cargo test`
+//!
+//! ## User Stories
+//!
+//! ### US-001: Story Title
+//! **Priority:** 1
+//! **Depends On:** US-000
+//!
+//! What this story accomplishes.
+//!
+//! **Acceptance Criteria:**
+//! - [ ] Criterion 1
+//!
+//! **Verify:**
+//! - `cargo test user_model`
+//!
+//! **Notes:** Implementation hints
+//! ```
+//!
+//! `## Verify` and `**Verify:**` are optional; `**Verify:** none` turns off
+//! the spec-level commands for one story.
+//!
+//! Anything outside this format produces line-numbered [`SpecDiagnostic`]s, so
+//! the caller can fall back to Claude or the user can fix the spec by hand.
+
+use std::fmt;
+
+use crate::spec::{Spec, StoryStatus, UserStory, SPEC_SCHEMA_VERSION};
+
+/// Branch name used when the spec has no `## Branch` section.
+const DEFAULT_BRANCH_NAME: &str = "autom8/feature";
+
+/// A problem found while parsing a Markdown spec.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SpecDiagnostic {
+    /// 1-based line number the problem was found on
+    pub line: usize,
+    pub message: String,
+}
+
+impl SpecDiagnostic {
+    fn new(line: usize, message: impl Into<String>) -> Self {
+        Self {
+            line,
+            message: message.into(),
+        }
+    }
+}
+
+impl fmt::Display for SpecDiagnostic {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "line {}: {}", self.line, self.message)
+    }
+}
+
+/// Top-level `##` sections of a spec.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Section {
+    Preamble,
+    Project,
+    Branch,
+    Description,
+    Verify,
+    UserStories,
+}
+
+impl Section {
+    fn from_heading(name: &str) -> Option<Self> {
+        match name.trim().to_lowercase().as_str() {
+            "project" => Some(Section::Project),
+            "branch" => Some(Section::Branch),
+            "description" => Some(Section::Description),
+            "verify" => Some(Section::Verify),
+            "user stories" => Some(Section::UserStories),
+            _ => None,
+        }
+    }
+}
+
+/// Which part of a story free text currently belongs to.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum StoryField {
+    Description,
+    AcceptanceCriteria,
+    Notes,
+    Verify,
+}
+
+/// A story being assembled while its lines are read.
+struct StoryDraft {
+    line: usize,
+    id: String,
+    title: String,
+    priority: Option<u32>,
+    depends_on: Vec<String>,
+    description: Vec<String>,
+    criteria: Vec<String>,
+    notes: Vec<String>,
+    verify: Option<Vec<String>>,
+    field: StoryField,
+}
+
+impl StoryDraft {
+    fn new(line: usize, id: String, title: String) -> Self {
+        Self {
+            line,
+            id,
+            title,
+            priority: None,
+            depends_on: Vec::new(),
+            description: Vec::new(),
+            criteria: Vec::new(),
+            notes: Vec::new(),
+            verify: None,
+            field: StoryField::Description,
+        }
+    }
+
+    fn push_text(&mut self, text: &str) {
+        match self.field {
+            StoryField::Description => self.description.push(text.to_string()),
+            StoryField::Notes => self.notes.push(text.to_string()),
+            StoryField::AcceptanceCriteria | StoryField::Verify => {
+                let items = match self.field {
+                    StoryField::Verify => self.verify.get_or_insert_with(Vec::new),
+                    _ => &mut self.criteria,
+                };
+                if let Some(last) = items.last_mut() {
+                    last.push(' ');
+                    last.push_str(text.trim());
+                }
+            }
+        }
+    }
+
+    fn finish(self, diagnostics: &mut Vec<SpecDiagnostic>) -> Option<UserStory> {
+        let Some(priority) = self.priority else {
+            diagnostics.push(SpecDiagnostic::new(
+                self.line,
+                format!("story {} is missing a '**Priority:**' line", self.id),
+            ));
+            return None;
+        };
+
+        Some(UserStory {
+            id: self.id,
+            title: self.title,
+            description: join_text(&self.description),
+            acceptance_criteria: self.criteria,
+            priority,
+            status: StoryStatus::default(),
+            notes: join_text(&self.notes),
+            depends_on: self.depends_on,
+            verify: self.verify,
+        })
+    }
+}
+
+/// Parse a Markdown spec into a [`Spec`].
+///
+/// Returns every problem found (not just the first) when the Markdown doesn't
+/// follow the standard format, or when the resulting spec fails validation.
+pub fn parse_markdown_spec(content: &str) -> Result<Spec, Vec<SpecDiagnostic>> {
+    let mut diagnostics = Vec::new();
+
+    let mut section = Section::Preamble;
+    let mut seen_sections: Vec<(Section, usize)> = Vec::new();
+    let mut project_lines: Vec<(usize, String)> = Vec::new();
+    let mut branch_lines: Vec<(usize, String)> = Vec::new();
+    let mut description_lines: Vec<String> = Vec::new();
+    let mut verify_commands: Vec<String> = Vec::new();
+    let mut stories: Vec<UserStory> = Vec::new();
+    let mut current_story: Option<StoryDraft> = None;
+    let mut in_code_fence = false;
+
+    for (index, raw_line) in content.lines().enumerate() {
+        let line_no = index + 1;
+        let line = raw_line.trim_end();
+        let trimmed = line.trim();
+
+        // Code blocks are opaque text: never treat their contents as structure
+        if trimmed.starts_with("```") {
+            in_code_fence = !in_code_fence;
+        }
+        let is_structural = !in_code_fence && !trimmed.starts_with("```");
+
+        if is_structural {
+            if let Some(name) = line.strip_prefix("## ") {
+                if let Some(story) = current_story.take() {
+                    stories.extend(story.finish(&mut diagnostics));
+                }
+                match Section::from_heading(name) {
+                    Some(next) => {
+                        if let Some((_, first_line)) = seen_sections.iter().find(|(s, _)| *s == next)
+                        {
+                            diagnostics.push(SpecDiagnostic::new(
+                                line_no,
+                                format!(
+                                    "duplicate '## {}' section (first defined on line {})",
+                                    name.trim(),
+                                    first_line
+                                ),
+                            ));
+                        }
+                        seen_sections.push((next, line_no));
+                        section = next;
+                    }
+                    None => diagnostics.push(SpecDiagnostic::new(
+                        line_no,
+                        format!(
+                            "unexpected section '## {}' (expected Project, Branch, Description, Verify or User Stories)",
+                            name.trim()
+                        ),
+                    )),
+                }
+                continue;
+            }
+
+            if line.starts_with("# ") {
+                if section != Section::Preamble {
+                    diagnostics.push(SpecDiagnostic::new(
+                        line_no,
+                        "top-level '#' heading is only allowed before the first section",
+                    ));
+                }
+                continue;
+            }
+
+            if let Some(heading) = line.strip_prefix("### ") {
+                if section != Section::UserStories {
+                    diagnostics.push(SpecDiagnostic::new(
+                        line_no,
+                        "story headings must be inside the '## User Stories' section",
+                    ));
+                    continue;
+                }
+                if let Some(story) = current_story.take() {
+                    stories.extend(story.finish(&mut diagnostics));
+                }
+                match parse_story_heading(heading) {
+                    Some((id, title)) => {
+                        current_story = Some(StoryDraft::new(line_no, id, title));
+                    }
+                    None => diagnostics.push(SpecDiagnostic::new(
+                        line_no,
+                        format!(
+                            "story heading '### {}' must look like '### US-001: Title'",
+                            heading.trim()
+                        ),
+                    )),
+                }
+                continue;
+            }
+        }
+
+        match section {
+            Section::Preamble => {
+                if !trimmed.is_empty() {
+                    diagnostics.push(SpecDiagnostic::new(
+                        line_no,
+                        "text before the first section (expected '## Project')",
+                    ));
+                }
+            }
+            Section::Project => {
+                if !trimmed.is_empty() {
+                    project_lines.push((line_no, trimmed.to_string()));
+                }
+            }
+            Section::Branch => {
+                if !trimmed.is_empty() {
+                    branch_lines.push((line_no, trimmed.trim_matches('`').to_string()));
+                }
+            }
+            Section::Description => description_lines.push(line.to_string()),
+            Section::Verify => {
+                if trimmed.is_empty() {
+                    continue;
+                }
+                match parse_list_item(trimmed) {
+                    Some(command) => verify_commands.push(strip_code(command).to_string()),
+                    None => diagnostics.push(SpecDiagnostic::new(
+                        line_no,
+                        format!(
+                            "expected a verify command like '- `cargo test`', got '{}'",
+                            trimmed
+                        ),
+                    )),
+                }
+            }
+            Section::UserStories => match current_story.as_mut() {
+                Some(story) if is_structural => {
+                    parse_story_line(story, line, line_no, &mut diagnostics);
+                }
+                Some(story) => story.push_text(line),
+                None => {
+                    if !trimmed.is_empty() {
+                        diagnostics.push(SpecDiagnostic::new(
+                            line_no,
+                            "text before the first story heading (expected '### US-001: Title')",
+                        ));
+                    }
+                }
+            },
+        }
+    }
+
+    if in_code_fence {
+        diagnostics.push(SpecDiagnostic::new(
+            content.lines().count(),
+            "unclosed code block",
+        ));
+    }
+
+    if let Some(story) = current_story.take() {
+        stories.extend(story.finish(&mut diagnostics));
+    }
+
+    let project = single_value(
+        Section::Project,
+        "Project",
+        &project_lines,
+        &seen_sections,
+        &mut diagnostics,
+    );
+    let branch_name = if section_line(&seen_sections, Section::Branch).is_some() {
+        single_value(
+            Section::Branch,
+            "Branch",
+            &branch_lines,
+            &seen_sections,
+            &mut diagnostics,
+        )
+    } else {
+        DEFAULT_BRANCH_NAME.to_string()
+    };
+
+    let stories_line = section_line(&seen_sections, Section::UserStories);
+    if stories.is_empty() && diagnostics.is_empty() {
+        diagnostics.push(SpecDiagnostic::new(
+            stories_line.unwrap_or(1),
+            "no user stories found (expected '### US-001: Title' under '## User Stories')",
+        ));
+    }
+
+    for (i, story) in stories.iter().enumerate() {
+        if stories[..i].iter().any(|s| s.id == story.id) {
+            diagnostics.push(SpecDiagnostic::new(
+                stories_line.unwrap_or(1),
+                format!("duplicate story id {}", story.id),
+            ));
+        }
+    }
+
+    if !diagnostics.is_empty() {
+        diagnostics.sort_by_key(|d| d.line);
+        return Err(diagnostics);
+    }
+
+    let spec = Spec {
+        schema_version: SPEC_SCHEMA_VERSION,
+        project,
+        branch_name,
+        description: join_text(&description_lines),
+        user_stories: stories,
+        verify: verify_commands,
+    };
+
+    // Catch anything the structure alone can't (e.g., dependency cycles)
+    spec.validate().map_err(|e| {
+        vec![SpecDiagnostic::new(
+            stories_line.unwrap_or(1),
+            e.to_string(),
+        )]
+    })?;
+
+    Ok(spec)
+}
+
+/// Parse a `US-001: Title` story heading into its ID and title.
+fn parse_story_heading(heading: &str) -> Option<(String, String)> {
+    let (id, title) = heading.split_once(':')?;
+    let id = id.trim();
+    let title = title.trim();
+    if id.is_empty() || title.is_empty() || id.contains(char::is_whitespace) {
+        return None;
+    }
+    Some((id.to_string(), title.to_string()))
+}
+
+/// Handle a non-heading line inside a story.
+fn parse_story_line(
+    story: &mut StoryDraft,
+    line: &str,
+    line_no: usize,
+    diagnostics: &mut Vec<SpecDiagnostic>,
+) {
+    let trimmed = line.trim();
+
+    if let Some((label, value)) = parse_field(trimmed) {
+        match label.to_lowercase().as_str() {
+            "priority" => {
+                if story.priority.is_some() {
+                    diagnostics.push(SpecDiagnostic::new(
+                        line_no,
+                        format!("story {} has more than one priority", story.id),
+                    ));
+                }
+                match value.parse::<u32>() {
+                    Ok(priority) => story.priority = Some(priority),
+                    Err(_) => diagnostics.push(SpecDiagnostic::new(
+                        line_no,
+                        format!("priority must be a whole number, got '{}'", value),
+                    )),
+                }
+            }
+            "depends on" => {
+                story.depends_on = parse_depends_on(value);
+            }
+            "description" => {
+                story.field = StoryField::Description;
+                if !value.is_empty() {
+                    story.description.push(value.to_string());
+                }
+            }
+            "acceptance criteria" => {
+                story.field = StoryField::AcceptanceCriteria;
+                if !value.is_empty() {
+                    diagnostics.push(SpecDiagnostic::new(
+                        line_no,
+                        "acceptance criteria must be listed on the lines after '**Acceptance Criteria:**'",
+                    ));
+                }
+            }
+            "notes" => {
+                story.field = StoryField::Notes;
+                if !value.is_empty() {
+                    story.notes.push(value.to_string());
+                }
+            }
+            "verify" => {
+                story.field = StoryField::Verify;
+                // `none` disables the spec-level commands for this story
+                let commands = story.verify.get_or_insert_with(Vec::new);
+                if !value.is_empty() && !value.eq_ignore_ascii_case("none") {
+                    commands.push(strip_code(value).to_string());
+                }
+            }
+            _ => diagnostics.push(SpecDiagnostic::new(
+                line_no,
+                format!(
+                    "unknown story field '**{}:**' (expected Priority, Depends On, Description, Acceptance Criteria, Verify or Notes)",
+                    label
+                ),
+            )),
+        }
+        return;
+    }
+
+    if story.field == StoryField::AcceptanceCriteria {
+        if trimmed.is_empty() {
+            return;
+        }
+        if let Some(criterion) = parse_list_item(trimmed) {
+            story.criteria.push(criterion.to_string());
+        } else if line.starts_with(char::is_whitespace) && !story.criteria.is_empty() {
+            // Indented continuation of the previous criterion
+            story.push_text(trimmed);
+        } else {
+            diagnostics.push(SpecDiagnostic::new(
+                line_no,
+                format!(
+                    "expected an acceptance criterion like '- [ ] ...', got '{}'",
+                    trimmed
+                ),
+            ));
+        }
+        return;
+    }
+
+    if story.field == StoryField::Verify {
+        if trimmed.is_empty() {
+            return;
+        }
+        match parse_list_item(trimmed) {
+            Some(command) => story
+                .verify
+                .get_or_insert_with(Vec::new)
+                .push(strip_code(command).to_string()),
+            None => diagnostics.push(SpecDiagnostic::new(
+                line_no,
+                format!(
+                    "expected a verify command like '- `cargo test`', got '{}'",
+                    trimmed
+                ),
+            )),
+        }
+        return;
+    }
+
+    story.push_text(line);
+}
+
+/// Strip surrounding inline-code backticks from a command.
+fn strip_code(command: &str) -> &str {
+    command.trim().trim_matches('`').trim()
+}
+
+/// Parse a `**Label:** value` line.
+fn parse_field(line: &str) -> Option<(&str, &str)> {
+    let rest = line.strip_prefix("**")?;
+    let (label, value) = rest.split_once(":**")?;
+    if label.is_empty() || label.contains('*') {
+        return None;
+    }
+    Some((label.trim(), value.trim()))
+}
+
+/// Parse a checklist or bullet item, returning its text.
+fn parse_list_item(line: &str) -> Option<&str> {
+    let item = line
+        .strip_prefix("- ")
+        .or_else(|| line.strip_prefix("* "))?
+        .trim_start();
+    let item = ["[ ]", "[x]", "[X]"]
+        .iter()
+        .find_map(|checkbox| item.strip_prefix(checkbox))
+        .unwrap_or(item)
+        .trim();
+    if item.is_empty() {
+        None
+    } else {
+        Some(item)
+    }
+}
+
+/// Parse a `**Depends On:**` value such as `US-001, US-002`.
+fn parse_depends_on(value: &str) -> Vec<String> {
+    let value = value.trim();
+    if value.is_empty() || value.eq_ignore_ascii_case("none") || value == "-" {
+        return Vec::new();
+    }
+    value
+        .split(|c: char| c == ',' || c.is_whitespace())
+        .map(|id| id.trim_matches('`'))
+        .filter(|id| !id.is_empty())
+        .map(String::from)
+        .collect()
+}
+
+/// Get the single value of a one-line section, reporting problems.
+fn single_value(
+    section: Section,
+    name: &str,
+    lines: &[(usize, String)],
+    seen_sections: &[(Section, usize)],
+    diagnostics: &mut Vec<SpecDiagnostic>,
+) -> String {
+    match (lines, section_line(seen_sections, section)) {
+        (_, None) => {
+            diagnostics.push(SpecDiagnostic::new(
+                1,
+                format!("missing '## {}' section", name),
+            ));
+            String::new()
+        }
+        ([], Some(heading_line)) => {
+            diagnostics.push(SpecDiagnostic::new(
+                heading_line,
+                format!("'## {}' section is empty", name),
+            ));
+            String::new()
+        }
+        ([(_, value)], _) => value.clone(),
+        ([_, (second_line, _), ..], _) => {
+            diagnostics.push(SpecDiagnostic::new(
+                *second_line,
+                format!("'## {}' section must contain a single line", name),
+            ));
+            String::new()
+        }
+    }
+}
+
+/// Line number of the first heading for a section, if present.
+fn section_line(seen_sections: &[(Section, usize)], section: Section) -> Option<usize> {
+    seen_sections
+        .iter()
+        .find(|(s, _)| *s == section)
+        .map(|(_, line)| *line)
+}
+
+/// Join text lines into a trimmed block, collapsing runs of blank lines.
+fn join_text(lines: &[String]) -> String {
+    let mut out = String::new();
+    let mut pending_blank = false;
+    for line in lines {
+        if line.trim().is_empty() {
+            pending_blank = !out.is_empty();
+            continue;
+        }
+        if pending_blank {
+            out.push_str("\n\n");
+        } else if !out.is_empty() {
+            out.push('\n');
+        }
+        out.push_str(line.trim_end());
+        pending_blank = false;
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const WELL_FORMED: &str = r#"# User Authentication
+
+## Project
+my-app
+
+## Branch
+feature/user-auth
+
+## Description
+Add login and logout.
+
+Sessions are stored in cookies.
+
+## User Stories
+
+### US-001: Add user model
+**Priority:** 1
+
+Create the user table and model.
+
+**Acceptance Criteria:**
+- [ ] Users table exists
+- [x] Passwords are hashed
+  with bcrypt
+
+**Notes:** Use the existing migration tool.
+
+### US-002: Add login endpoint
+**Priority:** 2
+**Depends On:** US-001
+
+Expose POST /login.
+
+```text
+## Not a section
+### Not a story
+```
+
+**Acceptance Criteria:**
+- [ ] Valid credentials return a session
+- [ ] Invalid credentials return 401
+"#;
+
+    fn messages(result: Result<Spec, Vec<SpecDiagnostic>>) -> Vec<String> {
+        result.unwrap_err().iter().map(|d| d.to_string()).collect()
+    }
+
+    // ===========================================
+    // Well-formed spec tests
+    // ===========================================
+
+    #[test]
+    fn test_parse_well_formed_spec() {
+        let spec = parse_markdown_spec(WELL_FORMED).unwrap();
+
+        assert_eq!(spec.project, "my-app");
+        assert_eq!(spec.branch_name, "feature/user-auth");
+        assert_eq!(
+            spec.description,
+            "Add login and logout.\n\nSessions are stored in cookies."
+        );
+        assert_eq!(spec.user_stories.len(), 2);
+
+        let first = &spec.user_stories[0];
+        assert_eq!(first.id, "US-001");
+        assert_eq!(first.title, "Add user model");
+        assert_eq!(first.priority, 1);
+        assert_eq!(first.description, "Create the user table and model.");
+        assert_eq!(
+            first.acceptance_criteria,
+            vec!["Users table exists", "Passwords are hashed with bcrypt"]
+        );
+        assert_eq!(first.notes, "Use the existing migration tool.");
+        assert!(!first.passes());
+        assert!(first.depends_on.is_empty());
+    }
+
+    #[test]
+    fn test_parse_keeps_code_blocks_as_text() {
+        let spec = parse_markdown_spec(WELL_FORMED).unwrap();
+        let second = &spec.user_stories[1];
+
+        assert_eq!(second.depends_on, vec!["US-001"]);
+        assert!(second.description.contains("## Not a section"));
+        assert!(second.description.contains("### Not a story"));
+        assert_eq!(second.acceptance_criteria.len(), 2);
+    }
+
+    #[test]
+    fn test_parse_defaults_branch_when_missing() {
+        let content = "## Project\napp\n\n## User Stories\n### US-001: Do it\n**Priority:** 1\n";
+        let spec = parse_markdown_spec(content).unwrap();
+        assert_eq!(spec.branch_name, "autom8/feature");
+        assert_eq!(spec.description, "");
+    }
+
+    #[test]
+    fn test_parse_verify_commands() {
+        let content = "## Project\napp\n\n## Verify\n- `cargo test`\n\n## User Stories\n\
+            ### US-001: Do it\n**Priority:** 1\n**Verify:**\n- `cargo build`\n- cargo clippy\n\n\
+            ### US-002: Docs only\n**Priority:** 2\n**Verify:** none\n\n\
+            ### US-003: Default\n**Priority:** 3\n";
+        let spec = parse_markdown_spec(content).unwrap();
+
+        assert_eq!(spec.verify, vec!["cargo test"]);
+        assert_eq!(
+            spec.user_stories[0].verify,
+            Some(vec!["cargo build".to_string(), "cargo clippy".to_string()])
+        );
+        assert_eq!(spec.user_stories[1].verify, Some(Vec::new()));
+        assert_eq!(spec.user_stories[2].verify, None);
+    }
+
+    #[test]
+    fn test_parse_depends_on_variants() {
+        assert_eq!(parse_depends_on("US-001, US-002"), vec!["US-001", "US-002"]);
+        assert_eq!(parse_depends_on("`US-003`"), vec!["US-003"]);
+        assert!(parse_depends_on("None").is_empty());
+        assert!(parse_depends_on("").is_empty());
+    }
+
+    // ===========================================
+    // Diagnostic tests
+    // ===========================================
+
+    #[test]
+    fn test_missing_project_is_reported() {
+        let content = "## User Stories\n### US-001: Do it\n**Priority:** 1\n";
+        assert_eq!(
+            messages(parse_markdown_spec(content)),
+            vec!["line 1: missing '## Project' section"]
+        );
+    }
+
+    #[test]
+    fn test_missing_priority_reports_story_line() {
+        let content = "## Project\napp\n\n## User Stories\n\n### US-001: Do it\nText\n";
+        assert_eq!(
+            messages(parse_markdown_spec(content)),
+            vec!["line 6: story US-001 is missing a '**Priority:**' line"]
+        );
+    }
+
+    #[test]
+    fn test_unknown_section_and_bad_heading_are_reported() {
+        let content = "## Project\napp\n\n## Introduction\nHello\n\n## User Stories\n### Do it\n";
+        let messages = messages(parse_markdown_spec(content));
+
+        assert!(messages[0].starts_with("line 4: unexpected section '## Introduction'"));
+        assert!(messages
+            .iter()
+            .any(|m| m.starts_with("line 8: story heading '### Do it' must look like")));
+    }
+
+    #[test]
+    fn test_invalid_priority_and_unknown_field_are_reported() {
+        let content =
+            "## Project\napp\n## User Stories\n### US-001: Do it\n**Priority:** high\n**Owner:** me\n";
+        let messages = messages(parse_markdown_spec(content));
+
+        assert!(messages
+            .iter()
+            .any(|m| m == "line 5: priority must be a whole number, got 'high'"));
+        assert!(messages
+            .iter()
+            .any(|m| m.starts_with("line 6: unknown story field '**Owner:**'")));
+    }
+
+    #[test]
+    fn test_non_checklist_criteria_line_is_reported() {
+        let content = "## Project\napp\n## User Stories\n### US-001: Do it\n**Priority:** 1\n**Acceptance Criteria:**\nWorks well\n";
+        assert_eq!(
+            messages(parse_markdown_spec(content)),
+            vec!["line 7: expected an acceptance criterion like '- [ ] ...', got 'Works well'"]
+        );
+    }
+
+    #[test]
+    fn test_duplicate_story_ids_are_reported() {
+        let content = "## Project\napp\n## User Stories\n### US-001: A\n**Priority:** 1\n### US-001: B\n**Priority:** 2\n";
+        assert_eq!(
+            messages(parse_markdown_spec(content)),
+            vec!["line 3: duplicate story id US-001"]
+        );
+    }
+
+    #[test]
+    fn test_validation_errors_become_diagnostics() {
+        let content = "## Project\napp\n## User Stories\n### US-001: A\n**Priority:** 1\n**Depends On:** US-009\n";
+        let messages = messages(parse_markdown_spec(content));
+        assert_eq!(messages.len(), 1);
+        assert!(messages[0].contains("depends on unknown story US-009"));
+    }
+}
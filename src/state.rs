@@ -1,10 +1,14 @@
 use crate::claude::{
-    extract_decisions, extract_files_context, extract_patterns, ClaudeUsage, FileContextEntry,
+    extract_decisions, extract_files_context, extract_patterns, ClaudeFailureKind, ClaudeUsage,
+    FileContextEntry,
 };
 use crate::config::{self, Config};
 use crate::error::Result;
 use crate::git;
 use crate::knowledge::{Decision, FileChange, FileInfo, Pattern, ProjectKnowledge, StoryChanges};
+use crate::progress::{format_cost_usd, format_tokens};
+use crate::spec::{Spec, StoryState};
+use crate::spec_diff::SpecFingerprint;
 use crate::worktree::{get_current_session_id, MAIN_SESSION_ID};
 use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
@@ -19,6 +23,8 @@ const LIVE_FILE: &str = "live.json";
 const SESSIONS_DIR: &str = "sessions";
 const RUNS_DIR: &str = "runs";
 const SPEC_DIR: &str = "spec";
+const KNOWLEDGE_DIR: &str = "knowledge";
+const WORKSPACES_DIR: &str = "workspaces";
 
 /// Maximum number of output lines to keep in LiveState.
 /// Prevents unbounded memory growth during long Claude runs.
@@ -99,6 +105,24 @@ pub struct LiveState {
     /// GUI/TUI should consider a run "active" if this is < 10 seconds old.
     #[serde(default = "Utc::now")]
     pub last_heartbeat: DateTime<Utc>,
+    /// When Claude last wrote a stream-json line, including tool calls that
+    /// produce no visible output. `None` until the first line arrives.
+    #[serde(default)]
+    pub last_output_at: Option<DateTime<Utc>>,
+    /// Claude's last tool call, e.g. `Bash: cargo test`.
+    #[serde(default)]
+    pub last_activity: Option<String>,
+    /// Seconds without output after which the session counts as stalled.
+    /// `None` disables stall detection.
+    #[serde(default)]
+    pub stall_after_secs: Option<u64>,
+    /// When a paused Claude attempt will be retried, e.g. once a usage limit
+    /// resets. `None` while not waiting.
+    #[serde(default)]
+    pub waiting_until: Option<DateTime<Utc>>,
+    /// Why the run is waiting (e.g. "Usage limit reached").
+    #[serde(default)]
+    pub waiting_reason: Option<String>,
 }
 
 /// Threshold for considering a heartbeat "stale" (run likely dead).
@@ -116,6 +140,11 @@ impl LiveState {
             updated_at: now,
             machine_state,
             last_heartbeat: now,
+            last_output_at: None,
+            last_activity: None,
+            stall_after_secs: None,
+            waiting_until: None,
+            waiting_reason: None,
         }
     }
 
@@ -154,6 +183,32 @@ impl LiveState {
             .num_seconds();
         age < HEARTBEAT_STALE_THRESHOLD_SECS
     }
+
+    /// Seconds since Claude's last output, if that is past the stall threshold.
+    ///
+    /// A stale heartbeat means autom8 itself is gone rather than Claude being
+    /// stuck, so it never counts as a stall. Before any output arrives, the
+    /// time since the live state was created is used.
+    pub fn stalled_for(&self) -> Option<u64> {
+        let threshold = self.stall_after_secs?;
+        if !self.is_heartbeat_fresh() {
+            return None;
+        }
+        let since = self.last_output_at.unwrap_or(self.updated_at);
+        let idle = Utc::now().signed_duration_since(since).num_seconds();
+        let idle = u64::try_from(idle).ok()?;
+        (idle >= threshold).then_some(idle)
+    }
+
+    /// Seconds left before a waiting run retries Claude, if it is waiting.
+    pub fn waiting_for(&self) -> Option<u64> {
+        let until = self.waiting_until?;
+        if !self.is_heartbeat_fresh() {
+            return None;
+        }
+        let left = until.signed_duration_since(Utc::now()).num_seconds();
+        Some(left.max(0) as u64)
+    }
 }
 
 /// Run mode for controlling execution flow.
@@ -210,6 +265,29 @@ pub struct IterationRecord {
     /// Token usage data for this iteration
     #[serde(default)]
     pub usage: Option<ClaudeUsage>,
+    /// Why the story's verification commands failed after this iteration
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub verify_failure: Option<String>,
+    /// Failed Claude attempts in this iteration that were retried
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub retries: Vec<ClaudeRetry>,
+    /// Model the story was run with, when one was configured
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub model: Option<String>,
+}
+
+/// A failed Claude attempt that was retried, after a transient failure or
+/// once a usage limit reset.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ClaudeRetry {
+    /// When the attempt failed
+    pub failed_at: DateTime<Utc>,
+    /// What Claude reported
+    pub error: String,
+    /// How the failure was classified
+    pub failure: ClaudeFailureKind,
+    /// How long autom8 waited before the next attempt
+    pub wait_secs: u64,
 }
 
 #[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
@@ -264,6 +342,41 @@ pub struct RunState {
     /// - "PR & Commit": commit generation + PR creation
     #[serde(default)]
     pub phase_usage: HashMap<String, ClaudeUsage>,
+    /// Stories that must run sequentially, even when parallel execution is
+    /// enabled (e.g., after their parallel branch failed to merge cleanly).
+    #[serde(default)]
+    pub sequential_stories: Vec<String>,
+    /// Stories the run gave up on under the `skip` failure policy.
+    /// They aren't picked again, and neither are stories that depend on them.
+    #[serde(default)]
+    pub failed_stories: Vec<FailedStory>,
+    /// Phases that did something their permission profile shouldn't allow,
+    /// such as a reviewer changing files.
+    #[serde(default)]
+    pub flagged_phases: Vec<FlaggedPhase>,
+    /// Fingerprint of the spec taken when the run started, used on resume to
+    /// detect stories that were edited in the meantime.
+    #[serde(default)]
+    pub spec_fingerprint: Option<SpecFingerprint>,
+    /// Whether the working tree had no uncommitted changes when the run
+    /// started, so any it has now are the run's own work.
+    #[serde(default)]
+    pub started_clean: bool,
+}
+
+/// A story the run gave up on, and why.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct FailedStory {
+    pub story_id: String,
+    pub reason: String,
+}
+
+/// A phase flagged for overstepping its permissions, and why.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct FlaggedPhase {
+    /// e.g. `Review 2`
+    pub phase: String,
+    pub reason: String,
 }
 
 impl RunState {
@@ -287,6 +400,11 @@ impl RunState {
             session_id: None,
             total_usage: None,
             phase_usage: HashMap::new(),
+            sequential_stories: Vec::new(),
+            failed_stories: Vec::new(),
+            flagged_phases: Vec::new(),
+            spec_fingerprint: None,
+            started_clean: false,
         }
     }
 
@@ -311,6 +429,11 @@ impl RunState {
             session_id: None,
             total_usage: None,
             phase_usage: HashMap::new(),
+            sequential_stories: Vec::new(),
+            failed_stories: Vec::new(),
+            flagged_phases: Vec::new(),
+            spec_fingerprint: None,
+            started_clean: false,
         }
     }
 
@@ -335,6 +458,11 @@ impl RunState {
             session_id: Some(session_id),
             total_usage: None,
             phase_usage: HashMap::new(),
+            sequential_stories: Vec::new(),
+            failed_stories: Vec::new(),
+            flagged_phases: Vec::new(),
+            spec_fingerprint: None,
+            started_clean: false,
         }
     }
 
@@ -364,6 +492,11 @@ impl RunState {
             session_id: Some(session_id),
             total_usage: None,
             phase_usage: HashMap::new(),
+            sequential_stories: Vec::new(),
+            failed_stories: Vec::new(),
+            flagged_phases: Vec::new(),
+            spec_fingerprint: None,
+            started_clean: false,
         }
     }
 
@@ -387,6 +520,11 @@ impl RunState {
             session_id: None,
             total_usage: None,
             phase_usage: HashMap::new(),
+            sequential_stories: Vec::new(),
+            failed_stories: Vec::new(),
+            flagged_phases: Vec::new(),
+            spec_fingerprint: None,
+            started_clean: false,
         }
     }
 
@@ -415,6 +553,11 @@ impl RunState {
             session_id: None,
             total_usage: None,
             phase_usage: HashMap::new(),
+            sequential_stories: Vec::new(),
+            failed_stories: Vec::new(),
+            flagged_phases: Vec::new(),
+            spec_fingerprint: None,
+            started_clean: false,
         }
     }
 
@@ -444,6 +587,11 @@ impl RunState {
             session_id: Some(session_id),
             total_usage: None,
             phase_usage: HashMap::new(),
+            sequential_stories: Vec::new(),
+            failed_stories: Vec::new(),
+            flagged_phases: Vec::new(),
+            spec_fingerprint: None,
+            started_clean: false,
         }
     }
 
@@ -482,6 +630,9 @@ impl RunState {
             output_snippet: String::new(),
             work_summary: None,
             usage: None,
+            verify_failure: None,
+            retries: Vec::new(),
+            model: None,
         });
     }
 
@@ -494,6 +645,83 @@ impl RunState {
         self.machine_state = MachineState::PickingStory;
     }
 
+    /// Get the most recent iteration record for a story.
+    ///
+    /// Parallel stories run several iterations at once, so their records are
+    /// not necessarily the last one.
+    pub fn iteration_for_story_mut(&mut self, story_id: &str) -> Option<&mut IterationRecord> {
+        self.iterations
+            .iter_mut()
+            .rev()
+            .find(|iter| iter.story_id == story_id)
+    }
+
+    /// Number of iterations run for a story so far.
+    pub fn story_iteration_count(&self, story_id: &str) -> u32 {
+        self.iterations
+            .iter()
+            .filter(|iter| iter.story_id == story_id)
+            .count() as u32
+    }
+
+    /// Returns true if the run gave up on this story.
+    pub fn is_story_failed(&self, story_id: &str) -> bool {
+        self.failed_stories.iter().any(|f| f.story_id == story_id)
+    }
+
+    /// Record that the run gave up on a story.
+    pub fn mark_story_failed(&mut self, story_id: &str, reason: &str) {
+        if !self.is_story_failed(story_id) {
+            self.failed_stories.push(FailedStory {
+                story_id: story_id.to_string(),
+                reason: reason.to_string(),
+            });
+        }
+    }
+
+    /// Stop skipping stories the spec no longer marks failed, e.g. after
+    /// `autom8 spec reset`, and forget their history so they start over
+    /// with a fresh iteration limit.
+    pub fn forget_reset_stories(&mut self, spec: &Spec) {
+        let reset: Vec<String> = self
+            .failed_stories
+            .iter()
+            .filter(|f| {
+                spec.story(&f.story_id)
+                    .is_none_or(|s| s.status.state != StoryState::Failed)
+            })
+            .map(|f| f.story_id.clone())
+            .collect();
+        if !reset.is_empty() {
+            self.forget_stories(&[], &reset);
+        }
+    }
+
+    /// Record that a phase overstepped its permissions.
+    pub fn flag_phase(&mut self, phase: &str, reason: &str) {
+        self.flagged_phases.push(FlaggedPhase {
+            phase: phase.to_string(),
+            reason: reason.to_string(),
+        });
+    }
+
+    /// Forget a run's history for stories removed from the spec or changed
+    /// so they run again: their iterations, knowledge and skip records.
+    pub fn forget_stories(&mut self, removed: &[String], changed: &[String]) {
+        let forgotten = |id: &String| removed.contains(id) || changed.contains(id);
+        self.iterations.retain(|iter| !forgotten(&iter.story_id));
+        self.knowledge.forget_stories(&[removed, changed].concat());
+        self.failed_stories.retain(|f| !forgotten(&f.story_id));
+        self.sequential_stories.retain(|id| !forgotten(id));
+        if self
+            .current_story
+            .as_ref()
+            .is_some_and(|id| removed.contains(id))
+        {
+            self.current_story = None;
+        }
+    }
+
     /// Set the work summary on the current (last) iteration
     pub fn set_work_summary(&mut self, summary: Option<String>) {
         if let Some(iter) = self.iterations.last_mut() {
@@ -585,6 +813,7 @@ impl RunState {
             files_modified,
             files_deleted,
             commit_hash,
+            base_commit: self.pre_story_commit.clone(),
         };
 
         self.knowledge.story_changes.push(story_changes);
@@ -689,6 +918,7 @@ impl RunState {
             files_modified: files_modified.clone(),
             files_deleted: files_deleted.clone(),
             commit_hash,
+            base_commit: self.pre_story_commit.clone(),
         };
         self.knowledge.story_changes.push(story_changes);
 
@@ -793,6 +1023,137 @@ impl RunState {
             iter.usage = usage;
         }
     }
+
+    /// Estimated cost of the run so far in USD.
+    ///
+    /// Each phase is priced separately, since `total_usage` only remembers
+    /// the first model it saw.
+    pub fn estimated_cost_usd(&self) -> f64 {
+        if self.phase_usage.is_empty() {
+            return self
+                .total_usage
+                .as_ref()
+                .map_or(0.0, ClaudeUsage::estimated_cost_usd);
+        }
+        self.phase_usage
+            .values()
+            .map(ClaudeUsage::estimated_cost_usd)
+            .sum()
+    }
+
+    /// Input + output tokens a story has used so far in this run.
+    pub fn story_tokens(&self, story_id: &str) -> u64 {
+        self.phase_usage
+            .get(story_id)
+            .map_or(0, ClaudeUsage::total_tokens)
+    }
+
+    /// Mark a stopped run as running again, to be continued by `autom8 resume`.
+    ///
+    /// The run keeps its config snapshot except for the budget limits, which
+    /// are taken from `config`.
+    pub fn resume(&mut self, config: &Config) {
+        self.status = RunStatus::Running;
+        self.finished_at = None;
+        if let Some(snapshot) = self.config.as_mut() {
+            snapshot.max_run_tokens = config.max_run_tokens;
+            snapshot.max_story_tokens = config.max_story_tokens;
+            snapshot.max_run_cost_usd = config.max_run_cost_usd;
+        }
+    }
+
+    /// Describe the budget this run has used up, if any.
+    ///
+    /// Checks `max_run_tokens` and `max_run_cost_usd`, plus `max_story_tokens`
+    /// for `story_id` when given. A limit of 0 is never reached.
+    pub fn exceeded_budget(&self, config: &Config, story_id: Option<&str>) -> Option<String> {
+        let run_tokens = self
+            .total_usage
+            .as_ref()
+            .map_or(0, ClaudeUsage::total_tokens);
+        if config.max_run_tokens > 0 && run_tokens >= config.max_run_tokens {
+            return Some(format!(
+                "The run has used {} tokens, reaching max_run_tokens ({})",
+                format_tokens(run_tokens),
+                format_tokens(config.max_run_tokens)
+            ));
+        }
+
+        let cost = self.estimated_cost_usd();
+        if config.max_run_cost_usd > 0.0 && cost >= config.max_run_cost_usd {
+            return Some(format!(
+                "The run has spent an estimated {}, reaching max_run_cost_usd ({})",
+                format_cost_usd(cost),
+                format_cost_usd(config.max_run_cost_usd)
+            ));
+        }
+
+        let story_id = story_id?;
+        let story_tokens = self.story_tokens(story_id);
+        if config.max_story_tokens > 0 && story_tokens >= config.max_story_tokens {
+            return Some(format!(
+                "{} has used {} tokens, reaching max_story_tokens ({})",
+                story_id,
+                format_tokens(story_tokens),
+                format_tokens(config.max_story_tokens)
+            ));
+        }
+        None
+    }
+
+    /// Record a retried Claude attempt on a story's most recent iteration.
+    pub fn record_retry(&mut self, story_id: &str, retry: ClaudeRetry) {
+        if let Some(iter) = self.iteration_for_story_mut(story_id) {
+            iter.retries.push(retry);
+        }
+    }
+
+    /// Number of times a story's latest iteration was retried after a
+    /// transient failure. Each iteration starts with a fresh count, and so
+    /// a fresh backoff. Usage-limit waits don't count.
+    pub fn transient_retry_count(&self, story_id: &str) -> u32 {
+        self.iterations
+            .iter()
+            .rev()
+            .find(|iter| iter.story_id == story_id)
+            .map_or(0, |iter| {
+                iter.retries
+                    .iter()
+                    .filter(|retry| retry.failure == ClaudeFailureKind::Transient)
+                    .count() as u32
+            })
+    }
+
+    /// Whether a story's latest iteration failed and is waiting to be retried.
+    pub fn is_awaiting_retry(&self, story_id: &str) -> bool {
+        self.iterations
+            .iter()
+            .rev()
+            .find(|iter| iter.story_id == story_id)
+            .is_some_and(|iter| iter.status == IterationStatus::Running && !iter.retries.is_empty())
+    }
+
+    /// Pick up a story's iteration that is waiting to be retried, instead of
+    /// starting a new one.
+    ///
+    /// The iteration is moved to the end so it is the current one again.
+    /// Returns `false` if the story has no iteration awaiting a retry.
+    pub fn resume_retried_iteration(&mut self, story_id: &str) -> bool {
+        if !self.is_awaiting_retry(story_id) {
+            return false;
+        }
+        if let Some(index) = self
+            .iterations
+            .iter()
+            .rposition(|iter| iter.story_id == story_id)
+        {
+            let iter = self.iterations.remove(index);
+            self.iterations.push(iter);
+        }
+        self.current_story = Some(story_id.to_string());
+        self.machine_state = MachineState::RunningClaude;
+        true
+    }
 }
 
 /// Manages session state storage with per-session directory structure.
@@ -924,6 +1285,16 @@ impl StateManager {
         self.base_dir.join(SPEC_DIR)
     }
 
+    /// Path to the project's knowledge store, shared by all sessions
+    pub fn knowledge_dir(&self) -> PathBuf {
+        self.base_dir.join(KNOWLEDGE_DIR)
+    }
+
+    /// Path to the spec copies of parallel story workspaces
+    pub fn workspaces_dir(&self) -> PathBuf {
+        self.base_dir.join(WORKSPACES_DIR)
+    }
+
     /// Migrate legacy state.json to the new sessions structure.
     ///
     /// On first run after upgrade, if there's a state.json in the project root,
@@ -1019,7 +1390,7 @@ impl StateManager {
         }
 
         // Sort by modification time, newest first
-        specs.sort_by(|a, b| b.1.cmp(&a.1));
+        specs.sort_by_key(|s| std::cmp::Reverse(s.1));
         Ok(specs.into_iter().map(|(p, _)| p).collect())
     }
 
@@ -1198,7 +1569,7 @@ impl StateManager {
             }
         }
 
-        runs.sort_by(|a, b| b.started_at.cmp(&a.started_at));
+        runs.sort_by_key(|r| std::cmp::Reverse(r.started_at));
         Ok(runs)
     }
 
@@ -1235,7 +1606,7 @@ impl StateManager {
         }
 
         // Sort by last_active_at descending
-        sessions.sort_by(|a, b| b.last_active_at.cmp(&a.last_active_at));
+        sessions.sort_by_key(|s| std::cmp::Reverse(s.last_active_at));
         Ok(sessions)
     }
 
@@ -1606,6 +1977,102 @@ mod tests {
         empty.set_work_summary(Some("Safe".to_string()));
     }
 
+    #[test]
+    fn test_story_iteration_count_and_failed_stories() {
+        let mut state = RunState::new(PathBuf::from("test.json"), "test-branch".to_string());
+        state.start_iteration("US-001");
+        state.start_iteration("US-002");
+        state.start_iteration("US-001");
+        assert_eq!(state.story_iteration_count("US-001"), 2);
+        assert_eq!(state.story_iteration_count("US-003"), 0);
+
+        state.mark_story_failed("US-001", "still not passing after 2 iterations");
+        state.mark_story_failed("US-001", "recorded once");
+        assert!(state.is_story_failed("US-001"));
+        state.flag_phase("Review 1", "reviewer changed src/lib.rs");
+        assert_eq!(state.flagged_phases[0].phase, "Review 1");
+        assert!(!state.is_story_failed("US-002"));
+        assert_eq!(state.failed_stories.len(), 1);
+        assert_eq!(
+            state.failed_stories[0].reason,
+            "still not passing after 2 iterations"
+        );
+
+        // Older state files have no failed stories
+        let json = serde_json::to_string(&state).unwrap();
+        let mut value: serde_json::Value = serde_json::from_str(&json).unwrap();
+        value.as_object_mut().unwrap().remove("failed_stories");
+        value.as_object_mut().unwrap().remove("flagged_phases");
+        let loaded: RunState = serde_json::from_value(value).unwrap();
+        assert!(loaded.failed_stories.is_empty());
+        assert!(loaded.flagged_phases.is_empty());
+        assert!(loaded.spec_fingerprint.is_none());
+    }
+
+    #[test]
+    fn test_forget_reset_stories_gives_a_fresh_iteration_limit() {
+        use crate::spec::{StoryStatus, UserStory};
+
+        let story = |id: &str, state: StoryState| UserStory {
+            id: id.to_string(),
+            title: id.to_string(),
+            description: String::new(),
+            acceptance_criteria: Vec::new(),
+            priority: 1,
+            status: StoryStatus::new(state, None),
+            notes: String::new(),
+            depends_on: Vec::new(),
+            verify: None,
+        };
+        let spec = Spec {
+            schema_version: crate::spec::SPEC_SCHEMA_VERSION,
+            project: "Test".to_string(),
+            branch_name: "test-branch".to_string(),
+            description: String::new(),
+            user_stories: vec![
+                story("US-001", StoryState::NotStarted),
+                story("US-002", StoryState::Failed),
+            ],
+            verify: Vec::new(),
+        };
+        let mut state = RunState::new(PathBuf::from("test.json"), "test-branch".to_string());
+        for id in ["US-001", "US-001", "US-002"] {
+            state.start_iteration(id);
+        }
+        state.mark_story_failed("US-001", "still not passing after 2 iterations");
+        state.mark_story_failed("US-002", "still not passing after 1 iterations");
+
+        state.forget_reset_stories(&spec);
+
+        assert!(!state.is_story_failed("US-001"));
+        assert_eq!(state.story_iteration_count("US-001"), 0);
+        assert!(state.is_story_failed("US-002"));
+        assert_eq!(state.story_iteration_count("US-002"), 1);
+    }
+
+    #[test]
+    fn test_forget_stories_drops_removed_and_changed_history() {
+        let mut state = RunState::new(PathBuf::from("test.json"), "test-branch".to_string());
+        state.start_iteration("US-001");
+        state.start_iteration("US-002");
+        state.start_iteration("US-003");
+        state.mark_story_failed("US-003", "gave up");
+        state.current_story = Some("US-002".to_string());
+
+        state.forget_stories(&["US-002".to_string()], &["US-003".to_string()]);
+
+        // Changed stories start over, with a fresh iteration limit
+        let ids: Vec<&str> = state
+            .iterations
+            .iter()
+            .map(|i| i.story_id.as_str())
+            .collect();
+        assert_eq!(ids, vec!["US-001"]);
+        assert_eq!(state.iteration, 3);
+        assert!(!state.is_story_failed("US-003"));
+        assert!(state.current_story.is_none());
+    }
+
     // =========================================================================
     // StateManager CRUD
     // =========================================================================
@@ -1771,6 +2238,23 @@ mod tests {
         assert!(!live.is_heartbeat_fresh());
     }
 
+    #[test]
+    fn test_live_state_stalled_for() {
+        let mut live = LiveState::new(MachineState::RunningClaude);
+        live.last_output_at = Some(chrono::Utc::now() - chrono::Duration::seconds(420));
+        assert_eq!(live.stalled_for(), None, "no threshold, no stall");
+
+        live.stall_after_secs = Some(600);
+        assert_eq!(live.stalled_for(), None);
+
+        live.stall_after_secs = Some(300);
+        assert!(live.stalled_for().is_some_and(|secs| secs >= 420));
+
+        // A dead run isn't a stalled one
+        live.last_heartbeat = chrono::Utc::now() - chrono::Duration::seconds(65);
+        assert_eq!(live.stalled_for(), None);
+    }
+
     #[test]
     fn test_live_state_persistence() {
         let temp_dir = TempDir::new().unwrap();
@@ -1796,8 +2280,10 @@ mod tests {
         let temp_dir = TempDir::new().unwrap();
         let sm = StateManager::with_dir(temp_dir.path().to_path_buf());
 
-        let mut config = crate::config::Config::default();
-        config.review = false;
+        let config = crate::config::Config {
+            review: false,
+            ..Default::default()
+        };
         let state =
             RunState::new_with_config(PathBuf::from("test.json"), "branch".to_string(), config);
         sm.save(&state).unwrap();
@@ -1826,6 +2312,7 @@ mod tests {
             }],
             files_deleted: vec![],
             commit_hash: None,
+            base_commit: None,
         });
         assert!(state
             .knowledge
@@ -1854,6 +2341,7 @@ mod tests {
             cache_creation_tokens: 100,
             thinking_tokens: 50,
             model: Some("claude-sonnet-4-20250514".to_string()),
+            cost_usd: None,
         });
         assert!(state.iterations[0].usage.is_some());
         assert_eq!(
@@ -1901,6 +2389,7 @@ mod tests {
             cache_creation_tokens: 500,
             thinking_tokens: 250,
             model: Some("claude-sonnet-4-20250514".to_string()),
+            cost_usd: None,
         });
         assert!(state.total_usage.is_some());
         assert_eq!(state.total_usage.as_ref().unwrap().total_tokens(), 7500);
@@ -1985,6 +2474,7 @@ mod tests {
             cache_creation_tokens: 100,
             thinking_tokens: 50,
             model: Some("claude-sonnet-4-20250514".to_string()),
+            cost_usd: None,
         });
 
         // Serialize
@@ -2042,6 +2532,163 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_estimated_cost_prices_each_phase_by_model() {
+        let mut state = RunState::new(PathBuf::from("test.json"), "test-branch".to_string());
+        assert_eq!(state.estimated_cost_usd(), 0.0);
+
+        state.capture_usage(
+            "US-001",
+            Some(ClaudeUsage {
+                input_tokens: 1_000_000,
+                model: Some("claude-sonnet-4-20250514".to_string()),
+                ..Default::default()
+            }),
+        );
+        state.capture_usage(
+            "Final Review",
+            Some(ClaudeUsage {
+                output_tokens: 100_000,
+                model: Some("claude-opus-4-20250514".to_string()),
+                ..Default::default()
+            }),
+        );
+
+        // $3 of Sonnet input + $7.50 of Opus output
+        assert!((state.estimated_cost_usd() - 10.5).abs() < 1e-9);
+        assert_eq!(state.story_tokens("US-001"), 1_000_000);
+        assert_eq!(state.story_tokens("US-002"), 0);
+    }
+
+    #[test]
+    fn test_exceeded_budget() {
+        let mut state = RunState::new(PathBuf::from("test.json"), "test-branch".to_string());
+        state.capture_usage(
+            "US-001",
+            Some(ClaudeUsage {
+                input_tokens: 400_000,
+                output_tokens: 100_000,
+                model: Some("claude-sonnet-4-20250514".to_string()),
+                ..Default::default()
+            }),
+        );
+
+        // No limits by default
+        assert_eq!(
+            state.exceeded_budget(&Config::default(), Some("US-001")),
+            None
+        );
+
+        let story_limit = Config {
+            max_story_tokens: 500_000,
+            ..Default::default()
+        };
+        assert_eq!(state.exceeded_budget(&story_limit, Some("US-002")), None);
+        assert_eq!(
+            state.exceeded_budget(&story_limit, Some("US-001")).unwrap(),
+            "US-001 has used 500,000 tokens, reaching max_story_tokens (500,000)"
+        );
+
+        let run_limit = Config {
+            max_run_tokens: 1_000_000,
+            max_run_cost_usd: 2.5,
+            ..Default::default()
+        };
+        // $1.20 input + $1.50 output
+        assert_eq!(
+            state.exceeded_budget(&run_limit, None).unwrap(),
+            "The run has spent an estimated $2.70, reaching max_run_cost_usd ($2.50)"
+        );
+    }
+
+    #[test]
+    fn test_transient_retries_are_counted_per_iteration() {
+        let retry = |failure| ClaudeRetry {
+            failed_at: Utc::now(),
+            error: "Overloaded".into(),
+            failure,
+            wait_secs: 30,
+        };
+        let mut state = RunState::new(PathBuf::from("test.json"), "test-branch".to_string());
+        state.start_iteration("US-001");
+        state.record_retry("US-001", retry(ClaudeFailureKind::Transient));
+        state.record_retry("US-001", retry(ClaudeFailureKind::Transient));
+        state.record_retry(
+            "US-001",
+            retry(ClaudeFailureKind::UsageLimit {
+                resets_at: Utc::now(),
+            }),
+        );
+        assert_eq!(state.transient_retry_count("US-001"), 2);
+        assert!(state.is_awaiting_retry("US-001"));
+
+        state.finish_iteration(IterationStatus::Failed, "gave up".into());
+        assert!(!state.is_awaiting_retry("US-001"));
+        state.start_iteration("US-001");
+        assert_eq!(state.transient_retry_count("US-001"), 0);
+    }
+
+    #[test]
+    fn test_resume_retried_iteration_continues_it() {
+        let mut state = RunState::new(PathBuf::from("test.json"), "test-branch".to_string());
+        state.start_iteration("US-001");
+        assert!(!state.resume_retried_iteration("US-001"));
+        state.record_retry(
+            "US-001",
+            ClaudeRetry {
+                failed_at: Utc::now(),
+                error: "Overloaded".into(),
+                failure: ClaudeFailureKind::Transient,
+                wait_secs: 30,
+            },
+        );
+        state.start_iteration("US-002");
+
+        assert!(state.resume_retried_iteration("US-001"));
+        assert_eq!(state.iteration, 2);
+        assert_eq!(state.story_iteration_count("US-001"), 1);
+        assert_eq!(state.iterations.last().unwrap().story_id, "US-001");
+        assert_eq!(state.current_story.as_deref(), Some("US-001"));
+        assert_eq!(state.transient_retry_count("US-001"), 1);
+    }
+
+    #[test]
+    fn test_resume_keeps_usage_and_takes_new_budget() {
+        let started_with = Config {
+            review: false,
+            max_run_tokens: 100,
+            ..Default::default()
+        };
+        let mut state = RunState::new_with_config(
+            PathBuf::from("test.json"),
+            "test-branch".to_string(),
+            started_with,
+        );
+        state.capture_usage(
+            "US-001",
+            Some(ClaudeUsage {
+                input_tokens: 150,
+                ..Default::default()
+            }),
+        );
+        state.status = RunStatus::Interrupted;
+        state.finished_at = Some(Utc::now());
+
+        let raised = Config {
+            max_run_tokens: 1_000,
+            ..Default::default()
+        };
+        state.resume(&raised);
+
+        assert_eq!(state.status, RunStatus::Running);
+        assert!(state.finished_at.is_none());
+        assert_eq!(state.total_usage.as_ref().unwrap().input_tokens, 150);
+        let config = state.effective_config();
+        assert_eq!(config.max_run_tokens, 1_000);
+        assert!(!config.review, "the rest of the snapshot is kept");
+        assert!(state.exceeded_budget(&config, None).is_none());
+    }
+
     #[test]
     fn test_from_spec_constructors_initialize_usage_fields() {
         let state = RunState::from_spec(
@@ -2075,6 +2722,7 @@ mod tests {
             cache_creation_tokens: 5,
             thinking_tokens: 3,
             model: Some("claude-sonnet-4".to_string()),
+            cost_usd: None,
         };
 
         state.capture_usage("Planning", Some(usage.clone()));
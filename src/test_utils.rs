@@ -1,8 +1,10 @@
 //! Test utilities shared across modules.
 //!
 //! This module provides common utilities for tests, including
-//! synchronization primitives for tests that modify global state.
+//! synchronization primitives for tests that modify global state and
+//! fixture builders for specs.
 
+use crate::spec::{Spec, StoryStatus, UserStory, SPEC_SCHEMA_VERSION};
 use std::sync::Mutex;
 
 /// Mutex to serialize tests that depend on or change the current working directory.
@@ -26,6 +28,42 @@ use std::sync::Mutex;
 /// ```
 pub static CWD_MUTEX: Mutex<()> = Mutex::new(());
 
+/// A not-started story with placeholder text and priority 1.
+///
+/// Tests override what they care about with struct update syntax:
+///
+/// ```ignore
+/// let story = UserStory {
+///     priority: 2,
+///     ..test_story("US-001")
+/// };
+/// ```
+pub fn test_story(id: &str) -> UserStory {
+    UserStory {
+        id: id.to_string(),
+        title: format!("Story {}", id),
+        description: format!("Description for {}", id),
+        acceptance_criteria: vec!["Criteria 1".to_string()],
+        priority: 1,
+        status: StoryStatus::default(),
+        notes: String::new(),
+        depends_on: Vec::new(),
+        verify: None,
+    }
+}
+
+/// A spec for `TestProject` on `test-branch` holding `stories`.
+pub fn test_spec(stories: Vec<UserStory>) -> Spec {
+    Spec {
+        schema_version: SPEC_SCHEMA_VERSION,
+        project: "TestProject".to_string(),
+        branch_name: "test-branch".to_string(),
+        description: "Test description".to_string(),
+        user_stories: stories,
+        verify: Vec::new(),
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -330,7 +330,7 @@ mod tests {
             let time = i as f64 * 0.01;
             let alpha = completed_glow_intensity(time);
             assert!(
-                alpha >= COMPLETED_GLOW_ALPHA_MIN && alpha <= COMPLETED_GLOW_ALPHA_MAX,
+                (COMPLETED_GLOW_ALPHA_MIN..=COMPLETED_GLOW_ALPHA_MAX).contains(&alpha),
                 "alpha {alpha} out of range at time {time}"
             );
         }
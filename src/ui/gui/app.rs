@@ -4,6 +4,8 @@
 //! configuration for the autom8 GUI.
 
 use crate::error::{Autom8Error, Result};
+use crate::progress::format_cost_usd;
+use crate::spec::StoryState;
 use crate::state::{IterationStatus, MachineState, RunMode, SessionStatus, StateManager};
 use crate::ui::gui::components::{
     badge_background_color, format_relative_time, format_run_duration, format_state,
@@ -19,9 +21,9 @@ use crate::ui::gui::modal::{Modal, ModalAction, ModalButton};
 use crate::ui::gui::theme::{self, colors, rounding, spacing};
 use crate::ui::gui::typography::{self, FontSize, FontWeight};
 use crate::ui::shared::{
-    is_pause_queued, is_session_resumable, load_project_run_history, load_session_by_id,
-    load_ui_data, request_session_pause, set_session_run_mode, spawn_resume_process, ProjectData,
-    RunHistoryEntry, SessionData,
+    format_attention_counts, format_stall_label, is_pause_queued, is_session_resumable,
+    load_project_run_history, load_session_by_id, load_ui_data, request_session_pause,
+    set_session_run_mode, spawn_resume_process, ProjectData, RunHistoryEntry, SessionData,
 };
 use eframe::egui::{self, Color32, Key, Order, Pos2, Rect, Rounding, Sense, Stroke, Vec2};
 use std::sync::Arc;
@@ -663,8 +665,10 @@ fn format_spec_summary_as_text(spec: &crate::config::SpecSummary) -> Vec<String>
         let first_line = desc_preview.lines().next().unwrap_or(&desc_preview);
         lines.push(first_line.to_string());
         lines.push(format!(
-            "({}/{} stories complete)",
-            spec.completed_count, spec.total_count
+            "({}/{} stories complete{})",
+            spec.completed_count,
+            spec.total_count,
+            spec_attention_suffix(spec)
         ));
         lines.push(String::new());
         return lines;
@@ -687,22 +691,59 @@ fn format_spec_summary_as_text(spec: &crate::config::SpecSummary) -> Vec<String>
     // Progress bar (simple text version)
     let progress_bar = make_progress_bar_text(spec.completed_count, spec.total_count, 12);
     lines.push(format!(
-        "Progress: [{}] {}/{} stories complete",
-        progress_bar, spec.completed_count, spec.total_count
+        "Progress: [{}] {}/{} stories complete{}",
+        progress_bar,
+        spec.completed_count,
+        spec.total_count,
+        spec_attention_suffix(spec)
     ));
     lines.push(String::new());
 
-    // User stories
+    // User stories, with the reason a story is stuck when there is one
     lines.push("User Stories:".to_string());
     for story in &spec.stories {
-        let status_icon = if story.passes { "✓" } else { "○" };
-        lines.push(format!("  {} {}: {}", status_icon, story.id, story.title));
+        lines.push(format!(
+            "  {} {}: {}",
+            story.status.state.icon(),
+            story.id,
+            story.title
+        ));
+        if story.status.reason.is_some() {
+            lines.push(format!("      {}", story.status.describe()));
+        }
     }
     lines.push(String::new());
 
     lines
 }
 
+/// Failed and blocked story counts for a spec, as ", 1 failed, 2 blocked".
+fn spec_attention_suffix(spec: &crate::config::SpecSummary) -> String {
+    format_attention_counts(
+        spec.count_in_state(StoryState::Failed),
+        spec.count_in_state(StoryState::Blocked),
+    )
+    .map(|attention| format!(", {}", attention))
+    .unwrap_or_default()
+}
+
+/// The configured `claude` CLI for a spec chat turn, confined to the configured
+/// sandbox with the project's spec directory writable, like the CLI's spec session.
+fn spec_chat_command() -> crate::error::Result<std::process::Command> {
+    use crate::claude::sandbox::{self, Sandbox};
+
+    let config = crate::config::get_effective_config()?;
+    let mut claude = std::process::Command::new(config.claude.binary());
+    claude.args(["--print", "--output-format", "stream-json", "--verbose"]);
+    let spec_dir = crate::config::spec_dir()?;
+    sandbox::confine(
+        Sandbox::from_config(&config)?.as_ref(),
+        claude,
+        None,
+        Some(&spec_dir),
+    )
+}
+
 /// Create a simple text progress bar.
 fn make_progress_bar_text(completed: usize, total: usize, width: usize) -> String {
     if total == 0 {
@@ -2063,6 +2104,8 @@ enum StoryStatus {
     Pending,
     /// Story failed during implementation.
     Failed,
+    /// Story is waiting on a story that failed.
+    Blocked,
 }
 
 impl StoryStatus {
@@ -2073,6 +2116,7 @@ impl StoryStatus {
             StoryStatus::Active => colors::STATUS_RUNNING,
             StoryStatus::Pending => colors::TEXT_MUTED,
             StoryStatus::Failed => colors::STATUS_ERROR,
+            StoryStatus::Blocked => colors::STATUS_WARNING,
         }
     }
 
@@ -2083,6 +2127,7 @@ impl StoryStatus {
             StoryStatus::Active => colors::STATUS_RUNNING_BG,
             StoryStatus::Pending => colors::SURFACE_HOVER,
             StoryStatus::Failed => colors::STATUS_ERROR_BG,
+            StoryStatus::Blocked => colors::STATUS_WARNING_BG,
         }
     }
 
@@ -2093,6 +2138,7 @@ impl StoryStatus {
             StoryStatus::Active => "[...]",
             StoryStatus::Pending => "[ ]",
             StoryStatus::Failed => "[x]",
+            StoryStatus::Blocked => "[!]",
         }
     }
 }
@@ -2108,13 +2154,16 @@ struct StoryItem {
     status: StoryStatus,
     /// Work summary for completed stories (from most recent successful iteration).
     work_summary: Option<String>,
+    /// Why the story is where it is (e.g., what a blocked story waits on).
+    reason: Option<String>,
 }
 
 /// Load story items from a session's cached user stories and run state.
 ///
 /// Returns a list of story items ordered by status: Active first, then Completed,
-/// then Failed, then Pending. The current story is marked as Active, completed
-/// stories are marked based on the spec's `passes` field, and the rest are Pending.
+/// then Failed, Blocked and Pending. The current story is marked as Active; the
+/// rest follow the story status in the spec, falling back to Failed when the
+/// story's last iteration failed.
 ///
 /// Work summaries from successful iterations are attached to completed stories.
 ///
@@ -2158,12 +2207,18 @@ fn load_story_items(session: &SessionData) -> Vec<StoryItem> {
         .map(|story| {
             let status = if Some(story.id.as_str()) == current_story_id {
                 StoryStatus::Active
-            } else if story.passes {
-                StoryStatus::Completed
-            } else if failed_stories.contains(story.id.as_str()) {
-                StoryStatus::Failed
             } else {
-                StoryStatus::Pending
+                match story.status.state {
+                    StoryState::Done => StoryStatus::Completed,
+                    StoryState::Failed => StoryStatus::Failed,
+                    StoryState::Blocked => StoryStatus::Blocked,
+                    StoryState::NotStarted | StoryState::InProgress
+                        if failed_stories.contains(story.id.as_str()) =>
+                    {
+                        StoryStatus::Failed
+                    }
+                    StoryState::NotStarted | StoryState::InProgress => StoryStatus::Pending,
+                }
             };
 
             // Attach work summary for completed stories
@@ -2178,6 +2233,7 @@ fn load_story_items(session: &SessionData) -> Vec<StoryItem> {
                 title: story.title.clone(),
                 status,
                 work_summary,
+                reason: story.status.reason.clone(),
             }
         })
         .collect();
@@ -2190,7 +2246,8 @@ fn load_story_items(session: &SessionData) -> Vec<StoryItem> {
             StoryStatus::Active => 0,
             StoryStatus::Completed => 1,
             StoryStatus::Failed => 2,
-            StoryStatus::Pending => 3,
+            StoryStatus::Blocked => 3,
+            StoryStatus::Pending => 4,
         };
         order(a).cmp(&order(b))
     });
@@ -5664,6 +5721,7 @@ impl Autom8App {
     /// Displays all 6 config fields grouped logically:
     /// - Pipeline group: review, commit, pull_request
     /// - Worktree group: worktree, worktree_path_pattern, worktree_cleanup
+    /// - Specs group: spec_migration_backup
     ///
     /// Boolean fields are rendered as interactive toggle switches (US-006).
     /// Text fields are rendered as editable inputs with real-time validation (US-007).
@@ -5706,6 +5764,7 @@ impl Autom8App {
         let mut pull_request_draft = config.pull_request_draft;
         let mut worktree = config.worktree;
         let mut worktree_cleanup = config.worktree_cleanup;
+        let mut spec_migration_backup = config.spec_migration_backup;
 
         // Create mutable copy of text field for editing (US-007)
         let mut worktree_path_pattern = config.worktree_path_pattern.clone();
@@ -5824,6 +5883,21 @@ impl Autom8App {
                     bool_changes.push((ConfigBoolField::WorktreeCleanup, worktree_cleanup));
                 }
 
+                ui.add_space(spacing::XL);
+
+                // Specs Settings Group
+                self.render_config_group_header(ui, "Specs");
+                ui.add_space(spacing::SM);
+
+                if self.render_config_bool_field(
+                    ui,
+                    "spec_migration_backup",
+                    &mut spec_migration_backup,
+                    "Spec upgrade backups. When enabled, keeps the original file as <file>.v<old version>.bak when an older spec is upgraded.",
+                ) {
+                    bool_changes.push((ConfigBoolField::SpecMigrationBackup, spec_migration_backup));
+                }
+
                 // Add some padding before the reset button
                 ui.add_space(spacing::XXL);
 
@@ -5885,6 +5959,7 @@ impl Autom8App {
         let mut pull_request_draft = config.pull_request_draft;
         let mut worktree = config.worktree;
         let mut worktree_cleanup = config.worktree_cleanup;
+        let mut spec_migration_backup = config.spec_migration_backup;
 
         // Create mutable copy of text field for editing (US-007)
         let mut worktree_path_pattern = config.worktree_path_pattern.clone();
@@ -6003,6 +6078,21 @@ impl Autom8App {
                     bool_changes.push((ConfigBoolField::WorktreeCleanup, worktree_cleanup));
                 }
 
+                ui.add_space(spacing::XL);
+
+                // Specs Settings Group
+                self.render_config_group_header(ui, "Specs");
+                ui.add_space(spacing::SM);
+
+                if self.render_config_bool_field(
+                    ui,
+                    "spec_migration_backup",
+                    &mut spec_migration_backup,
+                    "Spec upgrade backups. When enabled, keeps the original file as <file>.v<old version>.bak when an older spec is upgraded.",
+                ) {
+                    bool_changes.push((ConfigBoolField::SpecMigrationBackup, spec_migration_backup));
+                }
+
                 // Add some padding before the reset button
                 ui.add_space(spacing::XXL);
 
@@ -7133,14 +7223,15 @@ ui.label(
     ///
     /// This method:
     /// 1. Builds a prompt with conversation context (for multi-turn)
-    /// 2. Spawns `claude` CLI with proper arguments (--print, --output-format stream-json)
+    /// 2. Spawns the configured `claude` CLI with proper arguments (--print, --output-format
+    ///    stream-json), confined to the configured sandbox
     /// 3. Writes prompt to stdin and closes it (required for Claude to process)
     /// 4. Sets up a background thread to stream and parse stdout
     /// 5. Each call spawns a new process (Claude CLI doesn't support persistent sessions)
     fn spawn_claude_for_message(&mut self, user_message: &str, is_first_message: bool) {
         use crate::claude::extract_text_from_stream_line;
         use std::io::{BufRead, BufReader, Write};
-        use std::process::{Command, Stdio};
+        use std::process::Stdio;
 
         // Clear any previous error and reset state
         self.claude_error = None;
@@ -7186,8 +7277,16 @@ ui.label(
         };
 
         // Spawn the Claude CLI process with correct arguments
-        let child_result = Command::new("claude")
-            .args(["--print", "--output-format", "stream-json", "--verbose"])
+        let mut command = match spec_chat_command() {
+            Ok(command) => command,
+            Err(e) => {
+                self.claude_error = Some(format!("Failed to start Claude: {}", e));
+                self.is_waiting_for_claude = false;
+                self.claude_starting = false;
+                return;
+            }
+        };
+        let child_result = command
             .stdin(Stdio::piped())
             .stdout(Stdio::piped())
             .stderr(Stdio::piped())
@@ -8324,6 +8423,19 @@ ui.label(
                         ui.end_row();
                     }
 
+                    // Estimated cost, priced per phase
+                    ui.label(
+                        egui::RichText::new("Est. Cost:")
+                            .font(typography::font(FontSize::Body, FontWeight::Medium))
+                            .color(colors::TEXT_SECONDARY),
+                    );
+                    ui.label(
+                        egui::RichText::new(format_cost_usd(run_state.estimated_cost_usd()))
+                            .font(typography::font(FontSize::Body, FontWeight::Regular))
+                            .color(colors::TEXT_PRIMARY),
+                    );
+                    ui.end_row();
+
                     // Model name
                     if let Some(ref model) = usage.model {
                         ui.label(
@@ -9124,9 +9236,14 @@ ui.label(
 
                             // === STATUS ROW ===
                             let appears_stuck = session.appears_stuck();
+                            let stalled_for = session.stalled_for_secs();
+                            let waiting = session.waiting_label();
                             let (state, state_color) = if let Some(ref run) = session.run {
                                 let base_color = state_to_color(run.machine_state);
-                                let color = if appears_stuck {
+                                let color = if appears_stuck
+                                    || stalled_for.is_some()
+                                    || waiting.is_some()
+                                {
                                     colors::STATUS_WARNING
                                 } else {
                                     base_color
@@ -9154,6 +9271,14 @@ ui.label(
                                 // State text
                                 let state_text = if appears_stuck {
                                     format!("{} (Not responding)", format_state(state))
+                                } else if let Some(secs) = stalled_for {
+                                    format!(
+                                        "{} ({})",
+                                        format_state(state),
+                                        format_stall_label(secs)
+                                    )
+                                } else if let Some(ref waiting) = waiting {
+                                    format!("{} ({})", format_state(state), waiting)
                                 } else {
                                     format_state(state).to_string()
                                 };
@@ -9174,6 +9299,17 @@ ui.label(
                                             ))
                                             .color(colors::TEXT_SECONDARY),
                                     );
+                                    if let Some(attention) = progress.attention_summary() {
+                                        ui.add_space(spacing::SM);
+                                        ui.label(
+                                            egui::RichText::new(attention)
+                                                .font(typography::font(
+                                                    FontSize::Body,
+                                                    FontWeight::Regular,
+                                                ))
+                                                .color(colors::STATUS_WARNING),
+                                        );
+                                    }
                                 }
                             });
 
@@ -9440,6 +9576,16 @@ ui.label(
                                 );
                             }
 
+                            // Estimated cost so far
+                            if let Some(cost) = session.cost_label() {
+                                ui.add_space(spacing::XS);
+                                ui.label(
+                                    egui::RichText::new(cost)
+                                        .font(typography::font(FontSize::Body, FontWeight::Regular))
+                                        .color(colors::TEXT_MUTED),
+                                );
+                            }
+
                             // Infinity animation for non-idle, non-terminal states with progress
                             if state != MachineState::Idle
                                 && !is_terminal_state(state)
@@ -9485,6 +9631,10 @@ ui.label(
                                 && matches!(session.metadata.run_mode, RunMode::Step);
                             let state_text = if appears_stuck {
                                 format!("{} (Not responding)", format_state(state))
+                            } else if let Some(secs) = stalled_for {
+                                format!("{} ({})", format_state(state), format_stall_label(secs))
+                            } else if let Some(ref waiting) = waiting {
+                                format!("{} ({})", format_state(state), waiting)
                             } else if is_paused_step_mode {
                                 format!("{} (Step)", format_state(state))
                             } else {
@@ -9496,6 +9646,18 @@ ui.label(
                                     .color(colors::TEXT_PRIMARY),
                             );
 
+                            // What Claude was last doing before it went quiet
+                            if let Some(activity) = stalled_for.and(session.last_activity()) {
+                                ui.label(
+                                    egui::RichText::new(format!("Last activity: {}", activity))
+                                        .font(typography::font(
+                                            FontSize::Small,
+                                            FontWeight::Regular,
+                                        ))
+                                        .color(colors::STATUS_WARNING),
+                                );
+                            }
+
                             ui.add_space(spacing::SM);
 
                             // Output content with fixed height and internal scrolling
@@ -9603,6 +9765,16 @@ ui.label(
                                 .color(title_color),
                         );
 
+                        // Show why a story is stuck or retrying
+                        if let Some(ref reason) = story.reason {
+                            ui.add_space(spacing::XS);
+                            ui.label(
+                                egui::RichText::new(reason)
+                                    .font(typography::font(FontSize::Small, FontWeight::Regular))
+                                    .color(story.status.color()),
+                            );
+                        }
+
                         // Show work summary for completed stories
                         if let Some(ref summary) = story.work_summary {
                             ui.add_space(spacing::XS);
@@ -10447,6 +10619,11 @@ mod tests {
             session_id: Some("main".to_string()),
             total_usage: None,
             phase_usage: std::collections::HashMap::new(),
+            sequential_stories: Vec::new(),
+            failed_stories: Vec::new(),
+            flagged_phases: Vec::new(),
+            spec_fingerprint: None,
+            started_clean: false,
         }
     }
 
@@ -10509,6 +10686,9 @@ mod tests {
             output_snippet: String::new(),
             work_summary: None,
             usage: None,
+            verify_failure: None,
+            retries: Vec::new(),
+            model: None,
         });
         run.iterations.push(IterationRecord {
             number: 2,
@@ -10519,6 +10699,9 @@ mod tests {
             output_snippet: String::new(),
             work_summary: None,
             usage: None,
+            verify_failure: None,
+            retries: Vec::new(),
+            model: None,
         });
 
         let entry = RunHistoryEntry::from_run_state("test-project".to_string(), &run);
@@ -10601,6 +10784,9 @@ mod tests {
             output_snippet: "Previous iteration output\nLine 2\nLine 3".to_string(),
             work_summary: None,
             usage: None,
+            verify_failure: None,
+            retries: Vec::new(),
+            model: None,
         });
 
         // Create live output with EMPTY output_lines (new invocation just started)
@@ -10609,6 +10795,11 @@ mod tests {
             updated_at: Utc::now(),
             machine_state: MachineState::RunningClaude,
             last_heartbeat: Utc::now(),
+            last_output_at: None,
+            last_activity: None,
+            stall_after_secs: None,
+            waiting_until: None,
+            waiting_reason: None,
         };
 
         let session = make_test_session_data(Some(run), Some(live));
@@ -10648,6 +10839,11 @@ mod tests {
             updated_at: Utc::now(),
             machine_state: MachineState::RunningClaude,
             last_heartbeat: Utc::now(),
+            last_output_at: None,
+            last_activity: None,
+            stall_after_secs: None,
+            waiting_until: None,
+            waiting_reason: None,
         };
 
         let session = make_test_session_data(Some(run), Some(live));
@@ -10686,6 +10882,9 @@ mod tests {
             output_snippet: "Previous iteration completed\nImplemented feature X".to_string(),
             work_summary: Some("Implemented feature X".to_string()),
             usage: None,
+            verify_failure: None,
+            retries: Vec::new(),
+            model: None,
         });
 
         // Live output exists but is stale (older than freshness threshold)
@@ -10735,6 +10934,9 @@ mod tests {
             output_snippet: "First iteration output\nDid something useful".to_string(),
             work_summary: Some("Did something useful".to_string()),
             usage: None,
+            verify_failure: None,
+            retries: Vec::new(),
+            model: None,
         });
 
         // Current iteration - just started, no output yet
@@ -10747,6 +10949,9 @@ mod tests {
             output_snippet: String::new(), // No output yet
             work_summary: None,
             usage: None,
+            verify_failure: None,
+            retries: Vec::new(),
+            model: None,
         });
 
         // Live output exists but empty (new invocation just started)
@@ -10755,6 +10960,11 @@ mod tests {
             updated_at: Utc::now(),
             machine_state: MachineState::RunningClaude,
             last_heartbeat: Utc::now(),
+            last_output_at: None,
+            last_activity: None,
+            stall_after_secs: None,
+            waiting_until: None,
+            waiting_reason: None,
         };
 
         let session = make_test_session_data(Some(run), Some(live));
@@ -65,6 +65,8 @@ pub enum ConfigBoolField {
     Worktree,
     /// The `worktree_cleanup` field.
     WorktreeCleanup,
+    /// The `spec_migration_backup` field.
+    SpecMigrationBackup,
 }
 
 /// Identifier for text config fields (US-007).
@@ -361,6 +363,7 @@ impl ConfigTabState {
                 ConfigBoolField::PullRequestDraft => config.pull_request_draft = *value,
                 ConfigBoolField::Worktree => config.worktree = *value,
                 ConfigBoolField::WorktreeCleanup => config.worktree_cleanup = *value,
+                ConfigBoolField::SpecMigrationBackup => config.spec_migration_backup = *value,
             }
         }
 
@@ -453,6 +456,7 @@ impl ConfigTabState {
     /// - worktree = true
     /// - worktree_path_pattern = "{repo}-wt-{branch}"
     /// - worktree_cleanup = false
+    /// - spec_migration_backup = true
     ///
     /// The config is saved immediately and the UI updates to reflect the new values.
     pub fn reset_to_defaults(&mut self, is_global: bool, project_name: Option<&str>) {
@@ -530,9 +534,9 @@ mod tests {
     #[test]
     fn test_config_scope_constants_exist() {
         // Verify constants are accessible and have reasonable values
-        assert!(CONFIG_SCOPE_ROW_HEIGHT > 0.0);
-        assert!(CONFIG_SCOPE_ROW_PADDING_H > 0.0);
-        assert!(CONFIG_SCOPE_ROW_PADDING_V > 0.0);
+        const { assert!(CONFIG_SCOPE_ROW_HEIGHT > 0.0) };
+        const { assert!(CONFIG_SCOPE_ROW_PADDING_H > 0.0) };
+        const { assert!(CONFIG_SCOPE_ROW_PADDING_V > 0.0) };
     }
 
     // ========================================================================
@@ -588,6 +592,7 @@ mod tests {
         let _ = ConfigBoolField::PullRequestDraft;
         let _ = ConfigBoolField::Worktree;
         let _ = ConfigBoolField::WorktreeCleanup;
+        let _ = ConfigBoolField::SpecMigrationBackup;
     }
 
     #[test]
@@ -441,11 +441,11 @@ mod tests {
     #[test]
     fn test_spacing_scale() {
         // Verify spacing scale is monotonically increasing
-        assert!(spacing::XS < spacing::SM);
-        assert!(spacing::SM < spacing::MD);
-        assert!(spacing::MD < spacing::LG);
-        assert!(spacing::LG < spacing::XL);
-        assert!(spacing::XL < spacing::XXL);
+        const { assert!(spacing::XS < spacing::SM) };
+        const { assert!(spacing::SM < spacing::MD) };
+        const { assert!(spacing::MD < spacing::LG) };
+        const { assert!(spacing::LG < spacing::XL) };
+        const { assert!(spacing::XL < spacing::XXL) };
     }
 
     #[test]
@@ -453,7 +453,7 @@ mod tests {
         // WCAG 2.1 SC 2.5.5 specifies 44px as the minimum touch target size
         assert_eq!(accessibility::MIN_TOUCH_TARGET, 44.0);
         // Ensure it's larger than all spacing values (touch targets should be substantial)
-        assert!(accessibility::MIN_TOUCH_TARGET > spacing::XXL);
+        const { assert!(accessibility::MIN_TOUCH_TARGET > spacing::XXL) };
     }
 
     #[test]
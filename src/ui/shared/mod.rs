@@ -7,7 +7,8 @@
 
 use crate::config::{list_projects_tree, ProjectTreeInfo};
 use crate::error::Result;
-use crate::spec::{Spec, UserStory};
+use crate::progress::format_cost_usd;
+use crate::spec::{Spec, StoryState, UserStory};
 use crate::state::{
     IterationStatus, LiveState, MachineState, RunState, RunStatus, SessionMetadata, StateManager,
 };
@@ -189,6 +190,17 @@ pub fn format_relative_time_secs(total_secs: u64) -> String {
     }
 }
 
+/// Format how long Claude has gone without output (e.g., "stalled for 7m").
+pub fn format_stall_label(total_secs: u64) -> String {
+    let hours = total_secs / 3600;
+    let minutes = (total_secs % 3600) / 60;
+    if hours > 0 {
+        format!("stalled for {}h {}m", hours, minutes)
+    } else {
+        format!("stalled for {}m", minutes)
+    }
+}
+
 // ============================================================================
 // Shared Data Types
 // ============================================================================
@@ -203,12 +215,31 @@ pub struct RunProgress {
     pub completed: usize,
     /// Total number of stories.
     pub total: usize,
+    /// Number of stories the run gave up on.
+    pub failed: usize,
+    /// Number of stories waiting on a failed story.
+    pub blocked: usize,
 }
 
 impl RunProgress {
     /// Create a new RunProgress instance.
     pub fn new(completed: usize, total: usize) -> Self {
-        Self { completed, total }
+        Self {
+            completed,
+            total,
+            failed: 0,
+            blocked: 0,
+        }
+    }
+
+    /// Build progress from the story statuses in a spec.
+    pub fn from_spec(spec: &Spec) -> Self {
+        Self {
+            completed: spec.completed_count(),
+            total: spec.total_count(),
+            failed: spec.count_in_state(StoryState::Failed),
+            blocked: spec.count_in_state(StoryState::Blocked),
+        }
     }
 
     /// Calculate the progress as a fraction between 0.0 and 1.0.
@@ -250,6 +281,25 @@ impl RunProgress {
         let pct = (self.completed * 100) / self.total;
         format!("{}%", pct)
     }
+
+    /// Describe stories that need attention (e.g., "1 failed, 2 blocked").
+    ///
+    /// Returns `None` when nothing is failed or blocked.
+    pub fn attention_summary(&self) -> Option<String> {
+        format_attention_counts(self.failed, self.blocked)
+    }
+}
+
+/// Format failed and blocked story counts (e.g., "1 failed, 2 blocked").
+///
+/// Returns `None` when both are zero.
+pub fn format_attention_counts(failed: usize, blocked: usize) -> Option<String> {
+    let parts: Vec<String> = [(failed, "failed"), (blocked, "blocked")]
+        .into_iter()
+        .filter(|(count, _)| *count > 0)
+        .map(|(count, label)| format!("{} {}", count, label))
+        .collect();
+    (!parts.is_empty()).then(|| parts.join(", "))
 }
 
 /// Data collected from a single project for display.
@@ -361,6 +411,47 @@ impl SessionData {
             .unwrap_or(false) // No live output = can't determine stuck state
     }
 
+    /// Seconds since Claude's last output, if the session is running and
+    /// past its `stall_timeout_minutes`.
+    ///
+    /// Unlike `appears_stuck`, autom8 itself is still alive here; it is the
+    /// Claude process that has gone quiet.
+    pub fn stalled_for_secs(&self) -> Option<u64> {
+        if !self.metadata.is_running || self.is_stale {
+            return None;
+        }
+        self.live_output.as_ref().and_then(LiveState::stalled_for)
+    }
+
+    /// What a running session is waiting for before retrying Claude, e.g.
+    /// "Usage limit reached, retrying in 1h 5m 0s".
+    pub fn waiting_label(&self) -> Option<String> {
+        if !self.metadata.is_running || self.is_stale {
+            return None;
+        }
+        let live = self.live_output.as_ref()?;
+        let secs = live.waiting_for()?;
+        let reason = live.waiting_reason.as_deref().unwrap_or("Waiting");
+        Some(format!(
+            "{}, retrying in {}",
+            reason,
+            format_duration_secs(secs)
+        ))
+    }
+
+    /// Estimated spend of the session's run so far (e.g., "~$4.12"), once it
+    /// has used any tokens.
+    pub fn cost_label(&self) -> Option<String> {
+        let run = self.run.as_ref()?;
+        run.total_usage.as_ref()?;
+        Some(format!("~{}", format_cost_usd(run.estimated_cost_usd())))
+    }
+
+    /// Claude's last tool call (e.g., "Bash: cargo test"), if known.
+    pub fn last_activity(&self) -> Option<&str> {
+        self.live_output.as_ref()?.last_activity.as_deref()
+    }
+
     /// Get a truncated worktree path for display (last 2 components).
     pub fn truncated_worktree_path(&self) -> String {
         let path = &self.metadata.worktree_path;
@@ -572,12 +663,9 @@ fn load_project_data(info: &ProjectTreeInfo) -> ProjectData {
 
     // Load spec to get progress information
     let progress = active_run.as_ref().and_then(|run| {
-        Spec::load(&run.spec_json_path)
+        Spec::read(&run.spec_json_path)
             .ok()
-            .map(|spec| RunProgress {
-                completed: spec.completed_count(),
-                total: spec.total_count(),
-            })
+            .map(|spec| RunProgress::from_spec(&spec))
     });
 
     ProjectData {
@@ -722,12 +810,9 @@ fn load_sessions(project_filter: Option<&str>) -> Vec<SessionData> {
             // Load spec to get progress information and cache user stories
             let (progress, cached_user_stories) = run
                 .as_ref()
-                .and_then(|r| Spec::load(&r.spec_json_path).ok())
+                .and_then(|r| Spec::read(&r.spec_json_path).ok())
                 .map(|spec| {
-                    let progress = RunProgress {
-                        completed: spec.completed_count(),
-                        total: spec.total_count(),
-                    };
+                    let progress = RunProgress::from_spec(&spec);
                     (Some(progress), Some(spec.user_stories))
                 })
                 .unwrap_or((None, None));
@@ -747,7 +832,7 @@ fn load_sessions(project_filter: Option<&str>) -> Vec<SessionData> {
     }
 
     // Sort sessions by last_active_at descending
-    sessions.sort_by(|a, b| b.metadata.last_active_at.cmp(&a.metadata.last_active_at));
+    sessions.sort_by_key(|s| std::cmp::Reverse(s.metadata.last_active_at));
 
     sessions
 }
@@ -807,12 +892,9 @@ pub fn load_session_by_id(project_name: &str, session_id: &str) -> Option<Sessio
     // Load spec to get progress information and cache user stories
     let (progress, cached_user_stories) = run
         .as_ref()
-        .and_then(|r| Spec::load(&r.spec_json_path).ok())
+        .and_then(|r| Spec::read(&r.spec_json_path).ok())
         .map(|spec| {
-            let progress = RunProgress {
-                completed: spec.completed_count(),
-                total: spec.total_count(),
-            };
+            let progress = RunProgress::from_spec(&spec);
             (Some(progress), Some(spec.user_stories))
         })
         .unwrap_or((None, None));
@@ -881,7 +963,7 @@ pub fn load_run_history(
             if let Ok(archived) = sm.list_archived() {
                 for run in archived {
                     // Try to load the spec to get story counts
-                    let (completed, total) = Spec::load(&run.spec_json_path)
+                    let (completed, total) = Spec::read(&run.spec_json_path)
                         .map(|spec| (spec.completed_count(), spec.total_count()))
                         .unwrap_or_else(|_| {
                             // Fallback: count from iterations
@@ -910,7 +992,7 @@ pub fn load_run_history(
     }
 
     // Sort by date, most recent first
-    history.sort_by(|a, b| b.started_at.cmp(&a.started_at));
+    history.sort_by_key(|h| std::cmp::Reverse(h.started_at));
 
     // Apply limit if specified
     if let Some(max) = options.max_entries {
@@ -1141,6 +1223,48 @@ mod tests {
 
         // Simple fraction
         assert_eq!(RunProgress::new(2, 5).as_simple_fraction(), "2/5");
+        assert_eq!(RunProgress::new(2, 5).attention_summary(), None);
+    }
+
+    #[test]
+    fn test_run_progress_from_spec_counts_statuses() {
+        use crate::spec::StoryStatus;
+
+        let story = |id: &str, state: StoryState| UserStory {
+            id: id.to_string(),
+            title: id.to_string(),
+            description: String::new(),
+            acceptance_criteria: Vec::new(),
+            priority: 1,
+            status: StoryStatus::new(state, None),
+            notes: String::new(),
+            depends_on: Vec::new(),
+            verify: None,
+        };
+        let spec = Spec {
+            schema_version: crate::spec::SPEC_SCHEMA_VERSION,
+            project: "Test".to_string(),
+            branch_name: "feature/test".to_string(),
+            description: String::new(),
+            user_stories: vec![
+                story("US-001", StoryState::Done),
+                story("US-002", StoryState::Failed),
+                story("US-003", StoryState::Blocked),
+                story("US-004", StoryState::Blocked),
+                story("US-005", StoryState::NotStarted),
+            ],
+            verify: Vec::new(),
+        };
+
+        let progress = RunProgress::from_spec(&spec);
+        assert_eq!(progress.as_simple_fraction(), "1/5");
+        assert_eq!(progress.failed, 1);
+        assert_eq!(progress.blocked, 2);
+        assert_eq!(
+            progress.attention_summary().as_deref(),
+            Some("1 failed, 2 blocked")
+        );
+        assert_eq!(format_attention_counts(0, 3).as_deref(), Some("3 blocked"));
     }
 
     // =========================================================================
@@ -1219,6 +1343,68 @@ mod tests {
         assert!(!not_running.appears_stuck());
     }
 
+    #[test]
+    fn test_session_stalled_for() {
+        let mut live = LiveState::new(MachineState::RunningClaude);
+        live.stall_after_secs = Some(300);
+        live.last_output_at = Some(Utc::now() - chrono::Duration::seconds(7 * 60 + 5));
+        live.last_activity = Some("Bash: cargo test".to_string());
+
+        let mut stalled = make_test_session(true, true, false);
+        stalled.live_output = Some(live.clone());
+        let secs = stalled.stalled_for_secs().unwrap();
+        assert_eq!(format_stall_label(secs), "stalled for 7m");
+        assert_eq!(stalled.last_activity(), Some("Bash: cargo test"));
+        assert!(!stalled.appears_stuck());
+
+        let mut finished = make_test_session(true, false, false);
+        finished.live_output = Some(live);
+        assert_eq!(finished.stalled_for_secs(), None);
+
+        assert_eq!(format_stall_label(3900), "stalled for 1h 5m");
+    }
+
+    #[test]
+    fn test_session_cost_label() {
+        let mut session = make_test_session_with_run(true, true, RunStatus::Running);
+        assert_eq!(session.cost_label(), None);
+
+        session.run.as_mut().unwrap().capture_usage(
+            "US-001",
+            Some(crate::claude::ClaudeUsage {
+                input_tokens: 500_000,
+                output_tokens: 100_000,
+                model: Some("claude-sonnet-4-20250514".to_string()),
+                ..Default::default()
+            }),
+        );
+        assert_eq!(session.cost_label().as_deref(), Some("~$3.00"));
+    }
+
+    #[test]
+    fn test_session_waiting_label() {
+        let mut live = LiveState::new(MachineState::RunningClaude);
+        live.waiting_until = Some(Utc::now() + chrono::Duration::seconds(3600 + 300 + 30));
+        live.waiting_reason = Some("Usage limit reached".to_string());
+
+        let mut waiting = make_test_session(true, true, false);
+        waiting.live_output = Some(live.clone());
+        let label = waiting.waiting_label().unwrap();
+        assert!(
+            label.starts_with("Usage limit reached, retrying in 1h 5m"),
+            "{}",
+            label
+        );
+
+        let mut finished = make_test_session(true, false, false);
+        finished.live_output = Some(live);
+        assert_eq!(finished.waiting_label(), None);
+
+        let mut running = make_test_session(true, true, false);
+        running.live_output = Some(LiveState::new(MachineState::RunningClaude));
+        assert_eq!(running.waiting_label(), None);
+    }
+
     // =========================================================================
     // Status Mapping Tests
     // =========================================================================
@@ -1347,7 +1533,7 @@ mod tests {
 
     #[test]
     fn test_run_history_sorting() {
-        let mut history = vec![
+        let mut history = [
             make_history_entry("completed-old", RunStatus::Completed, 60),
             make_history_entry("running", RunStatus::Running, 3600),
             make_history_entry("completed-new", RunStatus::Completed, 0),
@@ -1451,6 +1637,11 @@ mod tests {
                 session_id: None,
                 total_usage: None,
                 phase_usage: Default::default(),
+                sequential_stories: Vec::new(),
+                failed_stories: Vec::new(),
+                flagged_phases: Vec::new(),
+                spec_fingerprint: None,
+                started_clean: false,
             }),
             progress: None,
             load_error: None,
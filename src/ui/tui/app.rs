@@ -6,8 +6,9 @@ use super::views::View;
 use crate::error::Result;
 use crate::state::{LiveState, MachineState, RunState};
 use crate::ui::shared::{
-    format_duration, format_relative_time, format_state_label, load_run_history, load_ui_data,
-    ProjectData, RunHistoryEntry, RunHistoryOptions, SessionData, Status,
+    format_duration, format_relative_time, format_stall_label, format_state_label,
+    load_run_history, load_ui_data, ProjectData, RunHistoryEntry, RunHistoryOptions, SessionData,
+    Status,
 };
 use chrono::Utc;
 use crossterm::{
@@ -410,16 +411,12 @@ impl MonitorApp {
                 }
             }
             // Left navigation (h) - only meaningful in Active Runs quadrant view
-            KeyCode::Left | KeyCode::Char('h') => {
-                if self.current_view == View::ActiveRuns {
-                    self.quadrant_move_left();
-                }
+            KeyCode::Left | KeyCode::Char('h') if self.current_view == View::ActiveRuns => {
+                self.quadrant_move_left();
             }
             // Right navigation (l) - only meaningful in Active Runs quadrant view
-            KeyCode::Right | KeyCode::Char('l') => {
-                if self.current_view == View::ActiveRuns {
-                    self.quadrant_move_right();
-                }
+            KeyCode::Right | KeyCode::Char('l') if self.current_view == View::ActiveRuns => {
+                self.quadrant_move_right();
             }
             KeyCode::Enter => {
                 self.handle_enter();
@@ -446,15 +443,11 @@ impl MonitorApp {
                 }
             }
             // Pagination for Active Runs view (n/] = next, p/[ = previous)
-            KeyCode::Char('n') | KeyCode::Char(']') => {
-                if self.current_view == View::ActiveRuns {
-                    self.next_quadrant_page();
-                }
+            KeyCode::Char('n') | KeyCode::Char(']') if self.current_view == View::ActiveRuns => {
+                self.next_quadrant_page();
             }
-            KeyCode::Char('p') | KeyCode::Char('[') => {
-                if self.current_view == View::ActiveRuns {
-                    self.prev_quadrant_page();
-                }
+            KeyCode::Char('p') | KeyCode::Char('[') if self.current_view == View::ActiveRuns => {
+                self.prev_quadrant_page();
             }
             _ => {}
         }
@@ -778,12 +771,17 @@ impl MonitorApp {
         // + 1 for Session type (always shown)
         // + 1 for Branch (always shown)
         // + 1 for Worktree path (only for worktree sessions in full mode)
+        // + 1 for Last activity (only while Claude is stalled)
         let base_height = 6; // State, Story, Progress, Duration, Session, Branch
+        let stalled_for = session.stalled_for_secs();
+        let waiting = session.waiting_label();
+        let last_activity = stalled_for.and(session.last_activity());
         let extra_height = if full && !session.is_main_session {
             1
         } else {
             0
-        }; // Worktree path
+        } // Worktree path
+            + u16::from(last_activity.is_some());
 
         // Split into header info and output snippet
         let chunks = Layout::default()
@@ -800,10 +798,18 @@ impl MonitorApp {
 
         let state_str = if appears_stuck {
             format!("{} (Not responding)", format_state_label(run.machine_state))
+        } else if let Some(secs) = stalled_for {
+            format!(
+                "{} ({})",
+                format_state_label(run.machine_state),
+                format_stall_label(secs)
+            )
+        } else if let Some(ref waiting) = waiting {
+            format!("{} ({})", format_state_label(run.machine_state), waiting)
         } else {
             format_state_label(run.machine_state).to_string()
         };
-        let state_color_value = if appears_stuck {
+        let state_color_value = if appears_stuck || stalled_for.is_some() || waiting.is_some() {
             COLOR_WARNING
         } else {
             state_color(run.machine_state)
@@ -815,7 +821,10 @@ impl MonitorApp {
         let progress_str = session
             .progress
             .as_ref()
-            .map(|p| p.as_fraction())
+            .map(|p| match p.attention_summary() {
+                Some(attention) => format!("{} ({})", p.as_fraction(), attention),
+                None => p.as_fraction(),
+            })
             .unwrap_or_else(|| "N/A".to_string());
 
         // Session type indicator with visual distinction
@@ -857,6 +866,13 @@ impl MonitorApp {
             ]),
         ];
 
+        if let Some(activity) = last_activity {
+            info_lines.push(Line::from(vec![
+                Span::styled("Last activity: ", Style::default().fg(COLOR_DIM)),
+                Span::styled(activity, Style::default().fg(COLOR_WARNING)),
+            ]));
+        }
+
         // Add worktree path for worktree sessions in full mode
         if full && !session.is_main_session {
             info_lines.insert(
@@ -0,0 +1,257 @@
+//! Story verification commands.
+//!
+//! A story's `verify` commands (or the spec-level default) are run by autom8
+//! after Claude marks the story as passing. The story only keeps `passes: true`
+//! if every command exits 0, so the orchestrator rather than the model decides
+//! when a story is done.
+
+use std::fmt;
+use std::fs::{self, File};
+use std::path::Path;
+use std::process::{Command, ExitStatus, Stdio};
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::time::{Duration, Instant};
+
+/// Maximum number of output characters kept from a failing command.
+///
+/// The tail is kept since test runners print their summary last.
+const MAX_FAILURE_OUTPUT_CHARS: usize = 4000;
+
+/// How often a command with a timeout is checked for having exited.
+const POLL_INTERVAL: Duration = Duration::from_millis(50);
+
+/// Numbers the output files of commands run at the same time.
+static OUTPUT_FILE_COUNTER: AtomicU64 = AtomicU64::new(0);
+
+/// A verification command that didn't exit 0.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct VerifyFailure {
+    pub command: String,
+    /// Exit code, or `None` if the command was killed by a signal or couldn't start
+    pub exit_code: Option<i32>,
+    /// Combined stdout and stderr (tail only)
+    pub output: String,
+}
+
+impl fmt::Display for VerifyFailure {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.exit_code {
+            Some(code) => write!(f, "`{}` exited with code {}", self.command, code),
+            None => write!(f, "`{}` did not complete", self.command),
+        }
+    }
+}
+
+impl VerifyFailure {
+    /// Describe the failure, including the command output, for a prompt.
+    pub fn to_prompt_text(&self) -> String {
+        if self.output.trim().is_empty() {
+            format!("{} with no output.", self)
+        } else {
+            format!("{}:\n```\n{}\n```", self, self.output.trim_end())
+        }
+    }
+}
+
+/// Run verification commands in order, stopping at the first failure.
+///
+/// Commands run through `sh -c` in `dir`, or the current directory if `None`.
+pub fn run_verify_commands(commands: &[String], dir: Option<&Path>) -> Result<(), VerifyFailure> {
+    for command in commands {
+        if let Err(failure) = run_shell(command, dir, &[], None) {
+            return Err(VerifyFailure {
+                command: command.clone(),
+                exit_code: failure.exit_code,
+                output: failure.output,
+            });
+        }
+    }
+    Ok(())
+}
+
+/// A shell command that didn't exit 0.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) struct ShellFailure {
+    /// Exit code, or `None` if the command was killed or couldn't start
+    pub exit_code: Option<i32>,
+    /// Combined stdout and stderr (tail only)
+    pub output: String,
+}
+
+/// Run `command` through `sh -c` in `dir`, or the current directory if `None`.
+///
+/// Output goes to a temporary file rather than a pipe, so a command that
+/// leaves a process running in the background (e.g. a dev server) doesn't
+/// keep autom8 waiting for the pipe to close. A command still running after
+/// `timeout` is killed.
+pub(crate) fn run_shell(
+    command: &str,
+    dir: Option<&Path>,
+    env: &[(&str, String)],
+    timeout: Option<Duration>,
+) -> Result<(), ShellFailure> {
+    let output_path = std::env::temp_dir().join(format!(
+        "autom8-shell-{}-{}.log",
+        std::process::id(),
+        OUTPUT_FILE_COUNTER.fetch_add(1, Ordering::Relaxed)
+    ));
+    let result = run_shell_to_file(command, dir, env, timeout, &output_path);
+    let mut output = fs::read(&output_path)
+        .map(|bytes| String::from_utf8_lossy(&bytes).into_owned())
+        .unwrap_or_default();
+    let _ = fs::remove_file(&output_path);
+
+    match result {
+        Ok(status) if status.success() => Ok(()),
+        Ok(status) => Err(ShellFailure {
+            exit_code: status.code(),
+            output: tail(&output, MAX_FAILURE_OUTPUT_CHARS),
+        }),
+        Err(message) => {
+            if !output.is_empty() && !output.ends_with('\n') {
+                output.push('\n');
+            }
+            output.push_str(&message);
+            Err(ShellFailure {
+                exit_code: None,
+                output: tail(&output, MAX_FAILURE_OUTPUT_CHARS),
+            })
+        }
+    }
+}
+
+/// Run a command with its output sent to `output_path`, waiting at most `timeout`.
+///
+/// Returns how it exited, or why it didn't.
+fn run_shell_to_file(
+    command: &str,
+    dir: Option<&Path>,
+    env: &[(&str, String)],
+    timeout: Option<Duration>,
+    output_path: &Path,
+) -> Result<ExitStatus, String> {
+    let start = |e: std::io::Error| format!("failed to run command: {}", e);
+    let stdout = File::create(output_path).map_err(start)?;
+    let stderr = stdout.try_clone().map_err(start)?;
+
+    let mut cmd = Command::new("sh");
+    cmd.args(["-c", command])
+        .envs(env.iter().map(|(key, value)| (key, value)))
+        .stdin(Stdio::null())
+        .stdout(stdout)
+        .stderr(stderr);
+    if let Some(dir) = dir {
+        cmd.current_dir(dir);
+    }
+    let mut child = cmd.spawn().map_err(start)?;
+
+    let Some(timeout) = timeout else {
+        return child.wait().map_err(start);
+    };
+    let started = Instant::now();
+    loop {
+        if let Some(status) = child.try_wait().map_err(start)? {
+            return Ok(status);
+        }
+        if started.elapsed() >= timeout {
+            let _ = child.kill();
+            let _ = child.wait();
+            return Err(format!("killed after running for {}s", timeout.as_secs()));
+        }
+        std::thread::sleep(POLL_INTERVAL);
+    }
+}
+
+/// The last `max_chars` characters of `text`.
+pub(crate) fn tail(text: &str, max_chars: usize) -> String {
+    let count = text.chars().count();
+    if count <= max_chars {
+        return text.to_string();
+    }
+    let skipped: String = text.chars().skip(count - max_chars).collect();
+    format!("...\n{}", skipped)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn commands(cmds: &[&str]) -> Vec<String> {
+        cmds.iter().map(|c| c.to_string()).collect()
+    }
+
+    #[test]
+    fn test_all_commands_succeed() {
+        assert_eq!(
+            run_verify_commands(&commands(&["true", "exit 0"]), None),
+            Ok(())
+        );
+        assert_eq!(run_verify_commands(&[], None), Ok(()));
+    }
+
+    #[test]
+    fn test_stops_at_first_failure_with_output() {
+        let dir = tempfile::TempDir::new().unwrap();
+        let failure = run_verify_commands(
+            &commands(&["echo out; echo err >&2; exit 3", "touch never-run"]),
+            Some(dir.path()),
+        )
+        .unwrap_err();
+
+        assert_eq!(failure.exit_code, Some(3));
+        assert!(failure.output.contains("out"));
+        assert!(failure.output.contains("err"));
+        assert!(!dir.path().join("never-run").exists());
+        assert_eq!(
+            failure.to_string(),
+            "`echo out; echo err >&2; exit 3` exited with code 3"
+        );
+    }
+
+    #[test]
+    fn test_runs_in_given_directory() {
+        let dir = tempfile::TempDir::new().unwrap();
+        std::fs::write(dir.path().join("marker"), "").unwrap();
+        assert!(run_verify_commands(&commands(&["test -f marker"]), Some(dir.path())).is_ok());
+    }
+
+    #[test]
+    fn test_prompt_text_includes_output() {
+        let failure = VerifyFailure {
+            command: "cargo test".into(),
+            exit_code: Some(101),
+            output: "test foo ... FAILED\n".into(),
+        };
+        let text = failure.to_prompt_text();
+        assert!(text.starts_with("`cargo test` exited with code 101:"));
+        assert!(text.contains("```\ntest foo ... FAILED\n```"));
+    }
+
+    #[test]
+    fn test_shell_does_not_wait_for_background_processes() {
+        let started = Instant::now();
+        assert_eq!(run_shell("sleep 5 & echo started", None, &[], None), Ok(()));
+        assert!(started.elapsed() < Duration::from_secs(4));
+    }
+
+    #[test]
+    fn test_shell_kills_command_after_timeout() {
+        let failure = run_shell(
+            "echo waiting; sleep 5",
+            None,
+            &[],
+            Some(Duration::from_millis(200)),
+        )
+        .unwrap_err();
+
+        assert_eq!(failure.exit_code, None);
+        assert!(failure.output.contains("waiting"));
+        assert!(failure.output.contains("killed after running"));
+    }
+
+    #[test]
+    fn test_tail_keeps_end_of_long_output() {
+        assert_eq!(tail("short", 10), "short");
+        assert_eq!(tail("abcdefghij", 3), "...\nhij");
+    }
+}